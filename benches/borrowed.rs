@@ -0,0 +1,17 @@
+//!Owned vs borrowed parsing of schema with 200 methods
+
+mod common;
+
+use flatbuffers_tools::borrowed::parse_ref;
+use flatbuffers_tools::parse_services;
+
+fn main() {
+    let schema = common::schema(10, 20, 12, 0);
+    assert_eq!(parse_services(&schema).unwrap().iter().map(|service| service.methods.len()).sum::<usize>(), 200);
+
+    common::bench("parse_services: 200 methods", || parse_services(&schema).unwrap());
+    common::bench("borrowed::parse_ref: 200 methods", || parse_ref(&schema).unwrap());
+    common::bench("borrowed::parse_ref + to_owned: 200 methods", || {
+        parse_ref(&schema).unwrap().iter().map(|service| service.to_owned()).collect::<Vec<_>>()
+    });
+}
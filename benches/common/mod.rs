@@ -0,0 +1,42 @@
+//!Minimal benchmark harness, run by `cargo bench`.
+
+#![allow(dead_code)]
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+///Runs `fun` repeatedly for about a second, printing average time of iteration.
+pub fn bench<T>(name: &str, mut fun: impl FnMut() -> T) {
+    for _ in 0..3 {
+        black_box(fun());
+    }
+
+    let mut iterations = 0u32;
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_secs(1) {
+        black_box(fun());
+        iterations += 1;
+    }
+    println!("{:<48} {:>12.2?}/iter ({} iterations)", name, start.elapsed() / iterations, iterations);
+}
+
+///Generates schema with `services` of `methods` each, using `types` distinct type names.
+///
+///Every service is preceded by `tables` table declarations, which are irrelevant to services.
+pub fn schema(services: usize, methods: usize, types: usize, tables: usize) -> String {
+    let mut schema = String::from("namespace Bench;\n\n");
+    let mut type_idx = 0;
+    for service in 0..services {
+        for table in 0..tables {
+            schema.push_str(&format!("///Table {service}.{table}\ntable Table{service}_{table} {{\n  id: ulong;\n  name: string (required);\n  tags: [string];\n}}\n\n"));
+        }
+        schema.push_str(&format!("///Service {service}\nrpc_service Service{service} {{\n"));
+        for method in 0..methods {
+            let (argument, result) = (type_idx % types, (type_idx + 1) % types);
+            type_idx += 1;
+            schema.push_str(&format!("  ///Method {method}\n  Method{method}(Type{argument}, extra: Type{result}): Type{result} (streaming: \"none\");\n"));
+        }
+        schema.push_str("}\n\n");
+    }
+    schema
+}
@@ -0,0 +1,46 @@
+//!Interning of type names on schema with 300 methods
+//!
+//!Same schema is parsed with a dozen of distinct type names and with distinct type names everywhere,
+//!so that difference shows cost of type names which are not shared.
+
+mod common;
+
+use flatbuffers_tools::parse_services;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+///System allocator, counting allocations
+struct Counter;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counter {
+    #[inline]
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    #[inline]
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counter = Counter;
+
+fn main() {
+    for (name, types) in [("12 types", 12), ("300 types", 300)] {
+        let schema = common::schema(15, 20, types, 0);
+
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        let services = parse_services(&schema).unwrap();
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+        assert_eq!(services.iter().map(|service| service.methods.len()).sum::<usize>(), 300);
+        println!("{:<48} {:>12} allocations", format!("parse_services: 300 methods, {name}"), allocations);
+
+        common::bench(&format!("parse_services: 300 methods, {name}"), || parse_services(&schema).unwrap());
+    }
+}
@@ -0,0 +1,25 @@
+//!Serial vs parallel parsing of 180 schema files
+
+mod common;
+
+use flatbuffers_tools::fs::{parse_file, parse_files};
+
+fn main() {
+    let dir = std::env::temp_dir().join(format!("flatbuffers-tools-bench-parallel-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("to create directory");
+    let schema = common::schema(2, 10, 12, 20);
+    let paths: Vec<_> = (0..180).map(|idx| {
+        let path = dir.join(format!("schema{idx}.fbs"));
+        std::fs::write(&path, &schema).expect("to write schema");
+        path
+    }).collect();
+
+    common::bench("parse_file: 180 files serially", || {
+        paths.iter().map(|path| parse_file(path).unwrap()).collect::<Vec<_>>()
+    });
+    common::bench("fs::parse_files: 180 files", || {
+        parse_files(&paths).into_iter().map(|(_, result)| result.unwrap()).collect::<Vec<_>>()
+    });
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
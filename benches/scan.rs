@@ -0,0 +1,18 @@
+//!Extraction of services from schema, where services are either rare or dominate
+
+mod common;
+
+use flatbuffers_tools::borrowed::parse_ref;
+use flatbuffers_tools::parse_services;
+
+fn main() {
+    let rare = common::schema(1, 5, 12, 400);
+    let dominant = common::schema(20, 30, 12, 0);
+    for (name, schema) in [("services <1% of lines", &rare), ("services dominate", &dominant)] {
+        let service_lines = schema.split("rpc_service").skip(1).map(|service| service[..service.find('}').unwrap()].lines().count()).sum::<usize>();
+        println!("{}: {} bytes, {} of {} lines within services", name, schema.len(), service_lines, schema.lines().count());
+
+        common::bench(&format!("parse_services: {name}"), || parse_services(schema).unwrap());
+        common::bench(&format!("borrowed::parse_ref: {name}"), || parse_ref(schema).unwrap());
+    }
+}
@@ -0,0 +1,13 @@
+//!Runs whole pipeline over arbitrary input
+//!
+//!`cargo fuzz run pipeline corpus/pipeline -- -max_total_time=60`, where `corpus/pipeline` has regression inputs.
+#![no_main]
+
+use flatbuffers_tools::fuzz::parse_to_debug_string;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let dump = parse_to_debug_string(data);
+    //Dump must not depend on anything but input
+    assert_eq!(dump, parse_to_debug_string(data));
+});
@@ -0,0 +1,11 @@
+//!Generates code of rpc_service declarations within flatbuffers schema
+
+fn main() {
+    if let Err(error) = flatbuffers_tools::cli::run(std::env::args().skip(1)) {
+        eprintln!("{error}");
+        std::process::exit(match error {
+            flatbuffers_tools::cli::Error::Usage(_) => 2,
+            _ => 1,
+        });
+    }
+}
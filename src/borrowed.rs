@@ -0,0 +1,425 @@
+//!Zero-copy parsing of services
+//!
+//!Definitions borrow from the input instead of allocating owned strings, which requires the
+//!whole input as single `&str`.
+
+use crate::{check_identifier, check_type_name, find_unquoted, find_unquoted_any, offset_of, parse_attributes_ref, parse_header_ref, parse_namespace, strip_comment, strip_keyword, unquoted_bytes};
+use crate::{Argument, IdentifierKind, Interner, ParseError, ParseErrorAt, Position, RpcMethod, RpcService, Span, TypeRef};
+
+use std::borrow::Cow;
+
+///Attribute borrowed from input, pair of key and optional value.
+pub type AttributeRef<'a> = (&'a str, Option<&'a str>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///rpc method argument, borrowed from input
+///
+///Refer to [Argument] for details.
+pub struct ArgumentRef<'a> {
+    ///Argument's name, if declared
+    pub name: Option<&'a str>,
+    ///Type as written
+    pub ty: &'a str,
+}
+
+impl<'a> ArgumentRef<'a> {
+    fn parse(text: &'a str) -> Result<Self, ParseError> {
+        let argument = match text.split_once(':') {
+            Some((name, ty)) => {
+                let name = name.trim();
+                check_identifier(IdentifierKind::ArgumentName, name)?;
+                Self {
+                    name: Some(name),
+                    ty: ty.trim(),
+                }
+            },
+            None => Self {
+                name: None,
+                ty: text.trim(),
+            },
+        };
+        check_type_name(IdentifierKind::Argument, argument.ty)?;
+        Ok(argument)
+    }
+
+    #[inline]
+    ///Creates owned copy of the argument.
+    pub fn to_owned(&self) -> Argument {
+        self.to_owned_with(TypeRef::new)
+    }
+
+    #[inline]
+    #[allow(clippy::wrong_self_convention)]
+    fn to_owned_with<F: FnMut(&str) -> TypeRef>(&self, mut type_name: F) -> Argument {
+        Argument {
+            name: self.name.map(str::to_owned),
+            ty: type_name(self.ty),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+///rpc method, borrowed from input
+///
+///Refer to [RpcMethod] for details.
+pub struct RpcMethodRef<'a> {
+    ///Method's name
+    pub name: &'a str,
+    ///List of arguments
+    pub arguments: Vec<ArgumentRef<'a>>,
+    ///Return type as written.
+    pub return_type: &'a str,
+    ///List of attributes in order of definition.
+    pub attributes: Vec<AttributeRef<'a>>,
+    ///Documentation comments `///` preceding definition, one entry per line.
+    pub docs: Vec<&'a str>,
+    ///Location of definition
+    pub span: Span,
+}
+
+impl<'a> RpcMethodRef<'a> {
+    pub(crate) fn parse(line: &'a str) -> Result<Self, ParseError> {
+        let line = strip_comment(line).trim();
+        let line = line.trim_end_matches(';').trim_end();
+        let method_args = match line.find(':') {
+            Some(idx) => &line[..idx],
+            None => return Err(ParseError::NoReturnType(line.to_owned())),
+        };
+
+        let (args_start, args_end) = match line.find('(') {
+            Some(start) => match line[start..].find(')') {
+                Some(end) => (start, start + end),
+                None => return Err(ParseError::InvalidMethodArgs(method_args.to_owned())),
+            },
+            None => return Err(ParseError::InvalidMethodArgs(method_args.to_owned())),
+        };
+        let name = line[..args_start].trim();
+        let args = line[args_start+1..args_end].trim();
+        let mut arguments = Vec::new();
+        if !args.is_empty() {
+            //Trailing comma is allowed
+            let args = args.strip_suffix(',').unwrap_or(args);
+            for argument in args.split(',') {
+                if argument.trim().is_empty() {
+                    return Err(ParseError::EmptyArgument(line[args_start..=args_end].to_owned()));
+                }
+                arguments.push(ArgumentRef::parse(argument)?);
+            }
+        }
+
+        let return_type = match line[args_end+1..].trim_start().strip_prefix(':') {
+            Some(return_type) => return_type.trim(),
+            //Arguments may be named, so colon can be within them
+            None => return Err(ParseError::InvalidMethodArgs(line[..=args_end].to_owned())),
+        };
+        //Attributes are optional and follow return type
+        let (return_type, attributes) = match return_type.find('(') {
+            Some(idx) => match return_type[idx+1..].strip_suffix(')') {
+                Some(attributes) => (return_type[..idx].trim_end(), parse_attributes_ref(attributes)?),
+                None => return Err(ParseError::InvalidAttributes(return_type[idx..].to_owned())),
+            },
+            None => (return_type, Vec::new()),
+        };
+        if return_type.is_empty() {
+            return Err(ParseError::NoReturnType(line.to_owned()));
+        }
+
+        check_identifier(IdentifierKind::Method, name)?;
+        check_type_name(IdentifierKind::ReturnType, return_type)?;
+
+        Ok(Self {
+            name,
+            arguments,
+            return_type,
+            attributes,
+            docs: Vec::new(),
+            span: Span::default(),
+        })
+    }
+
+    #[inline]
+    ///Returns whether method has `deprecated` attribute.
+    pub fn is_deprecated(&self) -> bool {
+        self.attributes.iter().any(|(key, _)| *key == "deprecated")
+    }
+
+    #[inline]
+    ///Creates owned copy of the method.
+    pub fn to_owned(&self) -> RpcMethod {
+        self.to_owned_with(TypeRef::new)
+    }
+
+    ///Creates owned copy of the method, using `type_name` to copy type names.
+    pub(crate) fn to_owned_with<F: FnMut(&str) -> TypeRef>(&self, mut type_name: F) -> RpcMethod {
+        RpcMethod {
+            name: self.name.to_owned(),
+            arguments: self.arguments.iter().map(|argument| argument.to_owned_with(&mut type_name)).collect(),
+            return_type: type_name(self.return_type),
+            attributes: self.attributes.iter().map(|(key, value)| ((*key).to_owned(), value.map(str::to_owned))).collect(),
+            docs: self.docs.iter().map(|doc| (*doc).to_owned()).collect(),
+            span: self.span,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+///rpc_service definition, borrowed from input
+///
+///Refer to [RpcService] for details.
+pub struct RpcServiceRef<'a> {
+    ///Service name
+    pub name: &'a str,
+    ///List of service methods
+    pub methods: Vec<RpcMethodRef<'a>>,
+    ///Documentation comments `///` preceding definition, one entry per line.
+    pub docs: Vec<&'a str>,
+    ///Namespace in effect at the definition
+    pub namespace: Option<&'a str>,
+    ///List of attributes in order of definition.
+    pub attributes: Vec<AttributeRef<'a>>,
+    ///Location of definition
+    pub span: Span,
+}
+
+impl<'a> RpcServiceRef<'a> {
+    ///Creates owned copy of the service, with type names shared between its methods.
+    ///
+    ///Code generators work with owned definitions only.
+    pub fn to_owned(&self) -> RpcService {
+        let mut interner = Interner::default();
+        RpcService {
+            name: self.name.to_owned(),
+            methods: self.methods.iter().map(|method| method.to_owned_with(|name| interner.intern(name))).collect(),
+            docs: self.docs.iter().map(|doc| (*doc).to_owned()).collect(),
+            namespace: self.namespace.map(str::to_owned),
+            attributes: self.attributes.iter().map(|(key, value)| ((*key).to_owned(), value.map(str::to_owned))).collect(),
+            span: self.span,
+        }
+    }
+}
+
+///Source text, with comments replaced by whitespace, so that offsets are the same as in original.
+struct Source<'a> {
+    text: &'a str,
+    stripped: Cow<'a, str>,
+    //Documentation comments with offsets of their lines
+    docs: Vec<(usize, &'a str)>,
+    //Offset of every line start
+    lines: Vec<usize>,
+}
+
+impl<'a> Source<'a> {
+    fn new(text: &'a str) -> Result<Self, ParseErrorAt> {
+        let mut lines = vec![0];
+        lines.extend(text.match_indices('\n').map(|(idx, _)| idx + 1));
+
+        let mut source = Self {
+            text,
+            stripped: Cow::Borrowed(text),
+            docs: Vec::new(),
+            lines,
+        };
+
+        if !text.contains('/') {
+            return Ok(source);
+        }
+
+        let mut stripped = text.as_bytes().to_owned();
+        let mut idx = 0;
+        //Search starts outside of quoted strings, as it starts after comment or `/`
+        while let Some(start) = unquoted_bytes(&text[idx..]).find(|(_, byte)| *byte == b'/').map(|(start, _)| idx + start) {
+            let comment = &text[start..];
+            let end = if let Some(block) = comment.strip_prefix("/*") {
+                match block.find("*/") {
+                    Some(end) => start + 2 + end + 2,
+                    None => return Err(ParseError::UnterminatedComment.at(source.position(start).line)),
+                }
+            } else if comment.starts_with("//") {
+                let end = start + comment.find('\n').unwrap_or(comment.len());
+                let line_start = source.lines[source.line_idx(start)];
+                if let Some(doc) = comment.strip_prefix("///") {
+                    //Same as original parser, documentation must be on the line of its own.
+                    if text[line_start..start].trim().is_empty() {
+                        let doc = &doc[..end - start - 3];
+                        let doc = doc.strip_suffix('\r').unwrap_or(doc);
+                        source.docs.push((start, doc.strip_prefix(' ').unwrap_or(doc)));
+                    }
+                }
+                end
+            } else {
+                idx = start + 1;
+                continue;
+            };
+
+            for byte in stripped[start..end].iter_mut() {
+                if *byte != b'\n' {
+                    *byte = b' ';
+                }
+            }
+            idx = end;
+        }
+
+        //Every byte of comment is replaced with ASCII space, which keeps text valid UTF-8
+        source.stripped = match String::from_utf8(stripped) {
+            Ok(stripped) => Cow::Owned(stripped),
+            Err(_) => unreachable!(),
+        };
+        Ok(source)
+    }
+
+    #[inline]
+    fn line_idx(&self, offset: usize) -> usize {
+        match self.lines.binary_search(&offset) {
+            Ok(idx) => idx,
+            Err(idx) => idx - 1,
+        }
+    }
+
+    #[inline]
+    fn position(&self, offset: usize) -> Position {
+        let idx = self.line_idx(offset);
+        Position::new(idx + 1, offset - self.lines[idx])
+    }
+
+    #[inline]
+    ///Returns the same slice of the original text as `slice` of stripped one.
+    fn original(&self, slice: &str) -> &'a str {
+        let start = offset_of(&self.stripped, slice);
+        &self.text[start..start + slice.len()]
+    }
+
+    ///Takes documentation comments preceding `offset`, starting with `docs_idx`
+    fn take_docs(&self, docs_idx: &mut usize, offset: usize) -> Vec<&'a str> {
+        let mut docs = Vec::new();
+        while let Some((doc_offset, doc)) = self.docs.get(*docs_idx) {
+            if *doc_offset >= offset {
+                break;
+            }
+            docs.push(*doc);
+            *docs_idx += 1;
+        }
+        docs
+    }
+}
+
+///Parses all services within `text`, borrowing from it.
+///
+///Behaves the same as default (lenient) [ParserIter](crate::ParserIter), but stops on the first error.
+pub fn parse_ref(text: &str) -> Result<Vec<RpcServiceRef<'_>>, ParseErrorAt> {
+    const SERVICE: &str = "rpc_service";
+
+    //Same as original parser, byte order mark is not part of the first line
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let source = Source::new(text)?;
+    let stripped = source.stripped.as_ref();
+    let mut services = Vec::new();
+    let mut namespace = None;
+    let mut docs_idx = 0;
+    let mut pos = 0;
+
+    loop {
+        let rest = &stripped[pos..];
+        let trimmed = rest.trim_start();
+        if trimmed.is_empty() {
+            break;
+        }
+        let start = pos + offset_of(rest, trimmed);
+        let docs = source.take_docs(&mut docs_idx, start);
+
+        if let Some(header) = strip_keyword(trimmed, SERVICE) {
+            let start_line = source.position(start).line;
+            let (header, mut body_pos) = match find_unquoted_any(header, '{', ';') {
+                Some((idx, b'{')) => (&header[..idx], offset_of(stripped, header) + idx + 1),
+                _ => return Err(ParseError::NoStartingBracket.at(start_line)),
+            };
+            let (name, attributes) = parse_header_ref(header).map_err(|error| error.at(start_line))?;
+            check_identifier(IdentifierKind::Service, name).map_err(|error| error.at(start_line))?;
+
+            let mut methods: Vec<RpcMethodRef<'_>> = Vec::new();
+            let end = loop {
+                let body = &stripped[body_pos..];
+                let (idx, separator) = match find_unquoted_any(body, ';', '}') {
+                    Some(found) => found,
+                    None => return Err(ParseError::UnexpectedEof(name.to_owned()).at(start_line)),
+                };
+
+                let statement = body[..idx].trim();
+                if !statement.is_empty() {
+                    let statement_start = offset_of(stripped, statement);
+                    let statement_end = match separator {
+                        b';' => body_pos + idx,
+                        _ => statement_start + statement.len() - 1,
+                    };
+                    let docs = source.take_docs(&mut docs_idx, statement_start);
+                    let line = source.position(statement_start).line;
+                    let method = RpcMethodRef::parse(statement).map_err(|error| error.in_service(name, statement).at(line))?;
+                    if methods.iter().any(|prev| prev.name == method.name) {
+                        return Err(ParseError::DuplicateMethod {
+                            service: name.to_owned(),
+                            method: method.name.to_owned(),
+                        }.at(line));
+                    }
+
+                    methods.push(RpcMethodRef {
+                        name: source.original(method.name),
+                        arguments: method.arguments.iter().map(|argument| ArgumentRef {
+                            name: argument.name.map(|name| source.original(name)),
+                            ty: source.original(argument.ty),
+                        }).collect(),
+                        return_type: source.original(method.return_type),
+                        attributes: method.attributes.iter().map(|(key, value)| (source.original(key), value.map(|value| source.original(value)))).collect(),
+                        docs,
+                        span: Span {
+                            start: source.position(statement_start),
+                            end: source.position(statement_end),
+                        },
+                    });
+                }
+
+                body_pos += idx + 1;
+                if separator == b'}' {
+                    break body_pos - 1;
+                }
+            };
+            //Documentation at the end of body has nothing to describe
+            source.take_docs(&mut docs_idx, end);
+
+            services.push(RpcServiceRef {
+                name: source.original(name),
+                methods,
+                docs,
+                namespace,
+                attributes: attributes.iter().map(|(key, value)| (source.original(key), value.map(|value| source.original(value)))).collect(),
+                span: Span {
+                    start: source.position(start),
+                    end: source.position(end),
+                },
+            });
+            pos = end + 1;
+        } else if let Some(declaration) = strip_keyword(trimmed, "namespace") {
+            let line = source.position(start).line;
+            let end = match declaration.find(';') {
+                Some(end) => end,
+                None => return Err(ParseError::InvalidNamespace(declaration.trim().to_owned()).at(line)),
+            };
+            parse_namespace(&declaration[..=end]).map_err(|error| error.at(line))?;
+            namespace = Some(source.original(declaration[..end].trim()));
+            pos = offset_of(stripped, declaration) + end + 1;
+            //Same as flatc, documentation belongs only to the next definition
+            source.take_docs(&mut docs_idx, pos);
+        } else {
+            //Skip any other declaration, together with its body
+            pos = match find_unquoted_any(trimmed, ';', '{') {
+                Some((idx, b';')) => start + idx + 1,
+                Some((idx, _)) => match find_unquoted(&trimmed[idx..], '}') {
+                    Some(end) => start + idx + end + 1,
+                    None => break,
+                },
+                None => break,
+            };
+            source.take_docs(&mut docs_idx, pos);
+        }
+    }
+
+    Ok(services)
+}
@@ -0,0 +1,934 @@
+//!Code generation within build script
+//!
+//!```rust,no_run
+//!use flatbuffers_tools::{GenConfig, GenItem};
+//!
+//!let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("rpc.rs");
+//!let config = GenConfig::new().item(GenItem::Client);
+//!flatbuffers_tools::build::generate_from_file("schema/rpc.fbs", out, &config).expect("to generate");
+//!```
+
+use crate::gen;
+use crate::ident::KEYWORDS;
+use crate::{config, fs, Backend, GenConfig, GenError, HashAlgo, ParserIter, RpcService, RustBackend};
+
+use core::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub mod flatc;
+
+///Build script options of code generation
+///
+///By default, `cargo:rerun-if-changed` directive is printed to stdout for every schema and each file it includes,
+///making cargo to re-run build script once any of them changes.
+///
+///Each file is reported only once during lifetime of configuration, even if it is included by several schemas.
+pub struct BuildConfig {
+    is_rerun_if_changed: bool,
+    include_dirs: Vec<PathBuf>,
+    writer: Box<dyn io::Write>,
+    backend: Box<dyn Backend>,
+    //Canonical paths of already reported files
+    reported: Vec<PathBuf>,
+}
+
+impl BuildConfig {
+    ///Creates default configuration
+    pub fn new() -> Self {
+        Self {
+            is_rerun_if_changed: true,
+            include_dirs: Vec::new(),
+            writer: Box::new(io::stdout()),
+            backend: Box::new(RustBackend),
+            reported: Vec::new(),
+        }
+    }
+
+    #[inline]
+    ///Sets whether to emit `cargo:rerun-if-changed` directives.
+    ///
+    ///Defaults to `true`, disable it when not running within cargo build script.
+    pub fn rerun_if_changed(mut self, is_rerun_if_changed: bool) -> Self {
+        self.is_rerun_if_changed = is_rerun_if_changed;
+        self
+    }
+
+    #[inline]
+    ///Adds directory to look up included files, similarly to `flatc -I`.
+    ///
+    ///Refer to [fs::parse_file_with_include_dirs]
+    pub fn include_dir<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.include_dirs.push(dir.into());
+        self
+    }
+
+    #[inline]
+    ///Sets writer of build script directives, which is stdout by default.
+    pub fn writer<W: io::Write + 'static>(mut self, writer: W) -> Self {
+        self.writer = Box::new(writer);
+        self
+    }
+
+    #[inline]
+    ///Sets output language, which is Rust by default.
+    ///
+    ///Output files of [generate_from_dir_with] have extension of the `backend`.
+    pub fn backend<B: Backend + 'static>(mut self, backend: B) -> Self {
+        self.backend = Box::new(backend);
+        self
+    }
+
+    #[allow(clippy::result_large_err)]
+    ///Loads configuration of `schema` from explicit `path` or [config::FILE_NAME], found next to schema or within its parent directories.
+    ///
+    ///Environment variables are applied on top of it, refer to [config::load].
+    ///Unless disabled, `cargo:rerun-if-changed` of configuration file and `cargo:rerun-if-env-changed` of every variable are printed.
+    ///
+    ///```rust,no_run
+    ///use flatbuffers_tools::build::{self, BuildConfig};
+    ///
+    ///let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("rpc.rs");
+    ///let mut build = BuildConfig::new();
+    ///let config = build.load_config("schema/rpc.fbs", None).expect("to load configuration");
+    ///build::generate_from_file_with("schema/rpc.fbs", out, &config, &mut build).expect("to generate");
+    ///```
+    pub fn load_config<S: AsRef<Path>>(&mut self, schema: S, path: Option<&Path>) -> Result<GenConfig, BuildError> {
+        let path = match path {
+            Some(path) => Some(path.to_owned()),
+            None => config::find(schema),
+        };
+        if self.is_rerun_if_changed {
+            let mut directives = String::new();
+            if let Some(path) = path.as_ref() {
+                directives.push_str(&format!("cargo:rerun-if-changed={}\n", path.display()));
+            }
+            for name in config::env_vars() {
+                directives.push_str(&format!("cargo:rerun-if-env-changed={name}\n"));
+            }
+            if let Err(error) = self.writer.write_all(directives.as_bytes()) {
+                return Err(BuildError::Io(path.unwrap_or_default(), error));
+            }
+        }
+        config::load(None, path.as_deref()).map_err(BuildError::Config)
+    }
+
+    #[allow(clippy::result_large_err)]
+    ///Reports `schema` and every file it includes, unless already done.
+    fn rerun_if_changed_of(&mut self, schema: &Path) -> Result<(), BuildError> {
+        if !self.is_rerun_if_changed {
+            return Ok(());
+        }
+
+        let include_dirs: Vec<&Path> = self.include_dirs.iter().map(PathBuf::as_path).collect();
+        let files = fs::schema_files(schema, &include_dirs).map_err(BuildError::Schema)?;
+        for file in files {
+            if self.reported.contains(&file) {
+                continue;
+            }
+            let result = self.writer.write_fmt(format_args!("cargo:rerun-if-changed={}\n", file.display()));
+            if let Err(error) = result {
+                return Err(BuildError::Io(file, error));
+            }
+            self.reported.push(file);
+        }
+        Ok(())
+    }
+}
+
+impl Default for BuildConfig {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug)]
+///Error generating code from schema file
+pub enum BuildError {
+    ///Unable to parse schema
+    Schema(fs::Error),
+    ///Unable to generate code of service
+    Gen {
+        ///Schema file
+        path: PathBuf,
+        ///Line of service declaration
+        line: usize,
+        ///Error of code generation
+        error: GenError,
+    },
+    ///Schemas within directory map to the same module name
+    ModuleCollision {
+        ///Module name
+        name: String,
+        ///Schema, that first produced `name`
+        first: PathBuf,
+        ///Schema, that produced `name` again
+        second: PathBuf,
+    },
+    ///Unable to read directory or write output
+    Io(PathBuf, io::Error),
+    ///Unable to load configuration
+    Config(config::Error),
+    ///Unable to generate entry of manifest, refer to [run_manifest]
+    Entry {
+        ///Name of entry
+        name: String,
+        ///Error of generation
+        error: Box<BuildError>,
+    },
+}
+
+impl BuildError {
+    #[inline]
+    ///Returns path to the file where error occurred.
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Schema(error) => error.path(),
+            Self::Gen { path, .. } => path,
+            Self::ModuleCollision { second, .. } => second,
+            Self::Io(path, _) => path,
+            Self::Config(error) => error.path(),
+            Self::Entry { error, .. } => error.path(),
+        }
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Schema(error) => fmt::Display::fmt(error, fmt),
+            Self::Gen { path, line, error } => fmt.write_fmt(format_args!("{}:{}: {}", path.display(), line, error)),
+            Self::ModuleCollision { name, first, second } => fmt.write_fmt(format_args!("{}: module name '{}' is already used by {}", second.display(), name, first.display())),
+            Self::Io(path, error) => fmt.write_fmt(format_args!("{}: {}", path.display(), error)),
+            Self::Config(error) => fmt::Display::fmt(error, fmt),
+            Self::Entry { name, error } => fmt.write_fmt(format_args!("entry '{name}': {error}")),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Schema(error) => Some(error),
+            Self::Gen { error, .. } => Some(error),
+            Self::ModuleCollision { .. } => None,
+            Self::Io(_, error) => Some(error),
+            Self::Config(error) => Some(error),
+            Self::Entry { error, .. } => Some(error.as_ref()),
+        }
+    }
+}
+
+#[allow(clippy::result_large_err)]
+///Generates code of every service within `services` with `backend`, returning `None` if there is nothing to generate.
+///
+///Refer to [crate::render_services_with]
+fn generate_services(path: &Path, services: &[RpcService], config: &GenConfig, backend: &dyn Backend) -> Result<Option<String>, BuildError> {
+    if services.is_empty() {
+        return Ok(None);
+    }
+
+    gen::render_with(backend, services, config).map(Some).map_err(|(idx, error)| BuildError::Gen {
+        path: path.to_owned(),
+        line: services[idx].span.start.line,
+        error,
+    })
+}
+
+#[allow(clippy::result_large_err)]
+///Generates Rust code of every service within `schema` file, writing it into `out` file.
+///
+///Every service is placed within its own module, as generated by [RpcService::as_module_with],
+///containing method defines and items, selected by [GenConfig::item].
+///
+///Method ids are `u16` indexes, unless integer type is specified by `config`.
+///
+///`include` declarations are not generated, as included files are expected to be generated on their own,
+///but they are resolved to report every file, that schema depends on.
+///
+///Output starts with `// @generated` header, and schema without services produces only header.
+///Output file is not rewritten if its content is the same, refer to [write_if_changed].
+///
+///`cargo:rerun-if-changed` directives are printed to stdout, use [generate_from_file_with] to change it.
+pub fn generate_from_file<S: AsRef<Path>, O: AsRef<Path>>(schema: S, out: O, config: &GenConfig) -> Result<(), BuildError> {
+    generate_from_file_with(schema, out, config, &mut BuildConfig::new())
+}
+
+#[allow(clippy::result_large_err)]
+///Generates code of every service within `schema` file, writing it into `out` file, using `build` options.
+///
+///Code is generated by backend of `build`, which is Rust by default. Refer to [generate_from_file] for details.
+pub fn generate_from_file_with<S: AsRef<Path>, O: AsRef<Path>>(schema: S, out: O, config: &GenConfig, build: &mut BuildConfig) -> Result<(), BuildError> {
+    let schema = schema.as_ref();
+    let out = out.as_ref();
+
+    build.rerun_if_changed_of(schema)?;
+    let output = generate_file(schema, config, &*build.backend)?;
+    write_if_changed(out, output).map(|_| ()).map_err(|error| BuildError::Io(out.to_owned(), error))
+}
+
+#[allow(clippy::result_large_err)]
+///Generates content of output file for `schema` with `backend`, starting with `// @generated` header.
+///
+///Header contains `// source-hash: <hex>` line with hash of schema and generated code,
+///so that any change of either, including options affecting code, changes content of the file.
+///Lines of header start with line comment of `backend`, and header is omitted if there is none.
+fn generate_file(schema: &Path, config: &GenConfig, backend: &dyn Backend) -> Result<String, BuildError> {
+    let input = match std::fs::read_to_string(schema) {
+        Ok(input) => input,
+        Err(error) => return Err(BuildError::Schema(fs::Error::Io(schema.to_owned(), error))),
+    };
+    let mut services = Vec::new();
+    for service in ParserIter::new(input.lines()) {
+        match service {
+            Ok(service) => services.push(service),
+            Err(error) => return Err(BuildError::Schema(fs::Error::Parse(schema.to_owned(), error))),
+        }
+    }
+
+    let file_name = match schema.file_name() {
+        Some(file_name) => file_name.to_string_lossy(),
+        None => schema.to_string_lossy(),
+    };
+    let code = generate_services(schema, &services, config, backend)?.unwrap_or_default();
+    let mut output = String::new();
+    if let Some(comment) = backend.line_comment() {
+        let hash = HashAlgo::Fnv1a32.hash(&format!("{input}\0{code}"));
+        output = format!("{comment} @generated by flatbuffers-tools from `{file_name}`, do not edit.\n{comment} source-hash: {hash:08x}\n");
+    }
+    if !code.is_empty() {
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        output.push_str(&code);
+        if !code.ends_with('\n') {
+            output.push('\n');
+        }
+    }
+    Ok(output)
+}
+
+///Writes `contents` into file at `path`, unless it already has the same contents.
+///
+///Unchanged file is left untouched, keeping its modification time, so that it does not trigger rebuild of dependent crates.
+///
+///Returns whether file is written.
+pub fn write_if_changed<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> io::Result<bool> {
+    let path = path.as_ref();
+    let contents = contents.as_ref();
+    match std::fs::read(path) {
+        Ok(existing) if existing == contents => Ok(false),
+        _ => std::fs::write(path, contents).map(|_| true),
+    }
+}
+
+///Maximum number of differing lines, shown by [Drift::diff]
+const DRIFT_LINES: usize = 8;
+///Number of unchanged lines, shown around differing lines by [Drift::diff]
+const DRIFT_CONTEXT: usize = 3;
+///Maximum product of lengths of differing parts, that is compared line by line, to limit memory usage.
+const DRIFT_MAX_CELLS: usize = 1 << 22;
+
+///Returns edit script from `found` to `expected`, as `(sign, found index, expected index)` of every line.
+///
+///Sign is ` ` for unchanged line, `-` for line present only in `found` and `+` for line present only in `expected`.
+fn line_edits(found: &[&str], expected: &[&str]) -> Vec<(char, usize, usize)> {
+    let prefix = found.iter().zip(expected.iter()).take_while(|(found, expected)| found == expected).count();
+    let max_suffix = found.len().min(expected.len()) - prefix;
+    let suffix = found.iter().rev().zip(expected.iter().rev()).take(max_suffix).take_while(|(found, expected)| found == expected).count();
+    let old = &found[prefix..found.len() - suffix];
+    let new = &expected[prefix..expected.len() - suffix];
+
+    let mut edits: Vec<_> = (0..prefix).map(|idx| (' ', idx, idx)).collect();
+    if old.len().saturating_mul(new.len()) <= DRIFT_MAX_CELLS {
+        //Lengths of longest common subsequences of every pair of suffixes
+        let width = new.len() + 1;
+        let mut lcs = vec![0u32; (old.len() + 1) * width];
+        for i in (0..old.len()).rev() {
+            for j in (0..new.len()).rev() {
+                lcs[i * width + j] = if old[i] == new[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < old.len() || j < new.len() {
+            if i < old.len() && j < new.len() && old[i] == new[j] {
+                edits.push((' ', prefix + i, prefix + j));
+                i += 1;
+                j += 1;
+            } else if i < old.len() && (j == new.len() || lcs[(i + 1) * width + j] >= lcs[i * width + j + 1]) {
+                edits.push(('-', prefix + i, prefix + j));
+                i += 1;
+            } else {
+                edits.push(('+', prefix + i, prefix + j));
+                j += 1;
+            }
+        }
+    } else {
+        edits.extend((0..old.len()).map(|i| ('-', prefix + i, prefix)));
+        edits.extend((0..new.len()).map(|j| ('+', prefix + old.len(), prefix + j)));
+    }
+    edits.extend((0..suffix).map(|idx| (' ', found.len() - suffix + idx, expected.len() - suffix + idx)));
+    edits
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+///Difference between generated file and regenerated code
+pub struct Drift {
+    ///Generated file
+    pub path: PathBuf,
+    ///Line of the first difference within file, starting from 1.
+    pub line: usize,
+    ///Unified diff style summary of the first differing lines.
+    ///
+    ///Every hunk starts with `@@ -<line>,<len> +<line>,<len> @@`, followed by lines of file prefixed with `-`
+    ///and regenerated lines prefixed with `+`, surrounded by up to 3 unchanged lines prefixed with ` `.
+    ///At most 8 differing lines are shown, and the rest is summarized as `... <count> more differing lines`.
+    pub diff: String,
+}
+
+impl Drift {
+    ///Compares `found` content of file at `path` with `expected`, returning difference, if any.
+    ///
+    ///Line endings of `found` are normalized to `\n`, as generated code always uses them.
+    ///Lines are numbered after `offset` lines, that precede both contents.
+    fn between(path: &Path, found: &str, expected: &str, offset: usize) -> Option<Self> {
+        let found = found.replace("\r\n", "\n");
+        if found == expected {
+            return None;
+        }
+
+        let found: Vec<&str> = found.split('\n').collect();
+        let expected: Vec<&str> = expected.split('\n').collect();
+        let edits = line_edits(&found, &expected);
+        let changes: Vec<usize> = edits.iter().enumerate().filter(|(_, edit)| edit.0 != ' ').map(|(idx, _)| idx).collect();
+
+        let mut diff = String::new();
+        let mut next = 0;
+        let mut end = 0;
+        while next < changes.len() && next < DRIFT_LINES {
+            let start = changes[next].saturating_sub(DRIFT_CONTEXT).max(end);
+            let mut lines = String::new();
+            let (mut old_len, mut new_len) = (0, 0);
+            end = start;
+            while let Some(&(sign, old, new)) = edits.get(end) {
+                if sign == ' ' {
+                    let is_trailing = next > 0 && end - changes[next - 1] <= DRIFT_CONTEXT;
+                    let is_leading = next < changes.len() && next < DRIFT_LINES && changes[next] - end <= DRIFT_CONTEXT;
+                    if !is_trailing && !is_leading {
+                        break;
+                    }
+                } else if next == DRIFT_LINES {
+                    break;
+                } else {
+                    next += 1;
+                }
+
+                let line = if sign == '+' { expected[new] } else { found[old] };
+                lines.push('\n');
+                lines.push(sign);
+                lines.push_str(line);
+                if sign != '+' {
+                    old_len += 1;
+                }
+                if sign != '-' {
+                    new_len += 1;
+                }
+                end += 1;
+            }
+
+            let (_, old, new) = edits[start];
+            if !diff.is_empty() {
+                diff.push('\n');
+            }
+            diff.push_str(&format!("@@ -{},{} +{},{} @@", offset + old + 1, old_len, offset + new + 1, new_len));
+            diff.push_str(&lines);
+        }
+        if next < changes.len() {
+            diff.push_str(&format!("\n... {} more differing lines", changes.len() - next));
+        }
+
+        Some(Self {
+            path: path.to_owned(),
+            line: offset + edits[changes[0]].1 + 1,
+            diff,
+        })
+    }
+}
+
+#[derive(Debug)]
+///Error of conformance check of generated file
+pub enum ConformanceError {
+    ///Unable to regenerate code
+    Build(BuildError),
+    ///Generated file does not exist
+    Missing(PathBuf),
+    ///Generated file differs from regenerated code
+    Drift(Drift),
+}
+
+impl ConformanceError {
+    #[inline]
+    ///Returns path to the file where error occurred.
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Build(error) => error.path(),
+            Self::Missing(path) => path,
+            Self::Drift(drift) => &drift.path,
+        }
+    }
+}
+
+impl fmt::Display for ConformanceError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Build(error) => fmt::Display::fmt(error, fmt),
+            Self::Missing(path) => fmt.write_fmt(format_args!("{}: generated file does not exist", path.display())),
+            Self::Drift(drift) => fmt.write_fmt(format_args!("{}:{}: generated file is out of date\n{}", drift.path.display(), drift.line, drift.diff)),
+        }
+    }
+}
+
+impl std::error::Error for ConformanceError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Build(error) => Some(error),
+            Self::Missing(_) | Self::Drift(_) => None,
+        }
+    }
+}
+
+#[allow(clippy::result_large_err)]
+///Checks that `generated` file is up to date with `schema`, as if it is generated by [generate_from_file].
+///
+///Code is regenerated in memory and compared with content of the file, including `// @generated` header.
+///Header has no volatile parts, such as timestamp, hence its `source-hash` must match too,
+///while line endings of the file are not compared, so that `\r\n` checkouts conform.
+///
+///Intended to be run in CI, ensuring that committed code is regenerated after change of schema:
+///
+///```rust
+///use flatbuffers_tools::GenConfig;
+///use flatbuffers_tools::build::{self, BuildConfig, ConformanceError};
+///
+///let dir = std::env::temp_dir().join(format!("flatbuffers-tools-verify-{}", std::process::id()));
+///std::fs::create_dir_all(&dir).unwrap();
+///let schema = dir.join("rpc.fbs");
+///let out = dir.join("rpc.rs");
+///let _ = std::fs::remove_file(&out);
+///std::fs::write(&schema, "rpc_service Echo {\n  Ping(Req):Resp;\n}\n").unwrap();
+///let config = GenConfig::new();
+///
+///match build::verify(&schema, &out, &config) {
+///    Err(ConformanceError::Missing(path)) => assert_eq!(path, out),
+///    result => panic!("unexpected {:?}", result),
+///}
+///
+///build::generate_from_file_with(&schema, &out, &config, &mut BuildConfig::new().rerun_if_changed(false)).expect("to generate");
+///build::verify(&schema, &out, &config).expect("to match");
+///
+///std::fs::write(&schema, "rpc_service Echo {\n  Ping(Req):Resp;\n  Pong(Req):Resp;\n}\n").unwrap();
+///let drift = match build::verify(&schema, &out, &config) {
+///    Err(ConformanceError::Drift(drift)) => drift,
+///    result => panic!("unexpected {:?}", result),
+///};
+///assert_eq!(drift.path, out);
+///assert_eq!(drift.line, 2);
+///assert!(drift.diff.starts_with("@@ -1,"));
+///assert!(drift.diff.lines().any(|line| line.starts_with("-// source-hash: ")));
+///assert!(drift.diff.lines().any(|line| line.trim_end() == "+    pub const PONG: u16 = 1;"));
+///
+///std::fs::remove_dir_all(&dir).unwrap();
+///```
+pub fn verify<S: AsRef<Path>, G: AsRef<Path>>(schema: S, generated: G, config: &GenConfig) -> Result<(), ConformanceError> {
+    verify_with(schema, generated, config, &BuildConfig::new())
+}
+
+#[allow(clippy::result_large_err)]
+///Checks that `generated` file is up to date with `schema`, as if it is generated by [generate_from_file_with] using `build` options.
+///
+///No build script directives are printed. Refer to [verify] for details.
+pub fn verify_with<S: AsRef<Path>, G: AsRef<Path>>(schema: S, generated: G, config: &GenConfig, build: &BuildConfig) -> Result<(), ConformanceError> {
+    let expected = generate_file(schema.as_ref(), config, &*build.backend).map_err(ConformanceError::Build)?;
+    verify_output(generated, &expected)
+}
+
+#[allow(clippy::result_large_err)]
+///Checks that content of `generated` file is the same as `expected`, ignoring line endings of the file.
+///
+///Refer to [verify] to regenerate content of the file from schema.
+pub fn verify_output<G: AsRef<Path>>(generated: G, expected: &str) -> Result<(), ConformanceError> {
+    let generated = generated.as_ref();
+    let found = match std::fs::read_to_string(generated) {
+        Ok(found) => found,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Err(ConformanceError::Missing(generated.to_owned())),
+        Err(error) => return Err(ConformanceError::Build(BuildError::Io(generated.to_owned(), error))),
+    };
+    match Drift::between(generated, &found, expected, 0) {
+        Some(drift) => Err(ConformanceError::Drift(drift)),
+        None => Ok(()),
+    }
+}
+
+#[allow(clippy::result_large_err)]
+///Checks that `generated` Rust code is up to date with already parsed `services`, without accessing file system.
+///
+///Leading `// @generated` header, as written by [generate_from_file], is skipped, because its hash requires text of schema.
+///The rest is compared with code, regenerated in memory, same as [verify], but trailing whitespace is ignored.
+///
+///Errors refer to `<services>` and `<generated>` in place of paths.
+///
+///```rust
+///use flatbuffers_tools::{GenConfig, RpcService};
+///use flatbuffers_tools::build::{self, ConformanceError};
+///
+///let services = vec!["rpc_service Echo { Ping(Req):Resp; }".parse::<RpcService>().unwrap()];
+///let config = GenConfig::new();
+///let code = flatbuffers_tools::render_services(&services, &config).expect("to generate");
+///
+///build::verify_services(&services, &code, &config).expect("to match");
+///let file = format!("// @generated by flatbuffers-tools from `rpc.fbs`, do not edit.\n// source-hash: 01234567\n\n{code}\n");
+///build::verify_services(&services, &file, &config).expect("to match");
+///
+///let drifted = file.replace("PING", "PONG");
+///match build::verify_services(&services, &drifted, &config) {
+///    Err(ConformanceError::Drift(drift)) => {
+///        assert!(drift.line > 3);
+///        assert!(drift.diff.contains("PONG"));
+///    },
+///    result => panic!("unexpected {:?}", result),
+///}
+///```
+pub fn verify_services(services: &[RpcService], generated: &str, config: &GenConfig) -> Result<(), ConformanceError> {
+    let code = generate_services(Path::new("<services>"), services, config, &RustBackend).map_err(ConformanceError::Build)?.unwrap_or_default();
+
+    let mut offset = 0;
+    let mut rest = generated;
+    let mut lines = generated.split_inclusive('\n');
+    if let (Some(first), Some(second)) = (lines.next(), lines.next()) {
+        if first.starts_with("// @generated ") && second.starts_with("// source-hash: ") {
+            offset = 2;
+            rest = &generated[first.len() + second.len()..];
+            if let Some(code) = rest.strip_prefix('\n').or_else(|| rest.strip_prefix("\r\n")) {
+                offset = 3;
+                rest = code;
+            }
+        }
+    }
+    match Drift::between(Path::new("<generated>"), rest.trim_end(), code.trim_end(), offset) {
+        Some(drift) => Err(ConformanceError::Drift(drift)),
+        None => Ok(()),
+    }
+}
+
+///Converts file stem to valid Rust module name.
+///
+///Characters, not allowed in identifiers, are replaced with `_`, leading digit and keywords are escaped with `_`.
+fn module_name(stem: &str) -> String {
+    let mut name = String::with_capacity(stem.len() + 1);
+    if !stem.starts_with(|ch: char| ch.is_ascii_alphabetic() || ch == '_') {
+        name.push('_');
+    }
+    for ch in stem.chars() {
+        match ch {
+            ch if ch.is_ascii_alphanumeric() => name.push(ch),
+            _ => name.push('_'),
+        }
+    }
+    if name == "_" || KEYWORDS.contains(&name.as_str()) {
+        name.push('_');
+    }
+    name
+}
+
+///Collects `*.fbs` files within `dir` into `schemas`.
+fn find_schemas(dir: &Path, is_recursive: bool, schemas: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if is_recursive {
+                find_schemas(&path, is_recursive, schemas)?;
+            }
+        } else if path.extension().is_some_and(|ext| ext == "fbs") {
+            schemas.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[allow(clippy::result_large_err)]
+///Generates Rust code of every `*.fbs` schema within `dir`, writing it into `out_dir`.
+///
+///Each schema is generated by [generate_from_file] into `{module}.rs`, where `module` is file stem, converted to valid Rust identifier:
+///characters other than ASCII letters, digits and `_` are replaced with `_`, while leading digit and keywords are escaped with `_`.
+///
+///Sub-directories are searched only if [GenConfig::recursive] is enabled, but output is always placed directly within `out_dir`.
+///Schemas, that map to the same module name, are reported as error before anything is written.
+///
+///Schemas are parsed and generated concurrently, same as [fs::parse_files], but written in order of their paths,
+///returning paths of written files in the same order.
+///
+///`cargo:rerun-if-changed` directives are printed to stdout, use [generate_from_dir_with] to change it.
+pub fn generate_from_dir<D: AsRef<Path>, O: AsRef<Path>>(dir: D, out_dir: O, config: &GenConfig) -> Result<Vec<PathBuf>, BuildError> {
+    generate_from_dir_with(dir, out_dir, config, &mut BuildConfig::new())
+}
+
+#[allow(clippy::result_large_err)]
+///Generates code of every `*.fbs` schema within `dir`, writing it into `out_dir`, using `build` options.
+///
+///Code is generated by backend of `build`, and output files are named `{module}.{extension}` after its extension.
+///Refer to [generate_from_dir] for details.
+pub fn generate_from_dir_with<D: AsRef<Path>, O: AsRef<Path>>(dir: D, out_dir: O, config: &GenConfig, build: &mut BuildConfig) -> Result<Vec<PathBuf>, BuildError> {
+    let dir = dir.as_ref();
+    let out_dir = out_dir.as_ref();
+
+    let mut schemas = Vec::new();
+    find_schemas(dir, config.is_recursive, &mut schemas).map_err(|error| BuildError::Io(dir.to_owned(), error))?;
+    schemas.sort();
+
+    let mut outputs: Vec<(String, PathBuf)> = Vec::with_capacity(schemas.len());
+    for schema in schemas.iter() {
+        let name = match schema.file_stem() {
+            Some(stem) => module_name(&stem.to_string_lossy()),
+            None => continue,
+        };
+        if let Some((_, first)) = outputs.iter().find(|(used, _)| *used == name) {
+            return Err(BuildError::ModuleCollision {
+                name,
+                first: first.clone(),
+                second: schema.clone(),
+            });
+        }
+        outputs.push((name, schema.clone()));
+    }
+
+    //Schemas are parsed and generated concurrently, while output is written in order
+    let backend = &*build.backend;
+    let generated = fs::map_parallel(&outputs, |(_, schema)| generate_file(schema, config, backend));
+    let extension = backend.extension().to_owned();
+    let mut written = Vec::with_capacity(outputs.len());
+    for ((name, schema), output) in outputs.iter().zip(generated) {
+        let out = out_dir.join(format!("{name}.{extension}"));
+        build.rerun_if_changed_of(schema)?;
+        write_if_changed(&out, output?).map_err(|error| BuildError::Io(out.clone(), error))?;
+        written.push(out);
+    }
+    Ok(written)
+}
+
+///Name of file within state directory, where [run_manifest] keeps input hashes of generated entries
+pub const MANIFEST_STATE: &str = "fbs-rpc-manifest.state";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+///Summary of [run_manifest]
+pub struct ManifestSummary {
+    ///Names of regenerated entries, in order of manifest
+    pub regenerated: Vec<String>,
+    ///Names of entries, which inputs are unchanged since previous run, in order of manifest
+    pub skipped: Vec<String>,
+}
+
+impl fmt::Display for ManifestSummary {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_fmt(format_args!("regenerated {} of {} entries", self.regenerated.len(), self.regenerated.len() + self.skipped.len()))?;
+        if !self.regenerated.is_empty() {
+            fmt.write_fmt(format_args!(": {}", self.regenerated.join(", ")))?;
+        }
+        Ok(())
+    }
+}
+
+///Adds `bytes` to 64bit FNV-1a `hash`.
+fn fnv1a64(mut hash: u64, bytes: &[u8]) -> u64 {
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+#[allow(clippy::result_large_err)]
+///Returns hash of inputs of `entry`: version of this crate, output path, settings of profile, and path and content of every schema file.
+fn entry_hash(entry: &config::ManifestEntry, settings: &str, extension: &str, include_dirs: &[&Path]) -> Result<u64, BuildError> {
+    let mut hash = fnv1a64(0xcbf29ce484222325, concat!("flatbuffers-tools ", env!("CARGO_PKG_VERSION"), "\n").as_bytes());
+    hash = fnv1a64(hash, format!("{}\n{extension}\n{settings}\0", entry.out.display()).as_bytes());
+    let files = fs::schema_files(&entry.schema, include_dirs).map_err(BuildError::Schema)?;
+    for file in files {
+        let content = std::fs::read(&file).map_err(|error| BuildError::Schema(fs::Error::Io(file.clone(), error)))?;
+        hash = fnv1a64(hash, format!("{}\0", file.display()).as_bytes());
+        hash = fnv1a64(hash, &content);
+        hash = fnv1a64(hash, b"\0");
+    }
+    Ok(hash)
+}
+
+///Reads state of previous run, as pairs of entry name and input hash, ignoring malformed lines.
+fn read_state(path: &Path) -> Vec<(String, u64)> {
+    let text = std::fs::read_to_string(path).unwrap_or_default();
+    text.lines().filter_map(|line| {
+        let (hash, name) = line.split_once(' ')?;
+        Some((name.to_owned(), u64::from_str_radix(hash, 16).ok()?))
+    }).collect()
+}
+
+#[allow(clippy::result_large_err)]
+///Writes state of entries as line `{hash} {name}` per entry.
+fn write_state(dir: &Path, state: &[(String, u64)]) -> Result<(), BuildError> {
+    let path = dir.join(MANIFEST_STATE);
+    let mut text = String::new();
+    for (name, hash) in state {
+        text.push_str(&format!("{hash:016x} {name}\n"));
+    }
+    std::fs::create_dir_all(dir).and_then(|_| write_if_changed(&path, text)).map(|_| ()).map_err(|error| BuildError::Io(path, error))
+}
+
+#[allow(clippy::result_large_err)]
+///Generates every entry of [config::Manifest] at `path`, which inputs changed since previous run.
+///
+///State of runs is kept within `OUT_DIR` when running within build script, or within `target` next to manifest otherwise.
+///Refer to [run_manifest_with] for details.
+pub fn run_manifest<P: AsRef<Path>>(path: P) -> Result<ManifestSummary, BuildError> {
+    let path = path.as_ref();
+    let state_dir = match std::env::var_os("OUT_DIR") {
+        Some(out_dir) => PathBuf::from(out_dir),
+        None => path.parent().unwrap_or(Path::new("")).join("target"),
+    };
+    run_manifest_with(path, state_dir, &mut BuildConfig::new())
+}
+
+#[allow(clippy::result_large_err)]
+///Generates every entry of [config::Manifest] at `path`, which inputs changed since previous run, keeping state within `state_dir`, using `build` options.
+///
+///Input hash of entry covers schema and every file it includes, settings of its profile and output path,
+///which is stored within [MANIFEST_STATE] file of `state_dir`.
+///Entry is generated by [generate_from_file_with] if its hash differs from previous run, or its output does not exist,
+///creating parent directories of output.
+///
+///Entries are generated in order of manifest, stopping at the first error, that is reported as [BuildError::Entry] with name of entry.
+///State of previously generated entries is kept regardless.
+///
+///```rust
+///use flatbuffers_tools::build::{self, BuildConfig, BuildError};
+///
+///let dir = std::env::temp_dir().join(format!("flatbuffers-tools-manifest-{}", std::process::id()));
+///let _ = std::fs::remove_dir_all(&dir);
+///std::fs::create_dir_all(dir.join("schema")).unwrap();
+///std::fs::write(dir.join("schema").join("common.fbs"), "table Key {}\n").unwrap();
+///std::fs::write(dir.join("schema").join("storage.fbs"), "include \"common.fbs\";\nrpc_service Storage {\n  Get(Key):Key;\n}\n").unwrap();
+///std::fs::write(dir.join("schema").join("monitor.fbs"), "rpc_service Monitor {\n  Ping():Pong;\n}\n").unwrap();
+///let manifest = dir.join("fbs-rpc-manifest.toml");
+///let write_manifest = |monitor_profile: &str| std::fs::write(&manifest, format!(
+///    "[profile.async]\nasync = true\n\n[profile.plain]\n{monitor_profile}\n\n[[entry]]\nschema = \"schema/storage.fbs\"\nout = \"storage/src/generated/storage.rs\"\nprofile = \"async\"\n\n[[entry]]\nname = \"monitor\"\nschema = 'schema/monitor.fbs'\nout = \"monitor/src/generated/monitor.rs\"\nprofile = \"plain\"\n"
+///)).unwrap();
+///write_manifest("name_lookup = false");
+///let state = dir.join("target");
+///let run = || build::run_manifest_with(&manifest, &state, &mut BuildConfig::new().rerun_if_changed(false));
+///
+/////First run generates everything
+///let summary = run().unwrap();
+///assert_eq!(summary.regenerated, ["storage", "monitor"]);
+///assert!(summary.skipped.is_empty());
+///assert!(std::fs::read_to_string(dir.join("storage/src/generated/storage.rs")).unwrap().contains("pub fn route<"));
+///assert!(dir.join("target").join(build::MANIFEST_STATE).is_file());
+///
+/////Nothing changed
+///let summary = run().unwrap();
+///assert!(summary.regenerated.is_empty());
+///assert_eq!(summary.skipped, ["storage", "monitor"]);
+///assert_eq!(summary.to_string(), "regenerated 0 of 2 entries");
+///
+/////Edit of schema, including edit of included file, regenerates only its entry
+///std::fs::write(dir.join("schema").join("monitor.fbs"), "rpc_service Monitor {\n  Ping():Pong;\n  Stats():Pong;\n}\n").unwrap();
+///let summary = run().unwrap();
+///assert_eq!((summary.regenerated.as_slice(), summary.skipped.as_slice()), (&["monitor".to_owned()][..], &["storage".to_owned()][..]));
+///assert!(std::fs::read_to_string(dir.join("monitor/src/generated/monitor.rs")).unwrap().contains("pub const STATS: u16 = 1;"));
+///std::fs::write(dir.join("schema").join("common.fbs"), "table Key { id: uint; }\n").unwrap();
+///assert_eq!(run().unwrap().regenerated, ["storage"]);
+///
+/////Edit of profile regenerates only its entries, while comments are not part of profile
+///write_manifest("name_lookup = true");
+///let summary = run().unwrap();
+///assert_eq!(summary.to_string(), "regenerated 1 of 2 entries: monitor");
+///assert!(std::fs::read_to_string(dir.join("monitor/src/generated/monitor.rs")).unwrap().contains("pub const fn rpc_method_name(id: u16)"));
+///write_manifest("name_lookup = true #keep lookup");
+///assert!(run().unwrap().regenerated.is_empty());
+///
+/////Removed output is generated again
+///std::fs::remove_file(dir.join("storage/src/generated/storage.rs")).unwrap();
+///assert_eq!(run().unwrap().regenerated, ["storage"]);
+///
+/////Errors name entry
+///std::fs::write(dir.join("schema").join("monitor.fbs"), "rpc_service Monitor {\n  Ping(Req, Req):Pong;\n}\n").unwrap();
+///let error = run().unwrap_err();
+///assert!(matches!(&error, BuildError::Entry { name, .. } if name == "monitor"));
+///assert!(error.to_string().starts_with("entry 'monitor': "), "{error}");
+///write_manifest("name_lookup = true\n[[entry]]\nschema = \"schema/storage.fbs\"\nout = \"copy.rs\"");
+///assert_eq!(run().unwrap_err().to_string(), format!("{}:10: duplicate entry 'storage'", manifest.display()));
+///write_manifest("name_lookup = true\n[[entry]]\nschema = \"schema/storage.fbs\"\nprofile = \"sync\"");
+///assert!(run().unwrap_err().to_string().ends_with(":6: entry has no 'out'"));
+///std::fs::remove_dir_all(&dir).unwrap();
+///```
+pub fn run_manifest_with<P: AsRef<Path>, D: AsRef<Path>>(path: P, state_dir: D, build: &mut BuildConfig) -> Result<ManifestSummary, BuildError> {
+    let path = path.as_ref();
+    let state_dir = state_dir.as_ref();
+
+    if build.is_rerun_if_changed {
+        let result = build.writer.write_fmt(format_args!("cargo:rerun-if-changed={}\n", path.display()));
+        if let Err(error) = result {
+            return Err(BuildError::Io(path.to_owned(), error));
+        }
+    }
+    let manifest = config::Manifest::from_file(path).map_err(BuildError::Config)?;
+    let previous = read_state(&state_dir.join(MANIFEST_STATE));
+
+    let mut state: Vec<(String, u64)> = Vec::with_capacity(manifest.entries.len());
+    let mut summary = ManifestSummary::default();
+    for (idx, entry) in manifest.entries.iter().enumerate() {
+        match generate_entry(&manifest, entry, &previous, build) {
+            Ok((hash, is_generated)) => {
+                state.push((entry.name.clone(), hash));
+                match is_generated {
+                    true => summary.regenerated.push(entry.name.clone()),
+                    false => summary.skipped.push(entry.name.clone()),
+                }
+            },
+            Err(error) => {
+                //Entries, that are not reached, keep their previous state
+                let rest = manifest.entries.iter().skip(idx + 1).filter_map(|entry| previous.iter().find(|(name, _)| *name == entry.name));
+                state.extend(rest.cloned());
+                write_state(state_dir, &state)?;
+                return Err(BuildError::Entry {
+                    name: entry.name.clone(),
+                    error: Box::new(error),
+                });
+            },
+        }
+    }
+    write_state(state_dir, &state)?;
+    Ok(summary)
+}
+
+#[allow(clippy::result_large_err)]
+///Generates `entry` of `manifest`, unless its hash is within `previous` state, returning hash and whether it is generated.
+fn generate_entry(manifest: &config::Manifest, entry: &config::ManifestEntry, previous: &[(String, u64)], build: &mut BuildConfig) -> Result<(u64, bool), BuildError> {
+    let include_dirs: Vec<&Path> = build.include_dirs.iter().map(PathBuf::as_path).collect();
+    let hash = entry_hash(entry, manifest.settings(entry), build.backend.extension(), &include_dirs)?;
+    let is_unchanged = previous.iter().any(|(name, previous)| *name == entry.name && *previous == hash);
+    if is_unchanged && entry.out.is_file() {
+        build.rerun_if_changed_of(&entry.schema)?;
+        return Ok((hash, false));
+    }
+
+    if let Some(dir) = entry.out.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir).map_err(|error| BuildError::Io(dir.to_owned(), error))?;
+    }
+    generate_from_file_with(&entry.schema, &entry.out, &manifest.config(entry), build)?;
+    Ok((hash, true))
+}
@@ -0,0 +1,233 @@
+//!Invocation of `flatc` within build script
+//!
+//!```rust,no_run
+//!use flatbuffers_tools::GenConfig;
+//!use flatbuffers_tools::build::flatc::{Flatc, RpcOutput};
+//!
+//!let out_dir = std::env::var("OUT_DIR").unwrap();
+//!Flatc::new().schema("schema/rpc.fbs").out_dir(out_dir).rpc(RpcOutput::Sibling).run(&GenConfig::new()).expect("to generate");
+//!```
+
+use super::{generate_file, generate_services, write_if_changed, BuildError};
+use crate::{fs, GenConfig, RustBackend};
+
+use core::fmt;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+///Placement of generated rpc items relative to `flatc` output
+pub enum RpcOutput {
+    #[default]
+    ///Rpc items are not generated
+    None,
+    ///Rpc items are appended to `{stem}_generated.rs`, produced by `flatc`
+    Append,
+    ///Rpc items are written into sibling `{stem}_rpc.rs` file
+    Sibling,
+}
+
+#[derive(Debug)]
+///Error running `flatc`
+pub enum Error {
+    ///Unable to execute `flatc` binary
+    Spawn(PathBuf, io::Error),
+    ///`flatc` exited with failure
+    Failed {
+        ///Exit status
+        status: ExitStatus,
+        ///Output of `flatc` to stderr
+        stderr: String,
+    },
+    ///Unable to generate rpc items
+    Build(BuildError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Spawn(binary, error) => fmt.write_fmt(format_args!("unable to execute flatc '{}': {}", binary.display(), error)),
+            Self::Failed { status, stderr } => fmt.write_fmt(format_args!("flatc failed with {}: {}", status, stderr.trim_end())),
+            Self::Build(error) => fmt::Display::fmt(error, fmt),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Spawn(_, error) => Some(error),
+            Self::Failed { .. } => None,
+            Self::Build(error) => Some(error),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+///Builder of `flatc` invocation
+///
+///Runs `flatc --{lang} -o {out_dir} [-I {include_dir}]... {schema}...`, and then generates rpc items for every schema,
+///as specified by [RpcOutput]. Rpc items are Rust code, hence they should be generated with `rust` language only.
+pub struct Flatc {
+    binary: PathBuf,
+    include_dirs: Vec<PathBuf>,
+    lang: String,
+    schemas: Vec<PathBuf>,
+    out_dir: PathBuf,
+    rpc: RpcOutput,
+    is_dry_run: bool,
+}
+
+impl Flatc {
+    ///Creates new builder.
+    ///
+    ///Binary is taken from `FLATC` environment variable, or looked up as `flatc` within `PATH` otherwise.
+    pub fn new() -> Self {
+        let binary = match std::env::var_os("FLATC") {
+            Some(binary) => PathBuf::from(binary),
+            None => PathBuf::from("flatc"),
+        };
+
+        Self {
+            binary,
+            include_dirs: Vec::new(),
+            lang: "rust".to_owned(),
+            schemas: Vec::new(),
+            out_dir: PathBuf::from("."),
+            rpc: RpcOutput::None,
+            is_dry_run: false,
+        }
+    }
+
+    #[inline]
+    ///Sets path to `flatc` binary.
+    pub fn binary<P: Into<PathBuf>>(mut self, binary: P) -> Self {
+        self.binary = binary.into();
+        self
+    }
+
+    #[inline]
+    ///Adds directory to look up included files, passed as `-I`.
+    pub fn include_dir<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.include_dirs.push(dir.into());
+        self
+    }
+
+    #[inline]
+    ///Sets language of `flatc` output without leading dashes, `rust` by default.
+    pub fn lang(mut self, lang: &str) -> Self {
+        self.lang = lang.to_owned();
+        self
+    }
+
+    #[inline]
+    ///Adds schema file to generate.
+    pub fn schema<P: Into<PathBuf>>(mut self, schema: P) -> Self {
+        self.schemas.push(schema.into());
+        self
+    }
+
+    #[inline]
+    ///Sets output directory, current directory by default.
+    pub fn out_dir<P: Into<PathBuf>>(mut self, out_dir: P) -> Self {
+        self.out_dir = out_dir.into();
+        self
+    }
+
+    #[inline]
+    ///Sets placement of rpc items, which are not generated by default.
+    pub fn rpc(mut self, rpc: RpcOutput) -> Self {
+        self.rpc = rpc;
+        self
+    }
+
+    #[inline]
+    ///Sets whether to skip execution of `flatc`, while still generating rpc items.
+    pub fn dry_run(mut self, is_dry_run: bool) -> Self {
+        self.is_dry_run = is_dry_run;
+        self
+    }
+
+    ///Creates command to execute `flatc`.
+    pub fn command(&self) -> Command {
+        let mut command = Command::new(&self.binary);
+        command.arg(format!("--{}", self.lang));
+        command.arg("-o").arg(&self.out_dir);
+        for dir in self.include_dirs.iter() {
+            command.arg("-I").arg(dir);
+        }
+        command.args(self.schemas.iter());
+        command
+    }
+
+    #[allow(clippy::result_large_err)]
+    ///Executes `flatc`, unless dry run is set, and generates rpc items of every schema using `config`.
+    ///
+    ///Returns paths to files, receiving rpc items, in order of schemas.
+    pub fn run(&self, config: &GenConfig) -> Result<Vec<PathBuf>, Error> {
+        if !self.is_dry_run {
+            let output = match self.command().output() {
+                Ok(output) => output,
+                Err(error) => return Err(Error::Spawn(self.binary.clone(), error)),
+            };
+            if !output.status.success() {
+                return Err(Error::Failed {
+                    status: output.status,
+                    stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+                });
+            }
+        }
+
+        let mut written = Vec::new();
+        for schema in self.schemas.iter() {
+            let stem = match schema.file_stem() {
+                Some(stem) => stem.to_string_lossy(),
+                None => continue,
+            };
+            match self.rpc {
+                RpcOutput::None => (),
+                RpcOutput::Append => {
+                    let out = self.out_dir.join(format!("{stem}_generated.rs"));
+                    append_rpc(schema, &out, config).map_err(Error::Build)?;
+                    written.push(out);
+                },
+                RpcOutput::Sibling => {
+                    let out = self.out_dir.join(format!("{stem}_rpc.rs"));
+                    let output = generate_file(schema, config, &RustBackend).map_err(Error::Build)?;
+                    write_if_changed(&out, output).map_err(|error| Error::Build(BuildError::Io(out.clone(), error)))?;
+                    written.push(out);
+                },
+            }
+        }
+
+        Ok(written)
+    }
+}
+
+impl Default for Flatc {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(clippy::result_large_err)]
+///Appends rpc items of `schema` to `out`, doing nothing if there are no services.
+fn append_rpc(schema: &Path, out: &Path, config: &GenConfig) -> Result<(), BuildError> {
+    let services = fs::parse_file(schema).map_err(BuildError::Schema)?;
+    let code = match generate_services(schema, &services, config, &RustBackend)? {
+        Some(code) => code,
+        None => return Ok(()),
+    };
+
+    let file_name = match schema.file_name() {
+        Some(file_name) => file_name.to_string_lossy(),
+        None => schema.to_string_lossy(),
+    };
+    let result = std::fs::OpenOptions::new().create(true).append(true).open(out).and_then(|mut file| {
+        file.write_fmt(format_args!("\n// Rpc items generated by flatbuffers-tools from `{file_name}`\n\n{code}\n"))
+    });
+    result.map_err(|error| BuildError::Io(out.to_owned(), error))
+}
@@ -0,0 +1,192 @@
+use crate::{check_identifier, check_type_name, Argument, Attribute, IdentifierKind, ParseError, RpcMethod, RpcService, Span};
+
+#[derive(Debug, Clone)]
+///Builder of rpc method, validated by [RpcServiceBuilder::build]
+pub struct RpcMethodBuilder {
+    name: String,
+    arguments: Vec<Argument>,
+    return_type: String,
+    attributes: Vec<Attribute>,
+    docs: Vec<String>,
+}
+
+impl RpcMethodBuilder {
+    ///Creates method without arguments.
+    pub fn new(name: &str, return_type: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            arguments: Vec::new(),
+            return_type: return_type.to_owned(),
+            attributes: Vec::new(),
+            docs: Vec::new(),
+        }
+    }
+
+    #[inline]
+    ///Adds argument of type `ty`, which can be qualified with namespace.
+    pub fn argument(mut self, ty: &str) -> Self {
+        self.arguments.push(Argument {
+            name: None,
+            ty: ty.into(),
+        });
+        self
+    }
+
+    #[inline]
+    ///Adds argument with `name` of type `ty`, e.g. `from: Account`
+    pub fn named_argument(mut self, name: &str, ty: &str) -> Self {
+        self.arguments.push(Argument {
+            name: Some(name.to_owned()),
+            ty: ty.into(),
+        });
+        self
+    }
+
+    #[inline]
+    ///Adds attribute with `key` and optional `value`, e.g. `streaming: "server"`
+    pub fn attribute(mut self, key: &str, value: Option<&str>) -> Self {
+        self.attributes.push((key.to_owned(), value.map(str::to_owned)));
+        self
+    }
+
+    #[inline]
+    ///Adds line of documentation.
+    pub fn doc(mut self, line: &str) -> Self {
+        self.docs.push(line.to_owned());
+        self
+    }
+
+    ///Validates method, same as parser does.
+    fn build(self) -> Result<RpcMethod, ParseError> {
+        check_identifier(IdentifierKind::Method, &self.name)?;
+        for argument in self.arguments.iter() {
+            if let Some(name) = argument.name.as_ref() {
+                check_identifier(IdentifierKind::ArgumentName, name)?;
+            }
+            check_type_name(IdentifierKind::Argument, &argument.ty)?;
+        }
+        check_type_name(IdentifierKind::ReturnType, &self.return_type)?;
+        check_attributes(&self.attributes)?;
+
+        Ok(RpcMethod {
+            name: self.name,
+            arguments: self.arguments,
+            return_type: self.return_type.into(),
+            attributes: self.attributes,
+            docs: self.docs,
+            span: Span::default(),
+        })
+    }
+}
+
+fn check_attributes(attributes: &[Attribute]) -> Result<(), ParseError> {
+    match attributes.iter().find(|(key, _)| key.trim().is_empty()) {
+        Some((key, _)) => Err(ParseError::InvalidAttributes(key.clone())),
+        None => Ok(()),
+    }
+}
+
+#[derive(Debug, Clone)]
+///Builder of rpc service, enforcing the same rules as strict parser.
+///
+///```rust
+///use flatbuffers_tools::{RpcMethodBuilder, RpcServiceBuilder, RpcService};
+///
+///let built = RpcServiceBuilder::new("Storage").method("Put", &["Request"], "Response")
+///                                            .method_with(RpcMethodBuilder::new("Get", "Response").named_argument("key", "Key"))
+///                                            .build()
+///                                            .expect("valid service");
+///let parsed: RpcService = "rpc_service Storage {\n  Put(Request):Response;\n  Get(key: Key):Response;\n}".parse().unwrap();
+///assert_eq!(built, parsed);
+///assert_eq!(built.as_rpc_method_defines().to_string(), parsed.as_rpc_method_defines().to_string());
+///```
+pub struct RpcServiceBuilder {
+    name: String,
+    methods: Vec<RpcMethodBuilder>,
+    docs: Vec<String>,
+    namespace: Option<String>,
+    attributes: Vec<Attribute>,
+}
+
+impl RpcServiceBuilder {
+    ///Creates service without methods.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            methods: Vec::new(),
+            docs: Vec::new(),
+            namespace: None,
+            attributes: Vec::new(),
+        }
+    }
+
+    #[inline]
+    ///Adds method with unnamed arguments of types `args`.
+    pub fn method(self, name: &str, args: &[&str], return_type: &str) -> Self {
+        let method = args.iter().fold(RpcMethodBuilder::new(name, return_type), |method, ty| method.argument(ty));
+        self.method_with(method)
+    }
+
+    #[inline]
+    ///Adds method.
+    pub fn method_with(mut self, method: RpcMethodBuilder) -> Self {
+        self.methods.push(method);
+        self
+    }
+
+    #[inline]
+    ///Sets namespace of the service, e.g. `MyGame.Rpc`
+    pub fn namespace(mut self, namespace: &str) -> Self {
+        self.namespace = Some(namespace.to_owned());
+        self
+    }
+
+    #[inline]
+    ///Adds attribute with `key` and optional `value`.
+    pub fn attribute(mut self, key: &str, value: Option<&str>) -> Self {
+        self.attributes.push((key.to_owned(), value.map(str::to_owned)));
+        self
+    }
+
+    #[inline]
+    ///Adds line of documentation.
+    pub fn doc(mut self, line: &str) -> Self {
+        self.docs.push(line.to_owned());
+        self
+    }
+
+    ///Creates service, failing with the same error as parser would, if definition is invalid.
+    ///
+    ///Method names must be unique, and all names must be valid identifiers.
+    pub fn build(self) -> Result<RpcService, ParseError> {
+        check_identifier(IdentifierKind::Service, &self.name)?;
+        if let Some(namespace) = self.namespace.as_ref() {
+            if namespace.split('.').any(|part| !crate::is_identifier(part)) {
+                return Err(ParseError::InvalidNamespace(namespace.clone()));
+            }
+        }
+        check_attributes(&self.attributes)?;
+
+        let mut methods: Vec<RpcMethod> = Vec::with_capacity(self.methods.len());
+        for method in self.methods {
+            let method = method.build()?;
+            //Same as flatc, names are case sensitive
+            if methods.iter().any(|prev| prev.name == method.name) {
+                return Err(ParseError::DuplicateMethod {
+                    service: self.name,
+                    method: method.name,
+                });
+            }
+            methods.push(method);
+        }
+
+        Ok(RpcService {
+            name: self.name,
+            methods,
+            docs: self.docs,
+            namespace: self.namespace,
+            attributes: self.attributes,
+            span: Span::default(),
+        })
+    }
+}
@@ -0,0 +1,351 @@
+//!Command line interface of code generation
+//!
+//!Binary `fbs-rpc-gen` is thin wrapper over [run], which is available to use it without spawning process:
+//!
+//!```rust,no_run
+//!let args = ["schema/rpc.fbs", "--defines", "--trait", "--prefix", "RPC_", "-o", "src/rpc.rs"];
+//!flatbuffers_tools::cli::run(args.iter().copied()).expect("to generate");
+//!
+//!//Fails if committed output is out of date, e.g. within CI
+//!let args = ["schema/rpc.fbs", "--defines", "--trait", "--prefix", "RPC_", "-o", "src/rpc.rs", "--check"];
+//!flatbuffers_tools::cli::run(args.iter().copied()).expect("to be up to date");
+//!```
+
+use crate::build::{verify_output, ConformanceError};
+use crate::{config, fs, AsyncRouter, Backend, CBackend, GenConfig, GenError, IntType, RpcService, TsStyle, TypeScriptBackend, Visibility};
+
+use core::fmt;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+
+///Usage of command line interface
+pub const USAGE: &str = "Usage: fbs-rpc-gen <schema|-> [options]
+
+Generates code of rpc_service declarations within schema, reading stdin if schema is `-`.
+
+Options:
+    --defines               Emit method id constants (default)
+    --trait                 Emit handler trait and dispatcher
+    --enum                  Emit method enum
+    --dump-json             Emit JSON of parsed services instead of code
+    --lang <rust|c|ts>      Output language, rust by default
+    -o, --output <path>     Write output to file instead of stdout
+    --check                 Check that output file is up to date instead of writing it
+    --prefix <prefix>       Prefix of method constants' names
+    --visibility <vis>      Visibility of Rust items: pub, crate, super or private
+    --async                 Generate async router instead of dispatch function
+    --snake-case            Name TypeScript method ids as upper snake case constants
+    --config <path>         Read configuration from file instead of fbs-rpc.toml next to schema
+    -h, --help              Print this message
+";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///Output language
+pub enum Lang {
+    ///Rust code
+    Rust,
+    ///C header, generated by [crate::RpcCHeader]
+    C,
+    ///TypeScript module, generated by [crate::RpcTypeScript]
+    TypeScript,
+}
+
+#[derive(Debug)]
+///Error of command line interface
+pub enum Error {
+    ///Invalid command line arguments
+    Usage(String),
+    ///Unable to read or parse schema
+    Schema(fs::Error),
+    ///Unable to generate code
+    Gen(GenError),
+    ///Unable to write output
+    Io(PathBuf, io::Error),
+    ///Output file differs from generated code, reported by `--check`
+    Check(Box<ConformanceError>),
+    ///Unable to load configuration
+    Config(config::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Usage(error) => fmt.write_fmt(format_args!("{error}\n\n{USAGE}")),
+            Self::Schema(error) => fmt::Display::fmt(error, fmt),
+            Self::Gen(error) => fmt::Display::fmt(error, fmt),
+            Self::Io(path, error) => fmt.write_fmt(format_args!("{}: {}", path.display(), error)),
+            Self::Check(error) => fmt::Display::fmt(error, fmt),
+            Self::Config(error) => fmt::Display::fmt(error, fmt),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Usage(_) => None,
+            Self::Schema(error) => Some(error),
+            Self::Gen(error) => Some(error),
+            Self::Io(_, error) => Some(error),
+            Self::Check(error) => Some(&**error),
+            Self::Config(error) => Some(error),
+        }
+    }
+}
+
+///Parsed command line arguments
+pub struct Args {
+    ///Schema file, `None` to read stdin
+    pub schema: Option<PathBuf>,
+    ///Output file, `None` to write stdout
+    pub output: Option<PathBuf>,
+    ///Configuration file, `None` to look for [config::FILE_NAME] next to schema
+    pub config_path: Option<PathBuf>,
+    ///Output language
+    pub lang: Lang,
+    ///Specifies whether to emit method id constants
+    pub is_defines: bool,
+    ///Specifies whether to emit handler trait and dispatcher
+    pub is_trait: bool,
+    ///Specifies whether to emit method enum
+    pub is_enum: bool,
+    ///Specifies whether to emit JSON of services instead of code, refer to [crate::json]
+    pub is_dump_json: bool,
+    ///Specifies whether to check output file instead of writing it
+    pub is_check: bool,
+    ///Specifies whether usage is requested
+    pub is_help: bool,
+    ///Configuration of generated code
+    pub config: GenConfig,
+}
+
+impl Args {
+    ///Parses arguments, excluding name of the program.
+    ///
+    ///Options are applied on top of configuration file and environment variables, refer to [config::load].
+    ///
+    ///```rust
+    ///use flatbuffers_tools::cli::{Args, Error};
+    ///
+    ///let dir = std::env::temp_dir().join(format!("flatbuffers-tools-cli-config-{}", std::process::id()));
+    ///std::fs::create_dir_all(&dir).unwrap();
+    ///std::fs::write(dir.join("fbs-rpc.toml"), "prefix = \"RPC_\"\nname_lookup = true\n").unwrap();
+    ///std::fs::write(dir.join("other.toml"), "prefix = \"OTHER_\"\n").unwrap();
+    ///let schema = dir.join("rpc.fbs");
+    ///std::fs::write(&schema, "rpc_service Storage { Get(Key):Value; }").unwrap();
+    ///let schema = schema.to_str().unwrap();
+    ///
+    ///let generate = |args: &[&str]| {
+    ///    let args = Args::parse(args.iter().copied()).unwrap();
+    ///    args.generate(&args.services().unwrap()).unwrap()
+    ///};
+    ///let code = generate(&[schema]);
+    ///assert!(code.contains("pub const RPC_GET: &str = \"Get\";"));
+    ///assert!(code.contains("pub const fn rpc_rpc_method_name(id: u16)"));
+    /////Options win over file
+    ///assert!(generate(&[schema, "--prefix", "API_"]).contains("pub const API_GET: &str = \"Get\";"));
+    ///let other = dir.join("other.toml");
+    ///let code = generate(&[schema, "--config", other.to_str().unwrap()]);
+    ///assert!(code.contains("pub const OTHER_GET: &str = \"Get\";"));
+    ///assert!(!code.contains("method_name"));
+    ///
+    ///let json = generate(&[schema, "--dump-json"]);
+    ///assert!(json.starts_with("{\n  \"version\": 1,\n  \"services\": [\n    {\n      \"name\": \"Storage\",\n"));
+    ///assert!(matches!(Args::parse([schema, "--dump-json", "--trait"]), Err(Error::Usage(_))));
+    ///
+    ///let missing = dir.join("missing.toml");
+    ///assert!(matches!(Args::parse([schema, "--config", missing.to_str().unwrap()]), Err(Error::Config(_))));
+    ///std::fs::remove_dir_all(&dir).unwrap();
+    ///```
+    pub fn parse<I: IntoIterator<Item = S>, S: Into<String>>(args: I) -> Result<Self, Error> {
+        let args: Vec<String> = args.into_iter().map(Into::into).collect();
+        //Schema and configuration file are known only once all arguments are parsed
+        let result = Self::parse_with(&args, GenConfig::new())?;
+        if result.is_help {
+            return Ok(result);
+        }
+        let config = config::load(result.schema.as_deref(), result.config_path.as_deref()).map_err(Error::Config)?;
+        Self::parse_with(&args, config)
+    }
+
+    fn parse_with(args: &[String], config: GenConfig) -> Result<Self, Error> {
+        let mut result = Self {
+            schema: None,
+            output: None,
+            config_path: None,
+            lang: Lang::Rust,
+            is_defines: false,
+            is_trait: false,
+            is_enum: false,
+            is_dump_json: false,
+            is_check: false,
+            is_help: false,
+            config,
+        };
+        let mut schema = None;
+
+        let mut args = args.iter().cloned();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| match args.next() {
+                Some(value) => Ok(value),
+                None => Err(Error::Usage(format!("Missing value of '{name}'"))),
+            };
+
+            match arg.as_str() {
+                "-h" | "--help" => result.is_help = true,
+                "--defines" => result.is_defines = true,
+                "--trait" => result.is_trait = true,
+                "--enum" => result.is_enum = true,
+                "--dump-json" => result.is_dump_json = true,
+                "--check" => result.is_check = true,
+                "--async" => result.config = result.config.router(AsyncRouter::new()),
+                "--snake-case" => result.config = result.config.ts_style(TsStyle::Constants),
+                "-o" | "--output" => result.output = Some(value(&arg)?.into()),
+                "--config" => result.config_path = Some(value(&arg)?.into()),
+                "--prefix" => result.config = result.config.prefix(&value(&arg)?),
+                "--lang" => result.lang = match value(&arg)?.as_str() {
+                    "rust" => Lang::Rust,
+                    "c" => Lang::C,
+                    "ts" => Lang::TypeScript,
+                    lang => return Err(Error::Usage(format!("Unknown language '{lang}'"))),
+                },
+                "--visibility" => {
+                    let visibility = match value(&arg)?.as_str() {
+                        "pub" => Visibility::Pub,
+                        "crate" => Visibility::PubCrate,
+                        "super" => Visibility::PubSuper,
+                        "private" => Visibility::Private,
+                        visibility => return Err(Error::Usage(format!("Unknown visibility '{visibility}'"))),
+                    };
+                    result.config = result.config.visibility(visibility);
+                },
+                option if option.starts_with('-') && option != "-" => return Err(Error::Usage(format!("Unknown option '{option}'"))),
+                _ => match schema {
+                    None => schema = Some(arg),
+                    Some(_) => return Err(Error::Usage(format!("Unexpected argument '{arg}'"))),
+                },
+            }
+        }
+
+        if result.is_help {
+            return Ok(result);
+        }
+        match schema {
+            Some(schema) => if schema != "-" {
+                result.schema = Some(schema.into());
+            },
+            None => return Err(Error::Usage("Missing schema".to_owned())),
+        }
+        if result.is_check && result.output.is_none() {
+            return Err(Error::Usage("'--check' requires '--output'".to_owned()));
+        }
+        if result.is_dump_json && (result.is_defines || result.is_trait || result.is_enum || result.lang != Lang::Rust) {
+            return Err(Error::Usage("'--dump-json' cannot be combined with options selecting code".to_owned()));
+        }
+        if result.lang != Lang::Rust && (result.is_trait || result.is_enum) {
+            return Err(Error::Usage("Only method ids can be generated for languages other than rust".to_owned()));
+        }
+        if !result.is_trait && !result.is_enum {
+            result.is_defines = true;
+        }
+        //Items must use the same ids
+        if result.is_trait || result.is_enum {
+            result.config = result.config.int_type(IntType::U16);
+        }
+
+        Ok(result)
+    }
+
+    ///Parses schema, reading stdin if it is not specified.
+    pub fn services(&self) -> Result<Vec<RpcService>, Error> {
+        match self.schema.as_ref() {
+            Some(schema) => fs::parse_file(schema).map_err(Error::Schema),
+            None => {
+                let path = PathBuf::from("<stdin>");
+                let mut schema = String::new();
+                if let Err(error) = io::stdin().read_to_string(&mut schema) {
+                    return Err(Error::Schema(fs::Error::Io(path, error)));
+                }
+                crate::parse_services(&schema).map_err(|error| Error::Schema(fs::Error::Parse(path, error)))
+            },
+        }
+    }
+
+    ///Generates code of every service within `services`, as selected by arguments.
+    ///
+    ///With `--dump-json`, JSON of `services` is produced instead.
+    pub fn generate(&self, services: &[RpcService]) -> Result<String, Error> {
+        if self.is_dump_json {
+            return crate::json::to_json(services).map_err(|error| Error::Gen(error.into()));
+        }
+        match self.lang {
+            Lang::Rust => (),
+            Lang::C => return self.generate_with(&CBackend, services),
+            Lang::TypeScript => return self.generate_with(&TypeScriptBackend, services),
+        }
+
+        let config = &self.config;
+        let mut output = Vec::new();
+        if self.is_trait {
+            if let Some(service) = services.first() {
+                output.push(service.as_rpc_dispatcher_with(config).codec().to_string());
+            }
+        }
+
+        for service in services.iter() {
+            if self.is_defines {
+                output.push(service.as_rpc_method_defines_with(config).render().map_err(Error::Gen)?);
+            }
+            if self.is_enum {
+                output.push(service.as_rpc_method_enum_with(config).render().map_err(Error::Gen)?);
+            }
+            if self.is_trait {
+                output.push(service.as_rpc_dispatcher_with(config).render().map_err(Error::Gen)?);
+            }
+        }
+
+        Ok(output.join("\n\n"))
+    }
+
+    #[inline]
+    ///Generates code of every service within `services` with `backend`, ignoring language and items selected by arguments.
+    pub fn generate_with(&self, backend: &dyn Backend, services: &[RpcService]) -> Result<String, Error> {
+        crate::render_services_with(backend, services, &self.config).map_err(Error::Gen)
+    }
+}
+
+///Runs command line interface with `args`, excluding name of the program.
+///
+///Writes generated code into output file or stdout, as specified by `args`.
+///With `--check`, output file is compared with generated code, refer to [verify_output].
+pub fn run<I: IntoIterator<Item = S>, S: Into<String>>(args: I) -> Result<(), Error> {
+    execute(args, None)
+}
+
+#[inline]
+///Runs command line interface with `args`, generating code with custom `backend`.
+///
+///Options, that select language and items, are ignored, refer to [Args::generate_with].
+pub fn run_with<I: IntoIterator<Item = S>, S: Into<String>>(args: I, backend: &dyn Backend) -> Result<(), Error> {
+    execute(args, Some(backend))
+}
+
+fn execute<I: IntoIterator<Item = S>, S: Into<String>>(args: I, backend: Option<&dyn Backend>) -> Result<(), Error> {
+    let args = Args::parse(args)?;
+    if args.is_help {
+        let _ = io::stdout().write_all(USAGE.as_bytes());
+        return Ok(());
+    }
+
+    let services = args.services()?;
+    let output = match backend {
+        Some(backend) if !args.is_dump_json => args.generate_with(backend, &services)?,
+        _ => args.generate(&services)?,
+    };
+    match args.output {
+        Some(path) if args.is_check => verify_output(path, &output).map_err(|error| Error::Check(Box::new(error))),
+        Some(path) => crate::build::write_if_changed(&path, output).map(|_| ()).map_err(|error| Error::Io(path, error)),
+        None => io::stdout().write_all(output.as_bytes()).map_err(|error| Error::Io(PathBuf::from("<stdout>"), error)),
+    }
+}
@@ -0,0 +1,854 @@
+//!Generation profiles, loaded from configuration file and environment
+//!
+//![GenConfig::from_file] reads configuration file, usually `fbs-rpc.toml`, that is found by [find] next to the schema or within one of its parent directories,
+//!so that every workspace member can share the same settings without repeating them within build scripts.
+//![GenConfig::from_env] applies overrides of environment variables on top of it, e.g. `FBS_RPC_ASYNC=1`.
+//!
+//!File is subset of TOML with one `key = value` per line, where value is boolean, string or single line array of strings.
+//!Keys are the same as names of [GenConfig] methods, while unknown keys are errors, so that typos are not ignored:
+//!
+//!```toml
+//!#Settings of every schema within workspace
+//!prefix = "RPC_"
+//!visibility = "crate"
+//!async = true
+//!name_lookup = true
+//!items = ["client", "descriptor"]
+//!item_attributes = ['#[allow(clippy::too_many_arguments)]']
+//!```
+//!
+//!| Key | Value |
+//!|-----|-------|
+//!| `prefix` | String |
+//!| `visibility` | `pub`, `crate`, `super` or `private` |
+//!| `int_type` | `u8`, `u16`, `u32` or `u64` |
+//!| `id_strategy` | `index` or `fnv1a32` |
+//!| `receiver` | `mut`, `ref` or `arc` |
+//!| `deprecated_policy` | `keep`, `annotate` or `omit` |
+//!| `ts_style` | `const_enum` or `constants` |
+//!| `proto_namespace` | `package` or `qualified` |
+//!| `async` | Boolean, whether [AsyncRouter] is generated with default settings |
+//!| `items` | Array of `method_enum`, `dispatcher`, `client`, `descriptor`, `type_aliases`, `markers`, `mock` or `instrumentation` |
+//!| `item_attributes`, `method_attributes` | Array of attributes |
+//!| `include_service_name`, `service_router`, `method_count`, `fingerprint`, `name_lookup`, `id_lookup`, `registry`, `skip_docs`, `send_sync`, `method_timeout`, `no_std`, `alloc`, `sorted`, `type_aliases`, `crate_method_trait`, `framing`, `client_codec`, `runtime`, `assertions`, `recursive` | Boolean |
+//!
+//!Environment variable of every key is [ENV_PREFIX] followed by the key in upper case, e.g. `FBS_RPC_NAME_LOOKUP`.
+//!Booleans are `1`, `true`, `0` or `false`, while arrays are separated by `,`, unless they are written as TOML array.
+//!Values of environment replace values of file, including arrays.
+//!
+//![Manifest] uses the same keys within its profiles, that are selected by entries of the manifest, refer to [build::run_manifest](crate::build::run_manifest).
+//!
+//!```rust
+//!use flatbuffers_tools::GenConfig;
+//!use flatbuffers_tools::config::{self, Error};
+//!
+//!let dir = std::env::temp_dir().join(format!("flatbuffers-tools-config-{}", std::process::id()));
+//!std::fs::create_dir_all(dir.join("schema")).unwrap();
+//!let path = dir.join(config::FILE_NAME);
+//!std::fs::write(&path, "#Shared settings\nprefix = \"RPC_\" #of constants\nasync = true\nname_lookup = true\nitems = [\"dispatcher\", 'client']\n").unwrap();
+//!assert_eq!(config::find(dir.join("schema").join("rpc.fbs")).unwrap().canonicalize().unwrap(), path.canonicalize().unwrap());
+//!
+//!let services = flatbuffers_tools::parse_services("rpc_service Storage { Get(Key):Value; }").unwrap();
+//!let config = GenConfig::from_file(&path).unwrap();
+//!let code = flatbuffers_tools::render_services(&services, &config).unwrap();
+//!assert!(code.contains("pub const RPC_GET: u16 = 0;"));
+//!assert!(code.contains("pub const fn rpc_rpc_method_name(id: u16)"));
+//!assert!(code.contains("pub fn route<"));
+//!assert!(code.contains("pub struct StorageClient<T: Transport>"));
+//!
+//!//Environment wins over file
+//!std::env::set_var("FBS_RPC_ASYNC", "0");
+//!std::env::set_var("FBS_RPC_PREFIX", "API_");
+//!let config = GenConfig::from_env(GenConfig::from_file(&path).unwrap()).unwrap();
+//!let code = flatbuffers_tools::render_services(&services, &config).unwrap();
+//!assert!(code.contains("pub const API_GET: u16 = 0;"));
+//!assert!(code.contains("pub const fn api_rpc_method_name(id: u16)"));
+//!assert!(code.contains("pub fn dispatch<"));
+//!assert!(!code.contains("pub fn route<"));
+//!std::env::remove_var("FBS_RPC_ASYNC");
+//!std::env::remove_var("FBS_RPC_PREFIX");
+//!
+//!std::env::set_var("FBS_RPC_VISIBILITY", "public");
+//!let error = GenConfig::from_env(GenConfig::new()).err().unwrap();
+//!assert_eq!(error.to_string(), "FBS_RPC_VISIBILITY: invalid value 'public'");
+//!std::env::remove_var("FBS_RPC_VISIBILITY");
+//!
+//!std::fs::write(&path, "prefix = \"RPC_\"\nname_lokup = true\n").unwrap();
+//!match GenConfig::from_file(&path) {
+//!    Err(Error::UnknownKey { line, key, .. }) => assert_eq!((line, key.as_str()), (2, "name_lokup")),
+//!    _ => panic!("key is to be unknown"),
+//!}
+//!std::fs::write(&path, "prefix = true\nvisibility = \"public\" #typo\n").unwrap();
+//!assert!(matches!(GenConfig::from_file(&path), Err(Error::InvalidValue { line: 1, .. })));
+//!std::fs::write(&path, "visibility = \"public\" #typo\n").unwrap();
+//!let error = GenConfig::from_file(&path).err().unwrap();
+//!assert!(error.to_string().ends_with(":1: invalid value 'public' of 'visibility'"));
+//!std::fs::write(&path, "[profile]\n").unwrap();
+//!assert!(matches!(GenConfig::from_file(&path), Err(Error::Syntax { line: 1, .. })));
+//!std::fs::remove_dir_all(&dir).unwrap();
+//!```
+
+use crate::{AsyncRouter, DeprecatedPolicy, GenConfig, GenItem, HashAlgo, IdStrategy, IntType, ProtoNamespace, Receiver, TsStyle, Visibility};
+
+use core::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+///Name of configuration file, that is found by [find]
+pub const FILE_NAME: &str = "fbs-rpc.toml";
+///Prefix of environment variables, that are applied by [GenConfig::from_env]
+pub const ENV_PREFIX: &str = "FBS_RPC_";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Kind {
+    Bool,
+    String,
+    List,
+}
+
+const KEYS: [(&str, Kind); 32] = [
+    ("prefix", Kind::String),
+    ("visibility", Kind::String),
+    ("int_type", Kind::String),
+    ("id_strategy", Kind::String),
+    ("receiver", Kind::String),
+    ("deprecated_policy", Kind::String),
+    ("ts_style", Kind::String),
+    ("proto_namespace", Kind::String),
+    ("async", Kind::Bool),
+    ("items", Kind::List),
+    ("item_attributes", Kind::List),
+    ("method_attributes", Kind::List),
+    ("include_service_name", Kind::Bool),
+    ("service_router", Kind::Bool),
+    ("method_count", Kind::Bool),
+    ("fingerprint", Kind::Bool),
+    ("name_lookup", Kind::Bool),
+    ("id_lookup", Kind::Bool),
+    ("registry", Kind::Bool),
+    ("skip_docs", Kind::Bool),
+    ("send_sync", Kind::Bool),
+    ("method_timeout", Kind::Bool),
+    ("no_std", Kind::Bool),
+    ("alloc", Kind::Bool),
+    ("sorted", Kind::Bool),
+    ("type_aliases", Kind::Bool),
+    ("crate_method_trait", Kind::Bool),
+    ("framing", Kind::Bool),
+    ("client_codec", Kind::Bool),
+    ("runtime", Kind::Bool),
+    ("assertions", Kind::Bool),
+    ("recursive", Kind::Bool),
+];
+
+#[derive(Debug)]
+///Error loading configuration
+pub enum Error {
+    ///Unable to read file
+    Io(PathBuf, io::Error),
+    ///Line is not `key = value`
+    Syntax {
+        ///Configuration file
+        path: PathBuf,
+        ///Line number, starting from 1
+        line: usize,
+        ///Line as written
+        text: String,
+    },
+    ///Key is not known
+    UnknownKey {
+        ///Configuration file
+        path: PathBuf,
+        ///Line number, starting from 1
+        line: usize,
+        ///Key as written
+        key: String,
+    },
+    ///Key is specified more than once
+    DuplicateKey {
+        ///Configuration file
+        path: PathBuf,
+        ///Line number of repeated key, starting from 1
+        line: usize,
+        ///Key as written
+        key: String,
+    },
+    ///Value is not valid for the key
+    InvalidValue {
+        ///Configuration file
+        path: PathBuf,
+        ///Line number, starting from 1
+        line: usize,
+        ///Key
+        key: String,
+        ///Value without quotes
+        value: String,
+    },
+    ///Entry of manifest lacks required key
+    MissingKey {
+        ///Manifest file
+        path: PathBuf,
+        ///Line number of entry, starting from 1
+        line: usize,
+        ///Required key
+        key: &'static str,
+    },
+    ///Name of manifest entry is used by another entry
+    DuplicateEntry {
+        ///Manifest file
+        path: PathBuf,
+        ///Line number of entry, starting from 1
+        line: usize,
+        ///Name of entry
+        name: String,
+    },
+    ///Entry of manifest refers to profile, that is not defined
+    UnknownProfile {
+        ///Manifest file
+        path: PathBuf,
+        ///Line number of `profile` key, starting from 1
+        line: usize,
+        ///Name of profile
+        name: String,
+    },
+    ///Value of environment variable is not valid for its key
+    InvalidEnv {
+        ///Name of environment variable
+        name: String,
+        ///Value of variable
+        value: String,
+    },
+}
+
+impl Error {
+    #[inline]
+    ///Returns path to configuration file, where error occurred, which is empty for environment variables.
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Io(path, _) => path,
+            Self::Syntax { path, .. } | Self::UnknownKey { path, .. } | Self::DuplicateKey { path, .. } | Self::InvalidValue { path, .. } => path,
+            Self::MissingKey { path, .. } | Self::DuplicateEntry { path, .. } | Self::UnknownProfile { path, .. } => path,
+            Self::InvalidEnv { .. } => Path::new(""),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, error) => fmt.write_fmt(format_args!("{}: {}", path.display(), error)),
+            Self::Syntax { path, line, text } => fmt.write_fmt(format_args!("{}:{}: expected 'key = value', found '{}'", path.display(), line, text)),
+            Self::UnknownKey { path, line, key } => fmt.write_fmt(format_args!("{}:{}: unknown key '{}'", path.display(), line, key)),
+            Self::DuplicateKey { path, line, key } => fmt.write_fmt(format_args!("{}:{}: duplicate key '{}'", path.display(), line, key)),
+            Self::InvalidValue { path, line, key, value } => fmt.write_fmt(format_args!("{}:{}: invalid value '{}' of '{}'", path.display(), line, value, key)),
+            Self::MissingKey { path, line, key } => fmt.write_fmt(format_args!("{}:{}: entry has no '{}'", path.display(), line, key)),
+            Self::DuplicateEntry { path, line, name } => fmt.write_fmt(format_args!("{}:{}: duplicate entry '{}'", path.display(), line, name)),
+            Self::UnknownProfile { path, line, name } => fmt.write_fmt(format_args!("{}:{}: unknown profile '{}'", path.display(), line, name)),
+            Self::InvalidEnv { name, value } => fmt.write_fmt(format_args!("{name}: invalid value '{value}'")),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(_, error) => Some(error),
+            Self::Syntax { .. } | Self::UnknownKey { .. } | Self::DuplicateKey { .. } | Self::InvalidValue { .. } | Self::InvalidEnv { .. } => None,
+            Self::MissingKey { .. } | Self::DuplicateEntry { .. } | Self::UnknownProfile { .. } => None,
+        }
+    }
+}
+
+enum Value {
+    Bool(bool),
+    String(String),
+    List(Vec<String>),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(value) => fmt.write_fmt(format_args!("{value}")),
+            Self::String(value) => fmt.write_str(value),
+            Self::List(values) => fmt.write_fmt(format_args!("{values:?}")),
+        }
+    }
+}
+
+///Parses string, starting with quote, returning it with the rest of text.
+fn parse_string(text: &str) -> Option<(String, &str)> {
+    let mut chars = text.char_indices();
+    let quote = chars.next()?.1;
+    let mut result = String::new();
+    while let Some((idx, ch)) = chars.next() {
+        match ch {
+            _ if ch == quote => return Some((result, &text[idx + 1..])),
+            //Literal strings have no escapes
+            '\\' if quote == '"' => result.push(match chars.next()?.1 {
+                '"' => '"',
+                '\\' => '\\',
+                'n' => '\n',
+                't' => '\t',
+                'r' => '\r',
+                _ => return None,
+            }),
+            ch => result.push(ch),
+        }
+    }
+    None
+}
+
+///Parses value, returning it with the rest of text.
+fn parse_value(text: &str) -> Option<(Value, &str)> {
+    let text = text.trim_start();
+    if let Some(rest) = text.strip_prefix("true") {
+        Some((Value::Bool(true), rest))
+    } else if let Some(rest) = text.strip_prefix("false") {
+        Some((Value::Bool(false), rest))
+    } else if let Some(mut rest) = text.strip_prefix('[') {
+        let mut list = Vec::new();
+        loop {
+            rest = rest.trim_start();
+            if let Some(rest) = rest.strip_prefix(']') {
+                return Some((Value::List(list), rest));
+            }
+            let (value, next) = parse_string(rest)?;
+            list.push(value);
+            rest = next.trim_start();
+            match rest.strip_prefix(',') {
+                Some(next) => rest = next,
+                None if rest.starts_with(']') => (),
+                None => return None,
+            }
+        }
+    } else if text.starts_with('"') || text.starts_with('\'') {
+        parse_string(text).map(|(value, rest)| (Value::String(value), rest))
+    } else {
+        None
+    }
+}
+
+///Parses value of environment variable, according to `kind` of its key.
+fn parse_env(value: &str, kind: Kind) -> Option<Value> {
+    match kind {
+        Kind::Bool => match value.trim() {
+            "1" | "true" => Some(Value::Bool(true)),
+            "0" | "false" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        Kind::String => Some(Value::String(value.to_owned())),
+        Kind::List if value.trim_start().starts_with('[') => match parse_value(value) {
+            Some((Value::List(list), rest)) if rest.trim().is_empty() => Some(Value::List(list)),
+            _ => None,
+        },
+        Kind::List => Some(Value::List(value.split(',').map(str::trim).filter(|value| !value.is_empty()).map(str::to_owned).collect())),
+    }
+}
+
+fn parse_item(item: &str) -> Option<GenItem> {
+    match item {
+        "method_enum" => Some(GenItem::MethodEnum),
+        "dispatcher" => Some(GenItem::Dispatcher),
+        "client" => Some(GenItem::Client),
+        "descriptor" => Some(GenItem::Descriptor),
+        "type_aliases" => Some(GenItem::TypeAliases),
+        "markers" => Some(GenItem::Markers),
+        "mock" => Some(GenItem::Mock),
+        "instrumentation" => Some(GenItem::Instrumentation),
+        _ => None,
+    }
+}
+
+///Applies `value` of `key` to `config`, returning `None` if value is not valid.
+fn apply(mut config: GenConfig, key: &str, value: Value) -> Option<GenConfig> {
+    let config = match (key, value) {
+        ("prefix", Value::String(prefix)) => config.prefix(&prefix),
+        ("visibility", Value::String(visibility)) => config.visibility(match visibility.as_str() {
+            "pub" => Visibility::Pub,
+            "crate" => Visibility::PubCrate,
+            "super" => Visibility::PubSuper,
+            "private" => Visibility::Private,
+            _ => return None,
+        }),
+        ("int_type", Value::String(int_type)) => config.int_type(match int_type.as_str() {
+            "u8" => IntType::U8,
+            "u16" => IntType::U16,
+            "u32" => IntType::U32,
+            "u64" => IntType::U64,
+            _ => return None,
+        }),
+        ("id_strategy", Value::String(id_strategy)) => config.id_strategy(match id_strategy.as_str() {
+            "index" => IdStrategy::Index,
+            "fnv1a32" => IdStrategy::Hash(HashAlgo::Fnv1a32),
+            _ => return None,
+        }),
+        ("receiver", Value::String(receiver)) => config.receiver(match receiver.as_str() {
+            "mut" => Receiver::Mut,
+            "ref" => Receiver::Ref,
+            "arc" => Receiver::Arc,
+            _ => return None,
+        }),
+        ("deprecated_policy", Value::String(policy)) => config.deprecated_policy(match policy.as_str() {
+            "keep" => DeprecatedPolicy::Keep,
+            "annotate" => DeprecatedPolicy::Annotate,
+            "omit" => DeprecatedPolicy::Omit,
+            _ => return None,
+        }),
+        ("ts_style", Value::String(style)) => config.ts_style(match style.as_str() {
+            "const_enum" => TsStyle::ConstEnum,
+            "constants" => TsStyle::Constants,
+            _ => return None,
+        }),
+        ("proto_namespace", Value::String(namespace)) => config.proto_namespace(match namespace.as_str() {
+            "package" => ProtoNamespace::Package,
+            "qualified" => ProtoNamespace::Qualified,
+            _ => return None,
+        }),
+        ("async", Value::Bool(is_async)) => {
+            config.router = match is_async {
+                true => Some(AsyncRouter::new()),
+                false => None,
+            };
+            config
+        },
+        ("items", Value::List(items)) => {
+            config.items.clear();
+            for item in items.iter() {
+                config = config.item(parse_item(item)?);
+            }
+            config
+        },
+        ("item_attributes", Value::List(attributes)) | ("method_attributes", Value::List(attributes)) => {
+            if attributes.iter().any(|attribute| attribute.trim().is_empty()) {
+                return None;
+            }
+            match key {
+                "item_attributes" => config.item_attributes = attributes,
+                _ => config.method_attributes = attributes,
+            }
+            config
+        },
+        ("include_service_name", Value::Bool(value)) => config.include_service_name(value),
+        ("service_router", Value::Bool(value)) => config.service_router(value),
+        ("method_count", Value::Bool(value)) => config.method_count(value),
+        ("fingerprint", Value::Bool(value)) => config.fingerprint(value),
+        ("name_lookup", Value::Bool(value)) => config.name_lookup(value),
+        ("id_lookup", Value::Bool(value)) => config.id_lookup(value),
+        ("registry", Value::Bool(value)) => config.registry(value),
+        ("skip_docs", Value::Bool(value)) => config.skip_docs(value),
+        ("send_sync", Value::Bool(value)) => config.send_sync(value),
+        ("method_timeout", Value::Bool(value)) => config.method_timeout(value),
+        ("no_std", Value::Bool(value)) => config.no_std(value),
+        ("alloc", Value::Bool(value)) => config.alloc(value),
+        ("sorted", Value::Bool(value)) => config.sorted(value),
+        ("type_aliases", Value::Bool(value)) => config.type_aliases(value),
+        ("crate_method_trait", Value::Bool(value)) => config.crate_method_trait(value),
+        ("framing", Value::Bool(value)) => config.framing(value),
+        ("client_codec", Value::Bool(value)) => config.client_codec(value),
+        ("runtime", Value::Bool(value)) => config.runtime(value),
+        ("assertions", Value::Bool(value)) => config.assertions(value),
+        ("recursive", Value::Bool(value)) => config.recursive(value),
+        _ => return None,
+    };
+    Some(config)
+}
+
+///Parses `key = value` of trimmed `line`.
+fn parse_key_value<'a>(line: &'a str, line_no: usize, path: &Path) -> Result<(&'a str, Value), Error> {
+    let syntax = || Error::Syntax {
+        path: path.to_owned(),
+        line: line_no,
+        text: line.to_owned(),
+    };
+    let (key, value) = match line.split_once('=') {
+        Some((key, value)) => (key.trim(), value),
+        None => return Err(syntax()),
+    };
+    if key.is_empty() || !key.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-') {
+        return Err(syntax());
+    }
+    match parse_value(value) {
+        Some((value, rest)) if rest.trim().is_empty() || rest.trim_start().starts_with('#') => Ok((key, value)),
+        _ => Err(syntax()),
+    }
+}
+
+///Applies `value` of `key` at line `line_no` to `config`, unless key is within already applied `keys`.
+fn apply_key<'a>(config: GenConfig, keys: &mut Vec<&'a str>, key: &'a str, value: Value, line_no: usize, path: &Path) -> Result<GenConfig, Error> {
+    if !KEYS.iter().any(|(name, _)| *name == key) {
+        return Err(Error::UnknownKey {
+            path: path.to_owned(),
+            line: line_no,
+            key: key.to_owned(),
+        });
+    }
+    if keys.contains(&key) {
+        return Err(Error::DuplicateKey {
+            path: path.to_owned(),
+            line: line_no,
+            key: key.to_owned(),
+        });
+    }
+    keys.push(key);
+
+    let text = value.to_string();
+    match apply(config, key, value) {
+        Some(config) => Ok(config),
+        None => Err(Error::InvalidValue {
+            path: path.to_owned(),
+            line: line_no,
+            key: key.to_owned(),
+            value: text,
+        }),
+    }
+}
+
+///Applies every line of configuration file `text` to `config`.
+fn parse(mut config: GenConfig, text: &str, path: &Path) -> Result<GenConfig, Error> {
+    let mut keys: Vec<&str> = Vec::new();
+    for (idx, line) in text.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let (key, value) = parse_key_value(trimmed, idx + 1, path)?;
+        config = apply_key(config, &mut keys, key, value, idx + 1, path)?;
+    }
+    Ok(config)
+}
+
+///Returns path to configuration file [FILE_NAME], looking within directory of `schema` and every parent directory of it.
+///
+///`schema` can be directory of schemas as well.
+pub fn find<P: AsRef<Path>>(schema: P) -> Option<PathBuf> {
+    let schema = schema.as_ref();
+    let dir = match schema.is_dir() {
+        true => schema,
+        false => schema.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new(".")),
+    };
+    let dir = std::fs::canonicalize(dir).unwrap_or_else(|_| dir.to_owned());
+    dir.ancestors().map(|dir| dir.join(FILE_NAME)).find(|path| path.is_file())
+}
+
+///Loads configuration of `schema`, which is explicit `path` or file found by [find], with overrides of environment applied.
+///
+///Without configuration file, environment is applied on top of default configuration.
+pub fn load(schema: Option<&Path>, path: Option<&Path>) -> Result<GenConfig, Error> {
+    let path = match path {
+        Some(path) => Some(path.to_owned()),
+        None => schema.and_then(find),
+    };
+    let config = match path {
+        Some(path) => GenConfig::from_file(path)?,
+        None => GenConfig::new(),
+    };
+    GenConfig::from_env(config)
+}
+
+///Returns names of environment variables, which are applied by [GenConfig::from_env].
+pub fn env_vars() -> impl Iterator<Item = String> {
+    KEYS.iter().map(|(key, _)| format!("{ENV_PREFIX}{}", key.to_ascii_uppercase()))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+///Entry of [Manifest], generating single schema
+pub struct ManifestEntry {
+    ///Name of entry, which is `name` key or file stem of schema
+    pub name: String,
+    ///Schema file, relative to directory of manifest
+    pub schema: PathBuf,
+    ///Output file, relative to directory of manifest
+    pub out: PathBuf,
+    ///Name of profile, which configures generation, default configuration if not specified
+    pub profile: Option<String>,
+}
+
+struct Profile {
+    name: String,
+    config: GenConfig,
+    //Settings as written, that make hash of the profile
+    settings: String,
+}
+
+///Manifest of generation, listing schemas of workspace with their outputs and profiles
+///
+///Manifest is TOML file of the same subset as configuration file, where every `[profile.NAME]` table defines [GenConfig] of its keys,
+///and every `[[entry]]` table has `schema` and `out`, with optional `profile` and `name`.
+///Entries are named by file stem of schema by default, and must have unique names.
+///
+///```toml
+///[profile.async]
+///async = true
+///items = ["dispatcher", "client"]
+///
+///[[entry]]
+///schema = "schema/storage.fbs"
+///out = "storage/src/generated/storage.rs"
+///profile = "async"
+///```
+///
+///It is generated by [build::run_manifest](crate::build::run_manifest).
+pub struct Manifest {
+    ///Entries in order of declaration
+    pub entries: Vec<ManifestEntry>,
+    profiles: Vec<Profile>,
+}
+
+impl Manifest {
+    ///Loads manifest from file at `path`, resolving paths of entries within its directory.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) => return Err(Error::Io(path.to_owned(), error)),
+        };
+        let mut manifest = Self::parse(&text, path)?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        for entry in manifest.entries.iter_mut() {
+            entry.schema = dir.join(&entry.schema);
+            entry.out = dir.join(&entry.out);
+        }
+        Ok(manifest)
+    }
+
+    ///Parses manifest `text` of file `path`, keeping paths of entries as written.
+    fn parse(text: &str, path: &Path) -> Result<Self, Error> {
+        enum Section {
+            Profile,
+            Entry,
+        }
+        //Entry as written, with line of its header and line of its profile
+        struct RawEntry {
+            line: usize,
+            name: Option<String>,
+            schema: Option<String>,
+            out: Option<String>,
+            profile: Option<(usize, String)>,
+        }
+
+        let mut profiles: Vec<Profile> = Vec::new();
+        let mut raw_entries: Vec<RawEntry> = Vec::new();
+        let mut section = None;
+        let mut keys: Vec<&str> = Vec::new();
+        for (idx, line) in text.lines().enumerate() {
+            let line_no = idx + 1;
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            let header = trimmed.split_once('#').map_or(trimmed, |(header, _)| header).trim_end();
+            if header == "[[entry]]" {
+                raw_entries.push(RawEntry {
+                    line: line_no,
+                    name: None,
+                    schema: None,
+                    out: None,
+                    profile: None,
+                });
+                section = Some(Section::Entry);
+                keys.clear();
+                continue;
+            }
+            if let Some(name) = header.strip_prefix("[profile.").and_then(|name| name.strip_suffix(']')) {
+                if name.is_empty() || !name.bytes().all(|byte| byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-') {
+                    return Err(Error::Syntax {
+                        path: path.to_owned(),
+                        line: line_no,
+                        text: trimmed.to_owned(),
+                    });
+                }
+                if profiles.iter().any(|profile| profile.name == name) {
+                    return Err(Error::DuplicateKey {
+                        path: path.to_owned(),
+                        line: line_no,
+                        key: format!("profile.{name}"),
+                    });
+                }
+                profiles.push(Profile {
+                    name: name.to_owned(),
+                    config: GenConfig::new(),
+                    settings: String::new(),
+                });
+                section = Some(Section::Profile);
+                keys.clear();
+                continue;
+            }
+
+            match (section.as_ref(), profiles.last_mut(), raw_entries.last_mut()) {
+                (Some(Section::Profile), Some(profile), _) => {
+                    let (key, value) = parse_key_value(trimmed, line_no, path)?;
+                    profile.settings.push_str(&format!("{key} = {value}\n"));
+                    let config = core::mem::take(&mut profile.config);
+                    profile.config = apply_key(config, &mut keys, key, value, line_no, path)?;
+                },
+                (Some(Section::Entry), _, Some(entry)) => {
+                    let (key, value) = parse_key_value(trimmed, line_no, path)?;
+                    let field = match key {
+                        "name" => &mut entry.name,
+                        "schema" => &mut entry.schema,
+                        "out" => &mut entry.out,
+                        "profile" => {
+                            if entry.profile.is_some() {
+                                return Err(Error::DuplicateKey {
+                                    path: path.to_owned(),
+                                    line: line_no,
+                                    key: key.to_owned(),
+                                });
+                            }
+                            match value {
+                                Value::String(name) => entry.profile = Some((line_no, name)),
+                                value => return Err(Error::InvalidValue {
+                                    path: path.to_owned(),
+                                    line: line_no,
+                                    key: key.to_owned(),
+                                    value: value.to_string(),
+                                }),
+                            }
+                            continue;
+                        },
+                        _ => return Err(Error::UnknownKey {
+                            path: path.to_owned(),
+                            line: line_no,
+                            key: key.to_owned(),
+                        }),
+                    };
+                    if field.is_some() {
+                        return Err(Error::DuplicateKey {
+                            path: path.to_owned(),
+                            line: line_no,
+                            key: key.to_owned(),
+                        });
+                    }
+                    match value {
+                        Value::String(value) if !value.is_empty() => *field = Some(value),
+                        value => return Err(Error::InvalidValue {
+                            path: path.to_owned(),
+                            line: line_no,
+                            key: key.to_owned(),
+                            value: value.to_string(),
+                        }),
+                    }
+                },
+                //Keys outside of tables are not expected
+                _ => return Err(Error::Syntax {
+                    path: path.to_owned(),
+                    line: line_no,
+                    text: trimmed.to_owned(),
+                }),
+            }
+        }
+
+        let mut entries: Vec<ManifestEntry> = Vec::with_capacity(raw_entries.len());
+        for entry in raw_entries {
+            let line = entry.line;
+            let missing = |key| Error::MissingKey {
+                path: path.to_owned(),
+                line,
+                key,
+            };
+            let schema = entry.schema.ok_or_else(|| missing("schema"))?;
+            let out = entry.out.ok_or_else(|| missing("out"))?;
+            let name = match entry.name {
+                Some(name) => name,
+                None => match Path::new(&schema).file_stem() {
+                    Some(stem) => stem.to_string_lossy().into_owned(),
+                    None => schema.clone(),
+                },
+            };
+            if entries.iter().any(|existing| existing.name == name) {
+                return Err(Error::DuplicateEntry {
+                    path: path.to_owned(),
+                    line: entry.line,
+                    name,
+                });
+            }
+            if let Some((line, profile)) = entry.profile.as_ref() {
+                if !profiles.iter().any(|existing| existing.name == *profile) {
+                    return Err(Error::UnknownProfile {
+                        path: path.to_owned(),
+                        line: *line,
+                        name: profile.clone(),
+                    });
+                }
+            }
+            entries.push(ManifestEntry {
+                name,
+                schema: schema.into(),
+                out: out.into(),
+                profile: entry.profile.map(|(_, profile)| profile),
+            });
+        }
+
+        Ok(Self {
+            entries,
+            profiles,
+        })
+    }
+
+    #[inline]
+    fn find_profile(&self, entry: &ManifestEntry) -> Option<&Profile> {
+        let name = entry.profile.as_deref()?;
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    #[inline]
+    ///Returns configuration of `entry`, which is default configuration without profile.
+    pub fn config(&self, entry: &ManifestEntry) -> GenConfig {
+        match self.find_profile(entry) {
+            Some(profile) => profile.config.clone(),
+            None => GenConfig::new(),
+        }
+    }
+
+    #[inline]
+    ///Returns settings of profile of `entry`, one `key = value` per line, that is empty without profile.
+    ///
+    ///Settings are normalized, hence comments and formatting of manifest do not change them.
+    pub fn settings(&self, entry: &ManifestEntry) -> &str {
+        match self.find_profile(entry) {
+            Some(profile) => &profile.settings,
+            None => "",
+        }
+    }
+}
+
+impl GenConfig {
+    ///Loads configuration from file at `path`, on top of default configuration.
+    ///
+    ///Refer to [module](crate::config) for format of the file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        match std::fs::read_to_string(path) {
+            Ok(text) => parse(Self::new(), &text, path),
+            Err(error) => Err(Error::Io(path.to_owned(), error)),
+        }
+    }
+
+    ///Applies environment variables with [ENV_PREFIX] on top of `base` configuration.
+    ///
+    ///Refer to [module](crate::config) for names and values of variables.
+    pub fn from_env(base: Self) -> Result<Self, Error> {
+        let mut config = base;
+        for ((key, kind), name) in KEYS.iter().zip(env_vars()) {
+            let value = match std::env::var_os(&name) {
+                Some(value) => value,
+                None => continue,
+            };
+            let invalid = |value: String| Error::InvalidEnv {
+                name: name.clone(),
+                value,
+            };
+            let value = match value.into_string() {
+                Ok(value) => value,
+                Err(value) => return Err(invalid(value.to_string_lossy().into_owned())),
+            };
+            config = match parse_env(&value, *kind).and_then(|parsed| apply(config, key, parsed)) {
+                Some(config) => config,
+                None => return Err(invalid(value)),
+            };
+        }
+        Ok(config)
+    }
+}
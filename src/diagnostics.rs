@@ -0,0 +1,238 @@
+//!Non-fatal diagnostics of parser
+//!
+//!Parser is silent by default. Warnings are collected only when requested, either via
+//![parse_with_diagnostics](crate::parse_with_diagnostics) or [ParserIter::with_diagnostics](crate::ParserIter::with_diagnostics):
+//!
+//!```rust
+//!use flatbuffers_tools::parse_with_diagnostics;
+//!use flatbuffers_tools::diagnostics::{Checks, Severity};
+//!
+//!let schema = "rpc_service Empty {}\r\n\
+//!              servce Typo {}\n\
+//!              rpc_service Echo {\n\
+//!                Echo(Message):Message;\n\
+//!              }\n";
+//!let (services, diagnostics) = parse_with_diagnostics(schema, Checks::ALL).expect("to parse");
+//!assert_eq!(services.len(), 2);
+//!
+//!let lines: Vec<_> = diagnostics.iter().map(|diagnostic| (diagnostic.check, diagnostic.line)).collect();
+//!assert_eq!(lines, [
+//!    (Checks::EMPTY_SERVICE, 1),
+//!    (Checks::MIXED_LINE_ENDINGS, 2),
+//!    (Checks::UNKNOWN_KEYWORD, 2),
+//!    (Checks::SAME_TYPES, 4),
+//!]);
+//!assert!(diagnostics.iter().all(|diagnostic| diagnostic.severity == Severity::Warning));
+//!assert_eq!(diagnostics[2].to_string(), "2: warning: unknown keyword 'servce', declaration is skipped");
+//!
+//!//Only selected checks are run
+//!let (_, diagnostics) = parse_with_diagnostics(schema, Checks::EMPTY_SERVICE | Checks::SAME_TYPES).expect("to parse");
+//!assert_eq!(diagnostics.len(), 2);
+//!let (_, diagnostics) = parse_with_diagnostics(schema, Checks::NONE).expect("to parse");
+//!assert!(diagnostics.is_empty());
+//!```
+//!
+//!Bodies of declarations, that are skipped by parser of services, are not checked for unknown keywords:
+//!
+//!```rust
+//!use flatbuffers_tools::{ParserIter, RpcService};
+//!use flatbuffers_tools::diagnostics::Checks;
+//!
+//!let schema = "table Request {\n  key: string;\n}\nenum Color : byte { Red, Green }\nunknown_thing;\n";
+//!let mut parser = ParserIter::from_str(schema).with_diagnostics(Checks::ALL);
+//!let services: Vec<RpcService> = parser.by_ref().collect::<Result<_, _>>().expect("to parse");
+//!assert!(services.is_empty());
+//!assert_eq!(parser.diagnostics().len(), 1);
+//!assert_eq!(parser.diagnostics()[0].line, 5);
+//!```
+
+use core::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+///Severity of diagnostic
+pub enum Severity {
+    ///Definition is valid, but likely is not what is intended
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warning => fmt.write_str("warning"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///Set of checks, performed by parser
+///
+///Checks are combined with `|`, e.g. `Checks::EMPTY_SERVICE | Checks::SAME_TYPES`
+pub struct Checks(u8);
+
+impl Checks {
+    ///No checks
+    pub const NONE: Self = Self(0);
+    ///Service has no methods
+    pub const EMPTY_SERVICE: Self = Self(1);
+    ///Method returns the same type as one of its arguments, e.g. `Put(Request):Request`
+    pub const SAME_TYPES: Self = Self(1 << 1);
+    ///Top level declaration starts with unknown keyword, and is skipped by parser
+    pub const UNKNOWN_KEYWORD: Self = Self(1 << 2);
+    ///Input mixes `\n` and `\r\n` line endings
+    ///
+    ///Parser receives lines with line endings stripped, so it is checked only by [parse_with_diagnostics](crate::parse_with_diagnostics).
+    pub const MIXED_LINE_ENDINGS: Self = Self(1 << 3);
+    ///All checks
+    pub const ALL: Self = Self(Self::EMPTY_SERVICE.0 | Self::SAME_TYPES.0 | Self::UNKNOWN_KEYWORD.0 | Self::MIXED_LINE_ENDINGS.0);
+
+    #[inline]
+    ///Returns whether all checks of `other` are within set.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    #[inline]
+    ///Adds checks of `other` to the set.
+    pub const fn with(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    #[inline]
+    ///Removes checks of `other` from the set.
+    pub const fn without(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    #[inline]
+    ///Returns whether set has no checks.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl Default for Checks {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl core::ops::BitOr for Checks {
+    type Output = Self;
+
+    #[inline(always)]
+    fn bitor(self, other: Self) -> Self {
+        self.with(other)
+    }
+}
+
+impl core::ops::BitOrAssign for Checks {
+    #[inline(always)]
+    fn bitor_assign(&mut self, other: Self) {
+        *self = self.with(other);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///Diagnostic of the parser, that does not prevent parsing
+pub struct Diagnostic {
+    ///Severity
+    pub severity: Severity,
+    ///Check that produced diagnostic
+    pub check: Checks,
+    ///Description of the issue
+    pub message: String,
+    ///Line number, starting from 1.
+    pub line: usize,
+}
+
+impl fmt::Display for Diagnostic {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_fmt(format_args!("{}: {}: {}", self.line, self.severity, self.message))
+    }
+}
+
+///Keywords of top level declarations, known to flatc
+const KEYWORDS: &[&str] = &["attribute", "enum", "file_extension", "file_identifier", "include", "namespace", "native_include", "root_type", "rpc_service", "struct", "table", "union"];
+
+///Diagnostics, collected by parser
+pub(crate) struct Collector {
+    checks: Checks,
+    //Nesting of brackets within skipped text
+    depth: usize,
+    pub(crate) diagnostics: Vec<Diagnostic>,
+}
+
+impl Collector {
+    #[inline]
+    pub(crate) const fn new(checks: Checks) -> Self {
+        Self {
+            checks,
+            depth: 0,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    #[inline]
+    ///Adds warning of `check`, if it is enabled.
+    pub(crate) fn warn<F: FnOnce() -> String>(&mut self, check: Checks, line: usize, message: F) {
+        if self.checks.contains(check) {
+            self.diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                check,
+                message: message(),
+                line,
+            });
+        }
+    }
+
+    ///Checks `text`, that is skipped by parser at `line`.
+    ///
+    ///Bodies of skipped declarations are tracked, so that only top level declarations are checked.
+    pub(crate) fn skipped(&mut self, line: usize, text: &str) {
+        if !self.checks.contains(Checks::UNKNOWN_KEYWORD) {
+            return;
+        }
+
+        if self.depth == 0 {
+            let len = text.bytes().take_while(|byte| byte.is_ascii_alphanumeric() || *byte == b'_').count();
+            let keyword = &text[..len];
+            if !keyword.is_empty() && !KEYWORDS.contains(&keyword) {
+                self.warn(Checks::UNKNOWN_KEYWORD, line, || format!("unknown keyword '{keyword}', declaration is skipped"));
+            }
+        }
+
+        self.depth = crate::bracket_depth(text, self.depth);
+    }
+
+    ///Checks that all lines of `text` end the same way, reporting only the first line that differs.
+    pub(crate) fn line_endings(&mut self, text: &str) {
+        if !self.checks.contains(Checks::MIXED_LINE_ENDINGS) {
+            return;
+        }
+
+        let mut first = None;
+        for (idx, line) in text.split_inclusive('\n').enumerate() {
+            let is_crlf = match line.strip_suffix('\n') {
+                Some(line) => line.ends_with('\r'),
+                //Last line has no line ending
+                None => break,
+            };
+            match first {
+                None => first = Some(is_crlf),
+                Some(first) if first != is_crlf => {
+                    let (ending, expected) = if is_crlf { ("\\r\\n", "\\n") } else { ("\\n", "\\r\\n") };
+                    self.warn(Checks::MIXED_LINE_ENDINGS, idx + 1, || format!("line ends with '{ending}', while previous lines end with '{expected}'"));
+                    return;
+                },
+                Some(_) => (),
+            }
+        }
+    }
+}
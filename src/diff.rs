@@ -0,0 +1,277 @@
+//!Differences between revisions of services
+//!
+//!```rust
+//!use flatbuffers_tools::RpcService;
+//!use flatbuffers_tools::diff::{diff_service, CompatPolicy};
+//!
+//!let old: RpcService = "rpc_service Storage { Put(Req):Resp; }".parse().unwrap();
+//!let new: RpcService = "rpc_service Storage { Put(Req):Resp; Get(Key):Resp; }".parse().unwrap();
+//!let diff = diff_service(&old, &new);
+//!assert_eq!(diff.added, ["Get"]);
+//!assert!(diff.is_backward_compatible(CompatPolicy::new()));
+//!```
+
+use crate::schema::Schema;
+use crate::{Argument, Attribute, RpcMethod, RpcService, TypeRef};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///Rules of backward compatibility.
+///
+///By default, only appending methods is compatible, as method ids are indexes of methods.
+pub struct CompatPolicy {
+    ///Specifies whether change of method position is compatible, e.g. when ids are hashes of names.
+    pub allow_reorder: bool,
+    ///Specifies whether change of method attributes is compatible.
+    pub allow_attribute_changes: bool,
+}
+
+impl CompatPolicy {
+    #[inline]
+    ///Creates default policy.
+    pub const fn new() -> Self {
+        Self {
+            allow_reorder: false,
+            allow_attribute_changes: false,
+        }
+    }
+
+    #[inline]
+    ///Sets whether change of method position is compatible.
+    pub const fn allow_reorder(mut self, allow_reorder: bool) -> Self {
+        self.allow_reorder = allow_reorder;
+        self
+    }
+
+    #[inline]
+    ///Sets whether change of method attributes is compatible.
+    pub const fn allow_attribute_changes(mut self, allow_attribute_changes: bool) -> Self {
+        self.allow_attribute_changes = allow_attribute_changes;
+        self
+    }
+}
+
+impl Default for CompatPolicy {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+///Change of method
+pub enum Change {
+    ///Argument types are changed, names of arguments are ignored.
+    Arguments {
+        ///Previous arguments
+        old: Vec<Argument>,
+        ///New arguments
+        new: Vec<Argument>,
+    },
+    ///Return type is changed
+    ReturnType {
+        ///Previous return type
+        old: TypeRef,
+        ///New return type
+        new: TypeRef,
+    },
+    ///Attributes are changed, ignoring their order.
+    Attributes {
+        ///Previous attributes
+        old: Vec<Attribute>,
+        ///New attributes
+        new: Vec<Attribute>,
+    },
+    ///Index of method within service is changed
+    Position {
+        ///Previous index
+        old: usize,
+        ///New index
+        new: usize,
+    },
+}
+
+impl Change {
+    #[inline]
+    ///Returns whether change is compatible according to `policy`.
+    pub fn is_backward_compatible(&self, policy: CompatPolicy) -> bool {
+        match self {
+            Self::Arguments { .. } | Self::ReturnType { .. } => false,
+            Self::Attributes { .. } => policy.allow_attribute_changes,
+            Self::Position { .. } => policy.allow_reorder,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+///Changes of method, present in both revisions
+pub struct MethodChange {
+    ///Method name
+    pub name: String,
+    ///List of changes
+    pub changes: Vec<Change>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+///Differences between revisions of service
+pub struct ServiceDiff {
+    ///Service name
+    pub name: String,
+    ///Names of methods, that are present only in new revision, in order of declaration.
+    pub added: Vec<String>,
+    ///Names of methods, that are present only in old revision, in order of declaration.
+    pub removed: Vec<String>,
+    ///Methods, that are changed, in order of declaration within new revision.
+    pub changed: Vec<MethodChange>,
+}
+
+impl ServiceDiff {
+    #[inline]
+    ///Returns whether revisions are the same.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    ///Returns whether clients of old revision can use new revision, according to `policy`.
+    ///
+    ///Removal of method is never compatible.
+    pub fn is_backward_compatible(&self, policy: CompatPolicy) -> bool {
+        self.removed.is_empty() && self.changed.iter().flat_map(|method| method.changes.iter()).all(|change| change.is_backward_compatible(policy))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+///Differences between revisions of schema
+pub struct SchemaDiff {
+    ///Names of services, that are present only in new revision, qualified by namespace.
+    pub added: Vec<String>,
+    ///Names of services, that are present only in old revision, qualified by namespace.
+    pub removed: Vec<String>,
+    ///Services, that are changed, in order of declaration within new revision.
+    pub changed: Vec<ServiceDiff>,
+}
+
+impl SchemaDiff {
+    #[inline]
+    ///Returns whether revisions have the same services.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    #[inline]
+    ///Returns whether clients of old revision can use new revision, according to `policy`.
+    ///
+    ///Removal of service is never compatible.
+    pub fn is_backward_compatible(&self, policy: CompatPolicy) -> bool {
+        self.removed.is_empty() && self.changed.iter().all(|service| service.is_backward_compatible(policy))
+    }
+}
+
+///Returns attributes sorted, so that their order is ignored.
+fn sorted_attributes(method: &RpcMethod) -> Vec<Attribute> {
+    let mut attributes = method.attributes.clone();
+    attributes.sort();
+    attributes
+}
+
+///Returns changes between revisions of method.
+fn diff_method(old_idx: usize, old: &RpcMethod, new_idx: usize, new: &RpcMethod) -> Vec<Change> {
+    let mut changes = Vec::new();
+    if !old.arguments.iter().map(|argument| &argument.ty).eq(new.arguments.iter().map(|argument| &argument.ty)) {
+        changes.push(Change::Arguments {
+            old: old.arguments.clone(),
+            new: new.arguments.clone(),
+        });
+    }
+    if old.return_type != new.return_type {
+        changes.push(Change::ReturnType {
+            old: old.return_type.clone(),
+            new: new.return_type.clone(),
+        });
+    }
+    let old_attributes = sorted_attributes(old);
+    let new_attributes = sorted_attributes(new);
+    if old_attributes != new_attributes {
+        changes.push(Change::Attributes {
+            old: old.attributes.clone(),
+            new: new.attributes.clone(),
+        });
+    }
+    if old_idx != new_idx {
+        changes.push(Change::Position {
+            old: old_idx,
+            new: new_idx,
+        });
+    }
+    changes
+}
+
+///Compares revisions of service, matching methods by name.
+///
+///Documentation is ignored.
+pub fn diff_service(old: &RpcService, new: &RpcService) -> ServiceDiff {
+    let mut diff = ServiceDiff {
+        name: new.name.clone(),
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+    };
+
+    for (new_idx, method) in new.methods.iter().enumerate() {
+        match old.methods.iter().position(|prev| prev.name == method.name) {
+            Some(old_idx) => {
+                let changes = diff_method(old_idx, &old.methods[old_idx], new_idx, method);
+                if !changes.is_empty() {
+                    diff.changed.push(MethodChange {
+                        name: method.name.clone(),
+                        changes,
+                    });
+                }
+            },
+            None => diff.added.push(method.name.clone()),
+        }
+    }
+    for method in old.methods.iter() {
+        if !new.methods.iter().any(|next| next.name == method.name) {
+            diff.removed.push(method.name.clone());
+        }
+    }
+
+    diff
+}
+
+///Returns name of `service`, qualified by its namespace.
+fn qualified_name(service: &RpcService) -> String {
+    match service.namespace.as_ref() {
+        Some(namespace) => format!("{namespace}.{}", service.name),
+        None => service.name.clone(),
+    }
+}
+
+///Compares services of schema revisions, matching services by name, qualified by namespace.
+pub fn diff_schema(old: &Schema, new: &Schema) -> SchemaDiff {
+    let is_same = |left: &RpcService, right: &RpcService| left.name == right.name && left.namespace == right.namespace;
+    let mut diff = SchemaDiff {
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+    };
+
+    for service in new.services.iter() {
+        match old.services.iter().find(|prev| is_same(prev, service)) {
+            Some(prev) => {
+                let service = diff_service(prev, service);
+                if !service.is_empty() {
+                    diff.changed.push(service);
+                }
+            },
+            None => diff.added.push(qualified_name(service)),
+        }
+    }
+    for service in old.services.iter() {
+        if !new.services.iter().any(|next| is_same(service, next)) {
+            diff.removed.push(qualified_name(service));
+        }
+    }
+
+    diff
+}
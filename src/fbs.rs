@@ -0,0 +1,759 @@
+//!Printing of definitions as flatbuffers schema
+//!
+//!Output is accepted by parser of this crate and `flatc`, hence modified definitions can be written back into schema:
+//!
+//!```rust
+//!use flatbuffers_tools::RpcService;
+//!
+//!let text = "/// Key-value storage
+//!rpc_service Storage (internal) {
+//!  /// Stores value
+//!  Put(key: MyGame.Key, value: Value): Response (idempotent, streaming: \"none\");
+//!  Get(MyGame.Key): Value;
+//!}";
+//!let service: RpcService = text.parse().unwrap();
+//!let printed = service.to_fbs();
+//!let reparsed: RpcService = printed.parse().unwrap();
+//!assert_eq!(printed, text);
+//!assert_eq!(reparsed.methods[0].arguments, service.methods[0].arguments);
+//!assert_eq!(reparsed.methods[0].attributes, service.methods[0].attributes);
+//!assert_eq!(reparsed.docs, service.docs);
+//!```
+
+use crate::schema::{Enum, Field, Item, Schema, SchemaParser, Struct, Table, Union};
+use crate::{Attribute, ParseErrorAt, RpcMethod, RpcService};
+
+use core::fmt;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+///Placement of opening brace of declaration
+pub enum BraceStyle {
+    #[default]
+    ///Brace is placed on the same line as declaration, e.g. `table Foo {`
+    SameLine,
+    ///Brace is placed on the next line
+    NextLine,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///Style of printed schema
+pub struct FbsStyle<'a> {
+    ///Indentation of declaration body, two spaces by default.
+    pub indent: &'a str,
+    ///Placement of opening brace, [BraceStyle::SameLine] by default.
+    pub brace_style: BraceStyle,
+    ///Specifies whether return types of service methods start at the same column, `false` by default.
+    pub is_aligned: bool,
+}
+
+impl<'a> FbsStyle<'a> {
+    #[inline]
+    ///Creates default style.
+    pub const fn new() -> Self {
+        Self {
+            indent: "  ",
+            brace_style: BraceStyle::SameLine,
+            is_aligned: false,
+        }
+    }
+
+    #[inline]
+    ///Sets indentation of declaration body.
+    pub const fn indent(mut self, indent: &'a str) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    #[inline]
+    ///Sets placement of opening brace.
+    pub const fn brace_style(mut self, brace_style: BraceStyle) -> Self {
+        self.brace_style = brace_style;
+        self
+    }
+
+    #[inline]
+    ///Sets whether return types of service methods are aligned.
+    pub const fn align_return_types(mut self, is_aligned: bool) -> Self {
+        self.is_aligned = is_aligned;
+        self
+    }
+
+    #[inline]
+    fn open_brace(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.brace_style {
+            BraceStyle::SameLine => fmt.write_str(" {\n"),
+            BraceStyle::NextLine => fmt.write_str("\n{\n"),
+        }
+    }
+}
+
+impl Default for FbsStyle<'_> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///Writes documentation lines, each prefixed with `indent`.
+fn write_docs(fmt: &mut fmt::Formatter<'_>, indent: &str, docs: &[String]) -> fmt::Result {
+    for line in docs.iter() {
+        match line.is_empty() {
+            true => fmt.write_fmt(format_args!("{indent}///\n"))?,
+            //Parser strips single space after slashes
+            false => fmt.write_fmt(format_args!("{indent}/// {line}\n"))?,
+        }
+    }
+    Ok(())
+}
+
+///Writes attributes within parenthesis, preceded by space, if there are any.
+///
+///Values are quoted unless they are numbers or booleans.
+fn write_attributes(fmt: &mut fmt::Formatter<'_>, attributes: &[Attribute]) -> fmt::Result {
+    if attributes.is_empty() {
+        return Ok(());
+    }
+
+    fmt.write_str(" (")?;
+    for (idx, (key, value)) in attributes.iter().enumerate() {
+        if idx > 0 {
+            fmt.write_str(", ")?;
+        }
+        fmt.write_str(key)?;
+        match value {
+            Some(value) if value == "true" || value == "false" || value.parse::<f64>().is_ok() => fmt.write_fmt(format_args!(": {value}"))?,
+            Some(value) => fmt.write_fmt(format_args!(": \"{value}\""))?,
+            None => (),
+        }
+    }
+    fmt.write_str(")")
+}
+
+///Returns method name with arguments, e.g. `Put(key: Key, Value)`
+fn method_head(method: &RpcMethod) -> String {
+    let mut head = format!("{}(", method.name);
+    for (idx, argument) in method.arguments.iter().enumerate() {
+        if idx > 0 {
+            head.push_str(", ");
+        }
+        if let Some(name) = argument.name.as_ref() {
+            head.push_str(name);
+            head.push_str(": ");
+        }
+        head.push_str(&argument.ty);
+    }
+    head.push(')');
+    head
+}
+
+///Writes method, padding return type to start at `width` after indentation.
+fn write_method(fmt: &mut fmt::Formatter<'_>, indent: &str, width: usize, method: &RpcMethod) -> fmt::Result {
+    write_docs(fmt, indent, &method.docs)?;
+    let head = method_head(method);
+    let padding = width.saturating_sub(head.len() + 2);
+    fmt.write_fmt(format_args!("{indent}{head}: {:padding$}{}", "", method.return_type))?;
+    write_attributes(fmt, &method.attributes)?;
+    fmt.write_str(";\n")
+}
+
+fn write_service(fmt: &mut fmt::Formatter<'_>, style: FbsStyle<'_>, service: &RpcService) -> fmt::Result {
+    write_docs(fmt, "", &service.docs)?;
+    fmt.write_fmt(format_args!("rpc_service {}", service.name))?;
+    write_attributes(fmt, &service.attributes)?;
+    style.open_brace(fmt)?;
+    let width = match style.is_aligned {
+        true => service.methods.iter().map(|method| method_head(method).len() + 2).max().unwrap_or(0),
+        false => 0,
+    };
+    for method in service.methods.iter() {
+        write_method(fmt, style.indent, width, method)?;
+    }
+    fmt.write_str("}")
+}
+
+fn write_fields(fmt: &mut fmt::Formatter<'_>, indent: &str, fields: &[Field]) -> fmt::Result {
+    for field in fields.iter() {
+        write_docs(fmt, indent, &field.docs)?;
+        fmt.write_fmt(format_args!("{indent}{}: {}", field.name, field.ty))?;
+        if let Some(default) = field.default.as_ref() {
+            fmt.write_fmt(format_args!(" = {default}"))?;
+        }
+        write_attributes(fmt, &field.attributes)?;
+        fmt.write_str(";\n")?;
+    }
+    Ok(())
+}
+
+fn write_table(fmt: &mut fmt::Formatter<'_>, style: FbsStyle<'_>, table: &Table) -> fmt::Result {
+    write_docs(fmt, "", &table.docs)?;
+    fmt.write_fmt(format_args!("table {}", table.name))?;
+    write_attributes(fmt, &table.attributes)?;
+    style.open_brace(fmt)?;
+    write_fields(fmt, style.indent, &table.fields)?;
+    fmt.write_str("}")
+}
+
+fn write_struct(fmt: &mut fmt::Formatter<'_>, style: FbsStyle<'_>, structure: &Struct) -> fmt::Result {
+    write_docs(fmt, "", &structure.docs)?;
+    fmt.write_fmt(format_args!("struct {}", structure.name))?;
+    write_attributes(fmt, &structure.attributes)?;
+    style.open_brace(fmt)?;
+    write_fields(fmt, style.indent, &structure.fields)?;
+    fmt.write_str("}")
+}
+
+fn write_enum(fmt: &mut fmt::Formatter<'_>, style: FbsStyle<'_>, definition: &Enum) -> fmt::Result {
+    let indent = style.indent;
+    write_docs(fmt, "", &definition.docs)?;
+    fmt.write_fmt(format_args!("enum {}: {}", definition.name, definition.underlying_type))?;
+    write_attributes(fmt, &definition.attributes)?;
+    style.open_brace(fmt)?;
+    for (name, value) in definition.variants.iter() {
+        match value {
+            Some(value) => fmt.write_fmt(format_args!("{indent}{name} = {value},\n"))?,
+            None => fmt.write_fmt(format_args!("{indent}{name},\n"))?,
+        }
+    }
+    fmt.write_str("}")
+}
+
+fn write_union(fmt: &mut fmt::Formatter<'_>, style: FbsStyle<'_>, definition: &Union) -> fmt::Result {
+    let indent = style.indent;
+    write_docs(fmt, "", &definition.docs)?;
+    fmt.write_fmt(format_args!("union {}", definition.name))?;
+    write_attributes(fmt, &definition.attributes)?;
+    style.open_brace(fmt)?;
+    for (name, alias) in definition.members.iter() {
+        match alias {
+            Some(alias) => fmt.write_fmt(format_args!("{indent}{name}: {alias},\n"))?,
+            None => fmt.write_fmt(format_args!("{indent}{name},\n"))?,
+        }
+    }
+    fmt.write_str("}")
+}
+
+///Prints single schema declaration
+struct FbsItem<'a>(&'a Item, FbsStyle<'a>);
+
+impl fmt::Display for FbsItem<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let style = self.1;
+        match self.0 {
+            Item::Service(service) => write_service(fmt, style, service),
+            Item::Table(table) => write_table(fmt, style, table),
+            Item::Struct(structure) => write_struct(fmt, style, structure),
+            Item::Enum(definition) => write_enum(fmt, style, definition),
+            Item::Union(definition) => write_union(fmt, style, definition),
+            Item::Raw(declaration) => fmt.write_str(&declaration.text),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+///Prints service as `rpc_service` declaration.
+pub struct FbsService<'a> {
+    ///Service definition
+    pub service: &'a RpcService,
+    ///Style of output
+    pub style: FbsStyle<'a>,
+    ///Specifies whether to precede declaration with `namespace` of the service, if any.
+    pub include_namespace: bool,
+}
+
+impl<'a> FbsService<'a> {
+    #[inline]
+    ///Sets style of output.
+    pub const fn style(mut self, style: FbsStyle<'a>) -> Self {
+        self.style = style;
+        self
+    }
+
+    #[inline]
+    ///Sets whether to precede declaration with `namespace`.
+    pub const fn include_namespace(mut self, include_namespace: bool) -> Self {
+        self.include_namespace = include_namespace;
+        self
+    }
+}
+
+impl fmt::Display for FbsService<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.include_namespace {
+            if let Some(namespace) = self.service.namespace.as_ref() {
+                fmt.write_fmt(format_args!("namespace {namespace};\n\n"))?;
+            }
+        }
+        write_service(fmt, self.style, self.service)
+    }
+}
+
+#[derive(Copy, Clone)]
+///Prints every declaration of schema.
+///
+///Declarations are grouped by namespace, starting with ones without namespace, as namespace cannot be reset.
+///Within namespace, enums, unions, structs, tables and services follow in order of definition.
+///
+///```rust
+///use flatbuffers_tools::schema::Schema;
+///use flatbuffers_tools::fbs::{BraceStyle, FbsStyle};
+///
+///let text = "include \"common.fbs\";
+///
+///namespace MyGame;
+///
+///enum Color: ubyte (bit_flags) {
+///  Red = 1,
+///  Green,
+///}
+///
+///union Any {
+///  Key,
+///  Other: Common.Key,
+///}
+///
+///table Key {
+///  name: string (required);
+///  hp: short = 100;
+///}
+///
+///rpc_service Storage {
+///  Get(Key): Key (streaming: \"server\");
+///}
+///
+///root_type Key;
+///
+///file_identifier \"GAME\";
+///";
+///let schema: Schema = text.parse().unwrap();
+///assert_eq!(schema.to_fbs(), text);
+///
+///let style = FbsStyle::new().indent("\t").brace_style(BraceStyle::NextLine);
+///let printed = schema.as_fbs().style(style).to_string();
+///assert!(printed.contains("table Key\n{\n\tname: string (required);\n"));
+///let reparsed: Schema = printed.parse().unwrap();
+///assert_eq!(reparsed.to_fbs(), text);
+///```
+pub struct FbsSchema<'a> {
+    ///Schema definition
+    pub schema: &'a Schema,
+    ///Style of output
+    pub style: FbsStyle<'a>,
+}
+
+impl<'a> FbsSchema<'a> {
+    #[inline]
+    ///Sets style of output.
+    pub const fn style(mut self, style: FbsStyle<'a>) -> Self {
+        self.style = style;
+        self
+    }
+
+    ///Returns namespaces of definitions, starting with `None` and following in order of first appearance.
+    fn namespaces(&self) -> Vec<Option<&'a str>> {
+        let schema = self.schema;
+        let mut namespaces = vec![None];
+        let definitions = schema.enums.iter().map(|definition| definition.namespace.as_deref())
+                                      .chain(schema.unions.iter().map(|definition| definition.namespace.as_deref()))
+                                      .chain(schema.structs.iter().map(|definition| definition.namespace.as_deref()))
+                                      .chain(schema.tables.iter().map(|definition| definition.namespace.as_deref()))
+                                      .chain(schema.services.iter().map(|definition| definition.namespace.as_deref()));
+        for namespace in definitions {
+            if !namespaces.contains(&namespace) {
+                namespaces.push(namespace);
+            }
+        }
+        namespaces
+    }
+}
+
+impl fmt::Display for FbsSchema<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let schema = self.schema;
+        let style = self.style;
+        for include in schema.includes.iter() {
+            fmt.write_fmt(format_args!("include \"{include}\";\n"))?;
+        }
+        for attribute in schema.attributes.iter() {
+            fmt.write_fmt(format_args!("attribute \"{attribute}\";\n"))?;
+        }
+        if !schema.includes.is_empty() || !schema.attributes.is_empty() {
+            fmt.write_str("\n")?;
+        }
+
+        //Separates declarations with empty line
+        let mut is_first = true;
+        let mut separate = |fmt: &mut fmt::Formatter<'_>| match core::mem::replace(&mut is_first, false) {
+            true => Ok(()),
+            false => fmt.write_str("\n\n"),
+        };
+
+        let mut current = None;
+        for namespace in self.namespaces() {
+            if namespace != current {
+                separate(fmt)?;
+                fmt.write_fmt(format_args!("namespace {};", namespace.unwrap_or_default()))?;
+                current = namespace;
+            }
+            for definition in schema.enums.iter().filter(|definition| definition.namespace.as_deref() == namespace) {
+                separate(fmt)?;
+                write_enum(fmt, style, definition)?;
+            }
+            for definition in schema.unions.iter().filter(|definition| definition.namespace.as_deref() == namespace) {
+                separate(fmt)?;
+                write_union(fmt, style, definition)?;
+            }
+            for definition in schema.structs.iter().filter(|definition| definition.namespace.as_deref() == namespace) {
+                separate(fmt)?;
+                write_struct(fmt, style, definition)?;
+            }
+            for definition in schema.tables.iter().filter(|definition| definition.namespace.as_deref() == namespace) {
+                separate(fmt)?;
+                write_table(fmt, style, definition)?;
+            }
+            for definition in schema.services.iter().filter(|definition| definition.namespace.as_deref() == namespace) {
+                separate(fmt)?;
+                write_service(fmt, style, definition)?;
+            }
+        }
+
+        //Root type is resolved within the last namespace
+        if let Some(namespace) = schema.namespace.as_deref() {
+            if current != Some(namespace) && schema.root_type.is_some() {
+                separate(fmt)?;
+                fmt.write_fmt(format_args!("namespace {namespace};"))?;
+            }
+        }
+        if let Some(root_type) = schema.root_type.as_ref() {
+            separate(fmt)?;
+            fmt.write_fmt(format_args!("root_type {root_type};"))?;
+        }
+        if let Some(file_identifier) = schema.file_identifier.as_ref() {
+            separate(fmt)?;
+            fmt.write_fmt(format_args!("file_identifier \"{file_identifier}\";"))?;
+        }
+        if let Some(file_extension) = schema.file_extension.as_ref() {
+            separate(fmt)?;
+            fmt.write_fmt(format_args!("file_extension \"{file_extension}\";"))?;
+        }
+        if !is_first {
+            fmt.write_str("\n")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///Style of [format_schema]
+pub struct FmtStyle {
+    ///Number of spaces to indent declaration body, 2 by default.
+    pub indent: usize,
+    ///Placement of opening brace, [BraceStyle::SameLine] by default.
+    pub brace_style: BraceStyle,
+    ///Number of empty lines between declarations, 1 by default.
+    ///
+    ///Consecutive empty lines within other text are limited to the same number.
+    pub blank_lines: usize,
+    ///Specifies whether return types of service methods start at the same column, `false` by default.
+    pub is_aligned: bool,
+}
+
+impl FmtStyle {
+    #[inline]
+    ///Creates default style.
+    pub const fn new() -> Self {
+        Self {
+            indent: 2,
+            brace_style: BraceStyle::SameLine,
+            blank_lines: 1,
+            is_aligned: false,
+        }
+    }
+
+    #[inline]
+    ///Sets number of spaces to indent declaration body.
+    pub const fn indent(mut self, indent: usize) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    #[inline]
+    ///Sets placement of opening brace.
+    pub const fn brace_style(mut self, brace_style: BraceStyle) -> Self {
+        self.brace_style = brace_style;
+        self
+    }
+
+    #[inline]
+    ///Sets number of empty lines between declarations.
+    pub const fn blank_lines(mut self, blank_lines: usize) -> Self {
+        self.blank_lines = blank_lines;
+        self
+    }
+
+    #[inline]
+    ///Sets whether return types of service methods are aligned.
+    pub const fn align_return_types(mut self, is_aligned: bool) -> Self {
+        self.is_aligned = is_aligned;
+        self
+    }
+}
+
+impl Default for FmtStyle {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///Part of formatted schema
+enum Piece {
+    ///Text, that is not definition, split into lines
+    Text(Vec<String>),
+    ///Definition
+    Item(String),
+}
+
+///Lines of text, that is not definition
+struct TextLines {
+    lines: Vec<String>,
+    //Number of empty lines since the last line
+    empty: usize,
+    blank_lines: usize,
+}
+
+impl TextLines {
+    #[inline]
+    const fn new(blank_lines: usize) -> Self {
+        Self {
+            lines: Vec::new(),
+            empty: 0,
+            blank_lines,
+        }
+    }
+
+    ///Adds preceding empty lines, limited to `blank_lines`, unless there are no lines yet.
+    fn push_line(&mut self, line: &str) {
+        if !self.lines.is_empty() {
+            self.lines.extend(core::iter::repeat_n(String::new(), self.empty.min(self.blank_lines)));
+        }
+        self.empty = 0;
+        self.lines.push(line.to_owned());
+    }
+
+    ///Adds lines of `text` without trailing whitespace, skipping empty lines at its start and end.
+    fn push_text(&mut self, text: &str) {
+        for line in text.lines().map(str::trim_end) {
+            match line.is_empty() {
+                true => self.empty += 1,
+                false => self.push_line(line),
+            }
+        }
+    }
+
+    ///Adds lines of `text` as written.
+    fn push_verbatim(&mut self, text: &str) {
+        let mut lines = text.lines();
+        if let Some(line) = lines.next() {
+            self.push_line(line);
+        }
+        self.lines.extend(lines.map(str::to_owned));
+    }
+
+    ///Returns lines added so far, discarding trailing empty lines.
+    fn take(&mut self) -> Vec<String> {
+        self.empty = 0;
+        core::mem::take(&mut self.lines)
+    }
+}
+
+///Returns whether printing of `item` retains all text of its `source`.
+///
+///Comments, other than documentation of fields and methods, are not part of definition.
+fn is_printable(source: &str, item: &Item) -> bool {
+    let mut docs = 0;
+    for line in source.lines().map(str::trim_start) {
+        if line.starts_with("///") {
+            docs += 1;
+        } else if line.contains("//") || line.contains("/*") {
+            return false;
+        }
+    }
+
+    //Documentation at the end of body is discarded by parser
+    let expected = match item {
+        Item::Service(service) => service.methods.iter().map(|method| method.docs.len()).sum(),
+        Item::Table(table) => table.fields.iter().map(|field| field.docs.len()).sum(),
+        Item::Struct(structure) => structure.fields.iter().map(|field| field.docs.len()).sum(),
+        Item::Enum(_) | Item::Union(_) => 0,
+        Item::Raw(_) => return false,
+    };
+    docs == expected
+}
+
+///Formats schema `input`, reprinting every definition that crate parses.
+///
+///Definitions are printed without their documentation, which is kept as written right above definition,
+///while definitions with comments within them are kept as written.
+///Declarations that are not parsed, e.g. `root_type` or unknown ones, are kept as written in their positions.
+///Other text is kept as written, except trailing whitespace and excessive empty lines.
+///
+///Formatting is idempotent, so formatting output again yields the same text:
+///
+///```rust
+///use flatbuffers_tools::fbs::{format_schema, is_formatted, BraceStyle, FmtStyle};
+///
+///let fixtures = [
+///    "namespace MyGame;\ntable Key { name:string; }   \n\n\n\n/// Storage\nrpc_service Storage{Put(Key):Key;\n  /// Get value\n GetAll ( key : Key ) : Key(streaming:\"server\") ; }",
+///    "include \"common.fbs\";\nenum Color : ubyte { Red = 1, Green }\nunion Any { Color }\nstruct Vec2 { x: float; y: float; }",
+///    "rpc_service Storage {\n  Put(Key): Key; // not documentation\n}\nunknown_declaration Foo {\n    bar;\n}\nroot_type Key;",
+///    "table A {} table B { a: A; }\n/* block\n\n\n\ncomment */\nfile_identifier \"ABCD\";\n",
+///    "",
+///];
+///let styles = [
+///    FmtStyle::new(),
+///    FmtStyle::new().indent(4).brace_style(BraceStyle::NextLine).blank_lines(2).align_return_types(true),
+///    FmtStyle::new().blank_lines(0),
+///];
+///
+///for fixture in fixtures.iter() {
+///    for style in styles.iter() {
+///        let formatted = format_schema(fixture, style).unwrap();
+///        assert_eq!(format_schema(&formatted, style).unwrap(), formatted);
+///        assert!(is_formatted(&formatted, style).unwrap());
+///    }
+///}
+///
+///let formatted = format_schema(fixtures[0], &FmtStyle::new().align_return_types(true)).unwrap();
+///assert!(formatted.contains("/// Storage\nrpc_service Storage {\n  Put(Key):         Key;\n  /// Get value\n  GetAll(key: Key): Key (streaming: \"server\");\n}"));
+///assert!(!is_formatted(fixtures[0], &FmtStyle::new()).unwrap());
+///
+///let formatted = format_schema(fixtures[2], &FmtStyle::new()).unwrap();
+///assert!(formatted.contains("Put(Key): Key; // not documentation"));
+///assert!(formatted.contains("unknown_declaration Foo {\n    bar;\n}"));
+///```
+///
+///Hence schema, mixing definitions with declarations unknown to the crate, is written back byte for byte, once its definitions are formatted:
+///
+///```rust
+///use flatbuffers_tools::fbs::{format_schema, BraceStyle, FmtStyle};
+///
+///let schema = "include \"common.fbs\";
+///native_include \"vendor.h\";
+///
+///namespace MyGame;
+///
+///table Key {
+///  name: string (required);
+///}
+///
+///vendor_pragma optimize {
+///    level: 3;   
+///
+///
+///    /* nested { */ mode: \"fast\";
+///} // end of pragma
+///rpc_service_v2 Storage (streaming) {
+///  Put(Key): Key;
+///}
+///
+///rpc_service Storage {
+///  Get(Key): Key;
+///}
+///
+///root_type Key;
+///";
+///assert!(schema.contains("level: 3;   \n"));
+///assert_eq!(format_schema(schema, &FmtStyle::new()).unwrap(), schema);
+///
+/////Only known definitions are reformatted
+///let formatted = format_schema(schema, &FmtStyle::new().indent(4).brace_style(BraceStyle::NextLine)).unwrap();
+///let expected = schema.replace("table Key {\n  name", "table Key\n{\n    name").replace("rpc_service Storage {\n  Get", "rpc_service Storage\n{\n    Get");
+///assert_eq!(formatted, expected);
+///```
+pub fn format_schema(input: &str, style: &FmtStyle) -> Result<String, ParseErrorAt> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let mut line_starts = vec![0];
+    line_starts.extend(input.match_indices('\n').map(|(idx, _)| idx + 1));
+    let offset_of = |position: crate::Position| line_starts[position.line - 1] + position.column - 1;
+
+    let indent = " ".repeat(style.indent);
+    let fbs_style = FbsStyle::new().indent(&indent).brace_style(style.brace_style).align_return_types(style.is_aligned);
+
+    let mut pieces = Vec::new();
+    let mut text = TextLines::new(style.blank_lines);
+    let mut consumed = 0;
+    //Text after previous definition may start on its last line
+    let gap = |from: usize, to: usize| match input[..from].ends_with('\n') {
+        true => &input[from..to],
+        false => input[from..to].trim_start_matches([' ', '\t']),
+    };
+    for item in SchemaParser::from_str(input).lossless() {
+        let mut item = item?;
+        let span = item.span();
+        let start = offset_of(span.start);
+        let mut end = offset_of(span.end);
+        end += input[end..].chars().next().map_or(0, char::len_utf8);
+        text.push_text(gap(consumed, start));
+
+        if let Item::Raw(_) = item {
+            //Declaration, that is not parsed, is kept as written within text, together with comments till the end of its last line
+            let line_end = input[end..].find('\n').map_or(input.len(), |idx| end + idx);
+            text.push_verbatim(input[start..line_end].trim_end());
+            consumed = (line_end + 1).min(input.len());
+            continue;
+        }
+        pieces.push(Piece::Text(text.take()));
+
+        let source = &input[start..end];
+        if is_printable(source, &item) {
+            match &mut item {
+                Item::Service(service) => service.docs.clear(),
+                Item::Table(table) => table.docs.clear(),
+                Item::Struct(structure) => structure.docs.clear(),
+                Item::Enum(definition) => definition.docs.clear(),
+                Item::Union(definition) => definition.docs.clear(),
+                Item::Raw(_) => (),
+            }
+            pieces.push(Piece::Item(FbsItem(&item, fbs_style).to_string()));
+        } else {
+            let lines: Vec<_> = source.lines().map(str::trim_end).collect();
+            pieces.push(Piece::Item(lines.join("\n")));
+        }
+        consumed = end;
+    }
+    text.push_text(gap(consumed, input.len()));
+    pieces.push(Piece::Text(text.take()));
+
+    let mut output = String::with_capacity(input.len());
+    //Documentation right above definition is attached to it
+    let mut is_attached = false;
+    for piece in pieces.iter() {
+        let text = match piece {
+            Piece::Text(lines) if lines.is_empty() => continue,
+            Piece::Text(lines) => lines.join("\n"),
+            Piece::Item(item) => item.clone(),
+        };
+        if !output.is_empty() {
+            output.push('\n');
+            if !(is_attached && matches!(piece, Piece::Item(_))) {
+                output.extend(core::iter::repeat_n('\n', style.blank_lines));
+            }
+        }
+        is_attached = match piece {
+            Piece::Text(lines) => lines.last().is_some_and(|line| line.trim_start().starts_with("///")),
+            Piece::Item(_) => false,
+        };
+        output.push_str(&text);
+    }
+    if !output.is_empty() {
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+#[inline]
+///Returns whether `input` is already formatted, i.e. [format_schema] would not change it.
+pub fn is_formatted(input: &str, style: &FmtStyle) -> Result<bool, ParseErrorAt> {
+    format_schema(input, style).map(|formatted| formatted == input)
+}
@@ -0,0 +1,339 @@
+//!Sans-io framing of method calls
+//!
+//!Frame is a fixed 8 bytes header followed by payload, where every integer is unsigned little-endian:
+//!
+//!| Offset | Size | Field                                               |
+//!|--------|------|-----------------------------------------------------|
+//!| 0      | 2    | Magic [MAGIC] `0x5246`, i.e. bytes `46 52` (`"FR"`) |
+//!| 2      | 2    | Method id                                           |
+//!| 4      | 4    | Length of payload `N`                               |
+//!| 8      | `N`  | Payload                                             |
+//!
+//!There is no padding, alignment or checksum, and frames follow each other directly within stream.
+//!Response is framed the same way, with method id of the request.
+//!
+//!E.g. call of method `1` with payload `0a 0b 0c` is `46 52 01 00 03 00 00 00 0a 0b 0c`.
+//!
+//!```rust
+//!use flatbuffers_tools::framing::{decode_frame, encode_frame, FrameDecoder, FrameError};
+//!
+//!let mut frames = Vec::new();
+//!encode_frame(1, &[0xa, 0xb, 0xc], &mut frames);
+//!assert_eq!(frames, [0x46, 0x52, 0x01, 0x00, 0x03, 0x00, 0x00, 0x00, 0x0a, 0x0b, 0x0c]);
+//!encode_frame(2, &[], &mut frames);
+//!
+//!let frame = decode_frame(&frames).unwrap();
+//!assert_eq!((frame.method_id, frame.payload), (1, &[0xa, 0xb, 0xc][..]));
+//!assert_eq!(decode_frame(&frames[frame.encoded_len()..]).unwrap().method_id, 2);
+//!
+//!assert_eq!(decode_frame(&frames[..5]), Err(FrameError::Short(8)));
+//!assert_eq!(decode_frame(&frames[..10]), Err(FrameError::Short(11)));
+//!assert_eq!(decode_frame(&[0, 0, 1, 0, 0, 0, 0, 0]), Err(FrameError::BadMagic(0)));
+//!
+//!//Stream is fed in arbitrary chunks
+//!let mut decoder = FrameDecoder::new();
+//!let mut ids = Vec::new();
+//!for chunk in frames.chunks(3) {
+//!    decoder.feed(chunk);
+//!    while let Some(frame) = decoder.next_frame().unwrap() {
+//!        ids.push((frame.method_id, frame.payload.to_vec()));
+//!    }
+//!}
+//!assert_eq!(ids, [(1, vec![0xa, 0xb, 0xc]), (2, vec![])]);
+//!assert_eq!(decoder.buffered(), 0);
+//!
+//!//Length is checked as soon as header is received
+//!let mut decoder = FrameDecoder::new().max_payload_len(2);
+//!decoder.feed(&frames[..8]);
+//!assert_eq!(decoder.next_frame(), Err(FrameError::TooLong { len: 3, max: 2 }));
+//!```
+//!
+//!## Handshake
+//!
+//!Before any call client and server can exchange handshake with fingerprint of the service, as [RpcService::fingerprint](crate::RpcService::fingerprint) returns,
+//!so that peers, built from different schemas, are detected at connection time.
+//!It is fixed 12 bytes, where every integer is unsigned little-endian:
+//!
+//!| Offset | Size | Field                                                                    |
+//!|--------|------|--------------------------------------------------------------------------|
+//!| 0      | 2    | Magic [HANDSHAKE_MAGIC] `0x4846`, i.e. bytes `46 48` (`"FH"`)            |
+//!| 2      | 2    | Version of fingerprint [FINGERPRINT_VERSION](crate::FINGERPRINT_VERSION) |
+//!| 4      | 8    | Fingerprint                                                              |
+//!
+//!```rust
+//!use flatbuffers_tools::framing::{check_handshake, decode_handshake, encode_handshake, HandshakeError, HANDSHAKE_LEN};
+//!use flatbuffers_tools::RpcService;
+//!
+//!let service: RpcService = "rpc_service Store { Get(Key):Value; }".parse().unwrap();
+//!let mut handshake = Vec::new();
+//!encode_handshake(service.fingerprint(), &mut handshake);
+//!assert_eq!(handshake.len(), HANDSHAKE_LEN);
+//!assert_eq!(decode_handshake(&handshake), Ok(service.fingerprint()));
+//!assert_eq!(check_handshake(&handshake, service.fingerprint()), Ok(()));
+//!
+//!let other: RpcService = "rpc_service Store { Get(Key):Key; }".parse().unwrap();
+//!assert_eq!(check_handshake(&handshake, other.fingerprint()), Err(HandshakeError::Mismatch {
+//!    expected: other.fingerprint(),
+//!    actual: service.fingerprint(),
+//!}));
+//!assert_eq!(decode_handshake(&handshake[..4]), Err(HandshakeError::Short(HANDSHAKE_LEN)));
+//!handshake[2] = 0;
+//!assert_eq!(decode_handshake(&handshake), Err(HandshakeError::Version(0)));
+//!```
+
+use core::convert::TryFrom;
+use core::fmt;
+
+///Magic number, that starts every frame
+pub const MAGIC: u16 = 0x5246;
+///Length of frame header
+pub const HEADER_LEN: usize = 8;
+///Maximum length of payload, limited by its 4 bytes length
+pub const MAX_PAYLOAD_LEN: usize = u32::MAX as usize;
+///Magic number, that starts handshake
+pub const HANDSHAKE_MAGIC: u16 = 0x4846;
+///Length of handshake
+pub const HANDSHAKE_LEN: usize = 12;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///Decoded frame, borrowing its payload
+pub struct Frame<'a> {
+    ///Method id
+    pub method_id: u16,
+    ///Payload
+    pub payload: &'a [u8],
+}
+
+impl Frame<'_> {
+    #[inline(always)]
+    ///Returns length of encoded frame, including header.
+    pub const fn encoded_len(&self) -> usize {
+        HEADER_LEN + self.payload.len()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///Error decoding frame
+pub enum FrameError {
+    ///Buffer is shorter than frame, with specified number of bytes required to decode it.
+    ///
+    ///Until header is complete, it is length of header.
+    Short(usize),
+    ///Frame starts with unexpected magic number
+    BadMagic(u16),
+    ///Length of payload exceeds limit
+    TooLong {
+        ///Length of payload within header
+        len: u32,
+        ///Maximum length of payload
+        max: usize,
+    },
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Short(len) => fmt.write_fmt(format_args!("frame requires {len} bytes")),
+            Self::BadMagic(magic) => fmt.write_fmt(format_args!("invalid magic {magic:#06x}, expected {MAGIC:#06x}")),
+            Self::TooLong { len, max } => fmt.write_fmt(format_args!("payload length {len} exceeds maximum {max}")),
+        }
+    }
+}
+
+impl std::error::Error for FrameError {}
+
+///Appends frame of `payload` with `method_id` to `out`.
+///
+///Panics if payload is longer than [MAX_PAYLOAD_LEN].
+pub fn encode_frame(method_id: u16, payload: &[u8], out: &mut Vec<u8>) {
+    let len = match u32::try_from(payload.len()) {
+        Ok(len) => len,
+        Err(_) => panic!("Payload of {} bytes exceeds maximum length of frame", payload.len()),
+    };
+
+    out.reserve(HEADER_LEN + payload.len());
+    out.extend_from_slice(&MAGIC.to_le_bytes());
+    out.extend_from_slice(&method_id.to_le_bytes());
+    out.extend_from_slice(&len.to_le_bytes());
+    out.extend_from_slice(payload);
+}
+
+///Decodes header at the start of `buf`, returning method id and length of payload.
+fn decode_header(buf: &[u8], max_payload_len: usize) -> Result<(u16, usize), FrameError> {
+    if buf.len() < HEADER_LEN {
+        return Err(FrameError::Short(HEADER_LEN));
+    }
+
+    let magic = u16::from_le_bytes([buf[0], buf[1]]);
+    if magic != MAGIC {
+        return Err(FrameError::BadMagic(magic));
+    }
+    let method_id = u16::from_le_bytes([buf[2], buf[3]]);
+    let len = u32::from_le_bytes([buf[4], buf[5], buf[6], buf[7]]);
+    match usize::try_from(len) {
+        Ok(payload_len) if payload_len <= max_payload_len => Ok((method_id, payload_len)),
+        _ => Err(FrameError::TooLong {
+            len,
+            max: max_payload_len,
+        }),
+    }
+}
+
+///Decodes frame at the start of `buf`, ignoring bytes after it.
+///
+///Use [Frame::encoded_len] to find start of the next frame.
+pub fn decode_frame(buf: &[u8]) -> Result<Frame<'_>, FrameError> {
+    let (method_id, len) = decode_header(buf, MAX_PAYLOAD_LEN)?;
+    match buf.get(HEADER_LEN..HEADER_LEN + len) {
+        Some(payload) => Ok(Frame {
+            method_id,
+            payload,
+        }),
+        None => Err(FrameError::Short(HEADER_LEN + len)),
+    }
+}
+
+#[derive(Debug, Clone)]
+///Incremental decoder of frames within stream
+///
+///Received bytes are buffered until frame is complete, regardless of how stream is split.
+///Once error is returned, stream cannot be decoded further and decoder is to be discarded.
+pub struct FrameDecoder {
+    buffer: Vec<u8>,
+    //Start of the next frame within buffer
+    start: usize,
+    max_payload_len: usize,
+}
+
+impl FrameDecoder {
+    #[inline]
+    ///Creates decoder, accepting payload up to [MAX_PAYLOAD_LEN].
+    pub const fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            start: 0,
+            max_payload_len: MAX_PAYLOAD_LEN,
+        }
+    }
+
+    #[inline]
+    ///Sets maximum length of payload, so that buffer does not grow beyond it.
+    pub const fn max_payload_len(mut self, max_payload_len: usize) -> Self {
+        self.max_payload_len = max_payload_len;
+        self
+    }
+
+    ///Adds received bytes.
+    pub fn feed(&mut self, data: &[u8]) {
+        //Decoded frames are no longer borrowed
+        if self.start > 0 {
+            self.buffer.drain(..self.start);
+            self.start = 0;
+        }
+        self.buffer.extend_from_slice(data);
+    }
+
+    ///Decodes next frame, returning `None` if more bytes are required.
+    pub fn next_frame(&mut self) -> Result<Option<Frame<'_>>, FrameError> {
+        let buf = &self.buffer[self.start..];
+        let (method_id, len) = match decode_header(buf, self.max_payload_len) {
+            Ok(header) => header,
+            Err(FrameError::Short(_)) => return Ok(None),
+            Err(error) => return Err(error),
+        };
+        if buf.len() < HEADER_LEN + len {
+            return Ok(None);
+        }
+
+        let payload_start = self.start + HEADER_LEN;
+        self.start = payload_start + len;
+        Ok(Some(Frame {
+            method_id,
+            payload: &self.buffer[payload_start..self.start],
+        }))
+    }
+
+    #[inline]
+    ///Returns number of received bytes, that are not decoded yet.
+    pub fn buffered(&self) -> usize {
+        self.buffer.len() - self.start
+    }
+}
+
+impl Default for FrameDecoder {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///Error of handshake
+pub enum HandshakeError {
+    ///Buffer is shorter than handshake, with specified number of bytes required to decode it.
+    Short(usize),
+    ///Handshake starts with unexpected magic number
+    BadMagic(u16),
+    ///Fingerprint is of unsupported version
+    Version(u16),
+    ///Fingerprint of peer is not expected one
+    Mismatch {
+        ///Fingerprint of the service
+        expected: u64,
+        ///Fingerprint of peer
+        actual: u64,
+    },
+}
+
+impl fmt::Display for HandshakeError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Short(len) => fmt.write_fmt(format_args!("handshake requires {len} bytes")),
+            Self::BadMagic(magic) => fmt.write_fmt(format_args!("invalid magic {magic:#06x}, expected {HANDSHAKE_MAGIC:#06x}")),
+            Self::Version(version) => fmt.write_fmt(format_args!("unsupported fingerprint version {version}, expected {}", crate::FINGERPRINT_VERSION)),
+            Self::Mismatch { expected, actual } => fmt.write_fmt(format_args!("fingerprint {actual:#018x} of peer does not match {expected:#018x}")),
+        }
+    }
+}
+
+impl std::error::Error for HandshakeError {}
+
+///Appends handshake with `fingerprint` of the service to `out`.
+pub fn encode_handshake(fingerprint: u64, out: &mut Vec<u8>) {
+    out.reserve(HANDSHAKE_LEN);
+    out.extend_from_slice(&HANDSHAKE_MAGIC.to_le_bytes());
+    out.extend_from_slice(&(crate::FINGERPRINT_VERSION as u16).to_le_bytes());
+    out.extend_from_slice(&fingerprint.to_le_bytes());
+}
+
+///Decodes handshake at the start of `buf`, returning fingerprint of peer.
+///
+///Bytes after handshake are ignored.
+pub fn decode_handshake(buf: &[u8]) -> Result<u64, HandshakeError> {
+    if buf.len() < HANDSHAKE_LEN {
+        return Err(HandshakeError::Short(HANDSHAKE_LEN));
+    }
+
+    let magic = u16::from_le_bytes([buf[0], buf[1]]);
+    if magic != HANDSHAKE_MAGIC {
+        return Err(HandshakeError::BadMagic(magic));
+    }
+    let version = u16::from_le_bytes([buf[2], buf[3]]);
+    if u32::from(version) != crate::FINGERPRINT_VERSION {
+        return Err(HandshakeError::Version(version));
+    }
+    let mut fingerprint = [0; 8];
+    fingerprint.copy_from_slice(&buf[4..HANDSHAKE_LEN]);
+    Ok(u64::from_le_bytes(fingerprint))
+}
+
+///Decodes handshake at the start of `buf`, checking that fingerprint of peer is `expected`.
+pub fn check_handshake(buf: &[u8], expected: u64) -> Result<(), HandshakeError> {
+    match decode_handshake(buf)? {
+        actual if actual == expected => Ok(()),
+        actual => Err(HandshakeError::Mismatch {
+            expected,
+            actual,
+        }),
+    }
+}
@@ -0,0 +1,584 @@
+//!Filesystem aware parsing
+
+use crate::{reader, Limit, Limits, ParseError, ParseErrorAt, ParserIter, Position, RpcService, Span};
+use crate::schema::{Schema, SchemaParser};
+
+use core::fmt;
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+///Error parsing schema files
+pub enum Error {
+    ///Unable to read file
+    Io(PathBuf, io::Error),
+    ///File contains invalid definition
+    Parse(PathBuf, ParseErrorAt),
+    ///File includes file that cannot be found
+    IncludeNotFound {
+        ///File with `include` declaration
+        path: PathBuf,
+        ///Included path as declared
+        include: String,
+    },
+    ///File includes itself, directly or through other files
+    ///
+    ///Contains canonical paths of files within cycle, starting and ending with the same file.
+    IncludeCycle(Vec<PathBuf>),
+}
+
+impl Error {
+    #[inline]
+    ///Returns path to the file where error occurred.
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Io(path, _) => path,
+            Self::Parse(path, _) => path,
+            Self::IncludeNotFound { path, .. } => path,
+            //File with `include` declaration, that closes the cycle
+            Self::IncludeCycle(paths) => paths.iter().rev().nth(1).map_or(Path::new(""), PathBuf::as_path),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, error) => fmt.write_fmt(format_args!("{}: {}", path.display(), error)),
+            Self::Parse(path, error) => fmt.write_fmt(format_args!("{}:{}: {}", path.display(), error.line, error.error)),
+            Self::IncludeNotFound { path, include } => fmt.write_fmt(format_args!("{}: cannot find included file '{}'", path.display(), include)),
+            Self::IncludeCycle(paths) => {
+                fmt.write_str("include cycle ")?;
+                for (idx, path) in paths.iter().enumerate() {
+                    if idx > 0 {
+                        fmt.write_str(" -> ")?;
+                    }
+                    fmt.write_fmt(format_args!("{}", path.display()))?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(_, error) => Some(error),
+            Self::Parse(_, error) => Some(&error.error),
+            Self::IncludeNotFound { .. } | Self::IncludeCycle(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+///Origin of lines of schema, parsed from multiple files
+///
+///Files, parsed as one schema, are numbered as if they would follow each other in order of parsing,
+///so that every location within schema is unique.
+///Map resolves such logical line to the file and its line, e.g. to report [ParseErrorAt], [Diagnostic](crate::diagnostics::Diagnostic)
+///or [Span] of definition.
+pub struct SourceMap {
+    //Path of every file, with number of logical lines before it, in order of parsing
+    files: Vec<(PathBuf, usize)>,
+    //Total number of lines
+    lines: usize,
+}
+
+impl SourceMap {
+    ///Adds file with `lines` number of lines, returning number of logical lines before it.
+    fn push(&mut self, path: &Path, lines: usize) -> usize {
+        let start = self.lines;
+        self.files.push((path.to_owned(), start));
+        self.lines += lines;
+        start
+    }
+
+    #[inline]
+    ///Returns paths of files in order of parsing.
+    pub fn files(&self) -> impl Iterator<Item=&Path> + '_ {
+        self.files.iter().map(|(path, _)| path.as_path())
+    }
+
+    ///Resolves logical `line`, starting from 1, to the file and its line.
+    pub fn resolve_line(&self, line: usize) -> Option<(&Path, usize)> {
+        if line == 0 || line > self.lines {
+            return None;
+        }
+        //Files are ordered by their start
+        let idx = self.files.partition_point(|(_, start)| *start < line) - 1;
+        let (path, start) = &self.files[idx];
+        Some((path, line - start))
+    }
+
+    #[inline]
+    ///Resolves logical `position` to the file and position within it.
+    pub fn resolve(&self, position: Position) -> Option<(&Path, Position)> {
+        self.resolve_line(position.line).map(|(path, line)| (path, Position {
+            line,
+            column: position.column,
+        }))
+    }
+
+    #[inline]
+    ///Resolves logical `span` to the file and span within it.
+    pub fn resolve_span(&self, span: Span) -> Option<(&Path, Span)> {
+        let (path, start) = self.resolve(span.start)?;
+        let (_, end) = self.resolve(span.end)?;
+        Some((path, Span {
+            start,
+            end,
+        }))
+    }
+
+    ///Converts `error` at logical line to [Error::Parse] within the file.
+    ///
+    ///Error at line outside of the map is attributed to the last file.
+    pub fn resolve_error(&self, error: ParseErrorAt) -> Error {
+        let ParseErrorAt { line, error } = error;
+        match self.resolve_line(line) {
+            Some((path, line)) => Error::Parse(path.to_owned(), error.at(line)),
+            None => {
+                let (path, start) = self.files.last().map_or((Path::new(""), 0), |(path, start)| (path.as_path(), *start));
+                Error::Parse(path.to_owned(), error.at(line.saturating_sub(start)))
+            }
+        }
+    }
+}
+
+struct IncludeResolver<'a> {
+    include_dirs: &'a [&'a Path],
+    //Canonical paths of already parsed files
+    parsed: Vec<PathBuf>,
+    //Canonical paths of files, whose includes are being parsed
+    stack: Vec<PathBuf>,
+    schema: Schema,
+    //Origin of lines, if all declarations are parsed as one schema, instead of services only
+    source_map: Option<SourceMap>,
+    limits: Limits,
+}
+
+impl<'a> IncludeResolver<'a> {
+    #[inline]
+    fn new(include_dirs: &'a [&'a Path], source_map: Option<SourceMap>, limits: Limits) -> Self {
+        Self {
+            include_dirs,
+            parsed: Vec::new(),
+            stack: Vec::new(),
+            schema: Schema::default(),
+            source_map,
+            limits,
+        }
+    }
+
+    fn resolve(&self, path: &Path, include: &str) -> Option<PathBuf> {
+        let relative = match path.parent() {
+            Some(parent) => parent.join(include),
+            None => PathBuf::from(include),
+        };
+
+        if relative.is_file() {
+            return Some(relative);
+        }
+
+        self.include_dirs.iter().map(|dir| dir.join(include)).find(|path| path.is_file())
+    }
+
+    ///Parses `schema` text of file at `path`, returning its definitions with `include` declarations.
+    fn parse_text(&mut self, path: &Path, schema: &str) -> Result<Schema, Error> {
+        let source_map = match self.source_map.as_mut() {
+            Some(source_map) => source_map,
+            None => {
+                let mut parser = ParserIter::new(schema.lines()).unique_services().limits(self.limits);
+                let mut services = Vec::new();
+                for service in parser.by_ref() {
+                    match service {
+                        Ok(service) => services.push(service),
+                        Err(error) => return Err(Error::Parse(path.to_owned(), error)),
+                    }
+                }
+                return Ok(Schema {
+                    services,
+                    includes: parser.includes,
+                    ..Schema::default()
+                });
+            },
+        };
+
+        //Lines are numbered after lines of previous files, hence BOM is not at the first line
+        let schema = schema.strip_prefix('\u{feff}').unwrap_or(schema);
+        let start = source_map.push(path, schema.lines().count());
+        let parser = SchemaParser::new(schema.lines()).unique_services().limits(self.limits).after_lines(start);
+        Schema::parse_with(parser).map_err(|error| source_map.resolve_error(error))
+    }
+
+    fn parse(&mut self, path: &Path) -> Result<(), Error> {
+        let canonical = match path.canonicalize() {
+            Ok(canonical) => canonical,
+            Err(error) => return Err(Error::Io(path.to_owned(), error)),
+        };
+        if let Some(idx) = self.stack.iter().position(|parsing| *parsing == canonical) {
+            let mut cycle = self.stack[idx..].to_vec();
+            cycle.push(canonical);
+            return Err(Error::IncludeCycle(cycle));
+        }
+        //Same as flatc, every file is parsed only once, so that diamond includes are not duplicated
+        if self.parsed.contains(&canonical) {
+            return Ok(());
+        }
+        self.parsed.push(canonical.clone());
+
+        let max_input_size = self.limits.max_input_size;
+        match std::fs::metadata(path) {
+            Ok(metadata) if metadata.len() > max_input_size as u64 => return Err(Error::Parse(path.to_owned(), Limits::exceeded(Limit::InputSize, max_input_size).at(0))),
+            Ok(_) => (),
+            Err(error) => return Err(Error::Io(path.to_owned(), error)),
+        }
+        let schema = match std::fs::read_to_string(path) {
+            Ok(schema) => schema,
+            Err(error) => return Err(Error::Io(path.to_owned(), error)),
+        };
+        let mut schema = self.parse_text(path, &schema)?;
+
+        //Included definitions go first, as flatc would have it
+        self.stack.push(canonical);
+        //Location of include declaration is not known
+        if !schema.includes.is_empty() && self.stack.len() > self.limits.max_include_depth {
+            let max_include_depth = self.limits.max_include_depth;
+            return Err(Error::Parse(path.to_owned(), Limits::exceeded(Limit::IncludeDepth, max_include_depth).at(0)));
+        }
+        for include in schema.includes.iter() {
+            match self.resolve(path, include) {
+                Some(include) => self.parse(&include)?,
+                None => return Err(Error::IncludeNotFound {
+                    path: path.to_owned(),
+                    include: include.clone(),
+                }),
+            }
+        }
+        self.stack.pop();
+
+        //Definitions across files are checked as if they would be in the same file
+        for service in schema.services.iter() {
+            if self.schema.services.iter().any(|prev| prev.name == service.name && prev.namespace == service.namespace) {
+                let error = ParseError::DuplicateService(service.name.clone()).at(service.span.start.line);
+                return Err(match self.source_map.as_ref() {
+                    Some(source_map) => source_map.resolve_error(error),
+                    None => Error::Parse(path.to_owned(), error),
+                });
+            }
+        }
+
+        let result = &mut self.schema;
+        result.services.append(&mut schema.services);
+        result.tables.append(&mut schema.tables);
+        result.structs.append(&mut schema.structs);
+        result.enums.append(&mut schema.enums);
+        result.unions.append(&mut schema.unions);
+        for attribute in schema.attributes.drain(..) {
+            if !result.attributes.contains(&attribute) {
+                result.attributes.push(attribute);
+            }
+        }
+        //Declarations of the file itself are parsed last
+        if self.stack.is_empty() {
+            result.namespace = schema.namespace;
+            result.includes = schema.includes;
+            result.root_type = schema.root_type;
+            result.file_identifier = schema.file_identifier;
+            result.file_extension = schema.file_extension;
+        }
+        Ok(())
+    }
+}
+
+///Parses all services within schema file.
+///
+///`include` declarations are ignored, use [parse_file_with_includes] to follow them.
+pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<Vec<RpcService>, Error> {
+    let path = path.as_ref();
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(error) => return Err(Error::Io(path.to_owned(), error)),
+    };
+
+    let mut services = Vec::new();
+    for service in ParserIter::from_reader(io::BufReader::new(file)) {
+        match service {
+            Ok(service) => services.push(service),
+            Err(reader::Error::Io(error)) => return Err(Error::Io(path.to_owned(), error)),
+            Err(reader::Error::Parse(error)) => return Err(Error::Parse(path.to_owned(), error)),
+        }
+    }
+
+    Ok(services)
+}
+
+///Applies `map` to every item on all available cores, returning results in order of `items`.
+pub(crate) fn map_parallel<T: Sync, R: Send, F: Fn(&T) -> R + Sync>(items: &[T], map: F) -> Vec<R> {
+    let threads = std::thread::available_parallelism().map_or(1, |threads| threads.get()).min(items.len());
+    if threads <= 1 {
+        return items.iter().map(map).collect();
+    }
+
+    //Items are taken one by one, so that slow item does not hold others
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<Option<R>> = items.iter().map(|_| None).collect();
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads).map(|_| scope.spawn(|| {
+            let mut done = Vec::new();
+            loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                match items.get(idx) {
+                    Some(item) => done.push((idx, map(item))),
+                    None => break done,
+                }
+            }
+        })).collect();
+
+        for worker in workers {
+            match worker.join() {
+                Ok(done) => for (idx, result) in done {
+                    results[idx] = Some(result);
+                },
+                Err(panic) => std::panic::resume_unwind(panic),
+            }
+        }
+    });
+
+    results.into_iter().map(|result| result.expect("every item to be processed")).collect()
+}
+
+///Parses all services within every schema file concurrently, same as [parse_file].
+///
+///Results are in order of `paths`, regardless of order of completion.
+///Files are parsed independently of each other, without any shared state, on all available cores.
+///
+///```rust
+///use flatbuffers_tools::fs::parse_files;
+///
+///let dir = std::env::temp_dir().join(format!("fbs-parse-files-{}", std::process::id()));
+///std::fs::create_dir_all(&dir).unwrap();
+///let mut paths = Vec::new();
+///for idx in 0..8 {
+///    let path = dir.join(format!("service{idx}.fbs"));
+///    std::fs::write(&path, format!("include \"common.fbs\";\nrpc_service Service{idx} {{ Get(Key): Value; }}")).unwrap();
+///    paths.push(path);
+///}
+///paths.push(dir.join("missing.fbs"));
+///
+///let results = parse_files(&paths);
+///assert_eq!(results.len(), paths.len());
+///for (idx, (path, result)) in results.iter().take(8).enumerate() {
+///    assert_eq!(*path, paths[idx]);
+///    assert_eq!(result.as_ref().unwrap()[0].name, format!("Service{idx}"));
+///}
+///assert!(results[8].1.is_err());
+///std::fs::remove_dir_all(&dir).unwrap();
+///```
+pub fn parse_files<P: AsRef<Path> + Sync>(paths: &[P]) -> Vec<(PathBuf, Result<Vec<RpcService>, Error>)> {
+    map_parallel(paths, |path| {
+        let path = path.as_ref();
+        (path.to_owned(), parse_file(path))
+    })
+}
+
+#[inline]
+///Parses all services within schema file, following every `include` declaration.
+///
+///Included files are looked up relative to the including file.
+///
+///Services of included files precede services of including file.
+///Every file is parsed once, even if it is included by several files, while include cycle results in [Error::IncludeCycle].
+///
+///Service names must be unique within the same namespace across all files.
+///
+///```rust
+///use flatbuffers_tools::fs::{parse_file_with_includes, Error};
+///
+///let dir = std::env::temp_dir().join(format!("fbs-includes-{}", std::process::id()));
+///std::fs::create_dir_all(&dir).unwrap();
+///let write = |name: &str, schema: &str| std::fs::write(dir.join(name), schema).unwrap();
+///
+/////Diamond: both `a.fbs` and `b.fbs` include `common.fbs`
+///write("common.fbs", "rpc_service Common { Ping(Req):Resp; }");
+///write("a.fbs", "include \"common.fbs\";\nrpc_service A { Get(Req):Resp; }");
+///write("b.fbs", "include \"common.fbs\";\nrpc_service B { Get(Req):Resp; }");
+///write("main.fbs", "include \"a.fbs\";\ninclude \"b.fbs\";\nrpc_service Main { Get(Req):Resp; }");
+///let services = parse_file_with_includes(&dir.join("main.fbs")).unwrap();
+///let names: Vec<_> = services.iter().map(|service| service.name.as_str()).collect();
+///assert_eq!(names, ["Common", "A", "B", "Main"]);
+///
+/////Cycle of two files
+///write("ping.fbs", "include \"pong.fbs\";");
+///write("pong.fbs", "include \"ping.fbs\";");
+///let ping = dir.join("ping.fbs").canonicalize().unwrap();
+///let pong = dir.join("pong.fbs").canonicalize().unwrap();
+///match parse_file_with_includes(&ping) {
+///    Err(Error::IncludeCycle(cycle)) => assert_eq!(cycle, [ping.clone(), pong.clone(), ping.clone()]),
+///    result => panic!("unexpected result {result:?}"),
+///}
+///
+/////File including itself
+///write("self.fbs", "include \"self.fbs\";\nrpc_service Itself { Get(Req):Resp; }");
+///let itself = dir.join("self.fbs").canonicalize().unwrap();
+///let error = parse_file_with_includes(&itself).unwrap_err();
+///assert!(matches!(&error, Error::IncludeCycle(cycle) if *cycle == [itself.clone(), itself.clone()]));
+///assert_eq!(error.path(), itself);
+///
+///std::fs::remove_dir_all(&dir).unwrap();
+///```
+pub fn parse_file_with_includes(path: &Path) -> Result<Vec<RpcService>, Error> {
+    parse_file_with_include_dirs(path, &[])
+}
+
+#[inline]
+///Parses all services within schema file, following every `include` declaration.
+///
+///Included files are looked up relative to the including file, and then within each of
+///`include_dirs` in order, similarly to `flatc -I`.
+///
+///Services of included files precede services of including file.
+///Every file is parsed once, even if it is included by several files, while include cycle results in [Error::IncludeCycle].
+///
+///Service names must be unique within the same namespace across all files.
+pub fn parse_file_with_include_dirs(path: &Path, include_dirs: &[&Path]) -> Result<Vec<RpcService>, Error> {
+    parse_file_with_limits(path, include_dirs, Limits::new())
+}
+
+///Parses all services within schema file, following every `include` declaration, same as [parse_file_with_include_dirs], enforcing `limits`.
+///
+///Files are limited by their size, while errors of [Limit::InputSize] and [Limit::IncludeDepth] refer to the file without location, i.e. at line 0.
+///
+///```rust
+///use flatbuffers_tools::fs::{parse_file_with_limits, Error};
+///use flatbuffers_tools::{Limit, Limits, ParseError};
+///
+///let dir = std::env::temp_dir().join(format!("fbs-limits-{}", std::process::id()));
+///std::fs::create_dir_all(&dir).unwrap();
+///let write = |name: &str, schema: &str| std::fs::write(dir.join(name), schema).unwrap();
+///
+/////Chain of includes `file0.fbs` -> `file1.fbs` -> ... -> `file9.fbs`
+///for idx in 0..10 {
+///    write(&format!("file{idx}.fbs"), &format!("include \"file{}.fbs\";\nrpc_service Service{idx} {{ Get(Req):Resp; }}", idx + 1));
+///}
+///write("file10.fbs", "");
+///let exceeded = |path: &str, limits: Limits| match parse_file_with_limits(&dir.join(path), &[], limits) {
+///    Err(Error::Parse(path, error)) => (path, error.error),
+///    result => panic!("unexpected result {result:?}"),
+///};
+///
+///let (path, error) = exceeded("file0.fbs", Limits::new().max_include_depth(4));
+///assert_eq!(path, dir.join("file4.fbs"));
+///assert_eq!(error, ParseError::LimitExceeded { limit: Limit::IncludeDepth, max: 4 });
+///assert_eq!(parse_file_with_limits(&dir.join("file6.fbs"), &[], Limits::new().max_include_depth(4)).unwrap().len(), 4);
+///
+///let (path, error) = exceeded("file0.fbs", Limits::new().max_input_size(16));
+///assert_eq!(path, dir.join("file0.fbs"));
+///assert_eq!(error, ParseError::LimitExceeded { limit: Limit::InputSize, max: 16 });
+///
+///let (path, error) = exceeded("file8.fbs", Limits::new().max_line_length(32));
+///assert_eq!(path, dir.join("file8.fbs"));
+///assert_eq!(error, ParseError::LimitExceeded { limit: Limit::LineLength, max: 32 });
+///
+///std::fs::remove_dir_all(&dir).unwrap();
+///```
+pub fn parse_file_with_limits(path: &Path, include_dirs: &[&Path], limits: Limits) -> Result<Vec<RpcService>, Error> {
+    let mut resolver = IncludeResolver::new(include_dirs, None, limits);
+    resolver.parse(path)?;
+    Ok(resolver.schema.services)
+}
+
+#[derive(Debug, Clone)]
+///Schema, parsed from multiple files
+pub struct SchemaFiles {
+    ///Definitions of every file
+    ///
+    ///Locations are logical lines, resolved by [source_map](Self::source_map).
+    pub schema: Schema,
+    ///Origin of lines
+    pub source_map: SourceMap,
+}
+
+///Parses all declarations within schema file and files it includes as one schema.
+///
+///Includes are resolved the same way as by [parse_file_with_include_dirs], with definitions of included files preceding definitions of including file.
+///`namespace`, `root_type`, `file_identifier`, `file_extension` and `include` declarations of `path` itself are retained,
+///while `attribute` declarations of every file are merged.
+///
+///Locations of definitions are lines of all files as if they would follow each other in order of parsing,
+///and are resolved by [SourceMap], while errors refer to the file, where they occur.
+///
+///```rust
+///use flatbuffers_tools::fs::{parse_schema_with_includes, Error};
+///use flatbuffers_tools::ParseError;
+///
+///let dir = std::env::temp_dir().join(format!("fbs-source-map-{}", std::process::id()));
+///std::fs::create_dir_all(&dir).unwrap();
+///let write = |name: &str, schema: &str| std::fs::write(dir.join(name), schema).unwrap();
+///
+///write("common.fbs", "namespace Common;\n\nstruct Key { id: ulong; }\ntable Value { data: [ubyte]; }\n\nrpc_service Storage {\n  Get(Key): Value;\n}\n");
+///write("main.fbs", "include \"common.fbs\";\n\nnamespace Main;\n\ntable Request {}\n\nrpc_service Api {\n  Call(Request): Request;\n}\n\nroot_type Request;\n");
+///let files = parse_schema_with_includes(&dir.join("main.fbs"), &[]).unwrap();
+///assert_eq!(files.source_map.files().collect::<Vec<_>>(), [dir.join("main.fbs"), dir.join("common.fbs")]);
+///assert_eq!(files.schema.root_type.as_deref(), Some("Request"));
+///assert_eq!(files.schema.namespace.as_deref(), Some("Main"));
+///
+/////Definition of included file is resolved to that file
+///let (path, span) = files.source_map.resolve_span(files.schema.services[0].span).unwrap();
+///assert_eq!(path, dir.join("common.fbs"));
+///assert_eq!((span.start.line, span.end.line), (6, 8));
+///let (path, span) = files.source_map.resolve_span(files.schema.services[1].span).unwrap();
+///assert_eq!(path, dir.join("main.fbs"));
+///assert_eq!((span.start.line, span.end.line), (7, 9));
+///
+/////So do findings of validation, as `Key` is struct
+///let errors = files.schema.validate().unwrap_err();
+///assert_eq!(errors.len(), 1);
+///let (path, position) = files.source_map.resolve(errors[0].span.start).unwrap();
+///assert_eq!((path, position.line, position.column), (dir.join("common.fbs").as_path(), 7, 3));
+///
+/////Parse error of included file refers to it
+///write("common.fbs", "namespace Common;\n\ntable Key { id: ulong; }\n\nrpc_service Storage {\n  Get(Key): Key;\n  Put(Key) Key;\n}\n");
+///match parse_schema_with_includes(&dir.join("main.fbs"), &[]) {
+///    Err(Error::Parse(path, error)) => {
+///        assert_eq!(path, dir.join("common.fbs"));
+///        assert_eq!(error.line, 7);
+///    },
+///    result => panic!("unexpected result {result:?}"),
+///}
+///
+/////Same as errors across files
+///write("common.fbs", "table Request {}\nrpc_service Api {\n  Call(Request): Request;\n}\n");
+///write("main.fbs", "include \"common.fbs\";\n\nrpc_service Api {\n  Call(Request): Request;\n}\n");
+///match parse_schema_with_includes(&dir.join("main.fbs"), &[]) {
+///    Err(Error::Parse(path, error)) => {
+///        assert_eq!(path, dir.join("main.fbs"));
+///        assert_eq!(error.line, 3);
+///        assert_eq!(error.error, ParseError::DuplicateService("Api".to_owned()));
+///    },
+///    result => panic!("unexpected result {result:?}"),
+///}
+///
+///std::fs::remove_dir_all(&dir).unwrap();
+///```
+pub fn parse_schema_with_includes(path: &Path, include_dirs: &[&Path]) -> Result<SchemaFiles, Error> {
+    let mut resolver = IncludeResolver::new(include_dirs, Some(SourceMap::default()), Limits::new());
+    resolver.parse(path)?;
+    Ok(SchemaFiles {
+        schema: resolver.schema,
+        source_map: resolver.source_map.unwrap_or_default(),
+    })
+}
+
+///Returns canonical paths of schema file and every file it includes, directly or not.
+///
+///Includes are resolved the same way as by [parse_file_with_include_dirs], and every file is listed once,
+///starting with `path` itself, followed by included files in order of their parsing.
+pub fn schema_files(path: &Path, include_dirs: &[&Path]) -> Result<Vec<PathBuf>, Error> {
+    let mut resolver = IncludeResolver::new(include_dirs, None, Limits::new());
+    resolver.parse(path)?;
+    Ok(resolver.parsed)
+}
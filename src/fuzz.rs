@@ -0,0 +1,288 @@
+//!Generation of arbitrary definitions for fuzzing
+//!
+//!Definitions implement `Arbitrary` of [arbitrary] crate, producing only values that strict parser
+//!and [RpcServiceBuilder](crate::RpcServiceBuilder) accept:
+//!
+//!- Names are identifiers, starting with ASCII letter, and types are qualified by up to 2 namespace segments.
+//!- Method names of service are unique, even after conversion to snake case or upper snake case, so that generated names do not collide.
+//!- Attributes `deprecated`, `id` and `streaming`, which are interpreted by generators, are not produced.
+//!- Documentation lines are words separated by single space.
+//!- Spans are default, as definitions are not parsed.
+//!
+//!Since values are valid, printed schema is parsed back into the same definition:
+//!
+//!```rust
+//!use arbitrary::{Arbitrary, Unstructured};
+//!use flatbuffers_tools::{ParserIter, RpcService};
+//!
+//!//Deterministic input, expected to be provided by fuzzer
+//!let mut seed = 0x2545_f491_4f6c_dd1du64;
+//!for _ in 0..256 {
+//!    let data: Vec<u8> = (0..1024).map(|_| {
+//!        seed ^= seed << 13;
+//!        seed ^= seed >> 7;
+//!        seed ^= seed << 17;
+//!        seed as u8
+//!    }).collect();
+//!    let service = RpcService::arbitrary(&mut Unstructured::new(&data)).expect("to generate");
+//!
+//!    let printed = service.as_fbs().include_namespace(true).to_string();
+//!    let parsed: Vec<RpcService> = ParserIter::from_str(&printed).strict().collect::<Result<_, _>>().expect("to parse");
+//!    assert_eq!(parsed, [service]);
+//!}
+//!```
+//!
+//!Therefore method id constants never collide:
+//!
+//!```rust
+//!use arbitrary::{Arbitrary, Unstructured};
+//!use flatbuffers_tools::{GenConfig, RpcService};
+//!use std::collections::HashSet;
+//!
+//!let mut seed = 0x9e37_79b9_7f4a_7c15u64;
+//!for _ in 0..256 {
+//!    let data: Vec<u8> = (0..1024).map(|_| {
+//!        seed ^= seed << 13;
+//!        seed ^= seed >> 7;
+//!        seed ^= seed << 17;
+//!        seed as u8
+//!    }).collect();
+//!    let service = RpcService::arbitrary(&mut Unstructured::new(&data)).expect("to generate");
+//!
+//!    let code = service.as_rpc_method_defines_with(&GenConfig::new().prefix("RPC_")).render().expect("to generate");
+//!    let names: Vec<&str> = code.lines().filter_map(|line| line.trim().strip_prefix("pub const ")).map(|line| line.split(':').next().unwrap()).collect();
+//!    assert_eq!(names.len(), service.methods.len());
+//!    assert_eq!(names.iter().collect::<HashSet<_>>().len(), names.len());
+//!}
+//!```
+//!
+//!Arbitrary input, rather than valid definitions, is handled by [parse_to_debug_string], which is entry point of fuzz target `fuzz/fuzz_targets/pipeline.rs`.
+
+use crate::{to_snake_case, to_upper_snake_case, Argument, Attribute, Backend, GenError, RpcMethod, RpcService, Span, Streaming, TypeName};
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use core::fmt::Write;
+
+///Characters, that start identifier
+const FIRST_CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+///Characters, that follow the first one within identifier
+const CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789_";
+///Attributes, that are interpreted by generators and require specific values
+const RESERVED_ATTRIBUTES: &[&str] = &["deprecated", "id", "streaming"];
+///Maximum number of methods within service
+const MAX_METHODS: usize = 8;
+
+///Generates valid identifier of up to 16 characters, starting with ASCII letter.
+pub fn identifier(u: &mut Unstructured<'_>) -> Result<String> {
+    let len = u.int_in_range(0..=15)?;
+    let mut name = String::with_capacity(len + 1);
+    name.push(char::from(*u.choose(FIRST_CHARS)?));
+    for _ in 0..len {
+        name.push(char::from(*u.choose(CHARS)?));
+    }
+    Ok(name)
+}
+
+///Generates dot separated name of up to 3 identifiers, e.g. namespace or qualified type name.
+pub fn qualified_name(u: &mut Unstructured<'_>) -> Result<String> {
+    let mut name = identifier(u)?;
+    for _ in 0..u.int_in_range(0..=2)? {
+        name.push('.');
+        name.push_str(&identifier(u)?);
+    }
+    Ok(name)
+}
+
+///Generates up to 3 attributes, excluding ones interpreted by generators.
+pub fn attributes(u: &mut Unstructured<'_>) -> Result<Vec<Attribute>> {
+    let len = u.int_in_range(0..=3)?;
+    let mut attributes = Vec::with_capacity(len);
+    for _ in 0..len {
+        let mut key = identifier(u)?;
+        if RESERVED_ATTRIBUTES.contains(&key.as_str()) {
+            key.push('_');
+        }
+        let value = match bool::arbitrary(u)? {
+            true => Some(identifier(u)?),
+            false => None,
+        };
+        attributes.push((key, value));
+    }
+    Ok(attributes)
+}
+
+///Generates up to 2 lines of documentation.
+pub fn docs(u: &mut Unstructured<'_>) -> Result<Vec<String>> {
+    let len = u.int_in_range(0..=2)?;
+    let mut docs = Vec::with_capacity(len);
+    for _ in 0..len {
+        let mut line = identifier(u)?;
+        for _ in 0..u.int_in_range(0..=5)? {
+            line.push(' ');
+            line.push_str(&identifier(u)?);
+        }
+        docs.push(line);
+    }
+    Ok(docs)
+}
+
+impl<'a> Arbitrary<'a> for Streaming {
+    #[inline]
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.choose(&[Self::None, Self::Server, Self::Client, Self::Bidi]).copied()
+    }
+}
+
+impl<'a> Arbitrary<'a> for TypeName {
+    #[inline]
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        qualified_name(u).map(|name| Self::new(&name))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Argument {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let name = match bool::arbitrary(u)? {
+            true => Some(identifier(u)?),
+            false => None,
+        };
+        Ok(Self {
+            name,
+            ty: qualified_name(u)?.into(),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for RpcMethod {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let name = identifier(u)?;
+        let len = u.int_in_range(0..=3)?;
+        let mut arguments = Vec::with_capacity(len);
+        for _ in 0..len {
+            arguments.push(Argument::arbitrary(u)?);
+        }
+
+        Ok(Self {
+            name,
+            arguments,
+            return_type: qualified_name(u)?.into(),
+            attributes: attributes(u)?,
+            docs: docs(u)?,
+            span: Span::default(),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for RpcService {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let name = identifier(u)?;
+        let namespace = match bool::arbitrary(u)? {
+            true => Some(qualified_name(u)?),
+            false => None,
+        };
+
+        let len = u.int_in_range(0..=MAX_METHODS)?;
+        let mut methods: Vec<RpcMethod> = Vec::with_capacity(len);
+        for _ in 0..len {
+            let method = RpcMethod::arbitrary(u)?;
+            let (snake_case, constant) = (to_snake_case(&method.name), to_upper_snake_case(&method.name));
+            //Method is skipped, rather than renamed, so that input maps to output directly
+            let is_unique = methods.iter().all(|prev| prev.name != method.name && to_snake_case(&prev.name) != snake_case && to_upper_snake_case(&prev.name) != constant);
+            if is_unique {
+                methods.push(method);
+            }
+        }
+
+        Ok(Self {
+            name,
+            methods,
+            docs: docs(u)?,
+            namespace,
+            attributes: attributes(u)?,
+            span: Span::default(),
+        })
+    }
+}
+
+///Writes header of `section`, followed by `result` of generator.
+fn write_output(out: &mut String, section: &str, result: core::result::Result<String, GenError>) {
+    let _ = match result {
+        Ok(code) => out.write_fmt(format_args!("== {section}\n{code}\n")),
+        Err(error) => out.write_fmt(format_args!("== {section}\nerror: {error}\n")),
+    };
+}
+
+///Runs whole pipeline over arbitrary `data`, returning textual dump of every result.
+///
+///Input is decoded as lossy UTF-8 and is parsed by strict, lenient, recovering, borrowed and schema parsers, as well as formatted.
+///Then every generator with default configuration runs over services of lenient parser.
+///
+///It never panics, and dump is the same for the same input, so that it can be compared across runs, versions of crate or with other implementations.
+///
+///```rust
+///use flatbuffers_tools::fuzz::parse_to_debug_string;
+///
+///let dump = parse_to_debug_string(b"rpc_service Store { Get(Key):Value; }");
+///assert!(dump.contains("== rust Store\n"));
+///assert_eq!(dump, parse_to_debug_string(b"rpc_service Store { Get(Key):Value; }"));
+///
+/////Outputs, that cannot express method, fail instead of panicking
+///let dump = parse_to_debug_string(b"rpc_service Storage { Put(Key, Value):Key; }");
+///assert!(dump.contains("== client Storage\nerror: Method 'Put' of service 'Storage' has 2 arguments, while output allows only single argument\n"));
+///let dump = parse_to_debug_string(b"rpc_service Storage { Upload(Chunk):Receipt (streaming: \"client\"); }");
+///assert!(dump.contains("== client Storage\nerror: Method 'Upload' of service 'Storage' has streaming 'client', which output does not support\n"));
+///
+/////Regression inputs of fuzz target
+///let corpus = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("fuzz/corpus/pipeline");
+///for entry in std::fs::read_dir(corpus).into_iter().flatten() {
+///    let input = std::fs::read(entry.unwrap().path()).unwrap();
+///    assert_eq!(parse_to_debug_string(&input), parse_to_debug_string(&input));
+///}
+///```
+pub fn parse_to_debug_string(data: &[u8]) -> String {
+    let text = String::from_utf8_lossy(data);
+    let mut out = String::new();
+
+    let strict: core::result::Result<Vec<_>, _> = crate::ParserIter::from_str(&text).strict().collect();
+    let _ = out.write_fmt(format_args!("== strict\n{strict:?}\n"));
+    let lenient = crate::parse_services(&text);
+    let _ = out.write_fmt(format_args!("== lenient\n{lenient:?}\n"));
+    let recovered = crate::parse_all(text.lines());
+    let _ = out.write_fmt(format_args!("== recovering\n{recovered:?}\n"));
+    let borrowed = crate::borrowed::parse_ref(&text).map(|services| services.iter().map(|service| service.to_owned()).collect::<Vec<_>>());
+    let _ = out.write_fmt(format_args!("== borrowed\n{borrowed:?}\n"));
+    let schema: core::result::Result<crate::schema::Schema, _> = text.parse();
+    let _ = out.write_fmt(format_args!("== schema\n{schema:?}\n"));
+    let formatted = crate::fbs::format_schema(&text, &crate::fbs::FmtStyle::new());
+    let _ = out.write_fmt(format_args!("== format\n{formatted:?}\n"));
+
+    let services = match lenient {
+        Ok(services) => services,
+        Err(_) => return out,
+    };
+    for service in services.iter() {
+        let name = &service.name;
+        let _ = out.write_fmt(format_args!("== fbs {name}\n{}\n", service.to_fbs()));
+        write_output(&mut out, &format!("defines {name}"), service.as_rpc_method_defines().render());
+        write_output(&mut out, &format!("enum {name}"), service.as_rpc_method_enum().render());
+        write_output(&mut out, &format!("client {name}"), service.as_client_stub().render());
+        write_output(&mut out, &format!("dispatcher {name}"), service.as_rpc_dispatcher().render());
+        write_output(&mut out, &format!("descriptor {name}"), service.as_descriptor().render());
+        write_output(&mut out, &format!("aliases {name}"), service.as_type_aliases().render());
+        write_output(&mut out, &format!("markers {name}"), service.as_method_markers().render());
+        write_output(&mut out, &format!("rust {name}"), service.as_module().render());
+        write_output(&mut out, &format!("c {name}"), service.as_c_header().render());
+        write_output(&mut out, &format!("typescript {name}"), service.as_typescript().render());
+        write_output(&mut out, &format!("python {name}"), service.as_python().render());
+        write_output(&mut out, &format!("proto {name}"), service.as_proto().render());
+        write_output(&mut out, &format!("markdown {name}"), service.as_markdown().render());
+    }
+
+    let config = crate::GenConfig::new();
+    let backends: [&dyn Backend; 5] = [&crate::RustBackend, &crate::CBackend, &crate::TypeScriptBackend, &crate::PythonBackend, &crate::MarkdownBackend];
+    for backend in backends {
+        write_output(&mut out, &format!("services {}", backend.extension()), crate::render_services_with(backend, &services, &config));
+    }
+    out
+}
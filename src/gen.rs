@@ -1,4 +1,5 @@
-use crate::RpcService;
+use crate::{ident, Priority, RpcMethod, RpcService, Streaming, TypeName, TypeRef};
+use crate::registry::Assignments;
 
 use core::fmt;
 
@@ -6,210 +7,1921 @@ const TAB: &str = "    ";
 
 #[derive(Copy, Clone)]
 #[repr(transparent)]
-///Generates file with constants defining rpc method names.
-pub struct RpcMethodDefines<'a> {
-    ///Service definition
-    pub service: &'a RpcService,
-}
+///Rust identifier, escaped if it is keyword
+struct Ident<'a>(&'a str);
 
-impl<'a> fmt::Display for RpcMethodDefines<'a> {
+impl<'a> fmt::Display for Ident<'a> {
     #[inline]
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt.write_str("#[rustfmt::skip]\n")?;
+        fmt.write_str(&ident::escape_rust_keyword(self.0, ident::KeywordEscape::Raw))
+    }
+}
 
-        for method in self.service.methods.iter() {
-            let method = method.name.as_str();
-            let name = method.to_uppercase();
-            fmt.write_fmt(format_args!("\npub const {name}: &str = \"{method}\";"))?;
-        }
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+///Line of documentation comment, with characters that could be mistaken for markdown links or html escaped.
+struct DocLine<'a>(&'a str);
 
-        Ok(())
+impl<'a> fmt::Display for DocLine<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rest = self.0;
+        while let Some(idx) = rest.find(['[', ']', '<', '>']) {
+            fmt.write_str(&rest[..idx])?;
+            fmt.write_str("\\")?;
+            fmt.write_str(&rest[idx..idx + 1])?;
+            rest = &rest[idx + 1..];
+        }
+        fmt.write_str(rest)
     }
 }
 
-#[derive(Copy, Clone)]
-///Generates module file interface to parse and forward flatbuffer requests to user's created modules' functions
-///
-///Generated code has following requirements:
+#[inline]
+///Converts method `name` to snake case, suitable for Rust function name.
 ///
-///## Dependencies
+///Refer to [ident::to_snake] for details.
+pub fn to_snake_case(name: &str) -> String {
+    ident::to_snake(name)
+}
+
+#[inline]
+///Converts `name` to upper snake case, same as names of method constants within every output, e.g. `GetStatus` becomes `GET_STATUS`.
 ///
-///- `flatbuffers` - To parse and generate flatbuffer
-///- `xxhash-rust` - To generate efficient method dispatcher using 128bit hashes. Needs features `xxh3` and `const_xxh3`
+///Refer to [ident::to_screaming_snake] for details.
+pub fn to_upper_snake_case(name: &str) -> String {
+    ident::to_screaming_snake(name)
+}
+
+#[inline]
+///Converts `name` to upper camel case, same as flatc does, e.g. `get_status` becomes `GetStatus`.
 ///
-///## User modules
+///Refer to [ident::to_pascal] for details.
+pub fn to_camel_case(name: &str) -> String {
+    ident::to_pascal(name)
+}
+
+#[inline]
+///Returns every method of `service` with its name, converted by `convert`, failing if names of methods are the same.
 ///
-///Assuming schema has two methods `daily` and `transform_new_image`, file will import following
-///modules and functions:
+///`kind` describes name within error, e.g. `constant name`.
+pub fn unique_method_names<'a, F: Fn(&str) -> String>(service: &'a RpcService, kind: &'static str, convert: F) -> Result<Vec<(&'a RpcMethod, String)>, NameCollision> {
+    unique_names(service, service.methods.iter(), kind, convert)
+}
+
+///Returns names of `methods` of `service`, converted by `convert`, ensuring there are no duplicates.
 ///
-///```rust,ignore
-///mod daily;
-///pub use daily::daily;
-///mod transform_new_image;
-///pub use transform_new_image::transform_new_image;
-///```
+///`kind` describes name within error.
+fn unique_names<'a, I: Iterator<Item=&'a RpcMethod>, F: Fn(&str) -> String>(service: &RpcService, methods: I, kind: &'static str, convert: F) -> Result<Vec<(&'a RpcMethod, String)>, NameCollision> {
+    let mut result: Vec<(&'a RpcMethod, String)> = Vec::new();
+    for method in methods {
+        let name = convert(&method.name);
+        if let Some((prev, _)) = result.iter().find(|(_, prev)| *prev == name) {
+            return Err(NameCollision {
+                service: service.name.clone(),
+                first: prev.name.clone(),
+                second: method.name.clone(),
+                kind,
+                name,
+            });
+        }
+        result.push((method, name));
+    }
+    Ok(result)
+}
+
+///Returns Rust function names of `methods` of `service`, ensuring there are no duplicates.
 ///
-///## Module function signature:
+///Names are converted with [to_snake_case], unless `is_original` is set.
+fn function_names<'a, I: Iterator<Item=&'a RpcMethod>>(service: &RpcService, methods: I, is_original: bool) -> Result<Vec<(&'a RpcMethod, String)>, NameCollision> {
+    match is_original {
+        true => unique_names(service, methods, "function name", str::to_owned),
+        false => unique_names(service, methods, "function name", ident::to_snake),
+    }
+}
+
+#[inline]
+///Unwraps result of validation, as `Display` cannot report it otherwise.
+fn expect_valid<T, E: fmt::Display>(result: Result<T, E>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(error) => panic!("{}", error),
+    }
+}
+
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+///Rust path of flatc generated type, where namespace is converted to snake case modules.
 ///
-///```rust,ignore
-///pub async fn daily<'a, 'b>(
-///    rpc_argument: &Request,
-///    mandatory_flatbuffer_builder: &'b mut FlatBufferBuilder<'a>,
-///    extra_arg: &ExtraArgument,
-///) -> Result<ResponseBuilder<'a, 'b>, CommonError> {
-///```
+///E.g. `MyGame.Sample.Request` becomes `my_game::sample::Request`
+struct TypePath<'a>(&'a TypeName);
+
+impl<'a> fmt::Display for TypePath<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for segment in self.0.namespace() {
+            let module = ident::to_flatc_snake(segment);
+            fmt.write_fmt(format_args!("{}::", Ident(&module)))?;
+        }
+        fmt.write_str(self.0.name())
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+///Visibility of generated items
+pub enum Visibility {
+    #[default]
+    ///`pub`
+    Pub,
+    ///`pub(crate)`
+    PubCrate,
+    ///`pub(super)`
+    PubSuper,
+    ///No visibility keyword
+    Private,
+}
+
+impl fmt::Display for Visibility {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pub => fmt.write_str("pub "),
+            Self::PubCrate => fmt.write_str("pub(crate) "),
+            Self::PubSuper => fmt.write_str("pub(super) "),
+            Self::Private => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///Integer type of generated constants
+pub enum IntType {
+    ///`u8`
+    U8,
+    ///`u16`
+    U16,
+    ///`u32`
+    U32,
+    ///`u64`
+    U64,
+}
+
+impl IntType {
+    #[inline]
+    ///Returns maximum value of the type.
+    const fn max(self) -> u64 {
+        match self {
+            Self::U8 => u8::MAX as u64,
+            Self::U16 => u16::MAX as u64,
+            Self::U32 => u32::MAX as u64,
+            Self::U64 => u64::MAX,
+        }
+    }
+}
+
+impl fmt::Display for IntType {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::U8 => fmt.write_str("u8"),
+            Self::U16 => fmt.write_str("u16"),
+            Self::U32 => fmt.write_str("u32"),
+            Self::U64 => fmt.write_str("u64"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+///Library, that generated code may use
+pub enum StdLib {
+    #[default]
+    ///`std`
+    Std,
+    ///`core` and `alloc`, which are used via `alloc::` paths.
+    Alloc,
+    ///`core` only.
+    Core,
+}
+
+impl StdLib {
+    #[inline]
+    ///Returns path of `Vec`, if it is available
+    const fn vec(self) -> Option<&'static str> {
+        match self {
+            Self::Std => Some("Vec"),
+            Self::Alloc => Some("alloc::vec::Vec"),
+            Self::Core => None,
+        }
+    }
+
+    #[inline]
+    ///Returns path of `String`, if it is available
+    const fn string(self) -> Option<&'static str> {
+        match self {
+            Self::Std => Some("String"),
+            Self::Alloc => Some("alloc::string::String"),
+            Self::Core => None,
+        }
+    }
+
+    #[inline]
+    ///Returns name of the library, e.g. `alloc`
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Std => "std",
+            Self::Alloc => "alloc",
+            Self::Core => "core",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///Hash algorithm of method ids
+pub enum HashAlgo {
+    ///32bit FNV-1a of UTF-8 bytes.
+    ///
+    ///Starting with offset basis `0x811c9dc5`, for every byte hash is XORed with it and then multiplied by prime `0x01000193` modulo 2^32.
+    Fnv1a32,
+}
+
+impl HashAlgo {
+    ///Calculates hash of `input`.
+    pub const fn hash(self, input: &str) -> u32 {
+        match self {
+            Self::Fnv1a32 => {
+                let input = input.as_bytes();
+                let mut hash: u32 = 0x811c9dc5;
+                let mut idx = 0;
+                while idx < input.len() {
+                    hash ^= input[idx] as u32;
+                    hash = hash.wrapping_mul(0x01000193);
+                    idx += 1;
+                }
+                hash
+            },
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+///Strategy to assign method ids
 ///
-///## Result type
+///Method can pin its id with `id` attribute, e.g. `Get(Key):Resp (id: 4);`, regardless of strategy.
+///Pinned ids are unsigned decimal integers, and must be unique within service.
+///Other methods get ids of the strategy, so that both pinned and unpinned methods can be mixed within service.
 ///
-///In above example `ResponseBuilder` MUST be builder struct generated by `flatc` which uses `mandatory_flatbuffer_builder`
+///```rust
+///use flatbuffers_tools::{GenConfig, GenError, HashAlgo, IdStrategy, IntType, MethodIdError, RpcService};
 ///
-///## Error type
+///let service: RpcService = "rpc_service Storage { Put(Req):Resp (id: 1); Get(Key):Resp; List(Key):Resp (id: 3); Delete(Key):Resp; }".parse().unwrap();
+///let ids = service.ids(IdStrategy::Index).unwrap();
+///assert_eq!(ids.iter().map(|(id, method)| (id, method.name.as_str())).collect::<Vec<_>>(), [(1, "Put"), (0, "Get"), (3, "List"), (2, "Delete")]);
+///assert_eq!(service.method_id("List"), Some(3));
 ///
-///In above example `CommonError` must implement following method:
 ///
-///```rust,ignore
-///impl Error {
-///    #[inline]
-///    ///Creates flatbuffer error object
-///    pub fn to_interface<'a>(
-///        &self,
-///        builder: &mut flatbuffers::FlatBufferBuilder<'a>,
-///    ) -> flatbuffers::WIPOffset<interface::Error<'a>> {
-///     todo!()
-///    }
-///}
-///```
+/////Every method is pinned
+///let service: RpcService = "rpc_service Storage { Put(Req):Resp (id: 10); Get(Key):Resp (id: 20); }".parse().unwrap();
+///let config = GenConfig::new().int_type(IntType::U8);
+///let defines = service.as_rpc_method_defines_with(&config).render().unwrap();
+///assert!(defines.contains("pub const PUT: u8 = 10;\npub const GET: u8 = 20;"));
+///let method_enum = service.as_rpc_method_enum_with(&config).render().unwrap();
+///assert!(method_enum.contains("    Put = 10,\n    Get = 20,"));
 ///
-///Where `interface::Error` MUST be flatbuffer struct generated by `flatc`
+/////Pinned ids are never hashed
+///let ids = service.ids(IdStrategy::Hash(HashAlgo::Fnv1a32)).unwrap();
+///assert_eq!(ids.id("Get"), Some(20));
 ///
-///P.s. Don't ask why it is not trait.
+///let service: RpcService = "rpc_service Storage { Put(Req):Resp (id: 1); Get(Key):Resp (id: 1); }".parse().unwrap();
+///assert!(matches!(service.ids(IdStrategy::Index).unwrap_err(), MethodIdError::DuplicateId { id: 1, .. }));
+///let error = service.as_rpc_method_defines().render().unwrap_err();
+///assert_eq!(error.to_string(), "Methods 'Put' and 'Get' of service 'Storage' have the same id 1");
 ///
-///## Dispatch signature
+///let service: RpcService = "rpc_service Storage { Put(Req):Resp (id: 256); }".parse().unwrap();
+///let error = service.as_rpc_method_defines_with(&config).render().unwrap_err();
+///assert_eq!(error.to_string(), "Method 'Put' of service 'Storage' has id 256, which exceeds maximum of u8");
+///assert!(matches!(service.as_rpc_method_enum_with(&config).render().unwrap_err(), GenError::MethodId(MethodIdError::IdOverflow { id: 256, .. })));
+///assert!(service.as_rpc_method_defines().render().is_ok());
 ///
-///As result following signature will be generated:
-///```rust,ignore
-///pub async fn dispatch(extra_arg: &ExtraArgument, method: &str, input: &[u8], builder: &mut flatbuffers::FlatBufferBuilder<'_>) -> Option<Result<(), ()>>;
+///let service: RpcService = "rpc_service Storage { Put(Req):Resp (id: -1); }".parse().unwrap();
+///assert!(matches!(service.ids(IdStrategy::Index).unwrap_err(), MethodIdError::InvalidId { .. }));
+///assert_eq!(service.method_id("Put"), None);
 ///```
-///
-///Where return result indicates following:
-///
-///`None` - Unknown method
-///`Some(Ok(()))` - RPC call dispatched successfully, result is written to `builder`
-///`Some(Err(()))` - RPC call failed, error is written to `builder`
-pub struct RpcServiceImplDefines<'a> {
-    ///Service definition
-    pub service: &'a RpcService,
-    ///Extra arguments to define in `dispatch` and pass to every RPC method
+pub enum IdStrategy {
+    #[default]
+    ///Index of the method within service.
     ///
-    ///Following names are used by code generator:
-    ///- `method`
-    ///- `input`
-    ///- `builder`
-    pub extra_args: &'a [(&'a str, &'a str)],
-    ///Callback to be called when RPC input cannot be parsed
+    ///Unpinned methods get the lowest ids, that are not pinned, in order of declaration.
+    Index,
+    ///Hash of `{Service}.{Method}` string (e.g. `Storage.Put`), using names as declared within schema.
     ///
-    ///Must have following signature
+    ///Ids are `u32`, and do not change when other methods are added or removed.
+    Hash(HashAlgo),
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+///Scope, within which method ids are unique
+///
+///```rust
+///use flatbuffers_tools::{GenConfig, GenItem, IdScope, MethodIdError};
+///
+///let services = flatbuffers_tools::parse_services("rpc_service Storage { Put(Req):Resp; Get(Key):Resp; }\nrpc_service Monitor { Put(Req):Pong; }").unwrap();
+///let config = GenConfig::new().id_scope(IdScope::Schema);
+///let ids = flatbuffers_tools::assign_global_ids(&services, &config).unwrap();
+///assert_eq!(ids[0].ids, [("Put".to_owned(), 1), ("Get".to_owned(), 2)]);
+///assert_eq!(ids[1].ids, [("Put".to_owned(), 0)]);
+///assert_eq!(services[0].assigned_ids(&ids[0]).method_by_id(2).unwrap().name, "Get");
+///
+///let code = flatbuffers_tools::render_services(&services, &config.clone().item(GenItem::MethodEnum)).unwrap();
+///assert!(code.contains("pub const PUT: u16 = 1;\n    pub const GET: u16 = 2;"));
+///assert!(code.contains("pub const PUT: u16 = 0;\n"));
+///assert!(code.contains("Put = 1,\n        Get = 2,"));
+///
+///let services = flatbuffers_tools::parse_services("rpc_service Storage { Put(Req):Resp (id: 0); Get(Key):Resp; }\nrpc_service Monitor { Put(Req):Pong; }").unwrap();
+///let ids = flatbuffers_tools::assign_global_ids(&services, &config).unwrap();
+///assert_eq!(ids[0].ids, [("Put".to_owned(), 0), ("Get".to_owned(), 2)]);
+///assert_eq!(ids[1].ids, [("Put".to_owned(), 1)]);
+///
+///let services = flatbuffers_tools::parse_services("rpc_service Storage { Put(Req):Resp (id: 0); }\nrpc_service Monitor { Put(Req):Pong (id: 0); }").unwrap();
+///let error = flatbuffers_tools::assign_global_ids(&services, &config).unwrap_err();
+///assert!(matches!(error, MethodIdError::DuplicateGlobalId { id: 0, .. }));
+///assert_eq!(error.to_string(), "Methods 'Storage.Put' and 'Monitor.Put' have the same id 0, while ids are unique across services");
+///```
+pub enum IdScope {
+    #[default]
+    ///Ids are unique within service, hence methods of different services can have the same id.
+    Service,
+    ///Ids are unique across every service, that is generated together, e.g. to multiplex services over single connection.
     ///
-    ///```rust,ignore
-    ///fn on_invalid_request(builder: &mut flatbuffers::FlatBufferBuilder, error: flatbuffers::InvalidFlatbuffer) -> Option<Result<(), ()>>;
-    ///```
-    pub on_invalid_request_cb: &'a str,
-    ///Specifies whether flatbuffers input and output MUST be prefixed with `size`.
+    ///With [IdStrategy::Index] services are ordered by their names, qualified by namespace, and methods are numbered
+    ///in that order, following order of declaration within service.
+    ///Ids, pinned by `id` attribute, are global ids, and other methods get the lowest ids, that are not pinned by any service.
     ///
-    ///Namely it increases size of message by adding header with size when set to `true`.
-    pub is_size_prefixed: bool,
-    ///Defines constant to limit flatbuffer message size.
-    pub default_message_limit: &'a str,
+    ///Hash ids are the same within either scope, as hash includes service name, but collisions are checked across services.
+    Schema,
 }
 
-impl<'a> fmt::Display for RpcServiceImplDefines<'a> {
-    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let (parse_method, finish_method) = if self.is_size_prefixed {
-            ("size_prefixed_root_with_opts", "finish_size_prefixed")
-        } else {
-            ("root_with_opts", "finish")
-        };
-        let default_message_limit = self.default_message_limit;
-        let on_invalid_request_cb = self.on_invalid_request_cb;
+///Returns id of `method`, pinned by its `id` attribute, if any.
+fn pinned_id(service: &RpcService, method: &RpcMethod) -> Result<Option<u64>, MethodIdError> {
+    match method.attributes.iter().find(|(key, _)| key == "id") {
+        Some((_, value)) => match value.as_ref().and_then(|value| value.trim().parse().ok()) {
+            Some(id) => Ok(Some(id)),
+            None => Err(MethodIdError::InvalidId {
+                service: service.name.clone(),
+                method: method.name.clone(),
+                value: value.clone().unwrap_or_default(),
+            }),
+        },
+        None => Ok(None),
+    }
+}
 
-        fmt.write_str("//Generated by flatbuffers-tools\n")?;
+///Returns id of every method of `service`, in order of declaration.
+fn method_ids(service: &RpcService, id_strategy: IdStrategy) -> Result<Vec<u64>, MethodIdError> {
+    let methods = &service.methods;
+    let mut pinned = Vec::with_capacity(methods.len());
+    for method in methods.iter() {
+        pinned.push(pinned_id(service, method)?);
+    }
 
-        for method in self.service.methods.iter() {
-            let method = method.name.as_str();
-            fmt.write_fmt(format_args!("mod {method};\n"))?;
-            fmt.write_fmt(format_args!("pub use {method}::{method};\n"))?;
+    let mut ids = Vec::with_capacity(methods.len());
+    match id_strategy {
+        IdStrategy::Index => {
+            let mut next = 0;
+            for id in pinned.iter() {
+                match id {
+                    Some(id) => ids.push(*id),
+                    None => {
+                        while pinned.contains(&Some(next)) {
+                            next += 1;
+                        }
+                        ids.push(next);
+                        next += 1;
+                    },
+                }
+            }
+        },
+        IdStrategy::Hash(algo) => for (method, id) in methods.iter().zip(pinned.iter()) {
+            ids.push(id.unwrap_or_else(|| algo.hash(&format!("{}.{}", service.name, method.name)) as u64));
+        },
+    }
+
+    for (idx, id) in ids.iter().enumerate() {
+        if let Some(prev) = ids[..idx].iter().position(|prev| prev == id) {
+            return Err(match (id_strategy, pinned[prev], pinned[idx]) {
+                (IdStrategy::Hash(_), None, None) => MethodIdError::HashCollision(HashCollision {
+                    service: service.name.clone(),
+                    first: methods[prev].name.clone(),
+                    second: methods[idx].name.clone(),
+                    hash: *id as u32,
+                }),
+                _ => MethodIdError::DuplicateId {
+                    service: service.name.clone(),
+                    first: methods[prev].name.clone(),
+                    second: methods[idx].name.clone(),
+                    id: *id,
+                },
+            });
         }
+    }
+    Ok(ids)
+}
 
-        //dispatch signature
-        fmt.write_str("\n#[rustfmt::skip]\n")?;
-        fmt.write_str("pub async fn dispatch(")?;
-        for arg in self.extra_args.iter() {
-            let name = arg.0;
-            let typ = arg.1;
-            fmt.write_fmt(format_args!("{name}: {typ},"))?;
+///Returns ids of methods of every service, that are unique across services, in the same order as `services`.
+///
+///Fails with index of the service, which method id cannot be assigned.
+fn global_ids(services: &[RpcService], id_strategy: IdStrategy, int_type: IntType) -> Result<Vec<Vec<u64>>, (usize, MethodIdError)> {
+    let mut ids = Vec::with_capacity(services.len());
+    match id_strategy {
+        IdStrategy::Index => {
+            let mut pinned = Vec::with_capacity(services.len());
+            for (idx, service) in services.iter().enumerate() {
+                let mut service_pinned = Vec::with_capacity(service.methods.len());
+                for method in service.methods.iter() {
+                    service_pinned.push(pinned_id(service, method).map_err(|error| (idx, error))?);
+                }
+                pinned.push(service_pinned);
+                ids.push(Vec::new());
+            }
+
+            let mut order: Vec<usize> = (0..services.len()).collect();
+            order.sort_by_cached_key(|idx| qualified_name(&services[*idx]));
+            let is_pinned = |id: u64| pinned.iter().flatten().any(|pinned| *pinned == Some(id));
+            let mut next = 0;
+            for idx in order {
+                let service = &services[idx];
+                for (method, pinned) in service.methods.iter().zip(pinned[idx].iter()) {
+                    let id = match pinned {
+                        Some(id) => *id,
+                        None => {
+                            while is_pinned(next) {
+                                next += 1;
+                            }
+                            next += 1;
+                            next - 1
+                        },
+                    };
+                    if id > int_type.max() {
+                        return Err((idx, MethodIdError::IdOverflow {
+                            service: service.name.clone(),
+                            method: method.name.clone(),
+                            id,
+                            int_type,
+                        }));
+                    }
+                    ids[idx].push(id);
+                }
+            }
+        },
+        IdStrategy::Hash(_) => for (idx, service) in services.iter().enumerate() {
+            ids.push(method_ids(service, id_strategy).map_err(|error| (idx, error))?);
+        },
+    }
+
+    //Index of service and method, that has id
+    let mut used: std::collections::HashMap<u64, (usize, usize)> = std::collections::HashMap::new();
+    for (idx, service_ids) in ids.iter().enumerate() {
+        for (method_idx, id) in service_ids.iter().enumerate() {
+            let (prev_idx, prev_method) = match used.insert(*id, (idx, method_idx)) {
+                Some(prev) => prev,
+                None => continue,
+            };
+            let (prev, service) = (&services[prev_idx], &services[idx]);
+            let error = match prev_idx == idx {
+                true => MethodIdError::DuplicateId {
+                    service: service.name.clone(),
+                    first: service.methods[prev_method].name.clone(),
+                    second: service.methods[method_idx].name.clone(),
+                    id: *id,
+                },
+                false => MethodIdError::DuplicateGlobalId {
+                    first: format!("{}.{}", qualified_name(prev), prev.methods[prev_method].name),
+                    second: format!("{}.{}", qualified_name(service), service.methods[method_idx].name),
+                    id: *id,
+                },
+            };
+            return Err((idx, error));
         }
-        fmt.write_str("method: &str, input: &[u8], builder: &mut flatbuffers::FlatBufferBuilder<'_>) -> Option<Result<(), ()>> {\n")?;
+    }
+    Ok(ids)
+}
 
-        //HASH table
-        for method in self.service.methods.iter() {
-            //const hashes
-            let define_name = method.name.to_uppercase();
-            let method = method.name.as_str();
-            fmt.write_fmt(format_args!(
-                "{TAB}const {define_name}: u128 = xxhash_rust::const_xxh3::xxh3_128(b\"{method}\");\n"
-            ))?;
+///Checks that ids, pinned by methods of `service`, fit into `int_type`.
+fn check_pinned_ids(service: &RpcService, int_type: IntType) -> Result<(), MethodIdError> {
+    for method in service.methods.iter() {
+        if let Some(id) = pinned_id(service, method)? {
+            if id > int_type.max() {
+                return Err(MethodIdError::IdOverflow {
+                    service: service.name.clone(),
+                    method: method.name.clone(),
+                    id,
+                    int_type,
+                });
+            }
         }
+    }
+    Ok(())
+}
 
-        //flatbuffer options
-        fmt.write_fmt(format_args!(
-            r#"
-    const OPTIONS: flatbuffers::VerifierOptions = flatbuffers::VerifierOptions {{
-        max_depth: 64,
-        max_tables: 100,
-        max_apparent_size: {default_message_limit},
-        ignore_missing_null_terminator: false,
-    }};
-    "#
-        ))?;
-        //match method
-        fmt.write_fmt(format_args!(
-            "match xxhash_rust::xxh3::xxh3_128(method.as_bytes()) {{\n"
-        ))?;
+#[derive(Debug, Clone)]
+///Method ids of the service, indexed for lookup by name and id.
+///
+///Ids are assigned the same way as by [RpcMethodDefines] with the same [IdStrategy],
+///hence they are always the same as generated constants.
+///
+///```rust
+///use flatbuffers_tools::{GenConfig, HashAlgo, IdStrategy, RpcService, RpcServiceIds};
+///
+///let service: RpcService = "rpc_service Storage { Put(Req):Resp; Get(Key):Resp; }".parse().unwrap();
+///let ids = RpcServiceIds::new(&service, IdStrategy::Index).unwrap();
+///assert_eq!(ids.id("Get"), Some(1));
+///assert_eq!(ids.method_by_id(0).unwrap().name, "Put");
+///
+///let strategy = IdStrategy::Hash(HashAlgo::Fnv1a32);
+///let ids = service.ids(strategy).unwrap();
+///let defines = service.as_rpc_method_defines_with(&GenConfig::new().id_strategy(strategy)).to_string();
+///assert!(defines.contains(&format!("pub const GET: u32 = {};", ids.id("Get").unwrap())));
+///```
+pub struct RpcServiceIds<'a> {
+    service: &'a RpcService,
+    ids: Vec<u64>,
+    by_name: std::collections::HashMap<&'a str, usize>,
+    by_id: std::collections::HashMap<u64, usize>,
+}
 
-        for method in self.service.methods.iter() {
-            assert_eq!(
-                method.arguments.len(),
-                1,
-                "We require all RPC methods to have 1 argument"
-            );
-            let argument = &method.arguments[0];
-            let method = method.name.as_str();
-            let define_name = method.to_uppercase();
-            //parse flatbuffer
-            fmt.write_fmt(format_args!("{TAB}{TAB}{define_name} => match flatbuffers::{parse_method}::<crate::interface::{argument}>(&OPTIONS, input) {{\n"))?;
-            //dispatch task
-            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}Ok(req) => match {method}(req, builder"))?;
-            //forward extra arguments
-            for arg in self.extra_args.iter() {
-                let name = arg.0;
-                fmt.write_str(",")?;
-                fmt.write_str(name)?;
-            }
-            //finish dispsatch
-            fmt.write_str(").await {\n")?;
+impl<'a> RpcServiceIds<'a> {
+    ///Assigns ids to methods of `service` using `id_strategy`, failing if ids of methods are the same or pinned id is invalid.
+    pub fn new(service: &'a RpcService, id_strategy: IdStrategy) -> Result<Self, MethodIdError> {
+        method_ids(service, id_strategy).map(|ids| Self::from_ids(service, ids))
+    }
 
-            //handle task Ok
-            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{TAB}Ok(response) => {{\n"))?;
-            fmt.write_fmt(format_args!(
-                "{TAB}{TAB}{TAB}{TAB}{TAB}let result = response.finish();\n"
-            ))?;
-            fmt.write_fmt(format_args!(
-                "{TAB}{TAB}{TAB}{TAB}{TAB}builder.{finish_method}(result, None);\n"
-            ))?;
-            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{TAB}{TAB}Some(Ok(()))\n"))?;
-            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{TAB}}}\n"))?;
-            //handle task Err
-            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{TAB}Err(error) => {{\n"))?;
+    #[inline]
+    ///Uses ids of methods of `service`, recorded by [crate::registry::IdRegistry].
+    ///
+    ///Ids are the same as generated by [RpcMethodDefines] with the same `assignments`.
+    pub fn with_assignments(service: &'a RpcService, assignments: &Assignments) -> Self {
+        Self::from_ids(service, assignments.method_ids(service))
+    }
+
+    fn from_ids(service: &'a RpcService, ids: Vec<u64>) -> Self {
+        let mut by_name = std::collections::HashMap::with_capacity(ids.len());
+        let mut by_id = std::collections::HashMap::with_capacity(ids.len());
+        for (idx, (id, method)) in ids.iter().zip(service.methods.iter()).enumerate() {
+            by_name.entry(method.name.as_str()).or_insert(idx);
+            by_id.insert(*id, idx);
+        }
+
+        Self {
+            service,
+            ids,
+            by_name,
+            by_id,
+        }
+    }
+
+    #[inline]
+    ///Returns service definition.
+    pub fn service(&self) -> &'a RpcService {
+        self.service
+    }
+
+    #[inline]
+    ///Returns method with specified `name`, as declared within schema.
+    pub fn method(&self, name: &str) -> Option<&'a RpcMethod> {
+        self.by_name.get(name).map(|idx| &self.service.methods[*idx])
+    }
+
+    #[inline]
+    ///Returns id of method with specified `name`, as declared within schema.
+    pub fn id(&self, name: &str) -> Option<u64> {
+        self.by_name.get(name).map(|idx| self.ids[*idx])
+    }
+
+    #[inline]
+    ///Returns method with specified `id`.
+    pub fn method_by_id(&self, id: u64) -> Option<&'a RpcMethod> {
+        self.by_id.get(&id).map(|idx| &self.service.methods[*idx])
+    }
+
+    #[inline]
+    ///Returns iterator over pairs of id and method, in order of declaration.
+    pub fn iter(&self) -> impl Iterator<Item=(u64, &'a RpcMethod)> + '_ {
+        self.ids.iter().copied().zip(self.service.methods.iter())
+    }
+
+    #[inline]
+    ///Returns fingerprint of the service with these ids.
+    ///
+    ///Refer to [FINGERPRINT_VERSION] for algorithm.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fingerprint::new();
+        hasher.service(self.service, &self.ids);
+        hasher.0
+    }
+}
+
+///Version of fingerprint format, that is changed whenever fingerprint of the same service changes.
+///
+///Fingerprint is 64bit FNV-1a of UTF-8 text, that starts with line `v{FINGERPRINT_VERSION}`, followed by every service:
+///
+///- Line `rpc_service {name}`, where name is qualified with namespace of the service, e.g. `rpc_service MyGame.Store`.
+///- Line `{id} {name}({arguments}):{return type}` for every method, in order of ids, e.g. `0 Get(MyGame.Key):MyGame.Value`.
+///  Types are qualified the same as [TypeRef::resolve](crate::TypeRef::resolve) does, without whitespace, and arguments are separated by `,`.
+///
+///Every line ends with `\n`. Hash starts with offset basis `0xcbf29ce484222325`, and for every byte it is XORed with it and then multiplied by prime `0x100000001b3` modulo 2^64.
+///
+///Therefore fingerprint does not depend on formatting, documentation, attributes or names of arguments,
+///while change of method's name, id, argument or return type changes it.
+pub const FINGERPRINT_VERSION: u32 = 1;
+
+///64bit FNV-1a of written text
+struct Fingerprint(u64);
+
+impl Fingerprint {
+    #[inline]
+    fn new() -> Self {
+        let mut hasher = Self(0xcbf29ce484222325);
+        let _ = fmt::Write::write_fmt(&mut hasher, format_args!("v{FINGERPRINT_VERSION}\n"));
+        hasher
+    }
+
+    ///Adds `service` with method `ids` in order of declaration.
+    fn service(&mut self, service: &RpcService, ids: &[u64]) {
+        let mut methods: Vec<_> = ids.iter().zip(service.methods.iter()).collect();
+        methods.sort_by(|(left_id, left), (right_id, right)| left_id.cmp(right_id).then_with(|| left.name.cmp(&right.name)));
+
+        let _ = fmt::Write::write_fmt(self, format_args!("rpc_service {}\n", qualified_name(service)));
+        for (id, method) in methods {
+            let _ = fmt::Write::write_fmt(self, format_args!("{id} {}(", method.name));
+            for (idx, argument) in method.arguments.iter().enumerate() {
+                let separator = if idx > 0 { "," } else { "" };
+                let _ = fmt::Write::write_fmt(self, format_args!("{separator}{}", argument.ty.resolve(service.namespace.as_deref())));
+            }
+            let _ = fmt::Write::write_fmt(self, format_args!("):{}\n", method.return_type.resolve(service.namespace.as_deref())));
+        }
+    }
+}
+
+impl fmt::Write for Fingerprint {
+    #[inline]
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        for byte in text.bytes() {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+        Ok(())
+    }
+}
+
+///Returns fingerprint of `services`, ordered by their qualified names, with ids of [IdStrategy::Index].
+///
+///Ids of service, that cannot be assigned, are indexes of methods.
+pub(crate) fn fingerprint<'a>(services: impl IntoIterator<Item=&'a RpcService>) -> u64 {
+    let mut services: Vec<_> = services.into_iter().map(|service| (qualified_name(service), service)).collect();
+    services.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+    let mut hasher = Fingerprint::new();
+    for (_, service) in services {
+        let ids = method_ids(service, IdStrategy::Index).unwrap_or_else(|_| (0..service.methods.len() as u64).collect());
+        hasher.service(service, &ids);
+    }
+    hasher.0
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+///Two methods of the service have the same hash id
+pub struct HashCollision {
+    ///Service name
+    pub service: String,
+    ///Name of the first method
+    pub first: String,
+    ///Name of the second method
+    pub second: String,
+    ///Hash of both methods
+    pub hash: u32,
+}
+
+impl fmt::Display for HashCollision {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_fmt(format_args!("Methods '{}' and '{}' of service '{}' have the same hash id {:#010x}", self.first, self.second, self.service, self.hash))
+    }
+}
+
+impl std::error::Error for HashCollision {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+///Method ids cannot be assigned
+pub enum MethodIdError {
+    ///Hashes of methods are the same
+    HashCollision(HashCollision),
+    ///Methods have the same id, at least one of which is pinned by `id` attribute
+    DuplicateId {
+        ///Service name
+        service: String,
+        ///Name of the first method
+        first: String,
+        ///Name of the second method
+        second: String,
+        ///Id of both methods
+        id: u64,
+    },
+    ///Value of `id` attribute is not unsigned integer
+    InvalidId {
+        ///Service name
+        service: String,
+        ///Method name
+        method: String,
+        ///Value of attribute, or empty if it is missing
+        value: String,
+    },
+    ///Methods of different services have the same id, while ids are unique across services
+    DuplicateGlobalId {
+        ///Name of the first method, qualified by its service, e.g. `Storage.Put`
+        first: String,
+        ///Name of the second method, qualified by its service
+        second: String,
+        ///Id of both methods
+        id: u64,
+    },
+    ///Pinned id exceeds maximum of integer type of method ids
+    IdOverflow {
+        ///Service name
+        service: String,
+        ///Method name
+        method: String,
+        ///Pinned id
+        id: u64,
+        ///Integer type of method ids
+        int_type: IntType,
+    },
+}
+
+impl From<HashCollision> for MethodIdError {
+    #[inline(always)]
+    fn from(error: HashCollision) -> Self {
+        Self::HashCollision(error)
+    }
+}
+
+impl fmt::Display for MethodIdError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HashCollision(error) => fmt::Display::fmt(error, fmt),
+            Self::DuplicateId { service, first, second, id } => fmt.write_fmt(format_args!("Methods '{first}' and '{second}' of service '{service}' have the same id {id}")),
+            Self::InvalidId { service, method, value } => fmt.write_fmt(format_args!("Method '{method}' of service '{service}' has invalid id '{value}'")),
+            Self::DuplicateGlobalId { first, second, id } => fmt.write_fmt(format_args!("Methods '{first}' and '{second}' have the same id {id}, while ids are unique across services")),
+            Self::IdOverflow { service, method, id, int_type } => fmt.write_fmt(format_args!("Method '{method}' of service '{service}' has id {id}, which exceeds maximum of {int_type}")),
+        }
+    }
+}
+
+impl std::error::Error for MethodIdError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+///Two methods of the service have the same name within generated code, e.g. `getItem` and `GetItem` as constant `GET_ITEM`
+pub struct NameCollision {
+    ///Service name
+    pub service: String,
+    ///Name of the first method, as declared within schema
+    pub first: String,
+    ///Name of the second method, as declared within schema
+    pub second: String,
+    ///Kind of generated name, e.g. `constant name`
+    pub kind: &'static str,
+    ///Generated name of both methods
+    pub name: String,
+}
+
+impl fmt::Display for NameCollision {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_fmt(format_args!("Methods '{}' and '{}' of service '{}' have the same {} '{}'", self.first, self.second, self.service, self.kind, self.name))
+    }
+}
+
+impl std::error::Error for NameCollision {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+///Error of code generation
+pub enum GenError {
+    ///Unable to write generated code
+    Write(fmt::Error),
+    ///Method ids cannot be assigned
+    MethodId(MethodIdError),
+    ///Generated names of methods are the same
+    NameCollision(NameCollision),
+    ///Attributes are specified for method, that is not defined by service
+    UnknownMethod {
+        ///Service name
+        service: String,
+        ///Name of the method
+        method: String,
+    },
+    ///Generated name of method is the same as name of other generated item, e.g. marker `Method`
+    ReservedName {
+        ///Service name
+        service: String,
+        ///Name of the method, as declared within schema
+        method: String,
+        ///Generated name
+        name: String,
+    },
+    ///Services have the same module name, e.g. `Storage` of different namespaces
+    ModuleCollision {
+        ///Name of the first service, qualified by its namespace
+        first: String,
+        ///Name of the second service, qualified by its namespace
+        second: String,
+        ///Module name of both services
+        name: String,
+    },
+    ///Method has unknown value of `streaming` attribute
+    InvalidStreaming {
+        ///Service name
+        service: String,
+        ///Name of the method
+        method: String,
+        ///Value of attribute, empty if there is no value
+        value: String,
+    },
+    ///Method has malformed value of `timeout_ms` attribute or unknown value of `priority` attribute
+    InvalidAttribute {
+        ///Service name
+        service: String,
+        ///Name of the method
+        method: String,
+        ///Name of attribute
+        key: &'static str,
+        ///Value of attribute, empty if there is no value
+        value: String,
+    },
+    ///Method has more than one argument, which output cannot express
+    MultipleArguments {
+        ///Service name
+        service: String,
+        ///Name of the method
+        method: String,
+        ///Number of arguments
+        count: usize,
+    },
+    ///Kind of streaming of method is not supported by output, e.g. streaming method of client
+    UnsupportedStreaming {
+        ///Service name
+        service: String,
+        ///Name of the method
+        method: String,
+        ///Kind of streaming
+        streaming: Streaming,
+    },
+    ///Custom attribute of generated items is empty, e.g. specified by [GenConfig::item_attribute]
+    EmptyAttribute,
+    ///Argument of method has the same name as other argument of generated function, e.g. `builder` of [RpcServiceImplDefines]
+    ReservedArgument {
+        ///Service name
+        service: String,
+        ///Name of the method
+        method: String,
+        ///Name of the argument
+        name: String,
+    },
+    ///Generated item requires library, which output does not allow, e.g. mock with [StdLib::Core]
+    UnsupportedLibrary {
+        ///Item, that requires library, e.g. `Mock`
+        item: &'static str,
+        ///Required library
+        required: StdLib,
+    },
+}
+
+impl From<HashCollision> for GenError {
+    #[inline(always)]
+    fn from(error: HashCollision) -> Self {
+        Self::MethodId(error.into())
+    }
+}
+
+impl From<MethodIdError> for GenError {
+    #[inline(always)]
+    fn from(error: MethodIdError) -> Self {
+        Self::MethodId(error)
+    }
+}
+
+impl From<fmt::Error> for GenError {
+    #[inline(always)]
+    fn from(error: fmt::Error) -> Self {
+        Self::Write(error)
+    }
+}
+
+impl From<NameCollision> for GenError {
+    #[inline(always)]
+    fn from(error: NameCollision) -> Self {
+        Self::NameCollision(error)
+    }
+}
+
+impl fmt::Display for GenError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Write(_) => fmt.write_str("Unable to write generated code"),
+            Self::MethodId(error) => fmt::Display::fmt(error, fmt),
+            Self::NameCollision(error) => fmt::Display::fmt(error, fmt),
+            Self::UnknownMethod { service, method } => fmt.write_fmt(format_args!("Attributes are specified for method '{method}', which is not defined by service '{service}'")),
+            Self::ReservedName { service, method, name } => fmt.write_fmt(format_args!("Method '{method}' of service '{service}' has name '{name}', which is used by other generated item")),
+            Self::ModuleCollision { first, second, name } => fmt.write_fmt(format_args!("Services '{first}' and '{second}' have the same module name '{name}'")),
+            Self::InvalidStreaming { service, method, value } => fmt.write_fmt(format_args!("Method '{method}' of service '{service}' has unknown streaming '{value}', expected one of 'none', 'server', 'client' or 'bidi'")),
+            Self::InvalidAttribute { service, method, key: "priority", value } => fmt.write_fmt(format_args!("Method '{method}' of service '{service}' has unknown priority '{value}', expected one of 'low', 'normal' or 'high'")),
+            Self::InvalidAttribute { service, method, key, value } => fmt.write_fmt(format_args!("Method '{method}' of service '{service}' has invalid '{key}' attribute '{value}', expected unsigned integer")),
+            Self::MultipleArguments { service, method, count } => fmt.write_fmt(format_args!("Method '{method}' of service '{service}' has {count} arguments, while output allows only single argument")),
+            Self::UnsupportedStreaming { service, method, streaming } => fmt.write_fmt(format_args!("Method '{method}' of service '{service}' has streaming '{}', which output does not support", streaming.as_value())),
+            Self::EmptyAttribute => fmt.write_str("Custom attribute of generated items must not be empty"),
+            Self::ReservedArgument { service, method, name } => fmt.write_fmt(format_args!("Method '{method}' of service '{service}' has argument '{name}', which is used by generated code")),
+            Self::UnsupportedLibrary { item, required } => fmt.write_fmt(format_args!("{item} requires {}", required.name())),
+        }
+    }
+}
+
+impl std::error::Error for GenError {}
+
+#[inline]
+///Returns whether custom `attribute` has no text to write.
+fn is_empty_attribute(attribute: &str) -> bool {
+    attribute.trim().is_empty()
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+///Custom attributes of generated items, written verbatim on their own lines, e.g. `#[allow(clippy::too_many_arguments)]`
+pub struct Attributes<'a> {
+    ///Attributes of the item itself, e.g. trait or module.
+    pub item: &'a [String],
+    ///Attributes of every method.
+    pub method: &'a [String],
+    ///Attributes of methods, selected by name as declared within schema.
+    pub named: &'a [(String, String)],
+}
+
+impl<'a> Attributes<'a> {
+    ///Returns whether any attribute is empty.
+    fn has_empty(&self) -> bool {
+        let mut attributes = self.item.iter().chain(self.method.iter()).chain(self.named.iter().map(|(_, attribute)| attribute));
+        attributes.any(|attribute| is_empty_attribute(attribute))
+    }
+
+    ///Checks that attributes are not empty and every method with attributes is defined by `service`.
+    pub fn validate(&self, service: &RpcService) -> Result<(), GenError> {
+        if self.has_empty() {
+            return Err(GenError::EmptyAttribute);
+        }
+        match self.named.iter().find(|(name, _)| service.methods.iter().all(|method| method.name != *name)) {
+            Some((name, _)) => Err(GenError::UnknownMethod {
+                service: service.name.clone(),
+                method: name.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    ///Writes attributes of the item.
+    fn write_item(&self, fmt: &mut fmt::Formatter<'_>, indent: &str) -> fmt::Result {
+        for attribute in self.item.iter() {
+            fmt.write_fmt(format_args!("{indent}{attribute}\n"))?;
+        }
+        Ok(())
+    }
+
+    ///Writes attributes of `method`.
+    fn write_method(&self, fmt: &mut fmt::Formatter<'_>, indent: &str, method: &RpcMethod) -> fmt::Result {
+        for attribute in self.method.iter() {
+            fmt.write_fmt(format_args!("{indent}{attribute}\n"))?;
+        }
+        for (_, attribute) in self.named.iter().filter(|(name, _)| *name == method.name) {
+            fmt.write_fmt(format_args!("{indent}{attribute}\n"))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+///Category of generated items, that share custom attributes, e.g. `#[doc(hidden)]`
+///
+///Refer to [GenConfig::category_attribute]
+pub enum ItemCategory {
+    ///Constants of [RpcMethodDefines], including `FILE_IDENTIFIER`, `METHOD_COUNT` and `SERVICE_FINGERPRINT`.
+    Constants,
+    ///Functions `rpc_method_name` and `rpc_method_id`, and `METHODS` static of [RpcMethodDefines].
+    Lookup,
+    ///Static of [RpcServiceDescriptor].
+    Descriptor,
+    ///Structs of [RpcMethodMarkers], while their trait implementations have no attributes.
+    Markers,
+}
+
+///Writes every attribute of `category` within `attributes` at the start of its own line.
+fn write_category(fmt: &mut fmt::Formatter<'_>, attributes: &[(ItemCategory, String)], category: ItemCategory) -> fmt::Result {
+    for (_, attribute) in attributes.iter().filter(|(attribute_category, _)| *attribute_category == category) {
+        fmt.write_fmt(format_args!("{attribute}\n"))?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+///Handling of methods with `deprecated` attribute
+pub enum DeprecatedPolicy {
+    ///Methods are generated as any other method.
+    Keep,
+    #[default]
+    ///Methods are generated with `#[deprecated]`.
+    Annotate,
+    ///Methods are omitted from traits, dispatchers and clients, as if they were unknown.
+    ///
+    ///Method ids are still defined with `#[deprecated]`, as they remain reserved.
+    Omit,
+}
+
+impl DeprecatedPolicy {
+    #[inline]
+    ///Returns whether `method` is to be generated within traits, dispatchers and clients.
+    fn is_included(self, method: &RpcMethod) -> bool {
+        self != Self::Omit || !method.is_deprecated()
+    }
+
+    #[inline]
+    ///Returns whether `method` is to be marked as deprecated.
+    fn is_annotated(self, method: &RpcMethod) -> bool {
+        self != Self::Keep && method.is_deprecated()
+    }
+}
+
+#[derive(Copy, Clone)]
+///Generates file with constants defining rpc method names.
+///
+///Constant names are method names in upper snake case, e.g. `GetStatus` becomes `GET_STATUS`.
+pub struct RpcMethodDefines<'a> {
+    ///Service definition
+    pub service: &'a RpcService,
+    ///Schema's `file_identifier`, which is to be defined as `FILE_IDENTIFIER` constant, if specified.
+    pub file_identifier: Option<&'a str>,
+    ///Visibility of every constant, `pub` by default.
+    pub visibility: Visibility,
+    ///Prefix of every method constant's name, empty by default.
+    pub prefix: &'a str,
+    ///Specifies whether method constant's name includes service name, e.g. `STORAGE_PUT` instead of `PUT`.
+    pub include_service_name: bool,
+    ///Type of method constants, if they are to be integers.
+    ///
+    ///Integer value is id of the method, while by default constant is method's name string.
+    ///
+    ///With [IdStrategy::Index] ids are guaranteed to be contiguous `0..N` in order of declaration within schema,
+    ///including deprecated methods, hence they can be used to index arrays of `METHOD_COUNT` length.
+    ///Unless ids are pinned by `id` attribute, in which case they can have gaps and are not in order of declaration.
+    pub int_type: Option<IntType>,
+    ///Strategy to assign method ids, [IdStrategy::Index] by default.
+    ///
+    ///With [IdStrategy::Hash] method constants are always `u32` ids, regardless of `int_type`.
+    pub id_strategy: IdStrategy,
+    ///Method ids, recorded by [crate::registry::IdRegistry], that take precedence over `id_strategy` and `id` attributes.
+    ///
+    ///Ids are not contiguous, when methods are removed, and constants are `int_type` or `u16` by default,
+    ///unless ids are hashes.
+    pub assignments: Option<&'a Assignments>,
+    ///Specifies whether to define `METHOD_COUNT: usize` constant with number of methods within service.
+    ///
+    ///Constant name is affected by `prefix` and `include_service_name` same as method constants.
+    pub method_count: bool,
+    ///Specifies whether to define `SERVICE_FINGERPRINT: u64` constant with fingerprint of the service.
+    ///
+    ///Fingerprint is of ids of these defines, hence it is the same as [RpcService::fingerprint] only with [IdStrategy::Index].
+    ///Refer to [FINGERPRINT_VERSION] for algorithm.
+    ///
+    ///Constant name is affected by `prefix` and `include_service_name` same as method constants.
+    pub fingerprint: bool,
+    ///Specifies whether to define `const fn rpc_method_name(id) -> Option<&'static str>`, returning method name by its id.
+    ///
+    ///Argument type is the same as type of method constants, or `u16` if they are names.
+    ///
+    ///Function name is affected by `prefix` and `include_service_name` same as method constants, but in lower case.
+    pub name_lookup: bool,
+    ///Specifies whether to define `fn rpc_method_id(name: &str) -> Option<id>`, returning index of method by its name.
+    ///
+    ///Name must be exactly as declared within schema, and id is the same as `rpc_method_name` accepts.
+    ///
+    ///Return type and function name are the same as for `name_lookup`.
+    pub id_lookup: bool,
+    ///Specifies whether to define `METHODS: &[(&str, id)]` static with name and id of every method.
+    ///
+    ///Methods are in the same order as constants, and id type is the same as for `name_lookup`.
+    ///
+    ///Static name is affected by `prefix` and `include_service_name` same as method constants.
+    pub registry: bool,
+    ///Specifies whether documentation of methods is to be omitted.
+    ///
+    ///Otherwise method's documentation is placed above method constant.
+    pub skip_docs: bool,
+    ///Specifies whether methods are written in alphabetical order of their names, instead of declaration order.
+    ///
+    ///It affects only order of output, while ids are assigned according to `id_strategy` regardless.
+    ///Names, that differ only by case, are ordered by their case sensitive comparison.
+    ///
+    ///Outputs, that use ids of these defines, follow the same order, e.g. [RpcServiceDescriptor].
+    pub is_sorted: bool,
+    ///Handling of deprecated methods, [DeprecatedPolicy::Annotate] by default.
+    ///
+    ///Constants are defined regardless, while formatters using these defines follow the same policy,
+    ///e.g. [RpcServiceDispatcher] omits deprecated methods with [DeprecatedPolicy::Omit].
+    pub deprecated_policy: DeprecatedPolicy,
+    ///Custom attributes of items by their category, written verbatim on their own lines above every item of the category.
+    ///
+    ///Formatters using these defines write attributes of their categories, e.g. [RpcServiceDescriptor] of [ItemCategory::Descriptor].
+    pub category_attributes: &'a [(ItemCategory, String)],
+}
+
+impl<'a> RpcMethodDefines<'a> {
+    #[inline]
+    ///Sets `file_identifier` to define as `FILE_IDENTIFIER` constant.
+    pub const fn with_file_identifier(mut self, file_identifier: &'a str) -> Self {
+        self.file_identifier = Some(file_identifier);
+        self
+    }
+
+    #[inline]
+    ///Sets visibility of generated constants.
+    pub const fn visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    #[inline]
+    ///Sets prefix of method constants' names.
+    pub const fn prefix(mut self, prefix: &'a str) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    #[inline]
+    ///Sets whether method constant's name includes service name.
+    pub const fn include_service_name(mut self, include_service_name: bool) -> Self {
+        self.include_service_name = include_service_name;
+        self
+    }
+
+    #[inline]
+    ///Sets integer type of method constants, making them method indexes instead of names.
+    pub const fn int_type(mut self, int_type: IntType) -> Self {
+        self.int_type = Some(int_type);
+        self
+    }
+
+    #[inline]
+    ///Sets strategy to assign method ids.
+    pub const fn id_strategy(mut self, id_strategy: IdStrategy) -> Self {
+        self.id_strategy = id_strategy;
+        self
+    }
+
+    #[inline]
+    ///Sets method ids, recorded by [crate::registry::IdRegistry].
+    pub const fn assignments(mut self, assignments: &'a Assignments) -> Self {
+        self.assignments = Some(assignments);
+        self
+    }
+
+    #[inline]
+    ///Sets whether to define `METHOD_COUNT` constant.
+    pub const fn method_count(mut self, method_count: bool) -> Self {
+        self.method_count = method_count;
+        self
+    }
+
+    #[inline]
+    ///Sets whether to define `SERVICE_FINGERPRINT` constant.
+    pub const fn fingerprint(mut self, fingerprint: bool) -> Self {
+        self.fingerprint = fingerprint;
+        self
+    }
+
+    #[inline]
+    ///Sets whether to define `rpc_method_name` function.
+    pub const fn name_lookup(mut self, name_lookup: bool) -> Self {
+        self.name_lookup = name_lookup;
+        self
+    }
+
+    #[inline]
+    ///Sets whether to define `rpc_method_id` function.
+    pub const fn id_lookup(mut self, id_lookup: bool) -> Self {
+        self.id_lookup = id_lookup;
+        self
+    }
+
+    #[inline]
+    ///Sets whether to define `METHODS` static.
+    pub const fn registry(mut self, registry: bool) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    #[inline]
+    ///Sets whether documentation of methods is to be omitted.
+    pub const fn skip_docs(mut self, skip_docs: bool) -> Self {
+        self.skip_docs = skip_docs;
+        self
+    }
+
+    #[inline]
+    ///Sets whether methods are written in alphabetical order.
+    pub const fn sorted(mut self, is_sorted: bool) -> Self {
+        self.is_sorted = is_sorted;
+        self
+    }
+
+    #[inline]
+    ///Sets handling of deprecated methods.
+    pub const fn deprecated_policy(mut self, deprecated_policy: DeprecatedPolicy) -> Self {
+        self.deprecated_policy = deprecated_policy;
+        self
+    }
+
+    #[inline]
+    ///Sets custom attributes of items by their category.
+    pub const fn category_attributes(mut self, category_attributes: &'a [(ItemCategory, String)]) -> Self {
+        self.category_attributes = category_attributes;
+        self
+    }
+
+    #[inline]
+    ///Writes attributes of `category` on their own lines, each preceded by line end.
+    fn write_attributes(&self, fmt: &mut fmt::Formatter<'_>, category: ItemCategory) -> fmt::Result {
+        for (_, attribute) in self.category_attributes.iter().filter(|(attribute_category, _)| *attribute_category == category) {
+            fmt.write_fmt(format_args!("\n{attribute}"))?;
+        }
+        Ok(())
+    }
+
+    ///Returns every method with its id, in order of output.
+    fn ordered_ids(&self) -> Result<Vec<(u64, &'a RpcMethod)>, MethodIdError> {
+        let mut result: Vec<_> = self.method_ids()?.into_iter().zip(self.service.methods.iter()).collect();
+        if self.is_sorted {
+            result.sort_by(|(_, left), (_, right)| {
+                left.name.to_lowercase().cmp(&right.name.to_lowercase()).then_with(|| left.name.cmp(&right.name))
+            });
+        }
+        Ok(result)
+    }
+
+    ///Returns id of every method, in order of declaration.
+    ///
+    ///Pinned ids must fit into type of constants, or `u16` if constants are names.
+    fn method_ids(&self) -> Result<Vec<u64>, MethodIdError> {
+        match self.assignments {
+            Some(assignments) => Ok(assignments.method_ids(self.service)),
+            None => {
+                check_pinned_ids(self.service, self.id_type().unwrap_or(IntType::U16))?;
+                method_ids(self.service, self.id_strategy)
+            },
+        }
+    }
+
+    ///Returns common prefix of constants' names.
+    fn names_prefix(&self) -> String {
+        match self.include_service_name {
+            true => format!("{}{}_", self.prefix, ident::to_screaming_snake(&self.service.name)),
+            false => self.prefix.to_owned(),
+        }
+    }
+
+    #[inline]
+    ///Returns common prefix of names, that always includes service name, for outputs without namespaces.
+    fn qualified_prefix(&self) -> String {
+        format!("{}{}_", self.prefix, ident::to_screaming_snake(&self.service.name))
+    }
+
+    #[inline]
+    ///Returns type of method constants, if they are integers.
+    fn id_type(&self) -> Option<IntType> {
+        match self.id_strategy {
+            IdStrategy::Index if self.assignments.is_some() => Some(self.int_type.unwrap_or(IntType::U16)),
+            IdStrategy::Index => self.int_type,
+            IdStrategy::Hash(_) => Some(IntType::U32),
+        }
+    }
+
+    ///Checks that method ids can be assigned, names of constants are unique and attributes are not empty.
+    fn validate(&self) -> Result<(), GenError> {
+        if self.category_attributes.iter().any(|(_, attribute)| is_empty_attribute(attribute)) {
+            return Err(GenError::EmptyAttribute);
+        }
+        self.method_ids()?;
+        unique_names(self.service, self.service.methods.iter(), "constant name", ident::to_screaming_snake)?;
+        Ok(())
+    }
+
+    ///Generates defines, failing if method ids cannot be assigned or names of constants are the same.
+    ///
+    ///Prefer it over `Display`, which panics on [GenError].
+    pub fn render(&self) -> Result<String, GenError> {
+        self.validate()?;
+        Ok(self.to_string())
+    }
+}
+
+impl<'a> fmt::Display for RpcMethodDefines<'a> {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let visibility = self.visibility;
+        fmt.write_str("#[rustfmt::skip]\n")?;
+
+        if let Some(file_identifier) = self.file_identifier {
+            self.write_attributes(fmt, ItemCategory::Constants)?;
+            fmt.write_fmt(format_args!("\n{visibility}const FILE_IDENTIFIER: &[u8; 4] = b\""))?;
+            for byte in file_identifier.bytes() {
+                fmt.write_fmt(format_args!("{}", core::ascii::escape_default(byte)))?;
+            }
+            fmt.write_str("\";")?;
+        }
+
+        let prefix = self.names_prefix();
+        if self.method_count {
+            let count = self.service.methods.len();
+            self.write_attributes(fmt, ItemCategory::Constants)?;
+            fmt.write_fmt(format_args!("\n{visibility}const {prefix}METHOD_COUNT: usize = {count};"))?;
+        }
+        expect_valid(self.validate());
+        if self.fingerprint {
+            let mut hasher = Fingerprint::new();
+            hasher.service(self.service, &expect_valid(self.method_ids()));
+            self.write_attributes(fmt, ItemCategory::Constants)?;
+            fmt.write_fmt(format_args!("\n{visibility}const {prefix}SERVICE_FINGERPRINT: u64 = {:#018x};", hasher.0))?;
+        }
+        let ids = expect_valid(self.ordered_ids());
+        let int_type = self.id_type();
+        for (id, method) in ids.iter() {
+            if !self.skip_docs {
+                for line in method.docs.iter() {
+                    fmt.write_fmt(format_args!("\n///{}", DocLine(line)))?;
+                }
+            }
+            self.write_attributes(fmt, ItemCategory::Constants)?;
+            //Deprecated methods are still defined, as their names and indexes remain reserved
+            if self.deprecated_policy.is_annotated(method) {
+                fmt.write_str("\n#[deprecated]")?;
+            }
+            let method = method.name.as_str();
+            let name = ident::to_screaming_snake(method);
+            match int_type {
+                Some(int_type) => fmt.write_fmt(format_args!("\n{visibility}const {prefix}{name}: {int_type} = {id};"))?,
+                None => fmt.write_fmt(format_args!("\n{visibility}const {prefix}{name}: &str = \"{method}\";"))?,
+            }
+        }
+
+        let int_type = int_type.unwrap_or(IntType::U16);
+        let fn_prefix = prefix.to_lowercase();
+        if self.name_lookup {
+            fmt.write_str("\n")?;
+            self.write_attributes(fmt, ItemCategory::Lookup)?;
+            fmt.write_fmt(format_args!("\n{visibility}const fn {fn_prefix}rpc_method_name(id: {int_type}) -> Option<&'static str> {{\n{TAB}match id {{\n"))?;
+            for (id, method) in ids.iter() {
+                fmt.write_fmt(format_args!("{TAB}{TAB}{id} => Some(\"{}\"),\n", method.name))?;
+            }
+            fmt.write_fmt(format_args!("{TAB}{TAB}_ => None,\n{TAB}}}\n}}"))?;
+        }
+
+        if self.id_lookup {
+            fmt.write_str("\n")?;
+            self.write_attributes(fmt, ItemCategory::Lookup)?;
+            fmt.write_fmt(format_args!("\n{visibility}fn {fn_prefix}rpc_method_id(name: &str) -> Option<{int_type}> {{\n{TAB}match name {{\n"))?;
+            for (id, method) in ids.iter() {
+                fmt.write_fmt(format_args!("{TAB}{TAB}\"{}\" => Some({id}),\n", method.name))?;
+            }
+            fmt.write_fmt(format_args!("{TAB}{TAB}_ => None,\n{TAB}}}\n}}"))?;
+        }
+
+        if self.registry {
+            fmt.write_str("\n")?;
+            self.write_attributes(fmt, ItemCategory::Lookup)?;
+            fmt.write_fmt(format_args!("\n{visibility}static {prefix}METHODS: &[(&str, {int_type})] = &["))?;
+            if !ids.is_empty() {
+                fmt.write_str("\n")?;
+            }
+            for (id, method) in ids.iter() {
+                fmt.write_fmt(format_args!("{TAB}(\"{}\", {id}),\n", method.name))?;
+            }
+            fmt.write_str("];")?;
+        }
+
+        Ok(())
+    }
+}
+
+///Default derives of generated method enum
+pub const DEFAULT_ENUM_DERIVES: [&str; 6] = ["Debug", "Clone", "Copy", "PartialEq", "Eq", "Hash"];
+
+#[derive(Copy, Clone)]
+///Generates enum of rpc methods.
+///
+///Variants are method names in upper camel case, e.g. `get_status` becomes `GetStatus`,
+///while discriminant is id of the method with [IdStrategy::Index], same as [RpcMethodDefines] integer constants.
+///Id is index of the method within service, unless it is pinned by `id` attribute.
+///
+///In addition to enum itself, following is generated:
+///
+///- `TryFrom<int>` returning error type `Unknown{Enum}` on unknown index;
+///- `From<{Enum}>` for integer type;
+///- `as_str()` returning method name, as it is declared within schema.
+pub struct RpcMethodEnum<'a> {
+    ///Service definition
+    pub service: &'a RpcService,
+    ///Name of the enum, `{Service}Method` by default.
+    pub name: Option<&'a str>,
+    ///Derives of the enum, [DEFAULT_ENUM_DERIVES] by default.
+    pub derives: &'a [&'a str],
+    ///Integer type of discriminant, `u16` by default.
+    pub int_type: IntType,
+    ///Visibility of generated items, `pub` by default.
+    pub visibility: Visibility,
+    ///Library, that generated code may use.
+    ///
+    ///`std::error::Error` is implemented for error type only with [StdLib::Std].
+    pub library: StdLib,
+    ///Handling of deprecated methods, [DeprecatedPolicy::Annotate] by default.
+    ///
+    ///With [DeprecatedPolicy::Omit] indexes of deprecated methods are unknown.
+    pub deprecated_policy: DeprecatedPolicy,
+    ///Method ids to use as discriminants instead of indexes, e.g. ids of [IdScope::Schema] or [crate::registry::IdRegistry].
+    ///
+    ///Ids must fit into `int_type`.
+    pub assignments: Option<&'a Assignments>,
+}
+
+impl<'a> RpcMethodEnum<'a> {
+    #[inline]
+    ///Sets library, that generated code may use.
+    pub const fn library(mut self, library: StdLib) -> Self {
+        self.library = library;
+        self
+    }
+
+    #[inline]
+    ///Sets name of the enum.
+    pub const fn name(mut self, name: &'a str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    #[inline]
+    ///Sets derives of the enum.
+    pub const fn derives(mut self, derives: &'a [&'a str]) -> Self {
+        self.derives = derives;
+        self
+    }
+
+    #[inline]
+    ///Sets integer type of discriminant.
+    pub const fn int_type(mut self, int_type: IntType) -> Self {
+        self.int_type = int_type;
+        self
+    }
+
+    #[inline]
+    ///Sets handling of deprecated methods.
+    pub const fn deprecated_policy(mut self, deprecated_policy: DeprecatedPolicy) -> Self {
+        self.deprecated_policy = deprecated_policy;
+        self
+    }
+
+    #[inline]
+    ///Sets visibility of generated items.
+    pub const fn visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    #[inline]
+    ///Sets method ids to use as discriminants.
+    pub const fn assignments(mut self, assignments: &'a Assignments) -> Self {
+        self.assignments = Some(assignments);
+        self
+    }
+
+    #[inline]
+    ///Returns names of variants, ensuring they are unique.
+    fn variants(&self) -> Result<Vec<(&'a RpcMethod, String)>, NameCollision> {
+        unique_names(self.service, self.service.methods.iter(), "variant name", ident::to_pascal)
+    }
+
+    ///Returns discriminant of every method, in order of declaration.
+    fn ids(&self) -> Result<Vec<u64>, MethodIdError> {
+        let assignments = match self.assignments {
+            Some(assignments) => assignments,
+            None => {
+                check_pinned_ids(self.service, self.int_type)?;
+                return method_ids(self.service, IdStrategy::Index);
+            },
+        };
+        let ids = assignments.method_ids(self.service);
+        for (method, id) in self.service.methods.iter().zip(ids.iter()) {
+            if *id > self.int_type.max() {
+                return Err(MethodIdError::IdOverflow {
+                    service: self.service.name.clone(),
+                    method: method.name.clone(),
+                    id: *id,
+                    int_type: self.int_type,
+                });
+            }
+        }
+        Ok(ids)
+    }
+
+    ///Generates enum, failing if names of variants are the same or method ids cannot be assigned.
+    ///
+    ///Prefer it over `Display`, which panics on [GenError].
+    pub fn render(&self) -> Result<String, GenError> {
+        self.variants()?;
+        self.ids()?;
+        Ok(self.to_string())
+    }
+}
+
+impl<'a> fmt::Display for RpcMethodEnum<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let visibility = self.visibility;
+        let int_type = self.int_type;
+        let name = match self.name {
+            Some(name) => name.to_owned(),
+            None => format!("{}Method", self.service.name),
+        };
+        let policy = self.deprecated_policy;
+        //Id of method is its discriminant, even if preceding methods are omitted
+        let ids = expect_valid(self.ids());
+        let variants: Vec<(u64, &RpcMethod, String)> = expect_valid(self.variants()).into_iter().zip(ids).filter(|((method, _), _)| policy.is_included(method)).map(|((method, variant), id)| (id, method, variant)).collect();
+        let allow_deprecated = match variants.iter().any(|(_, method, _)| policy.is_annotated(method)) {
+            true => "#[allow(deprecated)]\n",
+            false => "",
+        };
+
+        //enum
+        fmt.write_str("#[rustfmt::skip]\n")?;
+        if !self.derives.is_empty() {
+            fmt.write_fmt(format_args!("{allow_deprecated}#[derive({})]\n", self.derives.join(", ")))?;
+        }
+        //Enum without variants cannot have integer representation
+        if !variants.is_empty() {
+            fmt.write_fmt(format_args!("#[repr({int_type})]\n"))?;
+        }
+        fmt.write_fmt(format_args!("{visibility}enum {name} {{\n"))?;
+        for (idx, method, variant) in variants.iter() {
+            if policy.is_annotated(method) {
+                fmt.write_fmt(format_args!("{TAB}#[deprecated]\n"))?;
+            }
+            fmt.write_fmt(format_args!("{TAB}{} = {idx},\n", Ident(variant)))?;
+        }
+        fmt.write_str("}\n")?;
+
+        //as_str
+        fmt.write_fmt(format_args!("\n{allow_deprecated}impl {name} {{\n"))?;
+        fmt.write_fmt(format_args!("{TAB}#[inline]\n{TAB}///Returns method name as declared within schema.\n"))?;
+        fmt.write_fmt(format_args!("{TAB}{visibility}const fn as_str(&self) -> &'static str {{\n{TAB}{TAB}match *self {{\n"))?;
+        for (_, method, variant) in variants.iter() {
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}Self::{} => \"{}\",\n", Ident(variant), method.name))?;
+        }
+        fmt.write_fmt(format_args!("{TAB}{TAB}}}\n{TAB}}}\n}}\n"))?;
+
+        //error
+        fmt.write_fmt(format_args!("\n#[derive(Debug, Clone, Copy, PartialEq, Eq)]\n///Unknown `{name}` index\n{visibility}struct Unknown{name}(pub {int_type});\n"))?;
+        fmt.write_fmt(format_args!("\nimpl core::fmt::Display for Unknown{name} {{\n"))?;
+        fmt.write_fmt(format_args!("{TAB}#[inline]\n{TAB}fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {{\n"))?;
+        fmt.write_fmt(format_args!("{TAB}{TAB}fmt.write_fmt(format_args!(\"unknown {} method index {{}}\", self.0))\n{TAB}}}\n}}\n", self.service.name))?;
+        if let StdLib::Std = self.library {
+            fmt.write_fmt(format_args!("\nimpl std::error::Error for Unknown{name} {{}}\n"))?;
+        }
+
+        //TryFrom
+        fmt.write_fmt(format_args!("\n{allow_deprecated}impl core::convert::TryFrom<{int_type}> for {name} {{\n{TAB}type Error = Unknown{name};\n\n"))?;
+        fmt.write_fmt(format_args!("{TAB}#[inline]\n{TAB}fn try_from(value: {int_type}) -> Result<Self, Self::Error> {{\n{TAB}{TAB}match value {{\n"))?;
+        for (idx, _, variant) in variants.iter() {
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{idx} => Ok(Self::{}),\n", Ident(variant)))?;
+        }
+        fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}_ => Err(Unknown{name}(value)),\n{TAB}{TAB}}}\n{TAB}}}\n}}\n"))?;
+
+        //From
+        fmt.write_fmt(format_args!("\n{allow_deprecated}impl From<{name}> for {int_type} {{\n"))?;
+        fmt.write_fmt(format_args!("{TAB}#[inline]\n{TAB}fn from(value: {name}) -> Self {{\n{TAB}{TAB}match value {{\n"))?;
+        for (idx, _, variant) in variants.iter() {
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{name}::{} => {idx},\n", Ident(variant)))?;
+        }
+        fmt.write_fmt(format_args!("{TAB}{TAB}}}\n{TAB}}}\n}}"))
+    }
+}
+
+#[derive(Copy, Clone)]
+///Generates module file interface to parse and forward flatbuffer requests to user's created modules' functions
+///
+///Generated code has following requirements:
+///
+///## Dependencies
+///
+///- `flatbuffers` - To parse and generate flatbuffer
+///- `xxhash-rust` - To generate efficient method dispatcher using 128bit hashes. Needs features `xxh3` and `const_xxh3`
+///
+///## User modules
+///
+///Assuming schema has two methods `daily` and `transform_new_image`, file will import following
+///modules and functions:
+///
+///```rust,ignore
+///mod daily;
+///pub use daily::daily;
+///mod transform_new_image;
+///pub use transform_new_image::transform_new_image;
+///```
+///
+///## Module function signature:
+///
+///```rust,ignore
+///pub async fn daily<'a, 'b>(
+///    rpc_argument: &Request,
+///    mandatory_flatbuffer_builder: &'b mut FlatBufferBuilder<'a>,
+///    extra_arg: &ExtraArgument,
+///) -> Result<ResponseBuilder<'a, 'b>, CommonError> {
+///```
+///
+///If method has no arguments, `rpc_argument` is omitted and input is not parsed.
+///
+///Module functions are plain `async fn` rather than trait methods, and are awaited by `dispatch`,
+///hence they can be used with any async runtime as it is.
+///Handler trait with `async fn` methods is generated by [RpcServiceDispatcher] with [Asyncness::Native] or [Asyncness::AsyncTrait].
+///
+///Argument types are expected within `crate::interface`, with namespaces as modules in snake case,
+///same as generated by `flatc`. E.g. `MyGame.Sample.Request` is `crate::interface::my_game::sample::Request`
+///
+///Types without namespace are within namespace of the service, e.g. `Request` of service in `MyGame` namespace
+///is `crate::interface::my_game::Request`.
+///
+///Method names are converted to snake case using [to_snake_case], e.g. `GetStatus` becomes `get_status`,
+///unless `is_original_names` is set.
+///
+///Method names, that are Rust keywords, are used as raw identifiers (e.g. `r#type`), except
+///`crate`, `self`, `Self` and `super`, which cannot be raw identifiers and therefore are suffixed with `_` (e.g. `self_`).
+///
+///## Result type
+///
+///In above example `ResponseBuilder` MUST be builder struct generated by `flatc` which uses `mandatory_flatbuffer_builder`
+///
+///## Error type
+///
+///In above example `CommonError` must implement following method:
+///
+///```rust,ignore
+///impl Error {
+///    #[inline]
+///    ///Creates flatbuffer error object
+///    pub fn to_interface<'a>(
+///        &self,
+///        builder: &mut flatbuffers::FlatBufferBuilder<'a>,
+///    ) -> flatbuffers::WIPOffset<interface::Error<'a>> {
+///     todo!()
+///    }
+///}
+///```
+///
+///Where `interface::Error` MUST be flatbuffer struct generated by `flatc`
+///
+///P.s. Don't ask why it is not trait.
+///
+///## Dispatch signature
+///
+///As result following signature will be generated:
+///```rust,ignore
+///pub async fn dispatch(extra_arg: &ExtraArgument, method: &str, input: &[u8], builder: &mut flatbuffers::FlatBufferBuilder<'_>) -> Option<Result<(), ()>>;
+///```
+///
+///Where return result indicates following:
+///
+///`None` - Unknown method
+///`Some(Ok(()))` - RPC call dispatched successfully, result is written to `builder`
+///`Some(Err(()))` - RPC call failed, error is written to `builder`
+pub struct RpcServiceImplDefines<'a> {
+    ///Service definition
+    pub service: &'a RpcService,
+    ///Extra arguments to define in `dispatch` and pass to every RPC method
+    ///
+    ///Following names are used by code generator:
+    ///- `method`
+    ///- `input`
+    ///- `builder`
+    ///- Names of methods' arguments, or `arg0` for unnamed argument
+    ///
+    ///Method's argument with any of these names is reported as [GenError::ReservedArgument].
+    pub extra_args: &'a [(&'a str, &'a str)],
+    ///Callback to be called when RPC input cannot be parsed
+    ///
+    ///Must have following signature
+    ///
+    ///```rust,ignore
+    ///fn on_invalid_request(builder: &mut flatbuffers::FlatBufferBuilder, error: flatbuffers::InvalidFlatbuffer) -> Option<Result<(), ()>>;
+    ///```
+    pub on_invalid_request_cb: &'a str,
+    ///Specifies whether flatbuffers input and output MUST be prefixed with `size`.
+    ///
+    ///Namely it increases size of message by adding header with size when set to `true`.
+    pub is_size_prefixed: bool,
+    ///Defines constant to limit flatbuffer message size.
+    pub default_message_limit: &'a str,
+    ///Specifies whether methods with `deprecated` attribute are to be skipped.
+    ///
+    ///When set to `true`, deprecated methods are neither imported nor dispatched, as if they were unknown.
+    ///
+    ///Otherwise their modules are marked with `#[deprecated]`, unless `deprecated_policy` is [DeprecatedPolicy::Keep].
+    ///
+    ///It is the same as [DeprecatedPolicy::Omit].
+    pub skip_deprecated: bool,
+    ///Specifies whether modules and functions are to be named exactly as methods within schema.
+    ///
+    ///Set it to `true` to keep names of previous versions, which did not convert names to snake case.
+    pub is_original_names: bool,
+    ///Handling of deprecated methods, [DeprecatedPolicy::Annotate] by default.
+    pub deprecated_policy: DeprecatedPolicy,
+}
+
+impl<'a> RpcServiceImplDefines<'a> {
+    #[inline]
+    fn methods(&self) -> impl Iterator<Item=&'a RpcMethod> {
+        let policy = self.policy();
+        self.service.methods.iter().filter(move |method| policy.is_included(method))
+    }
+
+    #[inline]
+    fn policy(&self) -> DeprecatedPolicy {
+        match self.skip_deprecated {
+            true => DeprecatedPolicy::Omit,
+            false => self.deprecated_policy,
+        }
+    }
+
+    ///Checks that names of methods' arguments are not used by `dispatch`.
+    fn check_argument_names(&self) -> Result<(), GenError> {
+        const RESERVED: [&str; 3] = ["method", "input", "builder"];
+
+        for method in self.methods() {
+            for argument in method.arguments.iter() {
+                let name = argument.name.as_deref().unwrap_or("arg0");
+                if RESERVED.contains(&name) || self.extra_args.iter().any(|(arg, _)| *arg == name) {
+                    return Err(GenError::ReservedArgument {
+                        service: self.service.name.clone(),
+                        method: method.name.clone(),
+                        name: name.to_owned(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    ///Generates modules, failing if names of modules are the same, method has multiple arguments or argument's name is used by `dispatch`.
+    ///
+    ///Prefer it over `Display`, which panics on [GenError].
+    ///
+    ///```rust
+    ///use flatbuffers_tools::{GenError, RpcServiceImplDefines, DeprecatedPolicy};
+    ///
+    ///let services = flatbuffers_tools::parse_services("rpc_service Storage {\n  Put(builder: Value):Key;\n  Get(Key):Value;\n}").unwrap();
+    ///let defines = RpcServiceImplDefines {
+    ///    service: &services[0],
+    ///    extra_args: &[("arg0", "&Context")],
+    ///    on_invalid_request_cb: "on_invalid_request",
+    ///    is_size_prefixed: false,
+    ///    default_message_limit: "1024",
+    ///    skip_deprecated: false,
+    ///    is_original_names: false,
+    ///    deprecated_policy: DeprecatedPolicy::Annotate,
+    ///};
+    ///let error = defines.render().unwrap_err();
+    ///assert_eq!(error, GenError::ReservedArgument { service: "Storage".to_owned(), method: "Put".to_owned(), name: "builder".to_owned() });
+    ///assert_eq!(error.to_string(), "Method 'Put' of service 'Storage' has argument 'builder', which is used by generated code");
+    ///
+    /////Unnamed argument is bound as `arg0`
+    ///let services = flatbuffers_tools::parse_services("rpc_service Storage {\n  Put(request: Value):Key;\n  Get(Key):Value;\n}").unwrap();
+    ///let error = RpcServiceImplDefines { service: &services[0], ..defines }.render().unwrap_err();
+    ///assert_eq!(error.to_string(), "Method 'Get' of service 'Storage' has argument 'arg0', which is used by generated code");
+    ///
+    ///let code = RpcServiceImplDefines { service: &services[0], extra_args: &[("ctx", "&Context")], ..defines }.render().unwrap();
+    ///assert!(code.contains("Ok(request) => match put(request, builder,ctx).await {"));
+    ///```
+    pub fn render(&self) -> Result<String, GenError> {
+        function_names(self.service, self.methods(), self.is_original_names)?;
+        check_arguments(self.service, self.methods())?;
+        self.check_argument_names()?;
+        Ok(self.to_string())
+    }
+}
+
+impl<'a> fmt::Display for RpcServiceImplDefines<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (parse_method, finish_method) = if self.is_size_prefixed {
+            ("size_prefixed_root_with_opts", "finish_size_prefixed")
+        } else {
+            ("root_with_opts", "finish")
+        };
+        let default_message_limit = self.default_message_limit;
+        let on_invalid_request_cb = self.on_invalid_request_cb;
+
+        fmt.write_str("//Generated by flatbuffers-tools\n")?;
+
+        let methods = expect_valid(function_names(self.service, self.methods(), self.is_original_names));
+        expect_valid(self.check_argument_names());
+        for (method, name) in methods.iter() {
+            let is_deprecated = self.policy().is_annotated(method);
+            let method = Ident(name);
+            if is_deprecated {
+                fmt.write_str("#[deprecated]\n")?;
+            }
+            fmt.write_fmt(format_args!("mod {method};\n"))?;
+            if is_deprecated {
+                fmt.write_str("#[allow(deprecated)]\n")?;
+            }
+            fmt.write_fmt(format_args!("pub use {method}::{method};\n"))?;
+        }
+
+        //dispatch signature
+        fmt.write_str("\n#[rustfmt::skip]\n")?;
+        if self.methods().any(|method| self.policy().is_annotated(method)) {
+            fmt.write_str("#[allow(deprecated)]\n")?;
+        }
+        fmt.write_str("pub async fn dispatch(")?;
+        for arg in self.extra_args.iter() {
+            let name = arg.0;
+            let typ = arg.1;
+            fmt.write_fmt(format_args!("{name}: {typ},"))?;
+        }
+        fmt.write_str("method: &str, input: &[u8], builder: &mut flatbuffers::FlatBufferBuilder<'_>) -> Option<Result<(), ()>> {\n")?;
+
+        //HASH table
+        for method in self.methods() {
+            //const hashes
+            let define_name = method.name.to_uppercase();
+            let method = method.name.as_str();
+            fmt.write_fmt(format_args!(
+                "{TAB}const {define_name}: u128 = xxhash_rust::const_xxh3::xxh3_128(b\"{method}\");\n"
+            ))?;
+        }
+
+        //flatbuffer options
+        fmt.write_fmt(format_args!(
+            r#"
+    const OPTIONS: flatbuffers::VerifierOptions = flatbuffers::VerifierOptions {{
+        max_depth: 64,
+        max_tables: 100,
+        max_apparent_size: {default_message_limit},
+        ignore_missing_null_terminator: false,
+    }};
+    "#
+        ))?;
+        //match method
+        fmt.write_fmt(format_args!(
+            "match xxhash_rust::xxh3::xxh3_128(method.as_bytes()) {{\n"
+        ))?;
+
+        for (method, name) in methods.iter() {
+            assert!(
+                method.arguments.len() <= 1,
+                "We require all RPC methods to have at most 1 argument"
+            );
+            let argument = method.arguments.first();
+            let define_name = method.name.to_uppercase();
+            let method = Ident(name);
+            match argument {
+                Some(argument) => {
+                    let name = Ident(argument.name.as_deref().unwrap_or("arg0"));
+                    let argument = argument.ty.resolve(self.service.namespace.as_deref());
+                    let argument = TypePath(&argument);
+                    //parse flatbuffer
+                    fmt.write_fmt(format_args!("{TAB}{TAB}{define_name} => match flatbuffers::{parse_method}::<crate::interface::{argument}>(&OPTIONS, input) {{\n"))?;
+                    //dispatch task
+                    fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}Ok({name}) => match {method}({name}, builder"))?;
+                },
+                //Nothing to parse, dispatch task right away
+                None => fmt.write_fmt(format_args!("{TAB}{TAB}{define_name} => match {method}(builder"))?,
+            }
+            //forward extra arguments
+            for arg in self.extra_args.iter() {
+                let name = arg.0;
+                fmt.write_str(",")?;
+                fmt.write_str(name)?;
+            }
+            //finish dispsatch
+            fmt.write_str(").await {\n")?;
+
+            //handle task Ok
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{TAB}Ok(response) => {{\n"))?;
+            fmt.write_fmt(format_args!(
+                "{TAB}{TAB}{TAB}{TAB}{TAB}let result = response.finish();\n"
+            ))?;
+            fmt.write_fmt(format_args!(
+                "{TAB}{TAB}{TAB}{TAB}{TAB}builder.{finish_method}(result, None);\n"
+            ))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{TAB}{TAB}Some(Ok(()))\n"))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{TAB}}}\n"))?;
+            //handle task Err
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{TAB}Err(error) => {{\n"))?;
             fmt.write_fmt(format_args!(
                 "{TAB}{TAB}{TAB}{TAB}{TAB}let result = error.to_interface(builder);\n"
             ))?;
@@ -219,17 +1931,5499 @@ impl<'a> fmt::Display for RpcServiceImplDefines<'a> {
             fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{TAB}{TAB}Some(Err(()))\n"))?;
             fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{TAB}}}\n"))?;
 
-            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}}}\n"))?;
-            //handle parse error
-            fmt.write_fmt(format_args!(
-                "{TAB}{TAB}{TAB}Err(error) => {on_invalid_request_cb}(builder, error),\n"
-            ))?;
-            fmt.write_fmt(format_args!("{TAB}{TAB}}}\n"))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}}}\n"))?;
+            if argument.is_some() {
+                //handle parse error
+                fmt.write_fmt(format_args!(
+                    "{TAB}{TAB}{TAB}Err(error) => {on_invalid_request_cb}(builder, error),\n"
+                ))?;
+                fmt.write_fmt(format_args!("{TAB}{TAB}}}\n"))?;
+            }
+        }
+        fmt.write_fmt(format_args!("{TAB}{TAB}_ => None,\n"))?;
+        fmt.write_fmt(format_args!("{TAB}}}\n"))?;
+
+        //end
+        fmt.write_str("}")
+    }
+}
+
+///Writes `docs` with `indent`, or `default` documentation if there are none or they are skipped.
+fn write_docs(fmt: &mut fmt::Formatter<'_>, indent: &str, docs: &[String], skip_docs: bool, default: fmt::Arguments<'_>) -> fmt::Result {
+    if skip_docs || docs.is_empty() {
+        return fmt.write_fmt(format_args!("{indent}///{default}\n"));
+    }
+    for line in docs.iter() {
+        fmt.write_fmt(format_args!("{indent}///{}\n", DocLine(line)))?;
+    }
+    Ok(())
+}
+
+///Maps schema type to Rust path, using `type_path` callback if specified.
+///
+///Type name is resolved within namespace of `service` first.
+fn map_type(type_path: Option<&dyn Fn(&TypeName) -> String>, service: &RpcService, ty: &TypeRef) -> String {
+    let name = ty.resolve(service.namespace.as_deref());
+    match type_path {
+        Some(type_path) => type_path(&name),
+        None => format!("crate::interface::{}", TypePath(&name)),
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///Generates client `Transport` trait and `ClientError` type, shared by every [RpcClientStub].
+///
+///It is to be generated once per output, with the same method id type, as stubs use.
+///
+///```rust,ignore
+///pub trait Transport {
+///    type Error;
+///    fn call(&mut self, method: u16, payload: &[u8]) -> Result<Vec<u8>, Self::Error>;
+///}
+///
+///pub enum ClientError<E> {
+///    Transport(E),
+///    InvalidResponse(flatbuffers::InvalidFlatbuffer),
+///}
+///```
+///
+///When framed, `call` accepts whole request frame, returning whole response frame instead:
+///
+///```rust,ignore
+///fn call(&mut self, frame: &[u8]) -> Result<Vec<u8>, Self::Error>;
+///```
+///
+///And `ClientError` has `InvalidFrame` variant, returned when response frame cannot be decoded.
+///
+///When messages are encoded by `Codec`, as with [RpcClientStub::codec], `InvalidResponse` holds `DispatchError` of `Codec` instead,
+///so that generated code has no dependency on `flatbuffers`.
+pub struct RpcClientTransport {
+    ///Type of method id, or `None` if method is identified by its name.
+    pub id_type: Option<IntType>,
+    ///Library, that generated code may use.
+    ///
+    ///`alloc` is required regardless, as responses are stored in `Vec`.
+    pub library: StdLib,
+    ///Specifies whether calls are framed, as encoded by [RpcClientStub::framing].
+    pub is_framed: bool,
+    ///Specifies whether responses are decoded by `Codec`, as with [RpcClientStub::codec].
+    pub is_codec: bool,
+}
+
+impl fmt::Display for RpcClientTransport {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let id_type = match self.id_type {
+            Some(id_type) => id_type.to_string(),
+            None => "&'static str".to_owned(),
+        };
+        let vec = self.library.vec().unwrap_or("alloc::vec::Vec");
+
+        fmt.write_str("#[rustfmt::skip]\n")?;
+        fmt.write_str("///Transport of RPC calls\n")?;
+        fmt.write_str("pub trait Transport {\n")?;
+        fmt.write_fmt(format_args!("{TAB}///Transport error\n{TAB}type Error;\n\n"))?;
+        match self.is_framed {
+            true => {
+                fmt.write_fmt(format_args!("{TAB}///Sends request `frame`, returning response frame.\n"))?;
+                fmt.write_fmt(format_args!("{TAB}fn call(&mut self, frame: &[u8]) -> Result<{vec}<u8>, Self::Error>;\n"))?;
+            },
+            false => {
+                fmt.write_fmt(format_args!("{TAB}///Calls `method` with serialized request `payload`, returning serialized response.\n"))?;
+                fmt.write_fmt(format_args!("{TAB}fn call(&mut self, method: {id_type}, payload: &[u8]) -> Result<{vec}<u8>, Self::Error>;\n"))?;
+            },
+        }
+        fmt.write_str("}\n\n")?;
+
+        fmt.write_str("#[derive(Debug)]\n")?;
+        fmt.write_str("///RPC call error\n")?;
+        fmt.write_str("pub enum ClientError<E> {\n")?;
+        fmt.write_fmt(format_args!("{TAB}///Transport failed to perform call\n{TAB}Transport(E),\n"))?;
+        match self.is_codec {
+            true => fmt.write_fmt(format_args!("{TAB}///Response cannot be decoded\n{TAB}InvalidResponse(DispatchError),\n"))?,
+            false => fmt.write_fmt(format_args!("{TAB}///Response is not valid flatbuffer\n{TAB}InvalidResponse(flatbuffers::InvalidFlatbuffer),\n"))?,
+        }
+        if self.is_framed {
+            fmt.write_fmt(format_args!("{TAB}///Response frame cannot be decoded\n{TAB}InvalidFrame,\n"))?;
+        }
+        fmt.write_str("}")
+    }
+}
+
+#[derive(Copy, Clone)]
+///Generates typed client of the service, using `Transport` generated by [RpcClientTransport].
+///
+///For service `Storage` it generates `StorageClient<T: Transport>` with method for every RPC method:
+///
+///```rust,ignore
+///pub fn put<F: FnOnce(&mut flatbuffers::FlatBufferBuilder<'static>) -> flatbuffers::WIPOffset<Request<'static>>>(&mut self, request: F) -> Result<Response<'_>, ClientError<T::Error>>;
+///```
+///
+///Where `request` builds argument within client's builder, and response is verified before returning.
+///If method has no arguments, `request` is omitted and payload is empty.
+///
+///Method ids are constants generated by `defines`, which must be in scope of the client.
+///
+///Errors of handlers, returned by [RpcServiceDispatcher] as `DispatchError::Failed`, are to be reported by `Transport` as its error.
+///
+///With `framing` request is encoded as frame with `u16` method id, and payload of response frame is verified instead.
+///
+///Streaming methods are not supported, as `Transport` has single request and single response.
+///
+///Generated client depends on `flatbuffers` crate, unless messages are encoded by `Codec`, generated by [RpcDispatchCodec], with [codec](Self::codec):
+///
+///```rust,ignore
+///pub fn put<C: Codec<Request> + Codec<Response>>(&mut self, request: &Request) -> Result<Response, ClientError<T::Error>>;
+///```
+///
+///Then the same codec as of [RpcServiceDispatcher] is used by both sides of a call.
+///
+///```rust
+///use flatbuffers_tools::{parse_services, render_services, GenConfig, GenItem, TypeName};
+///
+///let services = parse_services("rpc_service Storage { Put(Request):Response; Ping():Pong; }").unwrap();
+///let config = GenConfig::new().skip_docs(true).type_path(|name: &TypeName| format!("super::{}", name.name())).client_codec(true).item(GenItem::Client);
+///let code = render_services(&services, &config).unwrap();
+///assert!(code.contains("pub trait Codec<T> {"));
+///assert!(code.contains("    InvalidResponse(DispatchError),\n"));
+///assert!(code.contains("pub fn put<C: Codec<super::Request> + Codec<super::Response>>(&mut self, request: &super::Request) -> Result<super::Response, ClientError<T::Error>> {"));
+///assert!(code.contains("pub fn ping<C: Codec<super::Pong>>(&mut self) -> Result<super::Pong, ClientError<T::Error>> {"));
+///assert!(!code.contains("flatbuffers::"));
+///```
+pub struct RpcClientStub<'a> {
+    ///Service definition
+    pub service: &'a RpcService,
+    ///Method defines, which constants are used as method ids.
+    pub defines: RpcMethodDefines<'a>,
+    ///Callback to map type of argument or return type to Rust path.
+    ///
+    ///Type name is passed with namespace of the service, if it is not specified.
+    ///
+    ///Path must be of flatc generated table, which lifetime is not specified.
+    ///
+    ///By default types are expected within `crate::interface`, same as in [RpcServiceImplDefines].
+    pub type_path: Option<&'a dyn Fn(&TypeName) -> String>,
+    ///Specifies whether documentation of service and methods is to be omitted.
+    ///
+    ///Otherwise it is placed above client and its methods.
+    pub skip_docs: bool,
+    ///Library, that generated code may use.
+    ///
+    ///`alloc` is required regardless, as `flatbuffers` requires it.
+    pub library: StdLib,
+    ///Path to module with `encode_frame` and `decode_frame` functions, if calls are framed.
+    ///
+    ///E.g. `flatbuffers_tools::framing`, which requires `framing` feature.
+    ///It requires `u16` method ids.
+    pub framing: Option<&'a str>,
+    ///Specifies whether messages are encoded by `Codec`, instead of being built and verified by `flatbuffers`.
+    ///
+    ///Types are passed by value then, e.g. flatc generated object API.
+    pub codec: bool,
+}
+
+impl<'a> RpcClientStub<'a> {
+    #[inline]
+    ///Sets whether documentation of service and methods is to be omitted.
+    pub const fn skip_docs(mut self, skip_docs: bool) -> Self {
+        self.skip_docs = skip_docs;
+        self
+    }
+
+    #[inline]
+    ///Sets method defines, used as method ids.
+    pub const fn defines(mut self, defines: RpcMethodDefines<'a>) -> Self {
+        self.defines = defines;
+        self
+    }
+
+    #[inline]
+    ///Sets callback to map schema types to Rust paths.
+    pub const fn type_path(mut self, type_path: &'a dyn Fn(&TypeName) -> String) -> Self {
+        self.type_path = Some(type_path);
+        self
+    }
+
+    #[inline]
+    ///Sets library, that generated code may use.
+    pub const fn library(mut self, library: StdLib) -> Self {
+        self.library = library;
+        self
+    }
+
+    ///Checks defines, names, arguments and streaming of client methods.
+    fn validate(&self) -> Result<(), GenError> {
+        self.defines.validate()?;
+        function_names(self.service, self.service.methods.iter(), false)?;
+        check_streaming(self.service, self.service.methods.iter())?;
+        let policy = self.defines.deprecated_policy;
+        let methods = || self.service.methods.iter().filter(move |method| policy.is_included(method));
+        check_arguments(self.service, methods())?;
+        check_supported_streaming(self.service, methods(), |_, streaming| streaming == Streaming::None)
+    }
+
+    ///Generates client, failing if method ids cannot be assigned, generated names are the same, method has multiple arguments or streaming.
+    ///
+    ///Prefer it over `Display`, which panics on [GenError].
+    pub fn render(&self) -> Result<String, GenError> {
+        self.validate()?;
+        Ok(self.to_string())
+    }
+
+    #[inline]
+    ///Sets path to module of framing functions, so that calls are framed.
+    pub const fn framing(mut self, framing: &'a str) -> Self {
+        self.framing = Some(framing);
+        self
+    }
+
+    #[inline]
+    ///Sets whether messages are encoded by `Codec`.
+    pub const fn codec(mut self, codec: bool) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    #[inline]
+    ///Gets formatter of `Transport`, matching method id type, framing and codec of this client.
+    pub fn transport(&self) -> RpcClientTransport {
+        RpcClientTransport {
+            id_type: self.defines.id_type(),
+            library: self.library,
+            is_framed: self.framing.is_some(),
+            is_codec: self.codec,
+        }
+    }
+}
+
+impl RpcClientStub<'_> {
+    ///Writes call of transport with `payload`, storing response.
+    fn fmt_call(&self, fmt: &mut fmt::Formatter<'_>, id: &str, payload: &str) -> fmt::Result {
+        match self.framing {
+            Some(framing) => {
+                fmt.write_fmt(format_args!("{TAB}{TAB}self.frame.clear();\n"))?;
+                fmt.write_fmt(format_args!("{TAB}{TAB}{framing}::encode_frame({id}, {payload}, &mut self.frame);\n"))?;
+                fmt.write_fmt(format_args!("{TAB}{TAB}self.response = self.transport.call(&self.frame).map_err(ClientError::Transport)?;\n"))
+            },
+            None => fmt.write_fmt(format_args!("{TAB}{TAB}self.response = self.transport.call({id}, {payload}).map_err(ClientError::Transport)?;\n")),
+        }
+    }
+
+    ///Writes generics, signature and body of `method`, following its name, encoding request and decoding response by codec `C`.
+    fn fmt_codec_call(&self, fmt: &mut fmt::Formatter<'_>, method: &RpcMethod, id: &str, return_type: &str) -> fmt::Result {
+        match method.arguments.first() {
+            Some(argument) => {
+                let argument = map_type(self.type_path, self.service, &argument.ty);
+                match argument == return_type {
+                    true => fmt.write_fmt(format_args!("<C: Codec<{argument}>>"))?,
+                    false => fmt.write_fmt(format_args!("<C: Codec<{argument}> + Codec<{return_type}>>"))?,
+                }
+                fmt.write_fmt(format_args!("(&mut self, request: &{argument}) -> Result<{return_type}, ClientError<T::Error>> {{\n"))?;
+                fmt.write_fmt(format_args!("{TAB}{TAB}let request = <C as Codec<{argument}>>::encode(request);\n"))?;
+                self.fmt_call(fmt, id, "&request")?;
+            },
+            None => {
+                fmt.write_fmt(format_args!("<C: Codec<{return_type}>>(&mut self) -> Result<{return_type}, ClientError<T::Error>> {{\n"))?;
+                self.fmt_call(fmt, id, "&[]")?;
+            },
+        }
+        let payload = match self.framing {
+            Some(framing) => {
+                fmt.write_fmt(format_args!("{TAB}{TAB}let frame = {framing}::decode_frame(&self.response).map_err(|_| ClientError::InvalidFrame)?;\n"))?;
+                "frame.payload"
+            },
+            None => "&self.response",
+        };
+        fmt.write_fmt(format_args!("{TAB}{TAB}<C as Codec<{return_type}>>::decode({payload}).map_err(ClientError::InvalidResponse)\n{TAB}}}\n"))
+    }
+}
+
+impl<'a> fmt::Display for RpcClientStub<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        expect_valid(self.validate());
+        let client = format!("{}Client", self.service.name);
+        let prefix = self.defines.names_prefix();
+        let vec = self.library.vec().unwrap_or("alloc::vec::Vec");
+        if self.framing.is_some() {
+            assert!(self.defines.id_type() == Some(IntType::U16), "Framing requires u16 method ids");
+        }
+
+        fmt.write_str("#[rustfmt::skip]\n")?;
+        write_docs(fmt, "", &self.service.docs, self.skip_docs, format_args!("Client of `{}` service", self.service.name))?;
+        fmt.write_fmt(format_args!("pub struct {client}<T: Transport> {{\n{TAB}transport: T,\n"))?;
+        if !self.codec {
+            fmt.write_fmt(format_args!("{TAB}builder: flatbuffers::FlatBufferBuilder<'static>,\n"))?;
+        }
+        if self.framing.is_some() {
+            fmt.write_fmt(format_args!("{TAB}frame: {vec}<u8>,\n"))?;
+        }
+        fmt.write_fmt(format_args!("{TAB}response: {vec}<u8>,\n}}\n\n"))?;
+
+        let policy = self.defines.deprecated_policy;
+        if self.service.methods.iter().any(|method| policy.is_included(method) && policy.is_annotated(method)) {
+            fmt.write_str("#[allow(deprecated)]\n")?;
+        }
+        fmt.write_fmt(format_args!("impl<T: Transport> {client}<T> {{\n"))?;
+        fmt.write_fmt(format_args!("{TAB}#[inline]\n{TAB}///Creates new client\n{TAB}pub fn new(transport: T) -> Self {{\n"))?;
+        fmt.write_fmt(format_args!("{TAB}{TAB}Self {{\n{TAB}{TAB}{TAB}transport,\n"))?;
+        if !self.codec {
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}builder: flatbuffers::FlatBufferBuilder::new(),\n"))?;
+        }
+        if self.framing.is_some() {
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}frame: {vec}::new(),\n"))?;
+        }
+        fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}response: {vec}::new(),\n{TAB}{TAB}}}\n{TAB}}}\n"))?;
+        fmt.write_fmt(format_args!("\n{TAB}#[inline]\n{TAB}///Returns underlying transport\n{TAB}pub fn into_inner(self) -> T {{\n{TAB}{TAB}self.transport\n{TAB}}}\n"))?;
+
+        for (method, name) in expect_valid(function_names(self.service, self.service.methods.iter().filter(|method| policy.is_included(method)), false)) {
+            assert!(
+                method.arguments.len() <= 1,
+                "We require all RPC methods to have at most 1 argument"
+            );
+            assert!(streaming(method) == Streaming::None, "Client does not support streaming methods");
+            let id = format!("{prefix}{}", ident::to_screaming_snake(&method.name));
+            let return_type = map_type(self.type_path, self.service, &method.return_type);
+
+            fmt.write_str("\n")?;
+            if policy.is_annotated(method) {
+                fmt.write_fmt(format_args!("{TAB}#[deprecated]\n"))?;
+            }
+            write_docs(fmt, TAB, &method.docs, self.skip_docs, format_args!("Calls `{}`", method.name))?;
+            fmt.write_fmt(format_args!("{TAB}pub fn {}", Ident(&name)))?;
+            if self.codec {
+                self.fmt_codec_call(fmt, method, &id, &return_type)?;
+                continue;
+            }
+            match method.arguments.first() {
+                Some(argument) => {
+                    let argument = map_type(self.type_path, self.service, &argument.ty);
+                    fmt.write_fmt(format_args!("<F: FnOnce(&mut flatbuffers::FlatBufferBuilder<'static>) -> flatbuffers::WIPOffset<{argument}<'static>>>(&mut self, request: F) -> Result<{return_type}<'_>, ClientError<T::Error>> {{\n"))?;
+                    fmt.write_fmt(format_args!("{TAB}{TAB}self.builder.reset();\n"))?;
+                    fmt.write_fmt(format_args!("{TAB}{TAB}let request = request(&mut self.builder);\n"))?;
+                    fmt.write_fmt(format_args!("{TAB}{TAB}self.builder.finish(request, None);\n"))?;
+                    self.fmt_call(fmt, &id, "self.builder.finished_data()")?;
+                },
+                None => {
+                    fmt.write_fmt(format_args!("(&mut self) -> Result<{return_type}<'_>, ClientError<T::Error>> {{\n"))?;
+                    self.fmt_call(fmt, &id, "&[]")?;
+                },
+            }
+            match self.framing {
+                Some(framing) => {
+                    fmt.write_fmt(format_args!("{TAB}{TAB}let frame = {framing}::decode_frame(&self.response).map_err(|_| ClientError::InvalidFrame)?;\n"))?;
+                    fmt.write_fmt(format_args!("{TAB}{TAB}flatbuffers::root::<{return_type}>(frame.payload).map_err(ClientError::InvalidResponse)\n{TAB}}}\n"))?;
+                },
+                None => fmt.write_fmt(format_args!("{TAB}{TAB}flatbuffers::root::<{return_type}>(&self.response).map_err(ClientError::InvalidResponse)\n{TAB}}}\n"))?,
+            }
+        }
+
+        fmt.write_str("}")
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///Generates `Codec` trait and `DispatchError` type, shared by every [RpcServiceDispatcher].
+///
+///It is to be generated once per output, with the same method id type and library, as dispatchers use.
+///
+///```rust,ignore
+///pub trait Codec<T> {
+///    fn decode(payload: &[u8]) -> Result<T, DispatchError>;
+///    fn encode(message: &T) -> Vec<u8>;
+///}
+///
+///pub enum DispatchError {
+///    UnknownMethod(u16),
+///    InvalidRequest,
+///    Failed(Vec<u8>),
+///}
+///```
+///
+///With [StdLib::Core] responses are encoded into user provided buffer instead:
+///
+///```rust,ignore
+///pub trait Codec<T> {
+///    fn decode(payload: &[u8]) -> Result<T, DispatchError>;
+///    fn encode(message: &T, out: &mut [u8]) -> Result<usize, DispatchError>;
+///}
+///
+///pub enum DispatchError {
+///    UnknownMethod(u16),
+///    InvalidRequest,
+///    Failed(usize),
+///    Overflow,
+///}
+///```
+///
+///Where `Failed` contains length of encoded error and `UnknownMethod` has no value, if methods are identified by names.
+pub struct RpcDispatchCodec {
+    ///Type of method id, or `None` if method is identified by its name.
+    pub id_type: Option<IntType>,
+    ///Library, that generated code may use.
+    pub library: StdLib,
+}
+
+impl fmt::Display for RpcDispatchCodec {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let id_type = match (self.id_type, self.library.string()) {
+            (Some(id_type), _) => Some(id_type.to_string()),
+            (None, Some(string)) => Some(string.to_owned()),
+            (None, None) => None,
+        };
+        let vec = self.library.vec();
+
+        fmt.write_str("#[rustfmt::skip]\n")?;
+        fmt.write_str("///Codec of RPC messages of type `T`\n")?;
+        fmt.write_str("pub trait Codec<T> {\n")?;
+        fmt.write_fmt(format_args!("{TAB}///Decodes request from `payload`\n{TAB}fn decode(payload: &[u8]) -> Result<T, DispatchError>;\n"))?;
+        match vec {
+            Some(vec) => fmt.write_fmt(format_args!("{TAB}///Encodes response\n{TAB}fn encode(message: &T) -> {vec}<u8>;\n"))?,
+            None => fmt.write_fmt(format_args!("{TAB}///Encodes response into `out`, returning its length\n{TAB}fn encode(message: &T, out: &mut [u8]) -> Result<usize, DispatchError>;\n"))?,
+        }
+        fmt.write_str("}\n\n")?;
+
+        fmt.write_str("#[derive(Debug, Clone, PartialEq, Eq)]\n")?;
+        fmt.write_str("///RPC dispatch error\n")?;
+        fmt.write_str("pub enum DispatchError {\n")?;
+        match id_type {
+            Some(id_type) => fmt.write_fmt(format_args!("{TAB}///Method is not defined by service\n{TAB}UnknownMethod({id_type}),\n"))?,
+            None => fmt.write_fmt(format_args!("{TAB}///Method is not defined by service\n{TAB}UnknownMethod,\n"))?,
+        }
+        fmt.write_fmt(format_args!("{TAB}///Request cannot be decoded\n{TAB}InvalidRequest,\n"))?;
+        match vec {
+            Some(vec) => fmt.write_fmt(format_args!("{TAB}///Handler returned error, encoded by `Codec`\n{TAB}Failed({vec}<u8>),\n"))?,
+            None => {
+                fmt.write_fmt(format_args!("{TAB}///Handler returned error, encoded by `Codec` with specified length\n{TAB}Failed(usize),\n"))?;
+                fmt.write_fmt(format_args!("{TAB}///Output buffer is too small\n{TAB}Overflow,\n"))?;
+            },
+        }
+        fmt.write_str("}")
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+///Return type of handler methods
+pub enum ReturnStyle<'a> {
+    #[default]
+    ///Response type as it is.
+    Plain,
+    ///`Result<Response, Self::Error>`, with associated `type Error;` of handler trait.
+    Result,
+    ///`Result<Response, Error>`, where `Error` is specified path, e.g. `crate::RpcError`.
+    ResultWith(&'a str),
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+///Kind of argument or return type of methods
+pub enum TypeKind {
+    #[default]
+    ///Type without lifetime, e.g. object API type or builder.
+    Owned,
+    ///flatc generated table, e.g. `Request<'a>`, borrowing buffer.
+    Table,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///Default body of handler methods, allowing to implement only some of them
+pub enum DefaultBody<'a> {
+    ///`unimplemented!("Service::method")`
+    Unimplemented,
+    ///`todo!("Service::method")`
+    Todo,
+    ///`Err(error)`, where `error` is specified expression, e.g. `crate::RpcError::Unimplemented`.
+    ///
+    ///It requires error type to be specified by [ReturnStyle::ResultWith].
+    Error(&'a str),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///Style of per request context, passed to handler methods
+pub enum ContextStyle {
+    ///Associated `type Context;` of handler trait, passed as `ctx: &mut Self::Context`.
+    AssociatedType,
+    ///Generic parameter of handler trait (e.g. `StorageService<Ctx>`), passed as `ctx: &mut Ctx`.
+    Generic,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+///Receiver of handler methods, which determines how `dispatch` accepts handler
+///
+///```rust
+///use flatbuffers_tools::{parse_services, render_services, GenConfig, GenItem, Receiver, TypeName};
+///
+///let path = |name: &TypeName| format!("crate::{}", name.name());
+///let shared = GenConfig::new().skip_docs(true).type_path(path).receiver(Receiver::Ref).send_sync(true).item(GenItem::Dispatcher).item(GenItem::Mock);
+///let code = render_services(&parse_services("rpc_service Storage { Put(Request):Response; }").unwrap(), &shared).unwrap();
+///assert!(code.contains("    pub trait StorageService: Send + Sync {\n"));
+///assert!(code.contains("        fn put(&self, request: crate::Request) -> crate::Response;\n"));
+///assert!(code.contains("(handler: &H, method: u16, payload: &[u8])"));
+///
+///let arc = GenConfig::new().skip_docs(true).type_path(path).receiver(Receiver::Arc).item(GenItem::Dispatcher).item(GenItem::Instrumentation);
+///let code = render_services(&parse_services("rpc_service Cache { Get(Request):Response; }").unwrap(), &arc).unwrap();
+///assert!(code.contains("        fn get(self: std::sync::Arc<Self>, request: crate::Request) -> crate::Response;\n"));
+///assert!(code.contains("                let response = std::sync::Arc::clone(handler).get(request);\n"));
+///
+/////Arc requires alloc
+///let error = render_services(&parse_services("rpc_service Cache { Get(Request):Response; }").unwrap(), &arc.no_std(true)).unwrap_err();
+///assert_eq!(error.to_string(), "Arc receiver requires alloc");
+///```
+pub enum Receiver {
+    #[default]
+    ///`&mut self`, with handler passed as `handler: &mut H`.
+    Mut,
+    ///`&self`, for handler that is internally synchronized, with handler passed as `handler: &H`.
+    Ref,
+    ///`self: Arc<Self>`, with handler passed as `handler: &Arc<H>`, which is cloned for every call.
+    ///
+    ///It requires `alloc`, which is otherwise reported as [GenError::UnsupportedLibrary].
+    Arc,
+}
+
+impl Receiver {
+    ///Returns receiver of method, with specified path to `Arc`.
+    fn code(self, arc: &str) -> String {
+        match self {
+            Self::Mut => "&mut self".to_owned(),
+            Self::Ref => "&self".to_owned(),
+            Self::Arc => format!("self: {arc}<Self>"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+///Asyncness of handler methods, which are awaited by `dispatch`
+///
+///Handler methods keep arguments and return types, while `dispatch` becomes `async fn`, returning the same result:
+///
+///```rust,ignore
+///pub trait StorageService {
+///    async fn put(&mut self, request: Request) -> Response;
+///}
+///
+///pub async fn dispatch<H: StorageService, C: Codec<Request> + Codec<Response>>(handler: &mut H, method: u16, payload: &[u8]) -> Result<Vec<u8>, DispatchError>;
+///```
+///
+///It is not supported by [AsyncRouter], which returns boxed futures instead, and by streaming methods, which are reported as [GenError::UnsupportedStreaming].
+///
+///```rust
+///use flatbuffers_tools::{parse_services, render_services, Asyncness, GenConfig, GenError, TypeName};
+///
+///let path = |name: &TypeName| format!("crate::{}", name.name());
+///let config = GenConfig::new().skip_docs(true).type_path(path).asyncness(Asyncness::AsyncTrait);
+///let code = render_services(&parse_services("rpc_service Storage { Put(Request):Response; }").unwrap(), &config).unwrap();
+///assert!(code.contains("    #[async_trait::async_trait]\n    pub trait StorageService: Send + Sync {\n"));
+///assert!(code.contains("        async fn put(&mut self, request: crate::Request) -> crate::Response;\n"));
+///assert!(code.contains("                let response = handler.put(request).await;\n"));
+///
+///let services = parse_services("rpc_service Feed { Watch(Key):Item (streaming: \"server\"); }").unwrap();
+///let error = render_services(&services, &config.clone().asyncness(Asyncness::Native)).unwrap_err();
+///assert!(matches!(error, GenError::UnsupportedStreaming { .. }));
+///```
+pub enum Asyncness {
+    #[default]
+    ///Handler methods are plain functions.
+    Sync,
+    ///Handler methods are `async fn` of trait, which requires Rust 1.75.
+    ///
+    ///Handler trait is not object safe and futures of its methods are not required to be `Send`.
+    Native,
+    ///Handler methods are `async fn` of trait, annotated with `#[async_trait::async_trait]`, which requires `async-trait` crate.
+    ///
+    ///Handler trait is object safe and requires `Send + Sync`, so that futures of its methods are `Send`.
+    ///Its implementations are to be annotated the same, as mock and instrumented wrapper are.
+    AsyncTrait,
+}
+
+impl Asyncness {
+    #[inline]
+    ///Returns whether handler methods are `async fn`.
+    const fn is_async(self) -> bool {
+        !matches!(self, Self::Sync)
+    }
+
+    #[inline]
+    ///Returns keyword, preceding `fn` of handler methods.
+    const fn keyword(self) -> &'static str {
+        match self {
+            Self::Sync => "",
+            Self::Native | Self::AsyncTrait => "async ",
+        }
+    }
+
+    ///Writes attribute of handler trait and its implementations, if any.
+    fn write_attribute(self, fmt: &mut fmt::Formatter<'_>, indent: &str) -> fmt::Result {
+        match self {
+            Self::AsyncTrait => fmt.write_fmt(format_args!("{indent}#[async_trait::async_trait]\n")),
+            Self::Sync | Self::Native => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///Options of async router, generated by [RpcServiceDispatcher] instead of `dispatch`.
+///
+///Byte types are paths, so that generated code has no dependency on their crate, e.g. `bytes::Bytes`.
+///
+///Streaming methods of async handler accept and return boxed streams of `stream` trait, routed by `route_server`, `route_client` and `route_bidi`:
+///
+///```rust,ignore
+///fn watch(&self, request: Key) -> Pin<Box<dyn Stream<Item = Item> + Send>>; //streaming: "server"
+///fn upload(&self, requests: Pin<Box<dyn Stream<Item = Item> + Send>>) -> Pin<Box<dyn Future<Output = Key> + Send + '_>>; //streaming: "client"
+///fn chat(&self, requests: Pin<Box<dyn Stream<Item = Item> + Send>>) -> Pin<Box<dyn Stream<Item = Item> + Send>>; //streaming: "bidi"
+///
+///pub fn route_server<C: Codec<Key> + Codec<Item> + 'static>(handler: Arc<dyn FeedService>, method: u16, payload: Vec<u8>) -> Result<Pin<Box<dyn Stream<Item = Vec<u8>> + Send>>, DispatchError>;
+///pub fn route_client<C: Codec<Key> + Codec<Item> + 'static>(handler: Arc<dyn FeedService>, method: u16, payloads: Pin<Box<dyn Stream<Item = Vec<u8>> + Send>>) -> Pin<Box<dyn Future<Output = Result<Vec<u8>, DispatchError>> + Send>>;
+///pub fn route_bidi<C: Codec<Key> + Codec<Item> + 'static>(handler: Arc<dyn FeedService>, method: u16, payloads: Pin<Box<dyn Stream<Item = Vec<u8>> + Send>>) -> Result<Pin<Box<dyn Stream<Item = Vec<u8>> + Send>>, DispatchError>;
+///```
+///
+///Stream of responses is returned by handler right away, rather than by future.
+///
+///Router requires `alloc`, which is otherwise reported as [GenError::UnsupportedLibrary].
+///
+///```rust
+///use flatbuffers_tools::{parse_services, render_services, AsyncRouter, GenConfig, TypeName};
+///
+///let schema = "rpc_service Feed {\n  Watch(Key):Item (streaming: \"server\");\n  Upload(Item):Key (streaming: \"client\");\n  Chat(Item):Item (streaming: \"bidi\");\n}";
+///let services = parse_services(schema).unwrap();
+///let config = GenConfig::new().skip_docs(true)
+///                             .type_path(|name: &TypeName| format!("super::{}", name.name()))
+///                             .router(AsyncRouter::new().stream("super::Stream"));
+///let code = render_services(&services, &config).unwrap();
+///assert!(code.contains("fn watch(&self, request: super::Key) -> core::pin::Pin<Box<dyn super::Stream<Item = super::Item> + Send>>;"));
+///assert!(code.contains("pub fn route_client<C: Codec<super::Key> + Codec<super::Item> + 'static>(handler: std::sync::Arc<dyn FeedService>, method: u16, payloads: core::pin::Pin<Box<dyn super::Stream<Item = Vec<u8>> + Send>>)"));
+///
+///let error = render_services(&services, &config.no_std(true)).unwrap_err();
+///assert_eq!(error.to_string(), "Async router requires alloc");
+///```
+pub struct AsyncRouter<'a> {
+    ///Type of request payload, `Vec<u8>` by default.
+    ///
+    ///Decoded as `&[u8]`, hence it must dereference to it.
+    pub payload: &'a str,
+    ///Type of response, `Vec<u8>` by default.
+    ///
+    ///Converted with `Into` from `Vec<u8>` encoded by `Codec`.
+    pub output: &'a str,
+    ///Path to trait of asynchronous stream, used by streaming methods, `futures_core::Stream` by default.
+    ///
+    ///Trait must be the same as `futures_core::Stream`, i.e. with `Item` type and `poll_next` method.
+    pub stream: &'a str,
+}
+
+impl<'a> AsyncRouter<'a> {
+    #[inline]
+    ///Creates default options.
+    pub const fn new() -> Self {
+        Self {
+            payload: "Vec<u8>",
+            output: "Vec<u8>",
+            stream: "futures_core::Stream",
+        }
+    }
+
+    #[inline]
+    ///Sets type of request payload.
+    pub const fn payload(mut self, payload: &'a str) -> Self {
+        self.payload = payload;
+        self
+    }
+
+    #[inline]
+    ///Sets type of response.
+    pub const fn output(mut self, output: &'a str) -> Self {
+        self.output = output;
+        self
+    }
+
+    #[inline]
+    ///Sets path to trait of asynchronous stream.
+    pub const fn stream(mut self, stream: &'a str) -> Self {
+        self.stream = stream;
+        self
+    }
+}
+
+impl Default for AsyncRouter<'_> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///Checks that every method has at most one argument.
+fn check_arguments<'a, I: Iterator<Item=&'a RpcMethod>>(service: &RpcService, mut methods: I) -> Result<(), GenError> {
+    match methods.find(|method| method.arguments.len() > 1) {
+        Some(method) => Err(GenError::MultipleArguments {
+            service: service.name.clone(),
+            method: method.name.clone(),
+            count: method.arguments.len(),
+        }),
+        None => Ok(()),
+    }
+}
+
+///Checks that every method has kind of streaming, accepted by `is_supported`.
+fn check_supported_streaming<'a, I: Iterator<Item=&'a RpcMethod>, F: Fn(&RpcMethod, Streaming) -> bool>(service: &RpcService, methods: I, is_supported: F) -> Result<(), GenError> {
+    for method in methods {
+        let streaming = streaming(method);
+        if !is_supported(method, streaming) {
+            return Err(GenError::UnsupportedStreaming {
+                service: service.name.clone(),
+                method: method.name.clone(),
+                streaming,
+            });
+        }
+    }
+    Ok(())
+}
+
+///Checks that `streaming` attribute of every method has known value.
+fn check_streaming<'a, I: Iterator<Item=&'a RpcMethod>>(service: &RpcService, methods: I) -> Result<(), GenError> {
+    for method in methods {
+        if let Err(value) = method.streaming() {
+            return Err(GenError::InvalidStreaming {
+                service: service.name.clone(),
+                method: method.name.clone(),
+                value: value.to_owned(),
+            });
+        }
+    }
+    Ok(())
+}
+
+///Checks that `timeout_ms` and `priority` attributes of every method have valid values.
+fn check_attributes<'a, I: Iterator<Item=&'a RpcMethod>>(service: &RpcService, methods: I) -> Result<(), GenError> {
+    for method in methods {
+        let error = match (method.timeout_ms(), method.priority()) {
+            (Err(value), _) => ("timeout_ms", value),
+            (_, Err(value)) => ("priority", value),
+            _ => continue,
+        };
+        return Err(GenError::InvalidAttribute {
+            service: service.name.clone(),
+            method: method.name.clone(),
+            key: error.0,
+            value: error.1.to_owned(),
+        });
+    }
+    Ok(())
+}
+
+#[inline]
+///Returns kind of streaming of validated method.
+fn streaming(method: &RpcMethod) -> Streaming {
+    method.streaming().unwrap_or(Streaming::None)
+}
+
+#[derive(Copy, Clone)]
+///Generates handler trait of the service and `dispatch` function, routing calls by method id.
+///
+///For service `Storage` it generates `StorageService` trait with method for every RPC method:
+///
+///```rust,ignore
+///fn put(&mut self, request: Request) -> Response;
+///```
+///
+///And dispatch function, using `Codec` and `DispatchError` generated by [RpcDispatchCodec]:
+///
+///```rust,ignore
+///pub fn dispatch<H: StorageService, C: Codec<Request> + Codec<Response>>(handler: &mut H, method: u16, payload: &[u8]) -> Result<Vec<u8>, DispatchError>;
+///```
+///
+///If method has no arguments, `request` is omitted and payload is ignored.
+///
+///Method ids are constants generated by `defines`, which must be in scope of the dispatcher.
+///
+///With [AsyncRouter] handler trait is object safe, returning boxed futures, and `route` is generated instead of `dispatch`:
+///
+///```rust,ignore
+///pub trait StorageService: Send + Sync {
+///    fn put(&self, request: Request) -> Pin<Box<dyn Future<Output = Response> + Send + '_>>;
+///}
+///
+///pub fn route<C: Codec<Request> + Codec<Response> + 'static>(handler: Arc<dyn StorageService>, method: u16, payload: Vec<u8>) -> Pin<Box<dyn Future<Output = Result<Vec<u8>, DispatchError>> + Send>>;
+///```
+///
+///Unknown method results in future, that is ready with `DispatchError::UnknownMethod`.
+///
+///With `framing` it also generates `dispatch_frame`, decoding request frame and encoding response into frame with the same method id:
+///
+///```rust,ignore
+///pub fn dispatch_frame<H: StorageService, C: Codec<Request> + Codec<Response>>(handler: &mut H, frame: &[u8]) -> Result<Vec<u8>, DispatchError>;
+///```
+///
+///Methods with `streaming` attribute are not routed by `dispatch`, but by function of their kind, which is generated only if service has such methods:
+///
+///```rust,ignore
+///fn watch(&mut self, request: Key) -> impl Iterator<Item = Item>; //streaming: "server"
+///fn upload(&mut self, requests: impl Iterator<Item = Item>) -> Key; //streaming: "client"
+///fn chat(&mut self, requests: impl Iterator<Item = Item>) -> impl Iterator<Item = Item>; //streaming: "bidi"
+///
+///pub fn dispatch_server<'h, H: FeedService, C: Codec<Key> + Codec<Item>>(handler: &'h mut H, method: u16, payload: &[u8]) -> Result<Box<dyn Iterator<Item = Vec<u8>> + 'h>, DispatchError>;
+///pub fn dispatch_client<H: FeedService, C: Codec<Key> + Codec<Item>, P: AsRef<[u8]>, I: IntoIterator<Item = P>>(handler: &mut H, method: u16, payloads: I) -> Result<Vec<u8>, DispatchError>;
+///pub fn dispatch_bidi<'h, H: FeedService, C: Codec<Key> + Codec<Item>, P: AsRef<[u8]>, I: IntoIterator<Item = P>>(handler: &'h mut H, method: u16, payloads: I) -> Result<Box<dyn Iterator<Item = Vec<u8>> + 'h>, DispatchError>;
+///```
+///
+///Handler methods return `impl Iterator`, which requires Rust 1.75, while [AsyncRouter] uses boxed streams instead, refer to [AsyncRouter::stream].
+///Streaming methods have no default body and do not support tables.
+///They require `alloc`, which is otherwise reported as [GenError::UnsupportedLibrary], while unknown value of `streaming` attribute is reported as [GenError::InvalidStreaming].
+///
+///```rust
+///use flatbuffers_tools::{parse_services, render_services, GenConfig, GenError, TypeName};
+///
+///let schema = "rpc_service Feed {\n  Watch(Key):Item (streaming: \"server\");\n  Upload(Item):Key (streaming: \"client\");\n  Chat(Item):Item (streaming: \"bidi\");\n}";
+///let services = parse_services(schema).unwrap();
+///let config = GenConfig::new().skip_docs(true).type_path(|name: &TypeName| format!("super::{}", name.name()));
+///let code = render_services(&services, &config).unwrap();
+///assert!(code.contains("fn watch(&mut self, request: super::Key) -> impl Iterator<Item = super::Item>;"));
+///assert!(code.contains("fn upload(&mut self, requests: impl Iterator<Item = super::Item>) -> super::Key;"));
+///assert!(code.contains("pub fn dispatch_bidi<'h, H: FeedService, C: Codec<super::Key> + Codec<super::Item>, P: AsRef<[u8]>, I: IntoIterator<Item = P>>"));
+///
+///let error = render_services(&services, &config.clone().no_std(true)).unwrap_err();
+///assert_eq!(error.to_string(), "Streaming method requires alloc");
+///
+///let services = parse_services("rpc_service Feed { Watch(Key):Item (streaming: \"tail\"); }").unwrap();
+///let error = render_services(&services, &config).unwrap_err();
+///assert!(matches!(error, GenError::InvalidStreaming { .. }));
+///assert_eq!(error.to_string(), "Method 'Watch' of service 'Feed' has unknown streaming 'tail', expected one of 'none', 'server', 'client' or 'bidi'");
+///```
+pub struct RpcServiceDispatcher<'a> {
+    ///Service definition
+    pub service: &'a RpcService,
+    ///Method defines, which constants are used as method ids.
+    pub defines: RpcMethodDefines<'a>,
+    ///Callback to map type of argument or return type to Rust path.
+    ///
+    ///Type name is passed with namespace of the service, if it is not specified.
+    ///
+    ///By default types are expected within `crate::interface`, same as in [RpcServiceImplDefines].
+    pub type_path: Option<&'a dyn Fn(&TypeName) -> String>,
+    ///Style of context, passed to every handler method as first argument after `self`, if specified.
+    ///
+    ///`dispatch` accepts context as `ctx` argument after `handler`, forwarding it to handler.
+    pub context: Option<ContextStyle>,
+    ///Specifies whether documentation of service and methods is to be omitted.
+    ///
+    ///Otherwise it is placed above handler trait and its methods.
+    pub skip_docs: bool,
+    ///Return type of handler methods, [ReturnStyle::Plain] by default.
+    ///
+    ///When handler returns error, it is encoded by `Codec` of error type and returned as `DispatchError::Failed`.
+    pub return_style: ReturnStyle<'a>,
+    ///Library, that generated code may use.
+    ///
+    ///With [StdLib::Core] `dispatch` accepts `out: &mut [u8]` buffer to encode response,
+    ///returning its length instead.
+    pub library: StdLib,
+    ///Async router to generate instead of `dispatch`, if specified.
+    ///
+    ///It requires `alloc` and does not support context.
+    pub router: Option<AsyncRouter<'a>>,
+    ///Custom attributes of handler trait and its methods.
+    pub attributes: Attributes<'a>,
+    ///Specifies whether handler trait refers to type aliases, generated by [RpcTypeAliases], instead of types.
+    ///
+    ///Aliases are expected in the same module, e.g. [RpcServiceModule::with_type_aliases].
+    pub aliases: bool,
+    ///Default body of handler methods, if any.
+    ///
+    ///With async router [DefaultBody::Error] is returned by future, while other bodies panic on call.
+    ///Streaming methods have no default body.
+    pub default_body: Option<DefaultBody<'a>>,
+    ///Callback to determine kind of argument or return type, every type is [TypeKind::Owned] by default.
+    ///
+    ///Type name is passed the same as to `type_path`.
+    ///
+    ///Tables of handler method share its lifetime, e.g. `fn put<'a>(&mut self, request: Request<'a>) -> Response<'a>`,
+    ///while `dispatch` parses them with `flatbuffers::root` instead of `Codec`.
+    pub type_kind: Option<&'a dyn Fn(&TypeName) -> TypeKind>,
+    ///Path to module with `encode_frame` and `decode_frame` functions, to generate `dispatch_frame`, if specified.
+    ///
+    ///E.g. `flatbuffers_tools::framing`, which requires `framing` feature.
+    ///It requires `u16` method ids and `alloc`, which is otherwise reported as [GenError::UnsupportedLibrary], and is not supported by async router.
+    pub framing: Option<&'a str>,
+    ///Template of handler trait name, where `{service}` is replaced with service name, `{service}Service` by default.
+    ///
+    ///E.g. `Rpc{service}Handler` names trait of service `Storage` as `RpcStorageHandler`.
+    ///Mock and instrumented wrapper are named after trait, e.g. `MockRpcStorageHandler`.
+    pub trait_name: Option<&'a str>,
+    ///Receiver of handler methods, [Receiver::Mut] by default.
+    ///
+    ///Async router always uses `&self`.
+    pub receiver: Receiver,
+    ///Specifies whether handler trait requires `Send + Sync`, which async router always requires.
+    pub send_sync: bool,
+    ///Specifies whether to generate `method_timeout`, returning timeout of method, specified by `timeout_ms` attribute.
+    ///
+    ///E.g. `pub fn method_timeout(method: u16) -> Option<core::time::Duration>`, returning `None` for methods without timeout and unknown ids.
+    pub method_timeout: bool,
+    ///Asyncness of handler methods, [Asyncness::Sync] by default.
+    ///
+    ///It is not supported by async router and streaming methods.
+    pub asyncness: Asyncness,
+}
+
+impl<'a> RpcServiceDispatcher<'a> {
+    #[inline]
+    ///Sets whether documentation of service and methods is to be omitted.
+    pub const fn skip_docs(mut self, skip_docs: bool) -> Self {
+        self.skip_docs = skip_docs;
+        self
+    }
+
+    #[inline]
+    ///Sets library, that generated code may use.
+    pub const fn library(mut self, library: StdLib) -> Self {
+        self.library = library;
+        self
+    }
+
+    #[inline]
+    ///Sets return type of handler methods.
+    pub const fn return_style(mut self, return_style: ReturnStyle<'a>) -> Self {
+        self.return_style = return_style;
+        self
+    }
+
+    #[inline]
+    ///Sets style of context to pass to handler methods.
+    pub const fn context(mut self, context: ContextStyle) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    #[inline]
+    ///Sets async router to generate instead of `dispatch`.
+    pub const fn router(mut self, router: AsyncRouter<'a>) -> Self {
+        self.router = Some(router);
+        self
+    }
+
+    #[inline]
+    ///Sets custom attributes of handler trait and its methods.
+    pub const fn attributes(mut self, attributes: Attributes<'a>) -> Self {
+        self.attributes = attributes;
+        self
+    }
+
+    #[inline]
+    ///Sets whether handler trait refers to type aliases instead of types.
+    pub const fn aliases(mut self, aliases: bool) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    #[inline]
+    ///Sets default body of handler methods.
+    pub const fn default_body(mut self, default_body: DefaultBody<'a>) -> Self {
+        self.default_body = Some(default_body);
+        self
+    }
+
+    #[inline]
+    ///Sets callback to determine kind of schema types.
+    pub const fn type_kind(mut self, type_kind: &'a dyn Fn(&TypeName) -> TypeKind) -> Self {
+        self.type_kind = Some(type_kind);
+        self
+    }
+
+    #[inline]
+    ///Sets path to module of framing functions, so that `dispatch_frame` is generated.
+    pub const fn framing(mut self, framing: &'a str) -> Self {
+        self.framing = Some(framing);
+        self
+    }
+
+    #[inline]
+    ///Sets template of handler trait name, where `{service}` is replaced with service name.
+    pub const fn trait_name(mut self, trait_name: &'a str) -> Self {
+        self.trait_name = Some(trait_name);
+        self
+    }
+
+    #[inline]
+    ///Sets receiver of handler methods.
+    pub const fn receiver(mut self, receiver: Receiver) -> Self {
+        self.receiver = receiver;
+        self
+    }
+
+    #[inline]
+    ///Sets whether handler trait requires `Send + Sync`.
+    pub const fn send_sync(mut self, send_sync: bool) -> Self {
+        self.send_sync = send_sync;
+        self
+    }
+
+    #[inline]
+    ///Sets whether to generate `method_timeout`.
+    pub const fn method_timeout(mut self, method_timeout: bool) -> Self {
+        self.method_timeout = method_timeout;
+        self
+    }
+
+    #[inline]
+    ///Sets asyncness of handler methods.
+    pub const fn asyncness(mut self, asyncness: Asyncness) -> Self {
+        self.asyncness = asyncness;
+        self
+    }
+
+    ///Returns name of handler trait, e.g. `StorageService`.
+    pub fn handler_name(&self) -> String {
+        self.trait_name.unwrap_or("{service}Service").replace("{service}", &self.service.name)
+    }
+
+    ///Returns whether handler trait requires `Send + Sync`, which `#[async_trait]` requires as well.
+    fn is_send_sync(&self) -> bool {
+        self.send_sync || self.asyncness == Asyncness::AsyncTrait
+    }
+
+    ///Returns receiver of handler methods, which async router requires to be `&self`.
+    fn receiver_of(&self) -> Receiver {
+        match self.router {
+            Some(_) => Receiver::Ref,
+            None => self.receiver,
+        }
+    }
+
+    ///Returns type of `handler` argument, borrowed for `lifetime`, and expression to call its methods.
+    fn handler_arg(&self, lifetime: &str) -> (String, String) {
+        let arc = match self.library {
+            StdLib::Std => "std::sync::Arc",
+            _ => "alloc::sync::Arc",
+        };
+        match self.receiver {
+            Receiver::Mut => (format!("&{lifetime}mut H"), "handler".to_owned()),
+            Receiver::Ref => (format!("&{lifetime}H"), "handler".to_owned()),
+            Receiver::Arc => (format!("&{lifetime}{arc}<H>"), format!("{arc}::clone(handler)")),
+        }
+    }
+
+    ///Returns whether schema type `ty` is table with lifetime.
+    fn is_table(&self, ty: &TypeRef) -> bool {
+        match self.type_kind {
+            Some(type_kind) => type_kind(&ty.resolve(self.service.namespace.as_deref())) == TypeKind::Table,
+            None => false,
+        }
+    }
+
+    ///Checks that library allows `item`, which requires `alloc`.
+    fn check_alloc(&self, item: &'static str) -> Result<(), GenError> {
+        match self.library {
+            StdLib::Core => Err(GenError::UnsupportedLibrary { item, required: StdLib::Alloc }),
+            StdLib::Alloc | StdLib::Std => Ok(()),
+        }
+    }
+
+    ///Checks defines, names of handler methods, arguments, streaming, aliases, attributes and library.
+    fn validate(&self) -> Result<(), GenError> {
+        self.defines.validate()?;
+        function_names(self.service, self.service.methods.iter(), false)?;
+        check_streaming(self.service, self.methods())?;
+        check_arguments(self.service, self.methods())?;
+        check_supported_streaming(self.service, self.methods(), |method, streaming| !streaming.is_client() || !method.arguments.is_empty())?;
+        if self.router.is_some() {
+            self.check_alloc("Async router")?;
+        }
+        if self.framing.is_some() {
+            self.check_alloc("Framing")?;
+        }
+        if self.methods().any(|method| streaming(method) != Streaming::None) {
+            self.check_alloc("Streaming method")?;
+        }
+        if self.receiver == Receiver::Arc && self.router.is_none() {
+            self.check_alloc("Arc receiver")?;
+        }
+        //Streams of synchronous dispatchers are iterators
+        if self.asyncness.is_async() {
+            check_supported_streaming(self.service, self.methods(), |_, streaming| streaming == Streaming::None)?;
+        }
+        if self.aliases {
+            alias_names(self.service, self.service.methods.iter())?;
+        }
+        if self.method_timeout {
+            check_attributes(self.service, self.methods())?;
+        }
+        self.attributes.validate(self.service)
+    }
+
+    ///Generates dispatcher, failing if method ids cannot be assigned, generated names are the same, method has multiple arguments, streaming is unknown or unsupported, or attributes refer to unknown method.
+    ///
+    ///With [method_timeout](Self::method_timeout) it also fails if method has invalid `timeout_ms` or `priority`.
+    ///
+    ///Prefer it over `Display`, which panics on [GenError].
+    pub fn render(&self) -> Result<String, GenError> {
+        self.validate()?;
+        Ok(self.to_string())
+    }
+
+    #[inline]
+    ///Sets method defines, used as method ids.
+    pub const fn defines(mut self, defines: RpcMethodDefines<'a>) -> Self {
+        self.defines = defines;
+        self
+    }
+
+    #[inline]
+    ///Sets callback to map schema types to Rust paths.
+    pub const fn type_path(mut self, type_path: &'a dyn Fn(&TypeName) -> String) -> Self {
+        self.type_path = Some(type_path);
+        self
+    }
+
+    #[inline]
+    ///Gets formatter of `Codec`, matching method id type of this dispatcher.
+    pub fn codec(&self) -> RpcDispatchCodec {
+        RpcDispatchCodec {
+            id_type: self.defines.id_type(),
+            library: self.library,
+        }
+    }
+
+    #[inline]
+    ///Gets formatter of mock, implementing handler trait of this dispatcher.
+    pub const fn mock(&self) -> RpcServiceMock<'a> {
+        RpcServiceMock {
+            dispatcher: *self,
+        }
+    }
+
+    #[inline]
+    ///Gets formatter of wrapper, instrumenting handler trait of this dispatcher.
+    pub const fn instrumentation(&self) -> RpcServiceInstrumentation<'a> {
+        RpcServiceInstrumentation {
+            dispatcher: *self,
+            style: InstrumentStyle::Observer,
+        }
+    }
+
+    #[inline]
+    ///Gets formatter of type aliases, mapping types the same as this dispatcher.
+    pub fn type_aliases(&self) -> RpcTypeAliases<'a> {
+        RpcTypeAliases {
+            service: self.service,
+            type_path: self.type_path,
+            visibility: Visibility::Pub,
+            arguments: ArgumentAliases::Tuple,
+            lifetime: None,
+        }
+    }
+}
+
+impl<'a> RpcServiceDispatcher<'a> {
+    ///Methods, that are handled according to deprecated policy
+    fn methods(&self) -> impl Iterator<Item=&'a RpcMethod> {
+        let policy = self.defines.deprecated_policy;
+        self.service.methods.iter().filter(move |method| policy.is_included(method))
+    }
+
+    ///Returns type of stream of `message` type, that streaming method accepts or returns.
+    fn stream_type(&self, message: &str, boxed: &str) -> String {
+        match self.router {
+            Some(router) => format!("core::pin::Pin<{boxed}<dyn {}<Item = {message}> + Send>>", router.stream),
+            None => format!("impl Iterator<Item = {message}>"),
+        }
+    }
+
+    ///Returns methods of handler trait, implemented by wrappers of handler, with `error` as error type of [ReturnStyle::Result].
+    fn handler_methods(&self, error: &str) -> Result<Vec<HandlerMethod<'a>>, NameCollision> {
+        let boxed = match self.library {
+            StdLib::Std => "Box",
+            _ => "alloc::boxed::Box",
+        };
+        //Tables share lifetime of the method
+        let lifetime = |ty: &TypeRef| match self.is_table(ty) {
+            true => "<'a>",
+            false => "",
+        };
+        let names = function_names(self.service, self.methods(), false)?;
+        Ok(names.into_iter().map(|(method, name)| {
+            let kind = streaming(method);
+            let request = method.arguments.first().map(|argument| {
+                let request = format!("{}{}", map_type(self.type_path, self.service, &argument.ty), lifetime(&argument.ty));
+                match kind.is_client() {
+                    true => self.stream_type(&request, boxed),
+                    false => request,
+                }
+            });
+            let response = format!("{}{}", map_type(self.type_path, self.service, &method.return_type), lifetime(&method.return_type));
+            let response = match kind.is_server() {
+                true => self.stream_type(&response, boxed),
+                false => response,
+            };
+            let output = match self.return_style {
+                ReturnStyle::Plain => response,
+                ReturnStyle::Result => format!("Result<{response}, {error}>"),
+                ReturnStyle::ResultWith(error) => format!("Result<{response}, {error}>"),
+            };
+            HandlerMethod {
+                method,
+                name,
+                request,
+                output,
+                is_borrowed: method.arguments.iter().any(|argument| self.is_table(&argument.ty)) || self.is_table(&method.return_type),
+                //Stream of responses is returned right away
+                is_future: self.router.is_some() && !kind.is_server(),
+            }
+        }).collect())
+    }
+
+    ///Returns generic parameter, handler trait and argument of context, named `ctx`, for implementation of handler trait.
+    fn handler_context(&self, ctx: &str) -> (&'static str, String, String) {
+        let handler = self.handler_name();
+        match self.context {
+            Some(ContextStyle::AssociatedType) => ("", handler, format!(", {ctx}: &mut Self::Context")),
+            Some(ContextStyle::Generic) => ("Ctx, ", format!("{handler}<Ctx>"), format!(", {ctx}: &mut Ctx")),
+            None => ("", handler, String::new()),
+        }
+    }
+
+    ///Returns bounds of codec, required to dispatch every method, including error type, if any.
+    fn codec_bounds(&self, error_type: Option<&str>) -> Vec<String> {
+        //Tables are decoded by flatbuffers, while encoded tables can be of any lifetime
+        let mut types = Vec::new();
+        for method in self.methods() {
+            let arguments = method.arguments.iter().map(|argument| &argument.ty).filter(|name| !self.is_table(name));
+            for name in arguments.chain(Some(&method.return_type)) {
+                let bound = match self.is_table(name) {
+                    true => format!("for<'a> Codec<{}<'a>>", map_type(self.type_path, self.service, name)),
+                    false => format!("Codec<{}>", map_type(self.type_path, self.service, name)),
+                };
+                if !types.contains(&bound) {
+                    types.push(bound);
+                }
+            }
+        }
+        if let Some(error_type) = error_type {
+            types.push(format!("Codec<{error_type}>"));
+        }
+        types
+    }
+
+    ///Writes `method_timeout` function, following handler trait
+    fn fmt_method_timeout(&self, fmt: &mut fmt::Formatter<'_>, methods: &[&RpcMethod], method_type: &str) -> fmt::Result {
+        let prefix = self.defines.names_prefix();
+        let policy = self.defines.deprecated_policy;
+        let timeouts: Vec<(&RpcMethod, u64)> = methods.iter().filter_map(|method| match method.timeout_ms() {
+            Ok(Some(timeout_ms)) => Some((*method, timeout_ms)),
+            _ => None,
+        }).collect();
+
+        fmt.write_str("#[rustfmt::skip]\n")?;
+        if timeouts.iter().any(|(method, _)| policy.is_annotated(method)) {
+            fmt.write_str("#[allow(deprecated)]\n")?;
+        }
+        fmt.write_fmt(format_args!("///Returns timeout of `{}` method, specified by `timeout_ms` attribute\n", self.service.name))?;
+        fmt.write_fmt(format_args!("pub fn method_timeout(method: {method_type}) -> Option<core::time::Duration> {{\n"))?;
+        fmt.write_fmt(format_args!("{TAB}match method {{\n"))?;
+        for (method, timeout_ms) in timeouts {
+            fmt.write_fmt(format_args!("{TAB}{TAB}{prefix}{} => Some(core::time::Duration::from_millis({timeout_ms})),\n", ident::to_screaming_snake(&method.name)))?;
+        }
+        fmt.write_fmt(format_args!("{TAB}{TAB}_ => None,\n"))?;
+        fmt.write_fmt(format_args!("{TAB}}}\n}}\n\n"))
+    }
+
+    ///Writes `dispatch_frame` function, following `dispatch`
+    fn fmt_dispatch_frame(&self, fmt: &mut fmt::Formatter<'_>, framing: &str, types: &[String]) -> fmt::Result {
+        let service = self.handler_name();
+        let vec = self.library.vec().unwrap_or("alloc::vec::Vec");
+
+        fmt.write_str("\n\n#[rustfmt::skip]\n")?;
+        fmt.write_fmt(format_args!("///Dispatches `{}` method call, encoded as `frame`, to `handler`, returning response frame\n", self.service.name))?;
+        let asyncness = self.asyncness.keyword();
+        let generics = match self.context {
+            Some(ContextStyle::Generic) => {
+                fmt.write_fmt(format_args!("pub {asyncness}fn dispatch_frame<Ctx, H: {service}<Ctx>, C"))?;
+                "Ctx, H, C"
+            },
+            _ => {
+                fmt.write_fmt(format_args!("pub {asyncness}fn dispatch_frame<H: {service}, C"))?;
+                "H, C"
+            },
+        };
+        let wait = match self.asyncness.is_async() {
+            true => ".await",
+            false => "",
+        };
+        for (idx, bound) in types.iter().enumerate() {
+            match idx {
+                0 => fmt.write_fmt(format_args!(": {bound}"))?,
+                _ => fmt.write_fmt(format_args!(" + {bound}"))?,
+            }
+        }
+        let (ctx_arg, ctx) = match self.context {
+            Some(ContextStyle::AssociatedType) => (" ctx: &mut H::Context,", " ctx,"),
+            Some(ContextStyle::Generic) => (" ctx: &mut Ctx,", " ctx,"),
+            None => ("", ""),
+        };
+        let (handler, _) = self.handler_arg("");
+        fmt.write_fmt(format_args!(">(handler: {handler},{ctx_arg} frame: &[u8]) -> Result<{vec}<u8>, DispatchError> {{\n"))?;
+        fmt.write_fmt(format_args!("{TAB}let frame = {framing}::decode_frame(frame).map_err(|_| DispatchError::InvalidRequest)?;\n"))?;
+        fmt.write_fmt(format_args!("{TAB}let response = dispatch::<{generics}>(handler,{ctx} frame.method_id, frame.payload){wait}?;\n"))?;
+        fmt.write_fmt(format_args!("{TAB}let mut out = {vec}::new();\n"))?;
+        fmt.write_fmt(format_args!("{TAB}{framing}::encode_frame(frame.method_id, &response, &mut out);\n"))?;
+        fmt.write_fmt(format_args!("{TAB}Ok(out)\n}}"))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    ///Writes `route` function, following handler trait
+    fn fmt_router(&self, fmt: &mut fmt::Formatter<'_>, router: &AsyncRouter<'_>, types: &[String], error_type: Option<&str>, method_type: &str, unknown_method: &str, boxed: &str, arc: &str) -> fmt::Result {
+        let service = self.handler_name();
+        let prefix = self.defines.names_prefix();
+        let AsyncRouter { payload, output, .. } = router;
+
+        let future = format!("core::pin::Pin<{boxed}<dyn core::future::Future<Output = Result<{output}, DispatchError>> + Send>>");
+        let (error_generic, handler_type) = match self.return_style {
+            ReturnStyle::Result => ("E: 'static, ", format!("{arc}<dyn {service}<Error = E>>")),
+            _ => ("", format!("{arc}<dyn {service}>")),
+        };
+        fmt.write_fmt(format_args!("///Routes `{}` method call to `handler`, returning future of encoded response\n", self.service.name))?;
+        fmt.write_fmt(format_args!("pub fn route<{error_generic}C"))?;
+        for (idx, bound) in types.iter().enumerate() {
+            match idx {
+                0 => fmt.write_fmt(format_args!(": {bound}"))?,
+                _ => fmt.write_fmt(format_args!(" + {bound}"))?,
+            }
+        }
+        match types.is_empty() {
+            true => fmt.write_str(": 'static")?,
+            false => fmt.write_str(" + 'static")?,
+        }
+        fmt.write_fmt(format_args!(">(handler: {handler_type}, method: {method_type}, payload: {payload}) -> {future} {{\n"))?;
+
+        fmt.write_fmt(format_args!("{TAB}match method {{\n"))?;
+        let methods = self.methods().filter(|method| streaming(method) == Streaming::None);
+        for (method, name) in expect_valid(function_names(self.service, methods, false)).iter() {
+            let id = format!("{prefix}{}", ident::to_screaming_snake(&method.name));
+            let return_type = map_type(self.type_path, self.service, &method.return_type);
+            let name = Ident(name);
+            fmt.write_fmt(format_args!("{TAB}{TAB}{id} => {boxed}::pin(async move {{\n"))?;
+            let call = match method.arguments.first() {
+                Some(argument) => {
+                    let argument = map_type(self.type_path, self.service, &argument.ty);
+                    match self.is_table(&method.arguments[0].ty) {
+                        true => fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}let request = flatbuffers::root::<{argument}>(&payload).map_err(|_| DispatchError::InvalidRequest)?;\n"))?,
+                        false => fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}let request = <C as Codec<{argument}>>::decode(&payload)?;\n"))?,
+                    }
+                    format!("handler.{name}(request).await")
+                },
+                None => format!("handler.{name}().await"),
+            };
+            match error_type {
+                Some(error_type) => {
+                    fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}let response = match {call} {{\n"))?;
+                    fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{TAB}Ok(response) => response,\n"))?;
+                    fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{TAB}Err(error) => return Err(DispatchError::Failed(<C as Codec<{error_type}>>::encode(&error))),\n"))?;
+                    fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}}};\n"))?;
+                },
+                None => fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}let response = {call};\n"))?,
+            }
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}Ok(<C as Codec<{return_type}>>::encode(&response).into())\n{TAB}{TAB}}}),\n"))?;
+        }
+        fmt.write_fmt(format_args!("{TAB}{TAB}_ => {boxed}::pin(core::future::ready(Err({unknown_method}))),\n"))?;
+        fmt.write_fmt(format_args!("{TAB}}}\n}}"))
+    }
+
+    ///Writes bounds of `C` generic parameter, that is `Codec` of every type.
+    fn fmt_codec_bounds(fmt: &mut fmt::Formatter<'_>, types: &[String], suffix: &str) -> fmt::Result {
+        for (idx, bound) in types.iter().enumerate() {
+            match idx {
+                0 => fmt.write_fmt(format_args!(": {bound}"))?,
+                _ => fmt.write_fmt(format_args!(" + {bound}"))?,
+            }
+        }
+        match (types.is_empty(), suffix.is_empty()) {
+            (_, true) => Ok(()),
+            (true, false) => fmt.write_fmt(format_args!(": {suffix}")),
+            (false, false) => fmt.write_fmt(format_args!(" + {suffix}")),
+        }
+    }
+
+    ///Writes attributes and documentation of function, routing streaming methods of `kind`
+    fn fmt_stream_header(&self, fmt: &mut fmt::Formatter<'_>, kind: Streaming, methods: &[&(&RpcMethod, String)], action: &str, output: &str) -> fmt::Result {
+        let policy = self.defines.deprecated_policy;
+        fmt.write_str("\n\n#[rustfmt::skip]\n")?;
+        if methods.iter().any(|(method, _)| policy.is_annotated(method)) {
+            fmt.write_str("#[allow(deprecated)]\n")?;
+        }
+        if !kind.is_client() && methods.iter().all(|(method, _)| method.arguments.is_empty()) {
+            fmt.write_str("#[allow(unused_variables)]\n")?;
+        }
+        let kind_name = match kind {
+            Streaming::Server => "server",
+            Streaming::Client => "client",
+            _ => "bidirectional",
+        };
+        fmt.write_fmt(format_args!("///{action} {kind_name} streaming `{}` method call to `handler`, returning {output}\n", self.service.name))?;
+        if kind.is_client() {
+            fmt.write_str("///\n///Requests end at the first payload, that cannot be decoded.\n")?;
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    ///Writes `dispatch_server`, `dispatch_client` and `dispatch_bidi` functions for streaming methods of every kind, following `dispatch`
+    fn fmt_stream_dispatchers(&self, fmt: &mut fmt::Formatter<'_>, names: &[(&RpcMethod, String)], types: &[String], error_type: Option<&str>, method_type: &str, unknown_method: &str, boxed: &str) -> fmt::Result {
+        let service = self.handler_name();
+        let prefix = self.defines.names_prefix();
+        let vec = self.library.vec().unwrap_or("alloc::vec::Vec");
+
+        for (kind, function) in [(Streaming::Server, "dispatch_server"), (Streaming::Client, "dispatch_client"), (Streaming::Bidi, "dispatch_bidi")] {
+            let methods: Vec<_> = names.iter().filter(|(method, _)| streaming(method) == kind).collect();
+            if methods.is_empty() {
+                continue;
+            }
+            //Iterator of responses borrows handler
+            let lifetime = match kind.is_server() {
+                true => "'h ",
+                false => "",
+            };
+            match kind.is_server() {
+                true => self.fmt_stream_header(fmt, kind, &methods, "Dispatches", "iterator of encoded responses")?,
+                false => self.fmt_stream_header(fmt, kind, &methods, "Dispatches", "encoded response")?,
+            }
+            fmt.write_fmt(format_args!("pub fn {function}<"))?;
+            if kind.is_server() {
+                fmt.write_str("'h, ")?;
+            }
+            match self.context {
+                Some(ContextStyle::Generic) => fmt.write_fmt(format_args!("Ctx, H: {service}<Ctx>, C"))?,
+                _ => fmt.write_fmt(format_args!("H: {service}, C"))?,
+            }
+            Self::fmt_codec_bounds(fmt, types, "")?;
+            if kind.is_client() {
+                fmt.write_str(", P: AsRef<[u8]>, I: IntoIterator<Item = P>")?;
+            }
+            let ctx_arg = match self.context {
+                Some(ContextStyle::AssociatedType) => format!(" ctx: &{lifetime}mut H::Context,"),
+                Some(ContextStyle::Generic) => format!(" ctx: &{lifetime}mut Ctx,"),
+                None => String::new(),
+            };
+            let (handler, receiver) = self.handler_arg(lifetime);
+            fmt.write_fmt(format_args!(">(handler: {handler},{ctx_arg} method: {method_type}, "))?;
+            match kind {
+                Streaming::Server => fmt.write_fmt(format_args!("payload: &[u8]) -> Result<{boxed}<dyn Iterator<Item = {vec}<u8>> + 'h>, DispatchError> {{\n"))?,
+                Streaming::Client => fmt.write_fmt(format_args!("payloads: I) -> Result<{vec}<u8>, DispatchError> {{\n"))?,
+                _ => fmt.write_fmt(format_args!("payloads: I) -> Result<{boxed}<dyn Iterator<Item = {vec}<u8>> + 'h>, DispatchError> where I::IntoIter: 'h {{\n"))?,
+            }
+
+            fmt.write_fmt(format_args!("{TAB}match method {{\n"))?;
+            for (method, name) in methods.iter() {
+                let id = format!("{prefix}{}", ident::to_screaming_snake(&method.name));
+                let return_type = map_type(self.type_path, self.service, &method.return_type);
+                let mut args = Vec::new();
+                if self.context.is_some() {
+                    args.push("ctx");
+                }
+                fmt.write_fmt(format_args!("{TAB}{TAB}{id} => {{\n"))?;
+                if let Some(argument) = method.arguments.first() {
+                    let argument = map_type(self.type_path, self.service, &argument.ty);
+                    match kind.is_client() {
+                        true => {
+                            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}let requests = payloads.into_iter().map_while(|payload| <C as Codec<{argument}>>::decode(payload.as_ref()).ok());\n"))?;
+                            args.push("requests");
+                        },
+                        false => {
+                            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}let request = <C as Codec<{argument}>>::decode(payload)?;\n"))?;
+                            args.push("request");
+                        },
+                    }
+                }
+                let call = format!("{receiver}.{}({})", Ident(name), args.join(", "));
+                let response = match kind.is_server() {
+                    true => "responses",
+                    false => "response",
+                };
+                match error_type {
+                    Some(error_type) => {
+                        fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}let {response} = match {call} {{\n"))?;
+                        fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{TAB}Ok({response}) => {response},\n"))?;
+                        fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{TAB}Err(error) => return Err(DispatchError::Failed(<C as Codec<{error_type}>>::encode(&error))),\n"))?;
+                        fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}}};\n"))?;
+                    },
+                    None => fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}let {response} = {call};\n"))?,
+                }
+                match kind.is_server() {
+                    true => fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}Ok({boxed}::new(responses.map(|response| <C as Codec<{return_type}>>::encode(&response))))\n{TAB}{TAB}}},\n"))?,
+                    false => fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}Ok(<C as Codec<{return_type}>>::encode(&response))\n{TAB}{TAB}}},\n"))?,
+                }
+            }
+            fmt.write_fmt(format_args!("{TAB}{TAB}_ => Err({unknown_method}),\n"))?;
+            fmt.write_fmt(format_args!("{TAB}}}\n}}"))?;
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    ///Writes `route_server`, `route_client` and `route_bidi` functions for streaming methods of every kind, following `route`
+    fn fmt_stream_routers(&self, fmt: &mut fmt::Formatter<'_>, router: &AsyncRouter<'_>, names: &[(&RpcMethod, String)], types: &[String], error_type: Option<&str>, method_type: &str, unknown_method: &str, boxed: &str, arc: &str) -> fmt::Result {
+        let service = self.handler_name();
+        let prefix = self.defines.names_prefix();
+        let vec = self.library.vec().unwrap_or("alloc::vec::Vec");
+        let AsyncRouter { payload, output, stream } = router;
+        let poll = "core::task::Poll";
+
+        //Adapters between streams of payloads and streams of messages
+        if names.iter().any(|(method, _)| streaming(method).is_client()) {
+            fmt.write_str("\n\n#[rustfmt::skip]\n")?;
+            fmt.write_str("///Stream of requests, decoded by `Codec`, that ends at the first payload, that cannot be decoded\n")?;
+            fmt.write_str("struct DecodedRequests<T> {\n")?;
+            fmt.write_fmt(format_args!("{TAB}payloads: core::pin::Pin<{boxed}<dyn {stream}<Item = {payload}> + Send>>,\n"))?;
+            fmt.write_fmt(format_args!("{TAB}decode: fn(&[u8]) -> Result<T, DispatchError>,\n{TAB}is_done: bool,\n}}\n\n"))?;
+            fmt.write_str("#[rustfmt::skip]\n")?;
+            fmt.write_fmt(format_args!("impl<T> {stream} for DecodedRequests<T> {{\n{TAB}type Item = T;\n\n"))?;
+            fmt.write_fmt(format_args!("{TAB}fn poll_next(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> {poll}<Option<T>> {{\n"))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}if self.is_done {{\n{TAB}{TAB}{TAB}return {poll}::Ready(None);\n{TAB}{TAB}}}\n"))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}match {stream}::poll_next(self.payloads.as_mut(), cx) {{\n"))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{poll}::Ready(Some(payload)) => match (self.decode)(&payload) {{\n"))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{TAB}Ok(request) => {poll}::Ready(Some(request)),\n"))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{TAB}Err(_) => {{\n{TAB}{TAB}{TAB}{TAB}{TAB}self.is_done = true;\n{TAB}{TAB}{TAB}{TAB}{TAB}{poll}::Ready(None)\n{TAB}{TAB}{TAB}{TAB}}},\n"))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}}},\n"))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{poll}::Ready(None) => {poll}::Ready(None),\n"))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{poll}::Pending => {poll}::Pending,\n"))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}}}\n{TAB}}}\n}}"))?;
+        }
+        if names.iter().any(|(method, _)| streaming(method).is_server()) {
+            fmt.write_str("\n\n#[rustfmt::skip]\n")?;
+            fmt.write_str("///Stream of responses, encoded by `Codec`\n")?;
+            fmt.write_str("struct EncodedResponses<T> {\n")?;
+            fmt.write_fmt(format_args!("{TAB}responses: core::pin::Pin<{boxed}<dyn {stream}<Item = T> + Send>>,\n"))?;
+            fmt.write_fmt(format_args!("{TAB}encode: fn(&T) -> {vec}<u8>,\n}}\n\n"))?;
+            fmt.write_str("#[rustfmt::skip]\n")?;
+            fmt.write_fmt(format_args!("impl<T> {stream} for EncodedResponses<T> {{\n{TAB}type Item = {output};\n\n"))?;
+            fmt.write_fmt(format_args!("{TAB}fn poll_next(mut self: core::pin::Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> {poll}<Option<{output}>> {{\n"))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}match {stream}::poll_next(self.responses.as_mut(), cx) {{\n"))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{poll}::Ready(Some(response)) => {poll}::Ready(Some((self.encode)(&response).into())),\n"))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{poll}::Ready(None) => {poll}::Ready(None),\n"))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{poll}::Pending => {poll}::Pending,\n"))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}}}\n{TAB}}}\n}}"))?;
+        }
+
+        let (error_generic, handler_type) = match self.return_style {
+            ReturnStyle::Result => ("E: 'static, ", format!("{arc}<dyn {service}<Error = E>>")),
+            _ => ("", format!("{arc}<dyn {service}>")),
+        };
+        let responses = format!("core::pin::Pin<{boxed}<dyn {stream}<Item = {output}> + Send>>");
+        for (kind, function) in [(Streaming::Server, "route_server"), (Streaming::Client, "route_client"), (Streaming::Bidi, "route_bidi")] {
+            let methods: Vec<_> = names.iter().filter(|(method, _)| streaming(method) == kind).collect();
+            if methods.is_empty() {
+                continue;
+            }
+            match kind.is_server() {
+                true => self.fmt_stream_header(fmt, kind, &methods, "Routes", "stream of encoded responses")?,
+                false => self.fmt_stream_header(fmt, kind, &methods, "Routes", "future of encoded response")?,
+            }
+            fmt.write_fmt(format_args!("pub fn {function}<{error_generic}C"))?;
+            Self::fmt_codec_bounds(fmt, types, "'static")?;
+            fmt.write_fmt(format_args!(">(handler: {handler_type}, method: {method_type}, "))?;
+            match kind {
+                Streaming::Server => fmt.write_fmt(format_args!("payload: {payload}) -> Result<{responses}, DispatchError> {{\n"))?,
+                Streaming::Client => fmt.write_fmt(format_args!("payloads: core::pin::Pin<{boxed}<dyn {stream}<Item = {payload}> + Send>>) -> core::pin::Pin<{boxed}<dyn core::future::Future<Output = Result<{output}, DispatchError>> + Send>> {{\n"))?,
+                _ => fmt.write_fmt(format_args!("payloads: core::pin::Pin<{boxed}<dyn {stream}<Item = {payload}> + Send>>) -> Result<{responses}, DispatchError> {{\n"))?,
+            }
+
+            fmt.write_fmt(format_args!("{TAB}match method {{\n"))?;
+            for (method, name) in methods.iter() {
+                let id = format!("{prefix}{}", ident::to_screaming_snake(&method.name));
+                let return_type = map_type(self.type_path, self.service, &method.return_type);
+                match kind {
+                    Streaming::Client => fmt.write_fmt(format_args!("{TAB}{TAB}{id} => {boxed}::pin(async move {{\n"))?,
+                    _ => fmt.write_fmt(format_args!("{TAB}{TAB}{id} => {{\n"))?,
+                }
+                let arg = match method.arguments.first() {
+                    Some(argument) => {
+                        let argument = map_type(self.type_path, self.service, &argument.ty);
+                        match kind.is_client() {
+                            true => {
+                                fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}let requests = {boxed}::pin(DecodedRequests {{ payloads, decode: <C as Codec<{argument}>>::decode, is_done: false }});\n"))?;
+                                "requests"
+                            },
+                            false => {
+                                fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}let request = <C as Codec<{argument}>>::decode(&payload)?;\n"))?;
+                                "request"
+                            },
+                        }
+                    },
+                    None => "",
+                };
+                let call = match kind {
+                    Streaming::Client => format!("handler.{}({arg}).await", Ident(name)),
+                    _ => format!("handler.{}({arg})", Ident(name)),
+                };
+                let response = match kind.is_server() {
+                    true => "responses",
+                    false => "response",
+                };
+                match error_type {
+                    Some(error_type) => {
+                        fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}let {response} = match {call} {{\n"))?;
+                        fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{TAB}Ok({response}) => {response},\n"))?;
+                        fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{TAB}Err(error) => return Err(DispatchError::Failed(<C as Codec<{error_type}>>::encode(&error))),\n"))?;
+                        fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}}};\n"))?;
+                    },
+                    None => fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}let {response} = {call};\n"))?,
+                }
+                match kind {
+                    Streaming::Client => fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}Ok(<C as Codec<{return_type}>>::encode(&response).into())\n{TAB}{TAB}}}),\n"))?,
+                    _ => fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}Ok({boxed}::pin(EncodedResponses {{ responses, encode: <C as Codec<{return_type}>>::encode }}))\n{TAB}{TAB}}},\n"))?,
+                }
+            }
+            match kind {
+                Streaming::Client => fmt.write_fmt(format_args!("{TAB}{TAB}_ => {boxed}::pin(core::future::ready(Err({unknown_method}))),\n"))?,
+                _ => fmt.write_fmt(format_args!("{TAB}{TAB}_ => Err({unknown_method}),\n"))?,
+            }
+            fmt.write_fmt(format_args!("{TAB}}}\n}}"))?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for RpcServiceDispatcher<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let service = self.handler_name();
+        let prefix = self.defines.names_prefix();
+        let policy = self.defines.deprecated_policy;
+        let methods: Vec<&RpcMethod> = self.methods().collect();
+        expect_valid(self.validate());
+        if self.router.is_some() {
+            assert!(self.context.is_none(), "Async router does not support context");
+            assert!(!self.asyncness.is_async(), "Async router does not support async handler methods");
+        }
+        if let Some(DefaultBody::Error(_)) = self.default_body {
+            assert!(matches!(self.return_style, ReturnStyle::ResultWith(_)), "Default error body requires handler methods to return Result with specified error");
+        }
+        if self.framing.is_some() {
+            assert!(self.router.is_none(), "Async router does not support framing");
+            assert!(self.defines.id_type() == Some(IntType::U16), "Framing requires u16 method ids");
+        }
+        let (boxed, arc) = match self.library {
+            StdLib::Std => ("Box", "std::sync::Arc"),
+            _ => ("alloc::boxed::Box", "alloc::sync::Arc"),
+        };
+        let receiver = self.receiver_of().code(arc);
+        let asyncness = self.asyncness.keyword();
+
+        //handler trait
+        fmt.write_str("#[rustfmt::skip]\n")?;
+        write_docs(fmt, "", &self.service.docs, self.skip_docs, format_args!("Handler of `{}` service", self.service.name))?;
+        let (generics, ctx_type) = match self.context {
+            Some(ContextStyle::AssociatedType) => ("", "Self::Context"),
+            Some(ContextStyle::Generic) => ("<Ctx>", "Ctx"),
+            None => ("", ""),
+        };
+        self.attributes.write_item(fmt, "")?;
+        self.asyncness.write_attribute(fmt, "")?;
+        match (self.router, self.is_send_sync()) {
+            (Some(_), _) => fmt.write_fmt(format_args!("pub trait {service}: Send + Sync {{\n"))?,
+            (None, true) => fmt.write_fmt(format_args!("pub trait {service}{generics}: Send + Sync {{\n"))?,
+            (None, false) => fmt.write_fmt(format_args!("pub trait {service}{generics} {{\n"))?,
+        }
+        if let Some(ContextStyle::AssociatedType) = self.context {
+            fmt.write_fmt(format_args!("{TAB}///Per request context\n{TAB}type Context;\n\n"))?;
+        }
+        if let ReturnStyle::Result = self.return_style {
+            fmt.write_fmt(format_args!("{TAB}///Error of handler methods\n{TAB}type Error;\n\n"))?;
+        }
+        let names = expect_valid(function_names(self.service, methods.iter().copied(), false));
+        for (method, name) in names.iter() {
+            assert!(
+                method.arguments.len() <= 1,
+                "We require all RPC methods to have at most 1 argument"
+            );
+            if policy.is_annotated(method) {
+                fmt.write_fmt(format_args!("{TAB}#[deprecated]\n"))?;
+            }
+            //Tables share lifetime of the method
+            let lifetime = |ty: &TypeRef| match self.is_table(ty) {
+                true => "<'a>",
+                false => "",
+            };
+            let is_borrowed = method.arguments.iter().any(|argument| self.is_table(&argument.ty)) || self.is_table(&method.return_type);
+            let kind = streaming(method);
+            if kind != Streaming::None {
+                assert!(!is_borrowed, "Streaming methods do not support tables");
+                assert!(!kind.is_client() || !method.arguments.is_empty(), "Client streaming methods require argument");
+            }
+            //Arguments are unused by default body, which streaming methods do not have
+            let arg_prefix = match (self.default_body, kind) {
+                (Some(_), Streaming::None) => "_",
+                _ => "",
+            };
+            let (request_type, return_type) = match self.aliases {
+                true => (format!("{}Request", ident::to_pascal(&method.name)), format!("{}Response{}", ident::to_pascal(&method.name), lifetime(&method.return_type))),
+                false => (String::new(), format!("{}{}", map_type(self.type_path, self.service, &method.return_type), lifetime(&method.return_type))),
+            };
+            write_docs(fmt, TAB, &method.docs, self.skip_docs, format_args!("Handles `{}`", method.name))?;
+            self.attributes.write_method(fmt, TAB, method)?;
+            match (self.router, is_borrowed) {
+                (Some(_), true) => fmt.write_fmt(format_args!("{TAB}fn {}<'a>(&'a self", Ident(name)))?,
+                (Some(_), false) => fmt.write_fmt(format_args!("{TAB}fn {}(&self", Ident(name)))?,
+                (None, true) => fmt.write_fmt(format_args!("{TAB}{asyncness}fn {}<'a>({receiver}", Ident(name)))?,
+                (None, false) => fmt.write_fmt(format_args!("{TAB}{asyncness}fn {}({receiver}", Ident(name)))?,
+            }
+            if self.context.is_some() {
+                fmt.write_fmt(format_args!(", {arg_prefix}ctx: &mut {ctx_type}"))?;
+            }
+            if let Some(argument) = method.arguments.first() {
+                let lifetime = lifetime(&argument.ty);
+                let request_type = match self.aliases {
+                    true => format!("{request_type}{lifetime}"),
+                    false => format!("{}{lifetime}", map_type(self.type_path, self.service, &argument.ty)),
+                };
+                match kind.is_client() {
+                    true => fmt.write_fmt(format_args!(", requests: {}", self.stream_type(&request_type, boxed)))?,
+                    false => fmt.write_fmt(format_args!(", {arg_prefix}request: {request_type}"))?,
+                }
+            }
+            let return_type = match kind.is_server() {
+                true => self.stream_type(&return_type, boxed),
+                false => return_type,
+            };
+            let output = match self.return_style {
+                ReturnStyle::Plain => return_type,
+                ReturnStyle::Result => format!("Result<{return_type}, Self::Error>"),
+                ReturnStyle::ResultWith(error) => format!("Result<{return_type}, {error}>"),
+            };
+            //Stream of responses is returned right away
+            match (self.router, is_borrowed) {
+                (Some(_), _) if kind.is_server() => fmt.write_fmt(format_args!(") -> {output}"))?,
+                (Some(_), true) => fmt.write_fmt(format_args!(") -> core::pin::Pin<{boxed}<dyn core::future::Future<Output = {output}> + Send + 'a>>"))?,
+                (Some(_), false) => fmt.write_fmt(format_args!(") -> core::pin::Pin<{boxed}<dyn core::future::Future<Output = {output}> + Send + '_>>"))?,
+                (None, _) => fmt.write_fmt(format_args!(") -> {output}"))?,
+            }
+            match (self.default_body, self.router) {
+                (None, _) => fmt.write_str(";\n")?,
+                (Some(_), _) if kind != Streaming::None => fmt.write_str(";\n")?,
+                (Some(DefaultBody::Unimplemented), _) => fmt.write_fmt(format_args!(" {{\n{TAB}{TAB}unimplemented!(\"{}::{}\")\n{TAB}}}\n", self.service.name, method.name))?,
+                (Some(DefaultBody::Todo), _) => fmt.write_fmt(format_args!(" {{\n{TAB}{TAB}todo!(\"{}::{}\")\n{TAB}}}\n", self.service.name, method.name))?,
+                (Some(DefaultBody::Error(error)), Some(_)) => fmt.write_fmt(format_args!(" {{\n{TAB}{TAB}{boxed}::pin(async {{ Err({error}) }})\n{TAB}}}\n"))?,
+                (Some(DefaultBody::Error(error)), None) => fmt.write_fmt(format_args!(" {{\n{TAB}{TAB}Err({error})\n{TAB}}}\n"))?,
+            }
+        }
+        fmt.write_str("}\n\n")?;
+
+        //Every type must be encoded or decoded by codec
+        let error_type = match self.return_style {
+            ReturnStyle::Plain => None,
+            ReturnStyle::Result if self.router.is_some() => Some("E".to_owned()),
+            ReturnStyle::Result => Some("H::Error".to_owned()),
+            ReturnStyle::ResultWith(error) => Some(error.to_owned()),
+        };
+        let types = self.codec_bounds(error_type.as_deref());
+
+        //dispatch signature
+        let (method_type, unknown_method) = match (self.defines.id_type(), self.library) {
+            (Some(id_type), _) => (id_type.to_string(), "DispatchError::UnknownMethod(method)"),
+            (None, StdLib::Core) => ("&str".to_owned(), "DispatchError::UnknownMethod"),
+            (None, StdLib::Alloc) => ("&str".to_owned(), "DispatchError::UnknownMethod(method.into())"),
+            (None, StdLib::Std) => ("&str".to_owned(), "DispatchError::UnknownMethod(method.to_owned())"),
+        };
+        if self.method_timeout {
+            self.fmt_method_timeout(fmt, &methods, &method_type)?;
+        }
+        let vec = self.library.vec();
+        fmt.write_str("#[rustfmt::skip]\n")?;
+        if methods.iter().any(|method| policy.is_annotated(method)) {
+            fmt.write_str("#[allow(deprecated)]\n")?;
+        }
+        if methods.iter().all(|method| streaming(method) != Streaming::None) {
+            fmt.write_str("#[allow(unused_variables)]\n")?;
+        }
+        if let Some(router) = self.router.as_ref() {
+            self.fmt_router(fmt, router, &types, error_type.as_deref(), &method_type, unknown_method, boxed, arc)?;
+            return self.fmt_stream_routers(fmt, router, &names, &types, error_type.as_deref(), &method_type, unknown_method, boxed, arc);
+        }
+        fmt.write_fmt(format_args!("///Dispatches `{}` method call to `handler`\n", self.service.name))?;
+        match self.context {
+            Some(ContextStyle::AssociatedType) | None => fmt.write_fmt(format_args!("pub {asyncness}fn dispatch<H: {service}, C"))?,
+            Some(ContextStyle::Generic) => fmt.write_fmt(format_args!("pub {asyncness}fn dispatch<Ctx, H: {service}<Ctx>, C"))?,
+        }
+        for (idx, bound) in types.iter().enumerate() {
+            match idx {
+                0 => fmt.write_fmt(format_args!(": {bound}"))?,
+                _ => fmt.write_fmt(format_args!(" + {bound}"))?,
+            }
+        }
+        let (ctx_arg, ctx) = match self.context {
+            Some(ContextStyle::AssociatedType) => (" ctx: &mut H::Context,", "ctx"),
+            Some(ContextStyle::Generic) => (" ctx: &mut Ctx,", "ctx"),
+            None => ("", ""),
+        };
+        let (handler, receiver) = self.handler_arg("");
+        match vec {
+            Some(vec) => fmt.write_fmt(format_args!(">(handler: {handler},{ctx_arg} method: {method_type}, payload: &[u8]) -> Result<{vec}<u8>, DispatchError> {{\n"))?,
+            None => fmt.write_fmt(format_args!(">(handler: {handler},{ctx_arg} method: {method_type}, payload: &[u8], out: &mut [u8]) -> Result<usize, DispatchError> {{\n"))?,
+        }
+
+        //match method
+        fmt.write_fmt(format_args!("{TAB}match method {{\n"))?;
+        for (method, name) in names.iter().filter(|(method, _)| streaming(method) == Streaming::None) {
+            let id = format!("{prefix}{}", ident::to_screaming_snake(&method.name));
+            let return_type = map_type(self.type_path, self.service, &method.return_type);
+            let name = Ident(name);
+            fmt.write_fmt(format_args!("{TAB}{TAB}{id} => {{\n"))?;
+            if let Some(argument) = method.arguments.first() {
+                let argument = map_type(self.type_path, self.service, &argument.ty);
+                match self.is_table(&method.arguments[0].ty) {
+                    true => fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}let request = flatbuffers::root::<{argument}>(payload).map_err(|_| DispatchError::InvalidRequest)?;\n"))?,
+                    false => fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}let request = <C as Codec<{argument}>>::decode(payload)?;\n"))?,
+                }
+            }
+            let call = match (self.context, method.arguments.is_empty()) {
+                (Some(_), false) => format!("{receiver}.{name}({ctx}, request)"),
+                (None, false) => format!("{receiver}.{name}(request)"),
+                (_, true) => format!("{receiver}.{name}({ctx})"),
+            };
+            let call = match self.asyncness.is_async() {
+                true => format!("{call}.await"),
+                false => call,
+            };
+            match error_type.as_ref() {
+                Some(error_type) => {
+                    fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}let response = match {call} {{\n"))?;
+                    fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{TAB}Ok(response) => response,\n"))?;
+                    match vec {
+                        Some(_) => fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{TAB}Err(error) => return Err(DispatchError::Failed(<C as Codec<{error_type}>>::encode(&error))),\n"))?,
+                        None => fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}{TAB}Err(error) => return Err(DispatchError::Failed(<C as Codec<{error_type}>>::encode(&error, out)?)),\n"))?,
+                    }
+                    fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}}};\n"))?;
+                },
+                None => fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}let response = {call};\n"))?,
+            }
+            match vec {
+                Some(_) => fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}Ok(<C as Codec<{return_type}>>::encode(&response))\n{TAB}{TAB}}},\n"))?,
+                None => fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}<C as Codec<{return_type}>>::encode(&response, out)\n{TAB}{TAB}}},\n"))?,
+            }
+        }
+        fmt.write_fmt(format_args!("{TAB}{TAB}_ => Err({unknown_method}),\n"))?;
+        fmt.write_fmt(format_args!("{TAB}}}\n}}"))?;
+
+        if let Some(framing) = self.framing {
+            self.fmt_dispatch_frame(fmt, framing, &types)?;
+        }
+        self.fmt_stream_dispatchers(fmt, &names, &types, error_type.as_deref(), &method_type, unknown_method, boxed)
+    }
+}
+
+#[derive(Copy, Clone)]
+///Generates router of method calls to dispatchers of several services, which method ids are unique across services.
+///
+///Router is placed in parent module of service modules, e.g. along with [RpcDispatchCodec], and consists of:
+///
+///- `Service` enum with variant per service, named after service;
+///- `const fn service_of(method) -> Option<Service>`, resolving service, that defines method;
+///- `dispatch`, calling `dispatch` of resolved service, with handler implementing every handler trait.
+///
+///```rust,ignore
+///pub fn dispatch<H: storage::StorageService + monitor::MonitorService, C: Codec<Request> + Codec<Response> + Codec<Pong>>(handler: &mut H, method: u16, payload: &[u8]) -> Result<Vec<u8>, DispatchError>;
+///```
+///
+///Streaming methods are not routed, but their service is resolved by `service_of`.
+///Dispatchers are expected to use the same id type, library, receiver and asyncness, without async router and with either no context or [ContextStyle::Generic].
+///Types are mapped by dispatchers, hence type paths are to be the same within parent module, e.g. `crate::interface::Request`.
+///
+///Routes calls of services, which share method names, by their global ids:
+///
+///```rust
+///use flatbuffers_tools::{render_services, parse_services, GenConfig, IdScope, TypeName};
+///
+///let services = parse_services("rpc_service Storage { Put(Request):Response; Get(Request):Response; }\nrpc_service Monitor { Put(Request):Pong; }").unwrap();
+///let config = GenConfig::new().skip_docs(true).type_path(|name: &TypeName| format!("crate::{}", name.name())).id_scope(IdScope::Schema).service_router(true);
+///let code = render_services(&services, &config).unwrap();
+///assert!(code.contains("pub const fn service_of(method: u16) -> Option<Service> {"));
+///assert!(code.contains("        storage::PUT | storage::GET => Some(Service::Storage),\n        monitor::PUT => Some(Service::Monitor),\n"));
+///assert!(code.contains("Some(Service::Monitor) => monitor::dispatch::<H, C>(handler, method, payload),"));
+///
+/////Ids of services overlap without global scope
+///let error = render_services(&services, &config.clone().id_scope(IdScope::Service)).unwrap_err();
+///assert_eq!(error.to_string(), "Methods 'Storage.Put' and 'Monitor.Put' have the same id 0, while ids are unique across services");
+///```
+pub struct RpcServiceRouter<'a> {
+    ///Dispatchers of every service, each within its module, named after service in snake case.
+    pub dispatchers: &'a [RpcServiceDispatcher<'a>],
+    ///Visibility of generated items, `pub` by default.
+    pub visibility: Visibility,
+}
+
+impl<'a> RpcServiceRouter<'a> {
+    #[inline]
+    ///Creates router of `dispatchers`.
+    pub const fn new(dispatchers: &'a [RpcServiceDispatcher<'a>]) -> Self {
+        Self {
+            dispatchers,
+            visibility: Visibility::Pub,
+        }
+    }
+
+    #[inline]
+    ///Sets visibility of generated items.
+    pub const fn visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    ///Checks dispatchers and that method ids are unique across services.
+    fn validate(&self) -> Result<(), GenError> {
+        //Service and method of every id
+        let mut used: Vec<(u64, &RpcService, &RpcMethod)> = Vec::new();
+        for dispatcher in self.dispatchers.iter() {
+            dispatcher.validate()?;
+            let service = dispatcher.service;
+            for (id, method) in dispatcher.defines.ordered_ids()? {
+                if let Some((_, prev_service, prev)) = used.iter().find(|(prev, _, _)| *prev == id) {
+                    return Err(MethodIdError::DuplicateGlobalId {
+                        first: format!("{}.{}", qualified_name(prev_service), prev.name),
+                        second: format!("{}.{}", qualified_name(service), method.name),
+                        id,
+                    }.into());
+                }
+                used.push((id, service, method));
+            }
+        }
+        Ok(())
+    }
+
+    ///Generates router, failing if any dispatcher cannot be generated, or method ids are the same.
+    ///
+    ///Prefer it over `Display`, which panics on [GenError].
+    pub fn render(&self) -> Result<String, GenError> {
+        self.validate()?;
+        Ok(self.to_string())
+    }
+}
+
+impl fmt::Display for RpcServiceRouter<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let visibility = self.visibility;
+        let first = match self.dispatchers.first() {
+            Some(first) => first,
+            None => return Ok(()),
+        };
+        let id_type = first.defines.id_type().unwrap_or(IntType::U16);
+        let is_generic_ctx = first.context == Some(ContextStyle::Generic);
+        //Module, handler trait and variant of every service
+        let services: Vec<(String, String, String)> = self.dispatchers.iter().map(|dispatcher| {
+            let service = &dispatcher.service.name;
+            let handler = match is_generic_ctx {
+                true => format!("{}::{}<Ctx>", Ident(&ident::to_snake(service)), dispatcher.handler_name()),
+                false => format!("{}::{}", Ident(&ident::to_snake(service)), dispatcher.handler_name()),
+            };
+            (Ident(&ident::to_snake(service)).to_string(), handler, Ident(&ident::to_pascal(service)).to_string())
+        }).collect();
+        let is_deprecated = self.dispatchers.iter().any(|dispatcher| dispatcher.service.methods.iter().any(|method| dispatcher.defines.deprecated_policy.is_annotated(method)));
+        let allow_deprecated = match is_deprecated {
+            true => "#[allow(deprecated)]\n",
+            false => "",
+        };
+
+        //Service
+        fmt.write_str("#[rustfmt::skip]\n#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]\n///Service, that defines method\n")?;
+        fmt.write_fmt(format_args!("{visibility}enum Service {{\n"))?;
+        for (dispatcher, (_, _, variant)) in self.dispatchers.iter().zip(services.iter()) {
+            fmt.write_fmt(format_args!("{TAB}///`{}`\n{TAB}{variant},\n", qualified_name(dispatcher.service)))?;
+        }
+        fmt.write_str("}\n\n")?;
+
+        //service_of
+        fmt.write_fmt(format_args!("#[rustfmt::skip]\n{allow_deprecated}///Returns service, that defines method with specified id\n"))?;
+        fmt.write_fmt(format_args!("{visibility}const fn service_of(method: {id_type}) -> Option<Service> {{\n{TAB}match method {{\n"))?;
+        for (dispatcher, (module, _, variant)) in self.dispatchers.iter().zip(services.iter()) {
+            let prefix = dispatcher.defines.names_prefix();
+            let constants: Vec<String> = dispatcher.service.methods.iter().map(|method| format!("{module}::{prefix}{}", ident::to_screaming_snake(&method.name))).collect();
+            if !constants.is_empty() {
+                fmt.write_fmt(format_args!("{TAB}{TAB}{} => Some(Service::{variant}),\n", constants.join(" | ")))?;
+            }
+        }
+        fmt.write_fmt(format_args!("{TAB}{TAB}_ => None,\n{TAB}}}\n}}\n\n"))?;
+
+        //dispatch
+        let mut types: Vec<String> = Vec::new();
+        for (dispatcher, (_, handler, _)) in self.dispatchers.iter().zip(services.iter()) {
+            //Error type is associated with handler trait of the service
+            let error_type = match dispatcher.return_style {
+                ReturnStyle::Plain => None,
+                ReturnStyle::Result => Some(format!("<H as {handler}>::Error")),
+                ReturnStyle::ResultWith(error) => Some(error.to_owned()),
+            };
+            for bound in dispatcher.codec_bounds(error_type.as_deref()) {
+                if !types.contains(&bound) {
+                    types.push(bound);
+                }
+            }
+        }
+        let handlers: Vec<&str> = services.iter().map(|(_, handler, _)| handler.as_str()).collect();
+        let (generics, ctx_arg, ctx) = match is_generic_ctx {
+            true => ("Ctx, ", " ctx: &mut Ctx,", "ctx, "),
+            false => ("", "", ""),
+        };
+        let (out_arg, out, output) = match first.library.vec() {
+            Some(vec) => (String::new(), "", format!("{vec}<u8>")),
+            None => (", out: &mut [u8]".to_owned(), ", out", "usize".to_owned()),
+        };
+        fmt.write_str("#[rustfmt::skip]\n///Dispatches method call to `handler` of service, that defines method with specified id\n")?;
+        fmt.write_fmt(format_args!("{visibility}{}fn dispatch<{generics}H: {}, C", first.asyncness.keyword(), handlers.join(" + ")))?;
+        RpcServiceDispatcher::fmt_codec_bounds(fmt, &types, "")?;
+        let (handler, _) = first.handler_arg("");
+        fmt.write_fmt(format_args!(">(handler: {handler},{ctx_arg} method: {id_type}, payload: &[u8]{out_arg}) -> Result<{output}, DispatchError> {{\n"))?;
+        fmt.write_fmt(format_args!("{TAB}match service_of(method) {{\n"))?;
+        let wait = match first.asyncness.is_async() {
+            true => ".await",
+            false => "",
+        };
+        for (module, _, variant) in services.iter() {
+            fmt.write_fmt(format_args!("{TAB}{TAB}Some(Service::{variant}) => {module}::dispatch::<{generics}H, C>(handler, {ctx}method, payload{out}){wait},\n"))?;
+        }
+        fmt.write_fmt(format_args!("{TAB}{TAB}None => Err(DispatchError::UnknownMethod(method)),\n{TAB}}}\n}}"))
+    }
+}
+
+///Method of handler trait, as implemented by [RpcServiceMock] and [RpcServiceInstrumentation]
+struct HandlerMethod<'a> {
+    method: &'a RpcMethod,
+    ///Name of trait method
+    name: String,
+    ///Type of request or stream of requests, if method has argument
+    request: Option<String>,
+    ///Type, returned by method, or output of its future
+    output: String,
+    ///Tables of method share its lifetime
+    is_borrowed: bool,
+    ///Method returns boxed future
+    is_future: bool,
+}
+
+impl HandlerMethod<'_> {
+    ///Returns name of request argument, if any.
+    fn request_name(&self) -> Option<&'static str> {
+        match streaming(self.method).is_client() {
+            true => self.request.as_ref().map(|_| "requests"),
+            false => self.request.as_ref().map(|_| "request"),
+        }
+    }
+
+    ///Writes signature of method, implemented for `dispatcher`, up to opening bracket of its body.
+    fn write_signature(&self, fmt: &mut fmt::Formatter<'_>, dispatcher: &RpcServiceDispatcher<'_>, ctx_arg: &str) -> fmt::Result {
+        let (boxed, arc) = match dispatcher.library {
+            StdLib::Std => ("Box", "std::sync::Arc"),
+            _ => ("alloc::boxed::Box", "alloc::sync::Arc"),
+        };
+        let (generics, receiver) = match (dispatcher.router.is_some(), self.is_borrowed) {
+            (true, true) => ("<'a>", "&'a self".to_owned()),
+            (false, true) => ("<'a>", dispatcher.receiver_of().code(arc)),
+            (_, false) => ("", dispatcher.receiver_of().code(arc)),
+        };
+        fmt.write_fmt(format_args!("{TAB}{}fn {}{generics}({receiver}{ctx_arg}", dispatcher.asyncness.keyword(), Ident(&self.name)))?;
+        if let (Some(name), Some(request)) = (self.request_name(), self.request.as_ref()) {
+            fmt.write_fmt(format_args!(", {name}: {request}"))?;
+        }
+        let output = &self.output;
+        match (self.is_future, self.is_borrowed) {
+            (true, true) => fmt.write_fmt(format_args!(") -> core::pin::Pin<{boxed}<dyn core::future::Future<Output = {output}> + Send + 'a>> {{\n")),
+            (true, false) => fmt.write_fmt(format_args!(") -> core::pin::Pin<{boxed}<dyn core::future::Future<Output = {output}> + Send + '_>> {{\n")),
+            (false, _) => fmt.write_fmt(format_args!(") -> {output} {{\n")),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+///Generates mock of handler trait, generated by `dispatcher`, for use in tests.
+///
+///For service `Storage` it generates `StorageCall` enum with variant per method, containing its request,
+///and `MockStorageService`, implementing `StorageService` with methods to queue responses and inspect calls:
+///
+///```rust,ignore
+///impl MockStorageService {
+///    pub fn new() -> Self;
+///    pub fn calls(&self) -> &[StorageCall];
+///    pub fn expect_put<F: FnOnce(&Request) -> Response + 'static>(&mut self, response: F) -> &mut Self;
+///    pub fn return_put(&mut self, response: Response) -> &mut Self;
+///}
+///```
+///
+///Every call of handler method is recorded and answered by the first queued response of the method.
+///
+///## Panics
+///
+///Call of handler method without queued response panics, naming the method, so that unexpected call fails the test.
+///Generated mock documents it within `# Panics` section.
+///Requests are required to implement `Debug`, `Clone` and `PartialEq`, derived by `StorageCall`.
+///
+///Responses are whatever handler method returns, e.g. `Result` with [ReturnStyle::Result], in which case mock is generic over error type.
+///Context is ignored, with `()` as associated type.
+///
+///With [AsyncRouter] mock is shared by router, hence its methods accept `&self`, `calls` returns their copy and responses must be `Send`.
+///
+///Mock requires `alloc`, and `std` to record calls behind mutex, i.e. with async router, shared receiver or `Send + Sync` handler,
+///which is otherwise reported as [GenError::UnsupportedLibrary].
+///Tables of [TypeKind::Table] are not supported, while streaming methods are reported as [GenError::UnsupportedStreaming].
+///
+///Mock implements handler trait of the dispatcher within the same module:
+///
+///```rust
+///use flatbuffers_tools::{render_services, parse_services, GenConfig, GenError, GenItem, StdLib, TypeName};
+///
+///let services = parse_services("rpc_service Storage { put(Request):Response; ping():Pong; }").unwrap();
+///let config = GenConfig::new().skip_docs(true).type_path(|name: &TypeName| format!("super::{}", name.name())).item(GenItem::Mock);
+///let code = render_services(&services, &config).unwrap();
+///assert!(code.contains("pub struct MockStorageService {"));
+///assert!(code.contains("pub fn return_put(&mut self, response: super::Response) -> &mut Self {"));
+///assert!(code.contains("impl StorageService for MockStorageService {"));
+///assert!(code.contains("None => panic!(\"MockStorageService: unexpected call of `put`, no response is queued\"),"));
+///
+/////Calls are recorded within `Vec`
+///let error = render_services(&services, &config.clone().no_std(true)).unwrap_err();
+///assert_eq!(error, GenError::UnsupportedLibrary { item: "Mock", required: StdLib::Alloc });
+///assert_eq!(error.to_string(), "Mock requires alloc");
+///
+/////Mutex requires std
+///let error = render_services(&services, &config.clone().no_std(true).alloc(true).send_sync(true)).unwrap_err();
+///assert_eq!(error.to_string(), "Mock of shared handler requires std");
+///```
+pub struct RpcServiceMock<'a> {
+    ///Dispatcher, which handler trait is implemented, using its options.
+    pub dispatcher: RpcServiceDispatcher<'a>,
+}
+
+impl<'a> RpcServiceMock<'a> {
+    ///Returns methods of handler trait with variants of call enum.
+    fn names(&self) -> Result<Vec<(HandlerMethod<'a>, String)>, NameCollision> {
+        let variants = unique_names(self.dispatcher.service, self.dispatcher.methods(), "call variant", ident::to_pascal)?;
+        let methods = self.dispatcher.handler_methods("E")?;
+        Ok(methods.into_iter().zip(variants).map(|(method, (_, variant))| (method, variant)).collect())
+    }
+
+    #[inline]
+    ///Returns whether handler is not borrowed mutably, hence mock records calls behind mutex.
+    fn is_shared(&self) -> bool {
+        self.dispatcher.router.is_some() || self.dispatcher.receiver != Receiver::Mut || self.dispatcher.is_send_sync()
+    }
+
+    ///Checks dispatcher, names, streaming methods and library, that mock requires.
+    fn validate(&self) -> Result<(), GenError> {
+        self.dispatcher.validate()?;
+        self.names()?;
+        check_supported_streaming(self.dispatcher.service, self.dispatcher.methods(), |_, streaming| streaming == Streaming::None)?;
+        let (item, required) = match self.dispatcher.router {
+            Some(_) => ("Mock of async router", StdLib::Std),
+            None if self.is_shared() => ("Mock of shared handler", StdLib::Std),
+            None => ("Mock", StdLib::Alloc),
+        };
+        match (self.dispatcher.library, required) {
+            (StdLib::Core, _) | (StdLib::Alloc, StdLib::Std) => Err(GenError::UnsupportedLibrary { item, required }),
+            _ => Ok(()),
+        }
+    }
+
+    ///Generates mock, failing if dispatcher cannot be generated, names of call variants are the same, service has streaming methods
+    ///or library does not allow mock.
+    ///
+    ///Prefer it over `Display`, which panics on [GenError].
+    pub fn render(&self) -> Result<String, GenError> {
+        self.validate()?;
+        Ok(self.to_string())
+    }
+}
+
+impl<'a> fmt::Display for RpcServiceMock<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dispatcher = &self.dispatcher;
+        let service = dispatcher.service;
+        expect_valid(self.validate());
+        let names = expect_valid(self.names());
+        let (vec, boxed, deque) = match dispatcher.library {
+            StdLib::Std => ("Vec", "Box", "std::collections::VecDeque"),
+            StdLib::Alloc | StdLib::Core => ("alloc::vec::Vec", "alloc::boxed::Box", "alloc::collections::VecDeque"),
+        };
+        let is_async = dispatcher.router.is_some();
+        let is_shared = self.is_shared();
+        for (handler_method, _) in names.iter() {
+            let method = handler_method.method;
+            let types = method.arguments.iter().map(|argument| &argument.ty).chain(Some(&method.return_type));
+            assert!(!types.into_iter().any(|ty| dispatcher.is_table(ty)), "Mock does not support tables with lifetime");
+        }
+
+        let call = format!("{}Call", service.name);
+        let handler = dispatcher.handler_name();
+        let mock = format!("Mock{handler}");
+        let (generics, impl_generics) = match dispatcher.return_style {
+            ReturnStyle::Result if is_async || dispatcher.asyncness == Asyncness::AsyncTrait => ("<E>", "<E: Send + 'static>"),
+            ReturnStyle::Result => ("<E>", "<E: 'static>"),
+            _ => ("", ""),
+        };
+        let wrap = |inner: String| match is_shared {
+            true => format!("std::sync::Mutex<{inner}>"),
+            false => inner,
+        };
+        let lock = match is_shared {
+            true => ".lock().unwrap()",
+            false => "",
+        };
+        let (receiver, this) = match is_shared {
+            true => ("&self", "&Self"),
+            false => ("&mut self", "&mut Self"),
+        };
+        let send = match is_async || dispatcher.is_send_sync() {
+            true => " + Send",
+            false => "",
+        };
+        let callback = |method: &HandlerMethod<'_>| match method.request.as_ref() {
+            Some(request) => format!("FnOnce(&{request}) -> {}{send}", method.output),
+            None => format!("FnOnce() -> {}{send}", method.output),
+        };
+
+        //calls
+        fmt.write_str("#[rustfmt::skip]\n#[derive(Debug, Clone, PartialEq)]\n")?;
+        fmt.write_fmt(format_args!("///Call of `{}` method, recorded by `{mock}`\n", service.name))?;
+        fmt.write_fmt(format_args!("pub enum {call} {{\n"))?;
+        for (method, variant) in names.iter() {
+            match method.request.as_ref() {
+                Some(request) => fmt.write_fmt(format_args!("{TAB}///Call of `{}` with its request\n{TAB}{variant}({request}),\n", method.method.name))?,
+                None => fmt.write_fmt(format_args!("{TAB}///Call of `{}`\n{TAB}{variant},\n", method.method.name))?,
+            }
+        }
+        fmt.write_str("}\n\n")?;
+
+        //mock
+        fmt.write_str("#[rustfmt::skip]\n#[allow(clippy::type_complexity)]\n")?;
+        fmt.write_fmt(format_args!("///Mock of `{handler}`, answering calls with queued responses and recording them\n"))?;
+        fmt.write_str("///\n///# Panics\n///\n///Handler method panics, if it is called without queued response.\n")?;
+        fmt.write_fmt(format_args!("pub struct {mock}{generics} {{\n"))?;
+        fmt.write_fmt(format_args!("{TAB}calls: {},\n", wrap(format!("{vec}<{call}>"))))?;
+        for (method, _) in names.iter() {
+            fmt.write_fmt(format_args!("{TAB}expected_{}: {},\n", method.name, wrap(format!("{deque}<{boxed}<dyn {}>>", callback(method)))))?;
+        }
+        if !generics.is_empty() {
+            fmt.write_fmt(format_args!("{TAB}error: core::marker::PhantomData<fn() -> E>,\n"))?;
+        }
+        fmt.write_str("}\n\n")?;
+
+        let new = |ty: &str| match is_shared {
+            true => format!("std::sync::Mutex::new({ty}::new())"),
+            false => format!("{ty}::new()"),
+        };
+        fmt.write_fmt(format_args!("impl{impl_generics} {mock}{generics} {{\n"))?;
+        fmt.write_fmt(format_args!("{TAB}#[inline]\n{TAB}///Creates mock without queued responses\n{TAB}pub fn new() -> Self {{\n{TAB}{TAB}Self {{\n"))?;
+        fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}calls: {},\n", new(vec)))?;
+        for (method, _) in names.iter() {
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}expected_{}: {},\n", method.name, new(deque)))?;
+        }
+        if !generics.is_empty() {
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}error: core::marker::PhantomData,\n"))?;
+        }
+        fmt.write_fmt(format_args!("{TAB}{TAB}}}\n{TAB}}}\n\n"))?;
+        match is_shared {
+            true => fmt.write_fmt(format_args!("{TAB}#[inline]\n{TAB}///Returns copy of calls in order of their occurrence\n{TAB}pub fn calls(&self) -> {vec}<{call}> {{\n{TAB}{TAB}self.calls{lock}.clone()\n{TAB}}}\n"))?,
+            false => fmt.write_fmt(format_args!("{TAB}#[inline]\n{TAB}///Returns calls in order of their occurrence\n{TAB}pub fn calls(&self) -> &[{call}] {{\n{TAB}{TAB}&self.calls\n{TAB}}}\n"))?,
+        }
+        for (handler_method, _) in names.iter() {
+            let HandlerMethod { method, name, request, output, .. } = handler_method;
+            fmt.write_fmt(format_args!("\n{TAB}///Queues `response` to the next call of `{}`\n", method.name))?;
+            fmt.write_fmt(format_args!("{TAB}pub fn expect_{name}<F: {} + 'static>({receiver}, response: F) -> {this} {{\n", callback(handler_method)))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}self.expected_{name}{lock}.push_back({boxed}::new(response));\n{TAB}{TAB}self\n{TAB}}}\n"))?;
+            match request {
+                Some(_) => fmt.write_fmt(format_args!("\n{TAB}///Queues `response` to the next call of `{}`, regardless of its request\n", method.name))?,
+                None => fmt.write_fmt(format_args!("\n{TAB}///Queues `response` to the next call of `{}`\n", method.name))?,
+            }
+            fmt.write_fmt(format_args!("{TAB}pub fn return_{name}({receiver}, response: {output}) -> {this} {{\n"))?;
+            match request {
+                Some(_) => fmt.write_fmt(format_args!("{TAB}{TAB}self.expect_{name}(move |_| response)\n{TAB}}}\n"))?,
+                None => fmt.write_fmt(format_args!("{TAB}{TAB}self.expect_{name}(move || response)\n{TAB}}}\n"))?,
+            }
+        }
+        fmt.write_str("}\n\n")?;
+
+        fmt.write_fmt(format_args!("impl{impl_generics} Default for {mock}{generics} {{\n"))?;
+        fmt.write_fmt(format_args!("{TAB}#[inline(always)]\n{TAB}fn default() -> Self {{\n{TAB}{TAB}Self::new()\n{TAB}}}\n}}\n\n"))?;
+
+        //handler
+        let (ctx_generic, handler, ctx_arg) = dispatcher.handler_context("_ctx");
+        let impl_generics = match (ctx_generic, impl_generics) {
+            ("", impl_generics) => impl_generics.to_owned(),
+            (ctx, "") => format!("<{}>", ctx.trim_end_matches(", ")),
+            (ctx, impl_generics) => format!("<{ctx}{}", &impl_generics[1..]),
+        };
+        dispatcher.asyncness.write_attribute(fmt, "")?;
+        fmt.write_fmt(format_args!("impl{impl_generics} {handler} for {mock}{generics} {{\n"))?;
+        if let Some(ContextStyle::AssociatedType) = dispatcher.context {
+            fmt.write_fmt(format_args!("{TAB}type Context = ();\n\n"))?;
+        }
+        if let ReturnStyle::Result = dispatcher.return_style {
+            fmt.write_fmt(format_args!("{TAB}type Error = E;\n\n"))?;
+        }
+        for (idx, (handler_method, variant)) in names.iter().enumerate() {
+            let HandlerMethod { method, name, request, .. } = handler_method;
+            if idx > 0 {
+                fmt.write_str("\n")?;
+            }
+            handler_method.write_signature(fmt, dispatcher, &ctx_arg)?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}let response = self.expected_{name}{lock}.pop_front();\n"))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}let response = match response {{\n"))?;
+            match request {
+                Some(_) => fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}Some(response) => response(&request),\n"))?,
+                None => fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}Some(response) => response(),\n"))?,
+            }
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}None => panic!(\"{mock}: unexpected call of `{}`, no response is queued\"),\n{TAB}{TAB}}};\n", method.name))?;
+            match request {
+                Some(_) => fmt.write_fmt(format_args!("{TAB}{TAB}self.calls{lock}.push({call}::{variant}(request));\n"))?,
+                None => fmt.write_fmt(format_args!("{TAB}{TAB}self.calls{lock}.push({call}::{variant});\n"))?,
+            }
+            match is_async {
+                true => fmt.write_fmt(format_args!("{TAB}{TAB}{boxed}::pin(core::future::ready(response))\n{TAB}}}\n"))?,
+                false => fmt.write_fmt(format_args!("{TAB}{TAB}response\n{TAB}}}\n"))?,
+            }
+        }
+        fmt.write_str("}")
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///Generates `RpcObserver` trait, reported to by every handler of [RpcServiceInstrumentation] with [InstrumentStyle::Observer].
+///
+///It is to be generated once per output.
+///
+///```rust,ignore
+///pub trait RpcObserver {
+///    fn on_call_start(&self, method: &'static str);
+///    fn on_call_end(&self, method: &'static str, elapsed: core::time::Duration, is_ok: bool);
+///}
+///```
+pub struct RpcObserverTrait;
+
+impl fmt::Display for RpcObserverTrait {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("#[rustfmt::skip]\n")?;
+        fmt.write_str("///Observer of calls of instrumented handlers\n")?;
+        fmt.write_str("pub trait RpcObserver {\n")?;
+        fmt.write_fmt(format_args!("{TAB}///Reports start of `method` call, with method name as declared within schema\n"))?;
+        fmt.write_fmt(format_args!("{TAB}fn on_call_start(&self, method: &'static str);\n"))?;
+        fmt.write_fmt(format_args!("{TAB}///Reports end of `method` call after `elapsed` time, with `is_ok` being `false` if handler returned error\n"))?;
+        fmt.write_fmt(format_args!("{TAB}fn on_call_end(&self, method: &'static str, elapsed: core::time::Duration, is_ok: bool);\n"))?;
+        fmt.write_str("}")
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+///Reporting of calls by [RpcServiceInstrumentation]
+pub enum InstrumentStyle {
+    #[default]
+    ///Calls are reported to `RpcObserver`, generated by [RpcObserverTrait], and measured with `std::time::Instant`.
+    Observer,
+    ///Handler methods are annotated with `#[tracing::instrument]`, which requires `tracing` crate.
+    ///
+    ///Span is named after method, as declared within schema, and errors are recorded with `Debug`, if handler returns `Result`.
+    Tracing,
+}
+
+#[derive(Copy, Clone)]
+///Generates wrapper of handler, generated by `dispatcher`, implementing handler trait by instrumenting every call of wrapped handler.
+///
+///For service `Storage` it generates `InstrumentedStorageService`, implementing `StorageService`:
+///
+///```rust,ignore
+///pub struct InstrumentedStorageService<S, O> {
+///    pub inner: S,
+///    pub observer: O,
+///}
+///
+///impl<S, O> InstrumentedStorageService<S, O> {
+///    pub const fn new(inner: S, observer: O) -> Self;
+///}
+///
+///impl<S: StorageService, O: RpcObserver> StorageService for InstrumentedStorageService<S, O>;
+///```
+///
+///Every call is reported to observer with name of the method, as declared within schema, before and after `inner` handles it.
+///Call is reported to be failed, if handler returns `Err` with [ReturnStyle::Result] or [ReturnStyle::ResultWith].
+///
+///With [AsyncRouter] call ends, once future of `inner` is ready, and observer must be `Send + Sync`.
+///Call of method with stream of responses ends, once `inner` returns the stream, regardless of whether it is consumed.
+///With [InstrumentStyle::Tracing] there is no observer, and wrapper is `InstrumentedStorageService<S>`.
+///
+///Context and error type of `inner` are forwarded.
+///With [Receiver::Arc] wrapper holds `inner: Arc<S>`, so that wrapped handler is called with its clone.
+///Observer requires `std`, which is otherwise reported as [GenError::UnsupportedLibrary], while tracing supports any library.
+///
+///```rust
+///use flatbuffers_tools::{render_services, parse_services, GenConfig, GenItem, ReturnStyle, TypeName};
+///
+///let services = parse_services("rpc_service Storage { put(Request):Response; ping():Pong; }").unwrap();
+///let config = GenConfig::new().skip_docs(true)
+///                             .return_style(ReturnStyle::Result)
+///                             .type_path(|name: &TypeName| format!("super::{}", name.name()))
+///                             .item(GenItem::Instrumentation);
+///let code = render_services(&services, &config).unwrap();
+///assert!(code.contains("pub trait RpcObserver {"));
+///assert!(code.contains("impl<S: StorageService, O: RpcObserver> StorageService for InstrumentedStorageService<S, O> {"));
+///
+///let error = render_services(&services, &config.no_std(true).alloc(true)).unwrap_err();
+///assert_eq!(error.to_string(), "Instrumentation with observer requires std");
+///```
+pub struct RpcServiceInstrumentation<'a> {
+    ///Dispatcher, which handler trait is implemented, using its options.
+    pub dispatcher: RpcServiceDispatcher<'a>,
+    ///Reporting of calls, [InstrumentStyle::Observer] by default.
+    pub style: InstrumentStyle,
+}
+
+impl<'a> RpcServiceInstrumentation<'a> {
+    #[inline]
+    ///Sets reporting of calls.
+    pub const fn style(mut self, style: InstrumentStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    ///Checks dispatcher and that library supports observer.
+    fn validate(&self) -> Result<(), GenError> {
+        self.dispatcher.validate()?;
+        match (self.style, self.dispatcher.library) {
+            (InstrumentStyle::Observer, StdLib::Alloc | StdLib::Core) => Err(GenError::UnsupportedLibrary { item: "Instrumentation with observer", required: StdLib::Std }),
+            (InstrumentStyle::Observer, StdLib::Std) | (InstrumentStyle::Tracing, _) => Ok(()),
+        }
+    }
+
+    ///Generates wrapper, failing if dispatcher cannot be generated or observer is used without `std`.
+    ///
+    ///Prefer it over `Display`, which panics on [GenError].
+    pub fn render(&self) -> Result<String, GenError> {
+        self.validate()?;
+        Ok(self.to_string())
+    }
+}
+
+impl<'a> fmt::Display for RpcServiceInstrumentation<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dispatcher = &self.dispatcher;
+        let policy = dispatcher.defines.deprecated_policy;
+        expect_valid(self.validate());
+        let methods = expect_valid(dispatcher.handler_methods("Self::Error"));
+        let is_async = dispatcher.router.is_some();
+        let is_tracing = self.style == InstrumentStyle::Tracing;
+        //Async router requires alloc, hence box is not used without it
+        let boxed = match dispatcher.library {
+            StdLib::Std => "Box",
+            StdLib::Alloc | StdLib::Core => "alloc::boxed::Box",
+        };
+
+        let handler = dispatcher.handler_name();
+        let wrapper = format!("Instrumented{handler}");
+        let (generics, observer_bound) = match (is_tracing, is_async || dispatcher.is_send_sync()) {
+            (true, _) => ("<S>", ""),
+            (false, true) => ("<S, O>", ", O: RpcObserver + Send + Sync"),
+            (false, false) => ("<S, O>", ", O: RpcObserver"),
+        };
+        //Receiver of wrapper cannot be passed to methods of `inner`, hence it is shared
+        let (inner, call_inner) = match (dispatcher.receiver_of(), dispatcher.library) {
+            (Receiver::Arc, StdLib::Std) => ("std::sync::Arc<S>", "std::sync::Arc::clone(&self.inner)"),
+            (Receiver::Arc, _) => ("alloc::sync::Arc<S>", "alloc::sync::Arc::clone(&self.inner)"),
+            (_, _) => ("S", "self.inner"),
+        };
+
+        //wrapper
+        fmt.write_str("#[rustfmt::skip]\n")?;
+        match is_tracing {
+            true => fmt.write_fmt(format_args!("///`{handler}`, tracing every call of `inner`\n"))?,
+            false => fmt.write_fmt(format_args!("///`{handler}`, reporting every call of `inner` to `observer`\n"))?,
+        }
+        fmt.write_fmt(format_args!("pub struct {wrapper}{generics} {{\n{TAB}///Handler of calls\n{TAB}pub inner: {inner},\n"))?;
+        if !is_tracing {
+            fmt.write_fmt(format_args!("{TAB}///Observer of calls\n{TAB}pub observer: O,\n"))?;
+        }
+        fmt.write_str("}\n\n")?;
+
+        fmt.write_fmt(format_args!("impl{generics} {wrapper}{generics} {{\n{TAB}#[inline]\n"))?;
+        match is_tracing {
+            true => fmt.write_fmt(format_args!("{TAB}///Creates wrapper of `inner`\n{TAB}pub const fn new(inner: {inner}) -> Self {{\n{TAB}{TAB}Self {{\n{TAB}{TAB}{TAB}inner,\n"))?,
+            false => fmt.write_fmt(format_args!("{TAB}///Creates wrapper of `inner`, reporting to `observer`\n{TAB}pub const fn new(inner: {inner}, observer: O) -> Self {{\n{TAB}{TAB}Self {{\n{TAB}{TAB}{TAB}inner,\n{TAB}{TAB}{TAB}observer,\n"))?,
+        }
+        fmt.write_fmt(format_args!("{TAB}{TAB}}}\n{TAB}}}\n}}\n\n"))?;
+
+        //handler
+        let (ctx_generic, handler, ctx_arg) = dispatcher.handler_context("ctx");
+        let ctx = match dispatcher.context {
+            Some(_) => "ctx, ",
+            None => "",
+        };
+        dispatcher.asyncness.write_attribute(fmt, "")?;
+        fmt.write_fmt(format_args!("impl<{ctx_generic}S: {handler}{observer_bound}> {handler} for {wrapper}{generics} {{\n"))?;
+        if let Some(ContextStyle::AssociatedType) = dispatcher.context {
+            fmt.write_fmt(format_args!("{TAB}type Context = S::Context;\n\n"))?;
+        }
+        if let ReturnStyle::Result = dispatcher.return_style {
+            fmt.write_fmt(format_args!("{TAB}type Error = S::Error;\n\n"))?;
+        }
+        let is_ok = match dispatcher.return_style {
+            ReturnStyle::Plain => "true",
+            _ => "response.is_ok()",
+        };
+        for (idx, handler_method) in methods.iter().enumerate() {
+            let HandlerMethod { method, name, is_future, .. } = handler_method;
+            let is_future = *is_future;
+            if idx > 0 {
+                fmt.write_str("\n")?;
+            }
+            if policy.is_annotated(method) {
+                fmt.write_fmt(format_args!("{TAB}#[allow(deprecated)]\n"))?;
+            }
+            if is_tracing {
+                match dispatcher.return_style {
+                    ReturnStyle::Plain => fmt.write_fmt(format_args!("{TAB}#[tracing::instrument(name = \"{}\", skip_all)]\n", method.name))?,
+                    _ => fmt.write_fmt(format_args!("{TAB}#[tracing::instrument(name = \"{}\", skip_all, err(Debug))]\n", method.name))?,
+                }
+            }
+            handler_method.write_signature(fmt, dispatcher, &ctx_arg)?;
+
+            let request = handler_method.request_name().unwrap_or("");
+            let call = match dispatcher.asyncness.is_async() {
+                true => format!("{call_inner}.{}({ctx}{request}).await", Ident(name)),
+                false => format!("{call_inner}.{}({ctx}{request})", Ident(name)),
+            };
+            match (is_tracing, is_future) {
+                //Boxed async block is instrumented as async function
+                (true, true) => fmt.write_fmt(format_args!("{TAB}{TAB}{boxed}::pin(async move {{ {call}.await }})\n"))?,
+                (true, false) => fmt.write_fmt(format_args!("{TAB}{TAB}{call}\n"))?,
+                (false, is_future) => {
+                    fmt.write_fmt(format_args!("{TAB}{TAB}self.observer.on_call_start(\"{}\");\n", method.name))?;
+                    fmt.write_fmt(format_args!("{TAB}{TAB}let start = std::time::Instant::now();\n"))?;
+                    fmt.write_fmt(format_args!("{TAB}{TAB}let response = {call};\n"))?;
+                    match is_future {
+                        true => {
+                            fmt.write_fmt(format_args!("{TAB}{TAB}{boxed}::pin(async move {{\n{TAB}{TAB}{TAB}let response = response.await;\n"))?;
+                            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}self.observer.on_call_end(\"{}\", start.elapsed(), {is_ok});\n", method.name))?;
+                            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}response\n{TAB}{TAB}}})\n"))?;
+                        },
+                        false => {
+                            fmt.write_fmt(format_args!("{TAB}{TAB}self.observer.on_call_end(\"{}\", start.elapsed(), {is_ok});\n", method.name))?;
+                            fmt.write_fmt(format_args!("{TAB}{TAB}response\n"))?;
+                        },
+                    }
+                },
+            }
+            fmt.write_fmt(format_args!("{TAB}}}\n"))?;
+        }
+        fmt.write_str("}")
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+///Aliases of arguments of methods with multiple arguments
+pub enum ArgumentAliases {
+    #[default]
+    ///Single alias of tuple of arguments, e.g. `pub type PutRequest = (Key, Value);`
+    Tuple,
+    ///Alias per argument, suffixed with its index, e.g. `pub type PutRequest0 = Key;`
+    PerArgument,
+}
+
+///Returns names of request aliases of `methods` of `service`, ensuring there are no duplicates.
+///
+///Name of response alias is the same, but with `Response` suffix instead of `Request`.
+fn alias_names<'a, I: Iterator<Item=&'a RpcMethod>>(service: &RpcService, methods: I) -> Result<Vec<(&'a RpcMethod, String)>, NameCollision> {
+    unique_names(service, methods, "type alias", |name| format!("{}Request", ident::to_pascal(name)))
+}
+
+#[derive(Copy, Clone)]
+///Generates type aliases of requests and responses of every method.
+///
+///E.g. for method `put(Request):Response` it generates:
+///
+///```rust,ignore
+///pub type PutRequest = crate::interface::Request;
+///pub type PutResponse = crate::interface::Response;
+///```
+///
+///Request of method without arguments is `()`.
+///
+///Handler trait refers to aliases, when [RpcServiceDispatcher::aliases] is set.
+pub struct RpcTypeAliases<'a> {
+    ///Service definition
+    pub service: &'a RpcService,
+    ///Callback to map type of argument or return type to Rust path.
+    ///
+    ///By default types are expected within `crate::interface`, same as in [RpcServiceDispatcher].
+    pub type_path: Option<&'a dyn Fn(&TypeName) -> String>,
+    ///Visibility of aliases, `pub` by default.
+    pub visibility: Visibility,
+    ///Aliases of methods with multiple arguments, tuple by default.
+    pub arguments: ArgumentAliases,
+    ///Lifetime of aliased types, if any, e.g. `'a` for flatbuffers tables.
+    ///
+    ///When specified, aliases are generic over it, e.g. `pub type PutRequest<'a> = crate::interface::Request<'a>;`
+    pub lifetime: Option<&'a str>,
+}
+
+impl<'a> RpcTypeAliases<'a> {
+    #[inline]
+    ///Sets callback to map schema types to Rust paths.
+    pub const fn type_path(mut self, type_path: &'a dyn Fn(&TypeName) -> String) -> Self {
+        self.type_path = Some(type_path);
+        self
+    }
+
+    #[inline]
+    ///Sets visibility of aliases.
+    pub const fn visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    #[inline]
+    ///Sets aliases of methods with multiple arguments.
+    pub const fn arguments(mut self, arguments: ArgumentAliases) -> Self {
+        self.arguments = arguments;
+        self
+    }
+
+    #[inline]
+    ///Sets lifetime of aliased types.
+    pub const fn lifetime(mut self, lifetime: &'a str) -> Self {
+        self.lifetime = Some(lifetime);
+        self
+    }
+
+    ///Generates aliases, failing if their names are the same.
+    ///
+    ///Prefer it over `Display`, which panics on [GenError].
+    pub fn render(&self) -> Result<String, GenError> {
+        alias_names(self.service, self.service.methods.iter())?;
+        Ok(self.to_string())
+    }
+
+    ///Returns Rust path of schema type `ty`, with lifetime if any.
+    fn path(&self, ty: &TypeRef) -> String {
+        let path = map_type(self.type_path, self.service, ty);
+        match self.lifetime {
+            Some(lifetime) => format!("{path}<{lifetime}>"),
+            None => path,
+        }
+    }
+
+    ///Writes alias `name` of `ty`
+    fn write_alias(&self, fmt: &mut fmt::Formatter<'_>, name: &str, ty: &str, docs: fmt::Arguments<'_>) -> fmt::Result {
+        let visibility = self.visibility;
+        fmt.write_fmt(format_args!("///{docs}\n"))?;
+        match self.lifetime {
+            Some(lifetime) if ty != "()" => fmt.write_fmt(format_args!("{visibility}type {name}<{lifetime}> = {ty};\n")),
+            _ => fmt.write_fmt(format_args!("{visibility}type {name} = {ty};\n")),
+        }
+    }
+}
+
+impl<'a> fmt::Display for RpcTypeAliases<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names = expect_valid(alias_names(self.service, self.service.methods.iter()));
+
+        for (idx, (method, request)) in names.iter().enumerate() {
+            let response = format!("{}Response", ident::to_pascal(&method.name));
+            if idx > 0 {
+                fmt.write_str("\n")?;
+            }
+            match (method.arguments.as_slice(), self.arguments) {
+                ([], _) => self.write_alias(fmt, request, "()", format_args!("Request of `{}`", method.name))?,
+                ([argument], _) => self.write_alias(fmt, request, &self.path(&argument.ty), format_args!("Request of `{}`", method.name))?,
+                (arguments, ArgumentAliases::Tuple) => {
+                    let types: Vec<String> = arguments.iter().map(|argument| self.path(&argument.ty)).collect();
+                    self.write_alias(fmt, request, &format!("({})", types.join(", ")), format_args!("Request of `{}`", method.name))?;
+                },
+                (arguments, ArgumentAliases::PerArgument) => for (idx, argument) in arguments.iter().enumerate() {
+                    self.write_alias(fmt, &format!("{request}{idx}"), &self.path(&argument.ty), format_args!("Argument {idx} of `{}`", method.name))?;
+                },
+            }
+            self.write_alias(fmt, &response, &self.path(&method.return_type), format_args!("Response of `{}`", method.name))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///Generates `Method` trait, implemented by every marker of [RpcMethodMarkers].
+///
+///It is to be generated once per output, with the same method id type, as markers use.
+///
+///```rust,ignore
+///pub trait Method {
+///    const ID: u16;
+///    const NAME: &'static str;
+///    type Request;
+///    type Response;
+///}
+///```
+///
+///Alternatively markers can implement trait of this crate, refer to [RpcMethodMarkers::trait_path].
+pub struct RpcMethodTrait {
+    ///Type of method id, or `None` if method is identified by its name.
+    pub id_type: Option<IntType>,
+}
+
+impl fmt::Display for RpcMethodTrait {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let id_type = match self.id_type {
+            Some(id_type) => id_type.to_string(),
+            None => "&'static str".to_owned(),
+        };
+
+        fmt.write_str("#[rustfmt::skip]\n")?;
+        fmt.write_str("///RPC method, identified by marker type\n")?;
+        fmt.write_str("pub trait Method {\n")?;
+        fmt.write_fmt(format_args!("{TAB}///Method id\n{TAB}const ID: {id_type};\n"))?;
+        fmt.write_fmt(format_args!("{TAB}///Method name, as declared within schema\n{TAB}const NAME: &'static str;\n"))?;
+        fmt.write_fmt(format_args!("{TAB}///Type of request, `()` if method has no arguments\n{TAB}type Request;\n"))?;
+        fmt.write_fmt(format_args!("{TAB}///Type of response\n{TAB}type Response;\n"))?;
+        fmt.write_str("}")
+    }
+}
+
+///Names of shared items, generated by other formatters, that cannot be used as names of markers.
+const MARKER_RESERVED_NAMES: [&str; 14] = [
+    "Method", "Codec", "DispatchError", "Transport", "ClientError", "ServiceDescriptor", "MethodDescriptor", "MethodPriority",
+    "Option", "Some", "None", "Result", "Ok", "Err",
+];
+
+#[derive(Copy, Clone)]
+///Generates zero-sized marker type per method, implementing `Method` trait.
+///
+///E.g. for method `put(Request):Response` it generates:
+///
+///```rust,ignore
+///pub struct Put;
+///
+///impl Method for Put {
+///    const ID: u16 = PUT;
+///    const NAME: &'static str = "put";
+///    type Request = crate::interface::Request;
+///    type Response = crate::interface::Response;
+///}
+///```
+///
+///Marker names are method names in upper camel case, same as variants of [RpcMethodEnum].
+///Request of method without arguments is `()`, while multiple arguments are tuple.
+///
+///Markers refer to method constants, generated by `defines`, and to the trait, generated by [RpcMethodTrait], which are expected in the same module.
+pub struct RpcMethodMarkers<'a> {
+    ///Service definition
+    pub service: &'a RpcService,
+    ///Method defines, which constants are used as method ids.
+    pub defines: RpcMethodDefines<'a>,
+    ///Callback to map type of argument or return type to Rust path.
+    ///
+    ///By default types are expected within `crate::interface`, same as in [RpcServiceDispatcher].
+    pub type_path: Option<&'a dyn Fn(&TypeName) -> String>,
+    ///Visibility of markers, `pub` by default.
+    pub visibility: Visibility,
+    ///Path of implemented trait, if it is not generated by [RpcMethodTrait].
+    ///
+    ///With `method-trait` feature this crate provides `flatbuffers_tools::Method` trait, which requires `u16` method ids.
+    pub trait_path: Option<&'a str>,
+    ///Lifetime argument of mapped types, if any, e.g. `'static` for flatbuffers tables.
+    pub lifetime: Option<&'a str>,
+    ///Specifies whether documentation of methods is to be omitted.
+    ///
+    ///Otherwise it is placed above markers.
+    pub skip_docs: bool,
+}
+
+impl<'a> RpcMethodMarkers<'a> {
+    #[inline]
+    ///Sets method defines, used as method ids.
+    pub const fn defines(mut self, defines: RpcMethodDefines<'a>) -> Self {
+        self.defines = defines;
+        self
+    }
+
+    #[inline]
+    ///Sets callback to map schema types to Rust paths.
+    pub const fn type_path(mut self, type_path: &'a dyn Fn(&TypeName) -> String) -> Self {
+        self.type_path = Some(type_path);
+        self
+    }
+
+    #[inline]
+    ///Sets visibility of markers.
+    pub const fn visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    #[inline]
+    ///Sets path of implemented trait, e.g. `flatbuffers_tools::Method`.
+    pub const fn trait_path(mut self, trait_path: &'a str) -> Self {
+        self.trait_path = Some(trait_path);
+        self
+    }
+
+    #[inline]
+    ///Sets lifetime argument of mapped types.
+    pub const fn lifetime(mut self, lifetime: &'a str) -> Self {
+        self.lifetime = Some(lifetime);
+        self
+    }
+
+    #[inline]
+    ///Sets whether documentation of methods is to be omitted.
+    pub const fn skip_docs(mut self, skip_docs: bool) -> Self {
+        self.skip_docs = skip_docs;
+        self
+    }
+
+    #[inline]
+    ///Gets formatter of `Method` trait, matching method id type of these markers.
+    pub fn method_trait(&self) -> RpcMethodTrait {
+        RpcMethodTrait {
+            id_type: self.defines.id_type(),
+        }
+    }
+
+    ///Methods, that are included according to deprecated policy
+    fn methods(&self) -> impl Iterator<Item=&'a RpcMethod> {
+        let policy = self.defines.deprecated_policy;
+        self.service.methods.iter().filter(move |method| policy.is_included(method))
+    }
+
+    ///Returns names of markers, ensuring they do not clash with each other or other generated items.
+    fn names(&self) -> Result<Vec<(&'a RpcMethod, String)>, GenError> {
+        let names = unique_names(self.service, self.methods(), "marker name", ident::to_pascal)?;
+        let service = &self.service.name;
+        let items = [format!("{service}Service"), format!("{service}Client"), format!("{service}Method"), format!("Unknown{service}Method")];
+        for (method, name) in names.iter() {
+            if MARKER_RESERVED_NAMES.contains(&name.as_str()) || items.contains(name) {
+                return Err(GenError::ReservedName {
+                    service: service.clone(),
+                    method: method.name.clone(),
+                    name: name.clone(),
+                });
+            }
+            //Type aliases of other methods
+            for other in self.service.methods.iter() {
+                let request = format!("{}Request", ident::to_pascal(&other.name));
+                let is_alias = *name == request || *name == format!("{}Response", ident::to_pascal(&other.name)) || match other.arguments.len() {
+                    0 | 1 => false,
+                    len => (0..len).any(|idx| *name == format!("{request}{idx}")),
+                };
+                if is_alias {
+                    return Err(NameCollision {
+                        service: service.clone(),
+                        first: other.name.clone(),
+                        second: method.name.clone(),
+                        kind: "type name",
+                        name: name.clone(),
+                    }.into());
+                }
+            }
+        }
+        Ok(names)
+    }
+
+    ///Generates markers, failing if method ids cannot be assigned or names of markers clash.
+    ///
+    ///Prefer it over `Display`, which panics on [GenError].
+    pub fn render(&self) -> Result<String, GenError> {
+        self.defines.validate()?;
+        self.names()?;
+        Ok(self.to_string())
+    }
+
+    ///Returns Rust path of schema type `ty`, with lifetime if any.
+    fn path(&self, ty: &TypeRef) -> String {
+        let path = map_type(self.type_path, self.service, ty);
+        match self.lifetime {
+            Some(lifetime) => format!("{path}<{lifetime}>"),
+            None => path,
+        }
+    }
+}
+
+impl<'a> fmt::Display for RpcMethodMarkers<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        expect_valid(self.defines.validate());
+        let names = expect_valid(self.names());
+        let visibility = self.visibility;
+        let prefix = self.defines.names_prefix();
+        let policy = self.defines.deprecated_policy;
+        let trait_path = self.trait_path.unwrap_or("Method");
+        let id_type = match self.defines.id_type() {
+            Some(id_type) => id_type.to_string(),
+            None => "&'static str".to_owned(),
+        };
+
+        for (idx, (method, name)) in names.iter().enumerate() {
+            if idx > 0 {
+                fmt.write_str("\n")?;
+            }
+            let name = Ident(name);
+            let id = format!("{prefix}{}", ident::to_screaming_snake(&method.name));
+            let request = match method.arguments.as_slice() {
+                [] => "()".to_owned(),
+                [argument] => self.path(&argument.ty),
+                arguments => {
+                    let types: Vec<String> = arguments.iter().map(|argument| self.path(&argument.ty)).collect();
+                    format!("({})", types.join(", "))
+                },
+            };
+            let is_deprecated = policy.is_annotated(method);
+
+            write_docs(fmt, "", &method.docs, self.skip_docs, format_args!("Marker of `{}`", method.name))?;
+            write_category(fmt, self.defines.category_attributes, ItemCategory::Markers)?;
+            if is_deprecated {
+                fmt.write_str("#[deprecated]\n")?;
+            }
+            fmt.write_fmt(format_args!("#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]\n{visibility}struct {name};\n\n"))?;
+            if is_deprecated {
+                fmt.write_str("#[allow(deprecated)]\n")?;
+            }
+            fmt.write_fmt(format_args!("impl {trait_path} for {name} {{\n"))?;
+            fmt.write_fmt(format_args!("{TAB}const ID: {id_type} = {id};\n"))?;
+            fmt.write_fmt(format_args!("{TAB}const NAME: &'static str = \"{}\";\n", method.name))?;
+            fmt.write_fmt(format_args!("{TAB}type Request = {request};\n"))?;
+            fmt.write_fmt(format_args!("{TAB}type Response = {};\n}}\n", self.path(&method.return_type)))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone)]
+///Generates types of service descriptors, generated by [RpcServiceDescriptor].
+///
+///It is to be generated once per output, with the same method id type, as descriptors use.
+///
+///```rust,ignore
+///pub struct ServiceDescriptor {
+///    pub name: &'static str,
+///    pub namespace: Option<&'static str>,
+///    pub attributes: &'static [(&'static str, Option<&'static str>)],
+///    pub methods: &'static [MethodDescriptor],
+///}
+///
+///pub struct MethodDescriptor {
+///    pub name: &'static str,
+///    pub id: u16,
+///    pub arguments: &'static [&'static str],
+///    pub response: &'static str,
+///    pub attributes: &'static [(&'static str, Option<&'static str>)],
+///    pub timeout_ms: Option<u64>,
+///    pub priority: Option<MethodPriority>,
+///}
+///
+///pub enum MethodPriority {
+///    Low,
+///    Normal,
+///    High,
+///}
+///```
+pub struct RpcDescriptorTypes {
+    ///Type of method id.
+    pub id_type: IntType,
+}
+
+impl fmt::Display for RpcDescriptorTypes {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let id_type = self.id_type;
+
+        fmt.write_str("#[rustfmt::skip]\n")?;
+        fmt.write_str("#[derive(Debug, Clone, Copy)]\n")?;
+        fmt.write_str("///Descriptor of RPC service\n")?;
+        fmt.write_str("pub struct ServiceDescriptor {\n")?;
+        fmt.write_fmt(format_args!("{TAB}///Service name\n{TAB}pub name: &'static str,\n"))?;
+        fmt.write_fmt(format_args!("{TAB}///Namespace in effect at the definition\n{TAB}pub namespace: Option<&'static str>,\n"))?;
+        fmt.write_fmt(format_args!("{TAB}///Attributes, as pairs of key and optional value\n{TAB}pub attributes: &'static [(&'static str, Option<&'static str>)],\n"))?;
+        fmt.write_fmt(format_args!("{TAB}///Service methods\n{TAB}pub methods: &'static [MethodDescriptor],\n"))?;
+        fmt.write_str("}\n\n")?;
+
+        fmt.write_str("impl ServiceDescriptor {\n")?;
+        fmt.write_fmt(format_args!("{TAB}///Returns method with specified `id`, if any.\n"))?;
+        fmt.write_fmt(format_args!("{TAB}pub fn method(&self, id: {id_type}) -> Option<&'static MethodDescriptor> {{\n"))?;
+        fmt.write_fmt(format_args!("{TAB}{TAB}self.methods.iter().find(|method| method.id == id)\n{TAB}}}\n\n"))?;
+        fmt.write_fmt(format_args!("{TAB}///Returns method with specified `name`, if any.\n"))?;
+        fmt.write_fmt(format_args!("{TAB}pub fn method_by_name(&self, name: &str) -> Option<&'static MethodDescriptor> {{\n"))?;
+        fmt.write_fmt(format_args!("{TAB}{TAB}self.methods.iter().find(|method| method.name == name)\n{TAB}}}\n"))?;
+        fmt.write_str("}\n\n")?;
+
+        fmt.write_str("#[rustfmt::skip]\n")?;
+        fmt.write_str("#[derive(Debug, Clone, Copy)]\n")?;
+        fmt.write_str("///Descriptor of RPC method\n")?;
+        fmt.write_str("pub struct MethodDescriptor {\n")?;
+        fmt.write_fmt(format_args!("{TAB}///Method name, as declared within schema\n{TAB}pub name: &'static str,\n"))?;
+        fmt.write_fmt(format_args!("{TAB}///Method id\n{TAB}pub id: {id_type},\n"))?;
+        fmt.write_fmt(format_args!("{TAB}///Fully qualified argument types\n{TAB}pub arguments: &'static [&'static str],\n"))?;
+        fmt.write_fmt(format_args!("{TAB}///Fully qualified return type\n{TAB}pub response: &'static str,\n"))?;
+        fmt.write_fmt(format_args!("{TAB}///Attributes, as pairs of key and optional value\n{TAB}pub attributes: &'static [(&'static str, Option<&'static str>)],\n"))?;
+        fmt.write_fmt(format_args!("{TAB}///Timeout in milliseconds, specified by `timeout_ms` attribute\n{TAB}pub timeout_ms: Option<u64>,\n"))?;
+        fmt.write_fmt(format_args!("{TAB}///Priority, specified by `priority` attribute\n{TAB}pub priority: Option<MethodPriority>,\n"))?;
+        fmt.write_str("}\n\n")?;
+
+        fmt.write_str("#[rustfmt::skip]\n")?;
+        fmt.write_str("#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]\n")?;
+        fmt.write_str("///Priority of RPC method\n")?;
+        fmt.write_str("pub enum MethodPriority {\n")?;
+        for priority in [Priority::Low, Priority::Normal, Priority::High] {
+            fmt.write_fmt(format_args!("{TAB}///Value `{}`\n{TAB}{priority:?},\n", priority.as_value()))?;
+        }
+        fmt.write_str("}\n\n")?;
+
+        fmt.write_str("impl MethodDescriptor {\n")?;
+        fmt.write_fmt(format_args!("{TAB}///Returns value of attribute `key`, which is `Some(None)` if attribute has no value.\n"))?;
+        fmt.write_fmt(format_args!("{TAB}pub fn attribute(&self, key: &str) -> Option<Option<&'static str>> {{\n"))?;
+        fmt.write_fmt(format_args!("{TAB}{TAB}self.attributes.iter().find(|attribute| attribute.0 == key).map(|attribute| attribute.1)\n{TAB}}}\n\n"))?;
+        fmt.write_fmt(format_args!("{TAB}#[inline]\n{TAB}///Returns value of `streaming` attribute, if any.\n"))?;
+        fmt.write_fmt(format_args!("{TAB}pub fn streaming(&self) -> Option<&'static str> {{\n"))?;
+        fmt.write_fmt(format_args!("{TAB}{TAB}self.attribute(\"streaming\").flatten()\n{TAB}}}\n\n"))?;
+        fmt.write_fmt(format_args!("{TAB}#[inline]\n{TAB}///Returns whether method has `deprecated` attribute.\n"))?;
+        fmt.write_fmt(format_args!("{TAB}pub fn is_deprecated(&self) -> bool {{\n"))?;
+        fmt.write_fmt(format_args!("{TAB}{TAB}self.attribute(\"deprecated\").is_some()\n{TAB}}}\n"))?;
+        fmt.write_str("}")
+    }
+}
+
+///Writes attributes as slice of static pairs
+fn write_attributes(fmt: &mut fmt::Formatter<'_>, attributes: &[crate::Attribute]) -> fmt::Result {
+    fmt.write_str("&[")?;
+    for (idx, (key, value)) in attributes.iter().enumerate() {
+        if idx > 0 {
+            fmt.write_str(", ")?;
+        }
+        match value {
+            Some(value) => fmt.write_fmt(format_args!("({key:?}, Some({value:?}))"))?,
+            None => fmt.write_fmt(format_args!("({key:?}, None)"))?,
+        }
+    }
+    fmt.write_str("]")
+}
+
+#[derive(Copy, Clone)]
+///Generates static descriptor of the service, describing its methods.
+///
+///Descriptor types are generated by [RpcDescriptorTypes], and expected to be in scope.
+///
+///```rust,ignore
+///pub static SERVICE: ServiceDescriptor = ServiceDescriptor {
+///    name: "Storage",
+///    namespace: None,
+///    attributes: &[],
+///    methods: &[
+///        MethodDescriptor {
+///            name: "Put",
+///            id: 0,
+///            arguments: &["Request"],
+///            response: "Response",
+///            attributes: &[("streaming", Some("server")), ("priority", Some("high"))],
+///            timeout_ms: None,
+///            priority: Some(MethodPriority::High),
+///        },
+///    ],
+///};
+///```
+pub struct RpcServiceDescriptor<'a> {
+    ///Service definition
+    pub service: &'a RpcService,
+    ///Method defines, which determine method ids.
+    ///
+    ///Integer type must be specified, unless ids are hashes.
+    pub defines: RpcMethodDefines<'a>,
+    ///Name of the static, `SERVICE` by default.
+    pub name: Option<&'a str>,
+    ///Visibility of the static, `pub` by default.
+    pub visibility: Visibility,
+}
+
+impl<'a> RpcServiceDescriptor<'a> {
+    #[inline]
+    ///Sets name of the static.
+    pub const fn name(mut self, name: &'a str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    #[inline]
+    ///Sets visibility of the static.
+    pub const fn visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    #[inline]
+    ///Gets formatter of descriptor types, matching method id type of this descriptor.
+    pub fn types(&self) -> RpcDescriptorTypes {
+        RpcDescriptorTypes {
+            id_type: self.defines.id_type().unwrap_or(IntType::U16),
+        }
+    }
+
+    ///Checks that method ids can be assigned, and that `timeout_ms` and `priority` attributes have valid values.
+    fn validate(&self) -> Result<(), GenError> {
+        self.defines.method_ids()?;
+        check_attributes(self.service, self.service.methods.iter())
+    }
+
+    ///Returns whether any method specifies `priority`, which requires `MethodPriority` in scope.
+    fn has_priority(&self) -> bool {
+        self.service.methods.iter().any(|method| !matches!(method.priority(), Ok(None)))
+    }
+
+    ///Generates descriptor, failing if method ids cannot be assigned or method has invalid `timeout_ms` or `priority`.
+    ///
+    ///Prefer it over `Display`, which panics on [GenError].
+    pub fn render(&self) -> Result<String, GenError> {
+        self.validate()?;
+        Ok(self.to_string())
+    }
+}
+
+impl<'a> fmt::Display for RpcServiceDescriptor<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let visibility = self.visibility;
+        let name = self.name.unwrap_or("SERVICE");
+        expect_valid(check_attributes(self.service, self.service.methods.iter()));
+        let ids = expect_valid(self.defines.ordered_ids());
+
+        fmt.write_str("#[rustfmt::skip]\n")?;
+        fmt.write_fmt(format_args!("///Descriptor of `{}` service\n", self.service.name))?;
+        write_category(fmt, self.defines.category_attributes, ItemCategory::Descriptor)?;
+        fmt.write_fmt(format_args!("{visibility}static {name}: ServiceDescriptor = ServiceDescriptor {{\n"))?;
+        fmt.write_fmt(format_args!("{TAB}name: \"{}\",\n", self.service.name))?;
+        match self.service.namespace.as_ref() {
+            Some(namespace) => fmt.write_fmt(format_args!("{TAB}namespace: Some(\"{namespace}\"),\n"))?,
+            None => fmt.write_fmt(format_args!("{TAB}namespace: None,\n"))?,
+        }
+        fmt.write_fmt(format_args!("{TAB}attributes: "))?;
+        write_attributes(fmt, &self.service.attributes)?;
+        fmt.write_fmt(format_args!(",\n{TAB}methods: &[\n"))?;
+        for (id, method) in ids.iter() {
+            fmt.write_fmt(format_args!("{TAB}{TAB}MethodDescriptor {{\n"))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}name: \"{}\",\n", method.name))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}id: {id},\n"))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}arguments: &["))?;
+            for (idx, argument) in method.arguments.iter().enumerate() {
+                if idx > 0 {
+                    fmt.write_str(", ")?;
+                }
+                fmt.write_fmt(format_args!("\"{}\"", argument.ty.resolve(self.service.namespace.as_deref())))?;
+            }
+            fmt.write_fmt(format_args!("],\n"))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}response: \"{}\",\n", method.return_type.resolve(self.service.namespace.as_deref())))?;
+            fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}attributes: "))?;
+            write_attributes(fmt, &method.attributes)?;
+            match method.timeout_ms() {
+                Ok(Some(timeout_ms)) => fmt.write_fmt(format_args!(",\n{TAB}{TAB}{TAB}timeout_ms: Some({timeout_ms}),\n"))?,
+                _ => fmt.write_fmt(format_args!(",\n{TAB}{TAB}{TAB}timeout_ms: None,\n"))?,
+            }
+            match method.priority() {
+                Ok(Some(priority)) => fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}priority: Some(MethodPriority::{priority:?}),\n"))?,
+                _ => fmt.write_fmt(format_args!("{TAB}{TAB}{TAB}priority: None,\n"))?,
+            }
+            fmt.write_fmt(format_args!("{TAB}{TAB}}},\n"))?;
+        }
+        fmt.write_fmt(format_args!("{TAB}],\n}};"))
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+///Style of method ids within C header
+pub enum CHeaderStyle {
+    #[default]
+    ///`#define` per method.
+    Defines,
+    ///`enum` of methods.
+    ///
+    ///Note that C requires enum values to fit `int`, prior to C23.
+    Enum,
+}
+
+impl IntType {
+    #[inline]
+    ///Returns suffix of C integer literal of the type.
+    const fn c_suffix(self) -> &'static str {
+        match self {
+            Self::U8 | Self::U16 | Self::U32 => "u",
+            Self::U64 => "ull",
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+///Generates C header with method ids of the service.
+///
+///Ids are assigned by [RpcMethodDefines], making them the same as ids of Rust constants.
+///Names of ids always include service name, e.g. for method `Put` of service `Storage`:
+///
+///```c
+///#ifndef STORAGE_RPC_H
+///#define STORAGE_RPC_H
+///
+///#define STORAGE_PUT 0u
+///
+///#endif /* STORAGE_RPC_H */
+///```
+pub struct RpcCHeader<'a> {
+    ///Service definition
+    pub service: &'a RpcService,
+    ///Method defines, which determine method ids and prefix of their names.
+    ///
+    ///Integer type must be specified, unless ids are hashes.
+    pub defines: RpcMethodDefines<'a>,
+    ///Style of method ids, `#define` by default.
+    pub style: CHeaderStyle,
+    ///Specifies whether documentation is to be omitted.
+    pub skip_docs: bool,
+}
+
+impl<'a> RpcCHeader<'a> {
+    #[inline]
+    ///Sets style of method ids.
+    pub const fn style(mut self, style: CHeaderStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    #[inline]
+    ///Sets whether documentation is to be omitted.
+    pub const fn skip_docs(mut self, skip_docs: bool) -> Self {
+        self.skip_docs = skip_docs;
+        self
+    }
+
+    ///Generates header, failing if method ids cannot be assigned or names of defines are the same.
+    ///
+    ///Prefer it over `Display`, which panics on [GenError].
+    pub fn render(&self) -> Result<String, GenError> {
+        self.defines.validate()?;
+        Ok(self.to_string())
+    }
+}
+
+impl<'a> fmt::Display for RpcCHeader<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        expect_valid(self.defines.validate());
+        let service = ident::to_flatc_snake(&self.service.name);
+        let prefix = self.defines.qualified_prefix();
+        let guard = format!("{prefix}RPC_H");
+        let suffix = self.defines.id_type().unwrap_or(IntType::U16).c_suffix();
+        let ids = expect_valid(self.defines.ordered_ids());
+
+        fmt.write_fmt(format_args!("/* Method ids of `{}` service */\n", self.service.name))?;
+        fmt.write_fmt(format_args!("#ifndef {guard}\n#define {guard}\n\n"))?;
+        if self.defines.method_count {
+            let count = self.service.methods.len();
+            fmt.write_fmt(format_args!("#define {prefix}METHOD_COUNT {count}u\n\n"))?;
+        }
+
+        let indent = match self.style {
+            CHeaderStyle::Defines => "",
+            CHeaderStyle::Enum => {
+                fmt.write_fmt(format_args!("enum {service}_method {{\n"))?;
+                TAB
+            },
+        };
+        for (id, method) in ids.iter() {
+            if !self.skip_docs {
+                for line in method.docs.iter() {
+                    fmt.write_fmt(format_args!("{indent}/* {} */\n", line.replace("*/", "* /")))?;
+                }
+            }
+            if self.defines.deprecated_policy.is_annotated(method) {
+                fmt.write_fmt(format_args!("{indent}/* deprecated */\n"))?;
+            }
+            let name = ident::to_screaming_snake(&method.name);
+            match self.style {
+                CHeaderStyle::Defines => fmt.write_fmt(format_args!("#define {prefix}{name} {id}{suffix}\n"))?,
+                CHeaderStyle::Enum => fmt.write_fmt(format_args!("{TAB}{prefix}{name} = {id}{suffix},\n"))?,
+            }
+        }
+        if let CHeaderStyle::Enum = self.style {
+            fmt.write_str("};\n")?;
+        }
+
+        fmt.write_fmt(format_args!("\n#endif /* {guard} */\n"))
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+///Style of method ids within TypeScript output
+pub enum TsStyle {
+    #[default]
+    ///`export const enum`, with members in upper camel case.
+    ConstEnum,
+    ///`export const` per method.
+    Constants,
+}
+
+#[derive(Copy, Clone)]
+///Generates TypeScript module with method ids of the service.
+///
+///Ids are assigned by [RpcMethodDefines], making them the same as ids of Rust constants.
+///For method `Put` of service `Storage`:
+///
+///```typescript
+///export const enum StorageMethod {
+///    Put = 0,
+///}
+///
+///export function methodName(id: number): string | undefined {
+///    switch (id) {
+///        case 0: return "Put";
+///        default: return undefined;
+///    }
+///}
+///```
+///
+///With [TsStyle::Constants] constants are named same as in [RpcCHeader], e.g. `export const STORAGE_PUT = 0;`
+///
+///As `methodName` is not qualified by service name, output is to be placed in its own file.
+pub struct RpcTypeScript<'a> {
+    ///Service definition
+    pub service: &'a RpcService,
+    ///Method defines, which determine method ids and prefix of constants' names.
+    pub defines: RpcMethodDefines<'a>,
+    ///Style of method ids, `const enum` by default.
+    pub style: TsStyle,
+    ///Specifies whether documentation is to be omitted.
+    pub skip_docs: bool,
+}
+
+impl<'a> RpcTypeScript<'a> {
+    #[inline]
+    ///Sets style of method ids.
+    pub const fn style(mut self, style: TsStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    #[inline]
+    ///Sets whether documentation is to be omitted.
+    pub const fn skip_docs(mut self, skip_docs: bool) -> Self {
+        self.skip_docs = skip_docs;
+        self
+    }
+
+    ///Returns names of methods in order of output.
+    fn names(&self, ids: &[(u64, &'a RpcMethod)]) -> Result<Vec<(&'a RpcMethod, String)>, NameCollision> {
+        let methods = ids.iter().map(|(_, method)| *method);
+        match self.style {
+            TsStyle::ConstEnum => unique_names(self.service, methods, "enum member name", ident::to_pascal),
+            TsStyle::Constants => {
+                let prefix = self.defines.qualified_prefix();
+                unique_names(self.service, methods, "constant name", |name| format!("{prefix}{}", ident::to_screaming_snake(name)))
+            },
+        }
+    }
+
+    ///Generates module, failing if method ids cannot be assigned or generated names are the same.
+    ///
+    ///Prefer it over `Display`, which panics on [GenError].
+    pub fn render(&self) -> Result<String, GenError> {
+        self.names(&self.defines.ordered_ids()?)?;
+        Ok(self.to_string())
+    }
+}
+
+impl<'a> fmt::Display for RpcTypeScript<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ids = expect_valid(self.defines.ordered_ids());
+        let names = expect_valid(self.names(&ids));
+        let indent = match self.style {
+            TsStyle::ConstEnum => TAB,
+            TsStyle::Constants => "",
+        };
+
+        fmt.write_fmt(format_args!("// Method ids of `{}` service\n\n", self.service.name))?;
+        if let TsStyle::ConstEnum = self.style {
+            fmt.write_fmt(format_args!("export const enum {}Method {{\n", self.service.name))?;
+        }
+        for ((id, _), (method, name)) in ids.iter().zip(names.iter()) {
+            if !self.skip_docs && !method.docs.is_empty() {
+                fmt.write_fmt(format_args!("{indent}/**\n"))?;
+                for line in method.docs.iter() {
+                    fmt.write_fmt(format_args!("{indent} * {}\n", line.replace("*/", "* /")))?;
+                }
+                fmt.write_fmt(format_args!("{indent} */\n"))?;
+            }
+            if self.defines.deprecated_policy.is_annotated(method) {
+                fmt.write_fmt(format_args!("{indent}/** @deprecated */\n"))?;
+            }
+            match self.style {
+                TsStyle::ConstEnum => fmt.write_fmt(format_args!("{TAB}{name} = {id},\n"))?,
+                TsStyle::Constants => fmt.write_fmt(format_args!("export const {name} = {id};\n"))?,
+            }
+        }
+        if let TsStyle::ConstEnum = self.style {
+            fmt.write_str("}\n")?;
+        }
+
+        fmt.write_str("\n/** Returns method name as declared within schema. */\n")?;
+        fmt.write_str("export function methodName(id: number): string | undefined {\n")?;
+        fmt.write_fmt(format_args!("{TAB}switch (id) {{\n"))?;
+        for (id, method) in ids.iter() {
+            fmt.write_fmt(format_args!("{TAB}{TAB}case {id}: return \"{}\";\n", method.name))?;
+        }
+        fmt.write_fmt(format_args!("{TAB}{TAB}default: return undefined;\n{TAB}}}\n}}\n"))
+    }
+}
+
+#[derive(Copy, Clone)]
+///Generates Python module with method ids of the service.
+///
+///Ids are assigned by [RpcMethodDefines], and members are named same as its constants, without prefix.
+///As names are in upper case, they never clash with Python keywords, e.g. method `Import` becomes `IMPORT`.
+///
+///```python
+///class StorageMethod(enum.IntEnum):
+///    PUT = 0
+///
+///
+///METHOD_NAMES: dict[int, str] = {
+///    0: "Put",
+///}
+///```
+pub struct RpcPython<'a> {
+    ///Service definition
+    pub service: &'a RpcService,
+    ///Method defines, which determine method ids.
+    pub defines: RpcMethodDefines<'a>,
+    ///Specifies whether documentation is to be omitted.
+    pub skip_docs: bool,
+}
+
+impl<'a> RpcPython<'a> {
+    #[inline]
+    ///Sets whether documentation is to be omitted.
+    pub const fn skip_docs(mut self, skip_docs: bool) -> Self {
+        self.skip_docs = skip_docs;
+        self
+    }
+
+    ///Generates module, failing if method ids cannot be assigned or names of members are the same.
+    ///
+    ///Prefer it over `Display`, which panics on [GenError].
+    pub fn render(&self) -> Result<String, GenError> {
+        self.defines.validate()?;
+        Ok(self.to_string())
+    }
+}
+
+impl<'a> fmt::Display for RpcPython<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let methods = &self.service.methods;
+        let ids = expect_valid(self.defines.ordered_ids());
+        let names = expect_valid(unique_names(self.service, ids.iter().map(|(_, method)| *method), "member name", ident::to_screaming_snake));
+
+        fmt.write_fmt(format_args!("\"\"\"Method ids of `{}` service.\n\nThis module is generated, do not edit.\n\"\"\"\n\n", self.service.name))?;
+        fmt.write_str("import enum\n\n\n")?;
+        fmt.write_fmt(format_args!("class {}Method(enum.IntEnum):\n", self.service.name))?;
+        fmt.write_fmt(format_args!("{TAB}\"\"\"Methods of `{}` service.\"\"\"\n", self.service.name))?;
+        for ((id, _), (method, name)) in ids.iter().zip(names.iter()) {
+            fmt.write_str("\n")?;
+            if !self.skip_docs {
+                for line in method.docs.iter() {
+                    fmt.write_fmt(format_args!("{TAB}# {line}\n"))?;
+                }
+            }
+            if self.defines.deprecated_policy.is_annotated(method) {
+                fmt.write_fmt(format_args!("{TAB}# deprecated\n"))?;
+            }
+            fmt.write_fmt(format_args!("{TAB}{name} = {id}\n"))?;
+        }
+
+        fmt.write_str("\n\nMETHOD_NAMES: dict[int, str] = {")?;
+        if !methods.is_empty() {
+            fmt.write_str("\n")?;
+        }
+        for (id, method) in ids.iter() {
+            fmt.write_fmt(format_args!("{TAB}{id}: \"{}\",\n", method.name))?;
+        }
+        fmt.write_str("}\n")
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+///Representation of service namespace within proto output
+pub enum ProtoNamespace {
+    #[default]
+    ///`package` statement with namespace of the service, while message names are written as within schema.
+    Package,
+    ///No `package` statement, while message names are qualified with namespace of the service, unless already qualified.
+    Qualified,
+}
+
+#[derive(Copy, Clone)]
+///Generates proto3 definition of the service, e.g. for gRPC.
+///
+///Every method becomes `rpc`, with `stream` qualifier of request and response according to [Streaming] of the method.
+///Proto accepts single request message, hence method without arguments requests `google.protobuf.Empty`,
+///while method with several arguments cannot be generated.
+///
+///Messages are expected to be defined elsewhere, and their names are mapped by `message_name` callback,
+///or written according to [ProtoNamespace].
+///Deprecated methods have `deprecated` option or are omitted according to [DeprecatedPolicy].
+///
+///```rust
+///use flatbuffers_tools::{DeprecatedPolicy, GenConfig, GenError, ProtoNamespace, RpcService, TypeName};
+///
+///let service: RpcService = "namespace MyGame.Rpc;\n\
+///                           /// Storage of monsters\n\
+///                           rpc_service MonsterStorage {\n\
+///                             /// Stores monster\n\
+///                             Store(Monster):Response;\n\
+///                             Watch(query: Query):Monster (streaming: \"server\");\n\
+///                             Upload(Monster):Response (streaming: \"client\");\n\
+///                             Sync(Common.Delta):Common.Delta (streaming: \"bidi\");\n\
+///                             Ping():Pong (deprecated);\n\
+///                           }".parse().unwrap();
+///
+///let proto = service.as_proto().render().unwrap();
+///assert!(proto.contains("\n\n// Storage of monsters\nservice MonsterStorage {\n"));
+///assert_eq!(proto.replace("// Storage of monsters\n", ""), "\
+///syntax = \"proto3\";
+///
+///package MyGame.Rpc;
+///
+///import \"google/protobuf/empty.proto\";
+///
+///service MonsterStorage {
+///    // Stores monster
+///    rpc Store (Monster) returns (Response);
+///    rpc Watch (Query) returns (stream Monster);
+///    rpc Upload (stream Monster) returns (Response);
+///    rpc Sync (stream Common.Delta) returns (stream Common.Delta);
+///    rpc Ping (google.protobuf.Empty) returns (Pong) {
+///        option deprecated = true;
+///    }
+///}
+///");
+///
+///let config = GenConfig::new().proto_namespace(ProtoNamespace::Qualified).deprecated_policy(DeprecatedPolicy::Omit).skip_docs(true);
+///assert_eq!(service.as_proto_with(&config).render().unwrap(), "\
+///syntax = \"proto3\";
+///
+///service MonsterStorage {
+///    rpc Store (MyGame.Rpc.Monster) returns (MyGame.Rpc.Response);
+///    rpc Watch (MyGame.Rpc.Query) returns (stream MyGame.Rpc.Monster);
+///    rpc Upload (stream MyGame.Rpc.Monster) returns (MyGame.Rpc.Response);
+///    rpc Sync (stream Common.Delta) returns (stream Common.Delta);
+///}
+///");
+///
+/////Message names are passed qualified with namespace of the service
+///let message_name = |name: &TypeName| format!("{}Msg", name.name());
+///let proto = service.as_proto_with(&config).message_name(&message_name).render().unwrap();
+///assert!(proto.contains("\n    rpc Sync (stream DeltaMsg) returns (stream DeltaMsg);\n"));
+///
+///let service: RpcService = "rpc_service Storage { Put(Key, Value):Response; }".parse().unwrap();
+///let error = service.as_proto().render().unwrap_err();
+///assert_eq!(error, GenError::MultipleArguments {
+///    service: "Storage".to_owned(),
+///    method: "Put".to_owned(),
+///    count: 2,
+///});
+///assert_eq!(error.to_string(), "Method 'Put' of service 'Storage' has 2 arguments, while output allows only single argument");
+///```
+pub struct RpcProto<'a> {
+    ///Service definition
+    pub service: &'a RpcService,
+    ///Representation of namespace, `package` statement by default.
+    pub namespace: ProtoNamespace,
+    ///Maps type name, qualified with namespace of the service, to message name.
+    ///
+    ///When specified, [ProtoNamespace] affects only `package` statement.
+    pub message_name: Option<&'a dyn Fn(&TypeName) -> String>,
+    ///Treatment of deprecated methods, annotated by default.
+    pub deprecated_policy: DeprecatedPolicy,
+    ///Specifies whether documentation is to be omitted.
+    pub skip_docs: bool,
+}
+
+impl<'a> RpcProto<'a> {
+    #[inline]
+    ///Sets representation of namespace.
+    pub const fn namespace(mut self, namespace: ProtoNamespace) -> Self {
+        self.namespace = namespace;
+        self
+    }
+
+    #[inline]
+    ///Sets callback to map type name to message name.
+    pub const fn message_name(mut self, message_name: &'a dyn Fn(&TypeName) -> String) -> Self {
+        self.message_name = Some(message_name);
+        self
+    }
+
+    #[inline]
+    ///Sets treatment of deprecated methods.
+    pub const fn deprecated_policy(mut self, deprecated_policy: DeprecatedPolicy) -> Self {
+        self.deprecated_policy = deprecated_policy;
+        self
+    }
+
+    #[inline]
+    ///Sets whether documentation is to be omitted.
+    pub const fn skip_docs(mut self, skip_docs: bool) -> Self {
+        self.skip_docs = skip_docs;
+        self
+    }
+
+    #[inline]
+    ///Returns methods in order of output.
+    fn methods(&self) -> impl Iterator<Item = &'a RpcMethod> + 'a {
+        let deprecated_policy = self.deprecated_policy;
+        self.service.methods.iter().filter(move |method| deprecated_policy.is_included(method))
+    }
+
+    ///Checks that every method has at most one argument and known streaming.
+    pub fn validate(&self) -> Result<(), GenError> {
+        check_streaming(self.service, self.methods())?;
+        check_arguments(self.service, self.methods())
+    }
+
+    ///Generates proto definition, failing if any method cannot be expressed within proto.
+    ///
+    ///Prefer it over `Display`, which panics on [GenError].
+    pub fn render(&self) -> Result<String, GenError> {
+        self.validate()?;
+        Ok(self.to_string())
+    }
+
+    ///Returns message name of type `ty`.
+    fn message(&self, ty: &TypeRef) -> String {
+        match (self.message_name, self.namespace) {
+            (Some(message_name), _) => message_name(&ty.resolve(self.service.namespace.as_deref())),
+            (None, ProtoNamespace::Package) => ty.to_string(),
+            (None, ProtoNamespace::Qualified) => ty.resolve(self.service.namespace.as_deref()).to_string(),
+        }
+    }
+}
+
+impl<'a> fmt::Display for RpcProto<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        expect_valid(self.validate());
+        let service = self.service;
+
+        fmt.write_str("syntax = \"proto3\";\n\n")?;
+        if let (ProtoNamespace::Package, Some(namespace)) = (self.namespace, service.namespace.as_ref()) {
+            fmt.write_fmt(format_args!("package {namespace};\n\n"))?;
+        }
+        if self.methods().any(|method| method.arguments.is_empty()) {
+            fmt.write_str("import \"google/protobuf/empty.proto\";\n\n")?;
+        }
+
+        if !self.skip_docs {
+            for line in service.docs.iter() {
+                fmt.write_fmt(format_args!("// {line}\n"))?;
+            }
+        }
+        fmt.write_fmt(format_args!("service {} {{\n", service.name))?;
+        for method in self.methods() {
+            if !self.skip_docs {
+                for line in method.docs.iter() {
+                    fmt.write_fmt(format_args!("{TAB}// {line}\n"))?;
+                }
+            }
+
+            let kind = streaming(method);
+            let request = match method.arguments.first() {
+                Some(argument) => self.message(&argument.ty),
+                None => "google.protobuf.Empty".to_owned(),
+            };
+            let response = self.message(&method.return_type);
+            let request_stream = if kind.is_client() { "stream " } else { "" };
+            let response_stream = if kind.is_server() { "stream " } else { "" };
+            fmt.write_fmt(format_args!("{TAB}rpc {} ({request_stream}{request}) returns ({response_stream}{response})", method.name))?;
+            if self.deprecated_policy.is_annotated(method) {
+                fmt.write_fmt(format_args!(" {{\n{TAB}{TAB}option deprecated = true;\n{TAB}}}\n"))?;
+            } else {
+                fmt.write_str(";\n")?;
+            }
+        }
+        fmt.write_str("}\n")
+    }
+}
+
+///Escapes characters, that are significant within Markdown text or table cell.
+struct MdText<'a>(&'a str);
+
+impl<'a> fmt::Display for MdText<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = self.0;
+        //Start of line can begin heading, list or quote
+        let len = text.bytes().take_while(u8::is_ascii_digit).count();
+        let mut rest = match text.as_bytes().get(len) {
+            Some(b'.') | Some(b')') if len > 0 => {
+                fmt.write_fmt(format_args!("{}\\", &text[..len]))?;
+                &text[len..]
+            },
+            Some(b'-') | Some(b'+') | Some(b'=') if len == 0 => {
+                fmt.write_str("\\")?;
+                text
+            },
+            _ => text,
+        };
+        while let Some(idx) = rest.find(['\\', '`', '*', '_', '[', ']', '<', '>', '|', '~', '#']) {
+            fmt.write_str(&rest[..idx])?;
+            fmt.write_str("\\")?;
+            fmt.write_str(&rest[idx..idx + 1])?;
+            rest = &rest[idx + 1..];
+        }
+        fmt.write_str(rest)
+    }
+}
+
+#[derive(Copy, Clone)]
+///Generates Markdown reference of the service.
+///
+///Service is level 2 heading, followed by its documentation and table of methods with their ids, types, attributes and documentation.
+///Names and documentation are escaped, so that they are rendered as plain text, while types are code.
+///
+///Ids are assigned by [RpcMethodDefines], and deprecated methods are struck through or omitted according to its [DeprecatedPolicy].
+///
+///```rust
+///use flatbuffers_tools::{DeprecatedPolicy, GenConfig, RpcService};
+///
+///let service: RpcService = "/// Key-value *storage*\n\
+///                            rpc_service Storage {\n\
+///                              /// Stores value\n\
+///                              /// under key\n\
+///                              put(Request):Response;\n\
+///                              get_item(key: Key):Value (deprecated, since: \"1|2\");\n\
+///                              ping():Pong;\n\
+///                            }".parse().unwrap();
+///
+///assert_eq!(service.as_markdown().to_string(), "\
+///### Storage
+///
+///Key-value \\*storage\\*
+///
+///| Method | ID | Request | Response | Attributes | Description |
+///|---|---|---|---|---|---|
+///| put | 0 | `Request` | `Response` |  | Stores value under key |
+///| ~~get\\_item~~ | 1 | key: `Key` | `Value` | deprecated, since: 1\\|2 |  |
+///| ping | 2 |  | `Pong` |  |  |
+///");
+///
+///let config = GenConfig::new().deprecated_policy(DeprecatedPolicy::Omit).skip_docs(true);
+///assert_eq!(service.as_markdown_with(&config).to_string(), "\
+///### Storage
+///
+///| Method | ID | Request | Response | Attributes |
+///|---|---|---|---|---|
+///| put | 0 | `Request` | `Response` |  |
+///| ping | 2 |  | `Pong` |  |
+///");
+///```
+pub struct RpcMarkdown<'a> {
+    ///Service definition
+    pub service: &'a RpcService,
+    ///Method defines, which determine method ids.
+    pub defines: RpcMethodDefines<'a>,
+    ///Specifies whether documentation is to be omitted, together with description column.
+    pub skip_docs: bool,
+}
+
+impl<'a> RpcMarkdown<'a> {
+    #[inline]
+    ///Sets whether documentation is to be omitted.
+    pub const fn skip_docs(mut self, skip_docs: bool) -> Self {
+        self.skip_docs = skip_docs;
+        self
+    }
+
+    ///Generates document, failing if method ids cannot be assigned.
+    ///
+    ///Prefer it over `Display`, which panics on [GenError].
+    pub fn render(&self) -> Result<String, GenError> {
+        self.defines.method_ids()?;
+        Ok(self.to_string())
+    }
+}
+
+impl<'a> fmt::Display for RpcMarkdown<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let policy = self.defines.deprecated_policy;
+        let ids = expect_valid(self.defines.ordered_ids());
+        let code = |name: &str| format!("`{name}`");
+
+        fmt.write_fmt(format_args!("## {}\n\n", MdText(&self.service.name)))?;
+        if let Some(namespace) = self.service.namespace.as_ref() {
+            fmt.write_fmt(format_args!("Namespace: `{namespace}`\n\n"))?;
+        }
+        if !self.skip_docs && !self.service.docs.is_empty() {
+            for line in self.service.docs.iter() {
+                fmt.write_fmt(format_args!("{}\n", MdText(line.trim())))?;
+            }
+            fmt.write_str("\n")?;
+        }
+
+        match self.skip_docs {
+            true => fmt.write_str("| Method | ID | Request | Response | Attributes |\n|---|---|---|---|---|\n")?,
+            false => fmt.write_str("| Method | ID | Request | Response | Attributes | Description |\n|---|---|---|---|---|---|\n")?,
+        }
+        for (id, method) in ids.iter().filter(|(_, method)| policy.is_included(method)) {
+            match policy.is_annotated(method) {
+                true => fmt.write_fmt(format_args!("| ~~{}~~ | {id} | ", MdText(&method.name)))?,
+                false => fmt.write_fmt(format_args!("| {} | {id} | ", MdText(&method.name)))?,
+            }
+            for (idx, argument) in method.arguments.iter().enumerate() {
+                if idx > 0 {
+                    fmt.write_str(", ")?;
+                }
+                if let Some(name) = argument.name.as_ref() {
+                    fmt.write_fmt(format_args!("{}: ", MdText(name)))?;
+                }
+                fmt.write_str(&code(&argument.ty))?;
+            }
+            fmt.write_fmt(format_args!(" | {} | ", code(&method.return_type)))?;
+            for (idx, (key, value)) in method.attributes.iter().enumerate() {
+                if idx > 0 {
+                    fmt.write_str(", ")?;
+                }
+                fmt.write_fmt(format_args!("{}", MdText(key)))?;
+                if let Some(value) = value.as_ref() {
+                    fmt.write_fmt(format_args!(": {}", MdText(value)))?;
+                }
+            }
+            if !self.skip_docs {
+                fmt.write_str(" | ")?;
+                for (idx, line) in method.docs.iter().enumerate() {
+                    if idx > 0 {
+                        fmt.write_str(" ")?;
+                    }
+                    fmt.write_fmt(format_args!("{}", MdText(line.trim())))?;
+                }
+            }
+            fmt.write_str(" |\n")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone)]
+///Generates module, named after service in snake case, containing generated items of the service.
+///
+///E.g. for service `Storage` it generates `pub mod storage { ... }`, so that items of several services do not clash.
+///
+///Items shared by services, generated by [RpcDispatchCodec], [RpcClientTransport], [RpcDescriptorTypes], [RpcMethodTrait] and [RpcObserverTrait], are expected in parent module,
+///unless they are imported from [RpcServiceModule::runtime].
+pub struct RpcServiceModule<'a> {
+    ///Service definition
+    pub service: &'a RpcService,
+    ///Visibility of the module, `pub` by default.
+    pub visibility: Visibility,
+    ///Method defines, generated by default.
+    pub defines: Option<RpcMethodDefines<'a>>,
+    ///Method enum
+    pub method_enum: Option<RpcMethodEnum<'a>>,
+    ///Handler trait and dispatcher
+    pub dispatcher: Option<RpcServiceDispatcher<'a>>,
+    ///Client stub
+    pub client: Option<RpcClientStub<'a>>,
+    ///Service descriptor
+    pub descriptor: Option<RpcServiceDescriptor<'a>>,
+    ///Type aliases of requests and responses, placed before dispatcher
+    pub type_aliases: Option<RpcTypeAliases<'a>>,
+    ///Method markers
+    pub markers: Option<RpcMethodMarkers<'a>>,
+    ///Mock of handler trait, placed after dispatcher, which is required.
+    pub mock: Option<RpcServiceMock<'a>>,
+    ///Instrumented wrapper of handler trait, placed after dispatcher, which is required.
+    pub instrumentation: Option<RpcServiceInstrumentation<'a>>,
+    ///Custom attributes of the module.
+    ///
+    ///Only [Attributes::item] are used, while methods' attributes are to be specified for dispatcher.
+    pub attributes: Attributes<'a>,
+    ///Specifies whether documentation of service is to be omitted.
+    ///
+    ///Otherwise it is placed above the module.
+    pub skip_docs: bool,
+    ///Specifies whether to append compile-time assertions, that items of the module are consistent with each other.
+    ///
+    ///Assertions are within `const _: () = { ... };` block, which has no runtime cost, and fail to compile if any of following differs:
+    ///
+    ///- `METHOD_COUNT`, length of `METHODS` and number of descriptor's methods, from number of methods;
+    ///- method constant, from method's entry of `METHODS` and descriptor, and result of `rpc_method_name`;
+    ///- enum discriminant, when it is method id, or `as_str()` of variant, from method constant.
+    ///
+    ///Only items, that are generated, are checked, hence there are no assertions without method defines.
+    ///Reading `METHODS` or descriptor static within constant requires Rust 1.83.
+    pub assertions: bool,
+    ///Path to module of shared items, that are imported instead of parent's items, e.g. `::flatbuffers_tools::runtime`.
+    ///
+    ///Shared items of `flatbuffers_tools::runtime`, which requires `runtime` feature, are defined for `u16` method ids and `std` or `alloc` library.
+    ///As it does not depend on `flatbuffers`, module defines alias `ClientError<E>` of client's error instead of importing it.
+    pub runtime: Option<&'a str>,
+}
+
+impl<'a> RpcServiceModule<'a> {
+    #[inline]
+    ///Sets visibility of the module.
+    pub const fn visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    #[inline]
+    ///Sets whether documentation of service is to be omitted.
+    pub const fn skip_docs(mut self, skip_docs: bool) -> Self {
+        self.skip_docs = skip_docs;
+        self
+    }
+
+    #[inline]
+    ///Sets whether to append compile-time assertions of consistency between items.
+    pub const fn assertions(mut self, assertions: bool) -> Self {
+        self.assertions = assertions;
+        self
+    }
+
+    #[inline]
+    ///Sets path to module of shared items, which are imported instead of parent's items.
+    pub const fn runtime(mut self, runtime: &'a str) -> Self {
+        self.runtime = Some(runtime);
+        self
+    }
+
+    #[inline]
+    ///Sets method defines to generate, or `None` to omit them.
+    pub const fn defines(mut self, defines: Option<RpcMethodDefines<'a>>) -> Self {
+        self.defines = defines;
+        self
+    }
+
+    #[inline]
+    ///Sets method enum to generate.
+    pub const fn with_method_enum(mut self, method_enum: RpcMethodEnum<'a>) -> Self {
+        self.method_enum = Some(method_enum);
+        self
+    }
+
+    #[inline]
+    ///Sets dispatcher to generate.
+    ///
+    ///Its method defines are expected to be the same as module's.
+    pub const fn with_dispatcher(mut self, dispatcher: RpcServiceDispatcher<'a>) -> Self {
+        self.dispatcher = Some(dispatcher);
+        self
+    }
+
+    #[inline]
+    ///Sets client stub to generate.
+    ///
+    ///Its method defines are expected to be the same as module's.
+    pub const fn with_client(mut self, client: RpcClientStub<'a>) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    #[inline]
+    ///Sets custom attributes of the module.
+    pub const fn attributes(mut self, attributes: Attributes<'a>) -> Self {
+        self.attributes = attributes;
+        self
+    }
+
+    ///Generates module, failing if any of its items cannot be generated.
+    ///
+    ///Prefer it over `Display`, which panics on [GenError].
+    pub fn render(&self) -> Result<String, GenError> {
+        if let Some(defines) = self.defines.as_ref() {
+            defines.validate()?;
+        }
+        if let Some(method_enum) = self.method_enum.as_ref() {
+            method_enum.variants()?;
+            method_enum.ids()?;
+        }
+        if let Some(dispatcher) = self.dispatcher.as_ref() {
+            dispatcher.validate()?;
+        }
+        if let Some(client) = self.client.as_ref() {
+            client.validate()?;
+        }
+        if let Some(descriptor) = self.descriptor.as_ref() {
+            descriptor.validate()?;
+        }
+        if let Some(type_aliases) = self.type_aliases.as_ref() {
+            alias_names(type_aliases.service, type_aliases.service.methods.iter())?;
+        }
+        if let Some(markers) = self.markers.as_ref() {
+            markers.names()?;
+        }
+        if let Some(mock) = self.mock.as_ref() {
+            mock.validate()?;
+        }
+        if let Some(instrumentation) = self.instrumentation.as_ref() {
+            instrumentation.validate()?;
+        }
+        Ok(self.to_string())
+    }
+
+    #[inline]
+    ///Sets service descriptor to generate.
+    ///
+    ///Its method defines are expected to be the same as module's.
+    pub const fn with_descriptor(mut self, descriptor: RpcServiceDescriptor<'a>) -> Self {
+        self.descriptor = Some(descriptor);
+        self
+    }
+
+    #[inline]
+    ///Sets type aliases to generate.
+    ///
+    ///They are required by dispatcher with [RpcServiceDispatcher::aliases].
+    pub const fn with_type_aliases(mut self, type_aliases: RpcTypeAliases<'a>) -> Self {
+        self.type_aliases = Some(type_aliases);
+        self
+    }
+
+    #[inline]
+    ///Sets method markers to generate.
+    ///
+    ///Its method defines are expected to be the same as module's.
+    pub const fn with_markers(mut self, markers: RpcMethodMarkers<'a>) -> Self {
+        self.markers = Some(markers);
+        self
+    }
+
+    #[inline]
+    ///Sets mock of handler trait to generate.
+    ///
+    ///Its dispatcher is expected to be the same as module's.
+    pub const fn with_mock(mut self, mock: RpcServiceMock<'a>) -> Self {
+        self.mock = Some(mock);
+        self
+    }
+
+    #[inline]
+    ///Sets instrumented wrapper of handler trait to generate.
+    ///
+    ///Its dispatcher is expected to be the same as module's.
+    pub const fn with_instrumentation(mut self, instrumentation: RpcServiceInstrumentation<'a>) -> Self {
+        self.instrumentation = Some(instrumentation);
+        self
+    }
+}
+
+impl RpcServiceModule<'_> {
+    ///Returns block of compile-time assertions, or empty string if there is nothing to check.
+    fn assertions_block(&self) -> String {
+        let defines = match self.defines.as_ref() {
+            Some(defines) => defines,
+            None => return String::new(),
+        };
+        let prefix = defines.names_prefix();
+        let fn_prefix = prefix.to_lowercase();
+        let int_type = defines.id_type();
+        let ids = expect_valid(defines.ordered_ids());
+        let descriptor = self.descriptor.as_ref().map(|descriptor| descriptor.name.unwrap_or("SERVICE"));
+        let mut assertions = Vec::new();
+
+        let count = ids.len();
+        if defines.method_count {
+            assertions.push(format!("{prefix}METHOD_COUNT == {count}"));
+        }
+        if defines.registry {
+            assertions.push(format!("{prefix}METHODS.len() == {count}"));
+        }
+        if let Some(descriptor) = descriptor {
+            assertions.push(format!("{descriptor}.methods.len() == {count}"));
+        }
+
+        //Variant of every method, that is included within enum, with its discriminant
+        let mut variants = Vec::new();
+        let mut is_enum_id = false;
+        if let Some(method_enum) = self.method_enum.as_ref() {
+            let enum_ids = expect_valid(method_enum.ids());
+            //Discriminants are indexes, which may differ from constants, e.g. with hash ids
+            is_enum_id = int_type.is_some() && enum_ids == expect_valid(defines.method_ids());
+            let name = match method_enum.name {
+                Some(name) => name.to_owned(),
+                None => format!("{}Method", self.service.name),
+            };
+            for (method, variant) in expect_valid(method_enum.variants()) {
+                if method_enum.deprecated_policy.is_included(method) {
+                    variants.push((method.name.as_str(), format!("{name}::{}", Ident(&variant))));
+                }
+            }
+        }
+
+        for (idx, (id, method)) in ids.iter().enumerate() {
+            let constant = format!("{prefix}{}", ident::to_screaming_snake(&method.name));
+            let (id, name) = match int_type {
+                Some(_) => (constant, format!("\"{}\"", method.name)),
+                None => (id.to_string(), constant),
+            };
+            if defines.registry {
+                assertions.push(format!("{prefix}METHODS[{idx}].1 == {id}"));
+                assertions.push(format!("same({prefix}METHODS[{idx}].0, {name})"));
+            }
+            if defines.name_lookup {
+                assertions.push(format!("matches!({fn_prefix}rpc_method_name({id}), Some(name) if same(name, {name}))"));
+            }
+            if let Some(descriptor) = descriptor {
+                assertions.push(format!("{descriptor}.methods[{idx}].id == {id}"));
+                assertions.push(format!("same({descriptor}.methods[{idx}].name, {name})"));
+            }
+            if let Some((_, variant)) = variants.iter().find(|(variant, _)| *variant == method.name) {
+                if is_enum_id {
+                    assertions.push(format!("{variant} as u64 == {id} as u64"));
+                }
+                assertions.push(format!("same({variant}.as_str(), {name})"));
+            }
+        }
+
+        if assertions.is_empty() {
+            return String::new();
+        }
+        let mut block = String::new();
+        block.push_str("#[rustfmt::skip]\n#[allow(deprecated)]\n");
+        block.push_str("//Compile-time checks, that items of the module are consistent with each other\n");
+        block.push_str("const _: () = {\n");
+        if assertions.iter().any(|assertion| assertion.contains("same(")) {
+            block.push_str(ASSERT_SAME_STR);
+        }
+        for assertion in assertions {
+            block.push_str(TAB);
+            block.push_str("assert!(");
+            block.push_str(&assertion);
+            block.push_str(");\n");
+        }
+        block.push_str("};");
+        block
+    }
+}
+
+///Comparison of strings within generated constant, as `==` cannot be used within constant context
+const ASSERT_SAME_STR: &str = "    const fn same(left: &str, right: &str) -> bool {
+        let (left, right) = (left.as_bytes(), right.as_bytes());
+        if left.len() != right.len() {
+            return false;
+        }
+        let mut idx = 0;
+        while idx < left.len() {
+            if left[idx] != right[idx] {
+                return false;
+            }
+            idx += 1;
+        }
+        true
+    }
+
+";
+
+impl<'a> fmt::Display for RpcServiceModule<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let visibility = self.visibility;
+        let name = ident::to_snake(&self.service.name);
+
+        let shared = self.runtime.unwrap_or("super");
+
+        let mut items = Vec::new();
+        if self.dispatcher.is_some() || self.client.is_some_and(|client| client.codec) {
+            items.push(format!("use {shared}::{{Codec, DispatchError}};"));
+        }
+        if let Some(client) = self.client.as_ref() {
+            let response_error = match client.codec {
+                true => "DispatchError",
+                false => "flatbuffers::InvalidFlatbuffer",
+            };
+            match (self.runtime, client.framing.is_some()) {
+                (None, _) => items.push("use super::{ClientError, Transport};".to_owned()),
+                //Runtime does not depend on flatbuffers, hence error of verification is specified by alias
+                (Some(runtime), is_framed) => {
+                    let runtime = match is_framed {
+                        true => format!("{runtime}::framed"),
+                        false => runtime.to_owned(),
+                    };
+                    items.push(format!("use {runtime}::Transport;\n\n///RPC call error\npub type ClientError<E> = {runtime}::ClientError<E, {response_error}>;"));
+                },
+            }
+        }
+        match self.descriptor.as_ref() {
+            Some(descriptor) if descriptor.has_priority() => items.push(format!("use {shared}::{{MethodDescriptor, MethodPriority, ServiceDescriptor}};")),
+            Some(_) => items.push(format!("use {shared}::{{MethodDescriptor, ServiceDescriptor}};")),
+            None => (),
+        }
+        if let Some(RpcMethodMarkers { trait_path: None, .. }) = self.markers {
+            items.push(format!("use {shared}::Method;"));
+        }
+        if let Some(RpcServiceInstrumentation { style: InstrumentStyle::Observer, .. }) = self.instrumentation {
+            items.push(format!("use {shared}::RpcObserver;"));
+        }
+        if let Some(defines) = self.defines.as_ref() {
+            items.push(defines.to_string());
+        }
+        if let Some(method_enum) = self.method_enum.as_ref() {
+            items.push(method_enum.to_string());
+        }
+        if let Some(type_aliases) = self.type_aliases.as_ref() {
+            items.push(type_aliases.to_string());
+        }
+        if let Some(dispatcher) = self.dispatcher.as_ref() {
+            items.push(dispatcher.to_string());
+        }
+        if let Some(mock) = self.mock.as_ref() {
+            items.push(mock.to_string());
+        }
+        if let Some(instrumentation) = self.instrumentation.as_ref() {
+            items.push(instrumentation.to_string());
+        }
+        if let Some(client) = self.client.as_ref() {
+            items.push(client.to_string());
+        }
+        if let Some(descriptor) = self.descriptor.as_ref() {
+            items.push(descriptor.to_string());
+        }
+        if let Some(markers) = self.markers.as_ref() {
+            items.push(markers.to_string());
+        }
+        if self.assertions {
+            let assertions = self.assertions_block();
+            if !assertions.is_empty() {
+                items.push(assertions);
+            }
+        }
+
+        if !self.skip_docs {
+            for line in self.service.docs.iter() {
+                fmt.write_fmt(format_args!("///{}\n", DocLine(line)))?;
+            }
+        }
+        self.attributes.write_item(fmt, "")?;
+        fmt.write_fmt(format_args!("{visibility}mod {} {{\n", Ident(&name)))?;
+        for (idx, item) in items.iter().enumerate() {
+            if idx > 0 {
+                fmt.write_str("\n")?;
+            }
+            for line in item.lines() {
+                match line.is_empty() {
+                    true => fmt.write_str("\n")?,
+                    false => fmt.write_fmt(format_args!("{TAB}{line}\n"))?,
+                }
+            }
+        }
+        fmt.write_str("}")
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///Item of service, generated by [crate::build] in addition to method defines
+pub enum GenItem {
+    ///[RpcMethodEnum]
+    MethodEnum,
+    ///[RpcServiceDispatcher], with [RpcDispatchCodec]
+    Dispatcher,
+    ///[RpcClientStub], with [RpcClientTransport]
+    Client,
+    ///[RpcServiceDescriptor], with [RpcDescriptorTypes]
+    Descriptor,
+    ///[RpcTypeAliases]
+    TypeAliases,
+    ///[RpcMethodMarkers], with [RpcMethodTrait] unless crate's trait is used
+    Markers,
+    ///[RpcServiceMock], with dispatcher it requires
+    Mock,
+    ///[RpcServiceInstrumentation], with dispatcher it requires and [RpcObserverTrait] unless tracing is used
+    Instrumentation,
+}
+
+///Returns name of `service`, qualified by its namespace.
+fn qualified_name(service: &RpcService) -> String {
+    match service.namespace.as_ref() {
+        Some(namespace) => format!("{namespace}.{}", service.name),
+        None => service.name.clone(),
+    }
+}
+
+///Ensures that module names of `services` are unique, returning index of the service, that clashes with previous one.
+fn check_module_names(services: &[RpcService]) -> Result<(), (usize, GenError)> {
+    let mut names: Vec<(&RpcService, String)> = Vec::with_capacity(services.len());
+    for (idx, service) in services.iter().enumerate() {
+        let name = ident::to_snake(&service.name);
+        if let Some((prev, _)) = names.iter().find(|(_, prev)| *prev == name) {
+            return Err((idx, GenError::ModuleCollision {
+                first: qualified_name(prev),
+                second: qualified_name(service),
+                name,
+            }));
+        }
+        names.push((service, name));
+    }
+    Ok(())
+}
+
+///Generates items, shared by modules of every service, as selected by `config`.
+fn render_shared(service: &RpcService, config: &GenConfig) -> Vec<String> {
+    let items = &config.items;
+    let mut output = Vec::new();
+    if config.is_runtime {
+        return output;
+    }
+    //Client with codec uses the same codec as dispatcher
+    let is_client_codec = items.contains(&GenItem::Client) && config.is_client_codec;
+    if items.is_empty() || items.contains(&GenItem::Dispatcher) || items.contains(&GenItem::Mock) || items.contains(&GenItem::Instrumentation) || is_client_codec {
+        output.push(service.as_rpc_dispatcher_with(config).codec().to_string());
+    }
+    if items.contains(&GenItem::Instrumentation) && config.instrument_style == InstrumentStyle::Observer {
+        output.push(RpcObserverTrait.to_string());
+    }
+    if items.contains(&GenItem::Client) {
+        let mut client = service.as_client_stub_with(config);
+        client.defines.int_type = Some(config.int_type.unwrap_or(IntType::U16));
+        output.push(client.transport().to_string());
+    }
+    if items.contains(&GenItem::Descriptor) {
+        output.push(service.as_descriptor_with(config).types().to_string());
+    }
+    if items.contains(&GenItem::Markers) {
+        let markers = service.as_method_markers_with(config);
+        if markers.trait_path.is_none() {
+            output.push(markers.method_trait().to_string());
+        }
+    }
+    output
+}
+
+///Returns whether dispatchers of services are generated along with their router, as selected by `config`.
+fn is_routed(config: &GenConfig) -> bool {
+    let items = &config.items;
+    let is_dispatcher = items.is_empty() || items.contains(&GenItem::Dispatcher) || items.contains(&GenItem::Mock) || items.contains(&GenItem::Instrumentation);
+    is_dispatcher && config.service_router && config.router.is_none() && config.context != Some(ContextStyle::AssociatedType)
+}
+
+///Returns method defines of `service`, that every generated item uses, with method `ids`.
+fn service_defines<'a>(service: &'a RpcService, ids: &'a Assignments, config: &'a GenConfig) -> RpcMethodDefines<'a> {
+    let mut defines = service.as_rpc_method_defines_with(config);
+    defines.int_type = Some(config.int_type.unwrap_or(IntType::U16));
+    defines.assignments = Some(ids);
+    defines
+}
+
+///Generates module of `service` with items, selected by `config`, using method `ids`.
+fn render_service(service: &RpcService, ids: &Assignments, config: &GenConfig) -> Result<String, GenError> {
+    let items = &config.items;
+    //Every item must use the same ids
+    let defines = service_defines(service, ids, config);
+
+    let mut module = service.as_module_with(config).defines(Some(defines));
+    if items.contains(&GenItem::MethodEnum) {
+        let mut method_enum = service.as_rpc_method_enum_with(config);
+        //Indexes of service are not unique across services
+        if config.id_scope == IdScope::Schema {
+            method_enum.assignments = Some(ids);
+        }
+        module = module.with_method_enum(method_enum);
+    }
+    if items.contains(&GenItem::TypeAliases) || config.type_aliases {
+        module = module.with_type_aliases(service.as_type_aliases_with(config));
+    }
+    if items.is_empty() || items.contains(&GenItem::Dispatcher) || items.contains(&GenItem::Mock) || items.contains(&GenItem::Instrumentation) {
+        let dispatcher = service.as_rpc_dispatcher_with(config).defines(defines);
+        module = module.with_dispatcher(dispatcher);
+        if items.contains(&GenItem::Mock) {
+            module = module.with_mock(dispatcher.mock());
+        }
+        if items.contains(&GenItem::Instrumentation) {
+            module = module.with_instrumentation(dispatcher.instrumentation().style(config.instrument_style));
+        }
+    }
+    if items.contains(&GenItem::Client) {
+        module = module.with_client(service.as_client_stub_with(config).defines(defines));
+    }
+    if items.contains(&GenItem::Descriptor) {
+        let mut descriptor = service.as_descriptor_with(config);
+        descriptor.defines = defines;
+        module = module.with_descriptor(descriptor);
+    }
+    if items.contains(&GenItem::Markers) {
+        module = module.with_markers(service.as_method_markers_with(config).defines(defines));
+    }
+    module.render()
+}
+
+///Generates code of every service, each within its own module, named after service in snake case.
+///
+///Modules contain method defines and items, selected by [GenConfig::item], in the same order as `services`.
+///Items, shared by every module (e.g. `Codec` or `Transport` traits), are generated once, before modules.
+///
+///Method ids are `u16` indexes, unless integer type is specified by `config`.
+///
+///Fails if services have the same module name, or code of any service cannot be generated.
+///Returns empty string if there are no services.
+///
+///```rust
+///use flatbuffers_tools::{GenConfig, GenItem};
+///
+///let services = flatbuffers_tools::parse_services("rpc_service Storage { put(Req):Resp; }\nrpc_service Monitor { ping():Pong; }").unwrap();
+///let code = flatbuffers_tools::render_services(&services, &GenConfig::new().item(GenItem::Client)).unwrap();
+///assert_eq!(code.matches("pub trait Transport").count(), 1);
+///assert!(code.contains("pub mod storage {"));
+///assert!(code.contains("pub mod monitor {"));
+///```
+pub fn render_services(services: &[RpcService], config: &GenConfig) -> Result<String, GenError> {
+    render_services_with(&RustBackend, services, config)
+}
+
+///Assigns ids to methods of `service` according to [GenConfig::id_strategy], same as every built-in output does.
+///
+///Pinned ids must fit into [GenConfig::int_type], which is `u16` by default, or `u32` with [IdStrategy::Hash].
+pub fn assign_ids(service: &RpcService, config: &GenConfig) -> Result<Assignments, MethodIdError> {
+    check_pinned_ids(service, config.id_type())?;
+    let ids = method_ids(service, config.id_strategy)?;
+    Ok(Assignments {
+        service: service.name.clone(),
+        ids: service.methods.iter().map(|method| method.name.clone()).zip(ids).collect(),
+    })
+}
+
+///Assigns ids to methods of every service, that are unique across `services`, as with [IdScope::Schema] regardless of [GenConfig::id_scope].
+///
+///Assignments are in the same order as `services`, and are accepted by generators same as ids of [crate::registry::IdRegistry],
+///e.g. [RpcMethodDefines::assignments] or [RpcService::assigned_ids](crate::RpcService::assigned_ids).
+///Ids must fit into [GenConfig::int_type], which is `u16` by default, or `u32` with [IdStrategy::Hash].
+pub fn assign_global_ids(services: &[RpcService], config: &GenConfig) -> Result<Vec<Assignments>, MethodIdError> {
+    scoped_ids(services, config, IdScope::Schema).map_err(|(_, error)| error)
+}
+
+///Assigns ids to methods of every service within `scope`, failing with index of the service, which ids cannot be assigned.
+fn scoped_ids(services: &[RpcService], config: &GenConfig, scope: IdScope) -> Result<Vec<Assignments>, (usize, MethodIdError)> {
+    let ids = match scope {
+        IdScope::Service => return services.iter().enumerate().map(|(idx, service)| assign_ids(service, config).map_err(|error| (idx, error))).collect(),
+        IdScope::Schema => global_ids(services, config.id_strategy, config.id_type())?,
+    };
+    Ok(services.iter().zip(ids).map(|(service, ids)| Assignments {
+        service: service.name.clone(),
+        ids: service.methods.iter().map(|method| method.name.clone()).zip(ids).collect(),
+    }).collect())
+}
+
+///Output language of code generation
+///
+///Code is generated by [render_services_with], which assigns method ids with [assign_ids], so that every backend uses the same ids.
+///Built-in outputs are available as [RustBackend], [CBackend], [TypeScriptBackend], [PythonBackend] and [MarkdownBackend].
+///
+///Options of `config` are available through fields of [RpcMethodDefines], created by [RpcService::as_rpc_method_defines_with].
+///Names are converted same as by built-in outputs with [to_snake_case], [to_upper_snake_case] and [to_camel_case],
+///while [unique_method_names] ensures that converted names do not collide.
+///
+///```rust
+///use core::fmt;
+///use flatbuffers_tools::{Backend, GenConfig, GenError, RpcService, unique_method_names, to_upper_snake_case};
+///use flatbuffers_tools::registry::Assignments;
+///
+///struct Csv;
+///
+///impl Backend for Csv {
+///    fn extension(&self) -> &str {
+///        "csv"
+///    }
+///
+///    fn line_comment(&self) -> Option<&str> {
+///        None
+///    }
+///
+///    fn render_service(&self, service: &RpcService, ids: &Assignments, config: &GenConfig, out: &mut dyn fmt::Write) -> Result<(), GenError> {
+///        let prefix = service.as_rpc_method_defines_with(config).prefix;
+///        out.write_str("name,id\n")?;
+///        for (method, name) in unique_method_names(service, "column", to_upper_snake_case)? {
+///            let id = ids.id(&method.name).expect("every method to have id");
+///            out.write_fmt(format_args!("{prefix}{name},{id}\n"))?;
+///        }
+///        Ok(())
+///    }
+///}
+///
+///let services = flatbuffers_tools::parse_services("rpc_service Storage { Put(Req):Resp; GetItem(Key):Resp; }").unwrap();
+///let config = GenConfig::new().prefix("RPC_");
+///let csv = flatbuffers_tools::render_services_with(&Csv, &services, &config).unwrap();
+///assert_eq!(csv, "name,id\nRPC_PUT,0\nRPC_GET_ITEM,1\n");
+///
+/////Build helpers accept the same backend
+///let dir = std::env::temp_dir().join(format!("fbs-backend-{}", std::process::id()));
+///std::fs::create_dir_all(&dir).unwrap();
+///std::fs::write(dir.join("storage.fbs"), "rpc_service Storage { Put(Req):Resp; }").unwrap();
+///let mut build = flatbuffers_tools::build::BuildConfig::new().rerun_if_changed(false).backend(Csv);
+///let written = flatbuffers_tools::build::generate_from_dir_with(&dir, &dir, &GenConfig::new(), &mut build).unwrap();
+///assert_eq!(written, [dir.join("storage.csv")]);
+///assert_eq!(std::fs::read_to_string(&written[0]).unwrap(), "name,id\nPUT,0\n");
+///std::fs::remove_dir_all(&dir).unwrap();
+///```
+pub trait Backend: Sync {
+    ///Extension of output files, without leading dot, e.g. `rs`.
+    fn extension(&self) -> &str;
+
+    #[inline]
+    ///Returns prefix of line comment, used by header of generated files, or `None` to omit header.
+    ///
+    ///Defaults to `//`.
+    fn line_comment(&self) -> Option<&str> {
+        Some("//")
+    }
+
+    #[inline]
+    ///Checks that `services` can be generated together, returning index of the service, that clashes with previous ones.
+    ///
+    ///Nothing is checked by default.
+    fn check_services(&self, _services: &[RpcService]) -> Result<(), (usize, GenError)> {
+        Ok(())
+    }
+
+    #[inline]
+    ///Writes code, shared by every service, that precedes code of services.
+    ///
+    ///Nothing is written by default.
+    fn render_shared(&self, _services: &[RpcService], _config: &GenConfig, _out: &mut dyn fmt::Write) -> Result<(), GenError> {
+        Ok(())
+    }
+
+    ///Writes code of `service`, where methods have `ids`.
+    fn render_service(&self, service: &RpcService, ids: &Assignments, config: &GenConfig, out: &mut dyn fmt::Write) -> Result<(), GenError>;
+}
+
+///Generates code of every service with `backend`, separating code of each service with empty line.
+///
+///Fails with index of the service, which cannot be generated, or `0` if shared code cannot be generated.
+pub(crate) fn render_with(backend: &dyn Backend, services: &[RpcService], config: &GenConfig) -> Result<String, (usize, GenError)> {
+    if services.is_empty() {
+        return Ok(String::new());
+    }
+    config.validate().map_err(|error| (0, error))?;
+    backend.check_services(services)?;
+    let assignments = scoped_ids(services, config, config.id_scope).map_err(|(idx, error)| (idx, error.into()))?;
+
+    let mut output = String::new();
+    backend.render_shared(services, config, &mut output).map_err(|error| (0, error))?;
+    for (idx, (service, ids)) in services.iter().zip(assignments.iter()).enumerate() {
+        if !output.is_empty() {
+            output.push_str("\n\n");
+        }
+        backend.render_service(service, ids, config, &mut output).map_err(|error| (idx, error))?;
+    }
+    Ok(output)
+}
+
+#[inline]
+///Generates code of every service with `backend`, in the same order as `services`.
+///
+///Shared code comes first, and code of each service is separated with empty line.
+///Returns empty string if there are no services.
+pub fn render_services_with(backend: &dyn Backend, services: &[RpcService], config: &GenConfig) -> Result<String, GenError> {
+    render_with(backend, services, config).map_err(|(_, error)| error)
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+///Rust modules of services, as generated by [render_services]
+pub struct RustBackend;
+
+impl Backend for RustBackend {
+    #[inline(always)]
+    fn extension(&self) -> &str {
+        "rs"
+    }
+
+    #[inline]
+    fn check_services(&self, services: &[RpcService]) -> Result<(), (usize, GenError)> {
+        check_module_names(services)
+    }
+
+    fn render_shared(&self, services: &[RpcService], config: &GenConfig, out: &mut dyn fmt::Write) -> Result<(), GenError> {
+        if let Some(service) = services.first() {
+            out.write_str(&render_shared(service, config).join("\n\n"))?;
+        }
+        if is_routed(config) {
+            let assignments = scoped_ids(services, config, config.id_scope).map_err(|(_, error)| error)?;
+            let dispatchers: Vec<_> = services.iter().zip(assignments.iter()).map(|(service, ids)| service.as_rpc_dispatcher_with(config).defines(service_defines(service, ids, config))).collect();
+            let router = RpcServiceRouter::new(&dispatchers).visibility(config.visibility).render()?;
+            if let Some(runtime) = config.runtime_path() {
+                out.write_fmt(format_args!("use {runtime}::{{Codec, DispatchError}};"))?;
+            }
+            out.write_str("\n\n")?;
+            out.write_str(&router)?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn render_service(&self, service: &RpcService, ids: &Assignments, config: &GenConfig, out: &mut dyn fmt::Write) -> Result<(), GenError> {
+        out.write_str(&render_service(service, ids, config)?)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+///C headers of services, generated by [RpcCHeader]
+pub struct CBackend;
+
+impl Backend for CBackend {
+    #[inline(always)]
+    fn extension(&self) -> &str {
+        "h"
+    }
+
+    fn render_service(&self, service: &RpcService, ids: &Assignments, config: &GenConfig, out: &mut dyn fmt::Write) -> Result<(), GenError> {
+        let mut header = service.as_c_header_with(config);
+        header.defines.assignments = Some(ids);
+        out.write_str(&header.render()?)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+///TypeScript modules of services, generated by [RpcTypeScript]
+pub struct TypeScriptBackend;
+
+impl Backend for TypeScriptBackend {
+    #[inline(always)]
+    fn extension(&self) -> &str {
+        "ts"
+    }
+
+    fn render_service(&self, service: &RpcService, ids: &Assignments, config: &GenConfig, out: &mut dyn fmt::Write) -> Result<(), GenError> {
+        let mut module = service.as_typescript_with(config);
+        module.defines.assignments = Some(ids);
+        out.write_str(&module.render()?)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+///Python modules of services, generated by [RpcPython]
+pub struct PythonBackend;
+
+impl Backend for PythonBackend {
+    #[inline(always)]
+    fn extension(&self) -> &str {
+        "py"
+    }
+
+    #[inline(always)]
+    fn line_comment(&self) -> Option<&str> {
+        Some("#")
+    }
+
+    fn render_service(&self, service: &RpcService, ids: &Assignments, config: &GenConfig, out: &mut dyn fmt::Write) -> Result<(), GenError> {
+        let mut module = service.as_python_with(config);
+        module.defines.assignments = Some(ids);
+        out.write_str(&module.render()?)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+///Markdown reference of services, generated by [RpcMarkdown]
+///
+///```rust
+///use flatbuffers_tools::{GenConfig, MarkdownBackend};
+///
+///let services = flatbuffers_tools::parse_services("namespace Rpc;\nrpc_service Storage { Put(Req):Resp; }\nrpc_service Monitor { Ping():Pong; }").unwrap();
+///let document = flatbuffers_tools::render_services_with(&MarkdownBackend, &services, &GenConfig::new()).unwrap();
+///assert!(document.starts_with("## Storage\n\nNamespace: `Rpc`\n\n"));
+///assert!(document.contains("\n\n## Monitor\n"));
+///assert!(document.ends_with("| Ping | 0 |  | `Pong` |  |  |\n"));
+///```
+pub struct MarkdownBackend;
+
+impl Backend for MarkdownBackend {
+    #[inline(always)]
+    fn extension(&self) -> &str {
+        "md"
+    }
+
+    #[inline(always)]
+    fn line_comment(&self) -> Option<&str> {
+        None
+    }
+
+    fn render_service(&self, service: &RpcService, ids: &Assignments, config: &GenConfig, out: &mut dyn fmt::Write) -> Result<(), GenError> {
+        let mut document = service.as_markdown_with(config);
+        document.defines.assignments = Some(ids);
+        out.write_str(&document.render()?)?;
+        Ok(())
+    }
+}
+
+//Callback to map schema type to Rust path
+type TypePathFn = dyn Fn(&TypeName) -> String + Send + Sync;
+//Callback to determine kind of schema type
+type TypeKindFn = dyn Fn(&TypeName) -> TypeKind + Send + Sync;
+
+#[derive(Clone)]
+///Code generation options, shared by every formatter.
+///
+///Default options are the same as used by formatters without configuration, e.g. [RpcService::as_rpc_method_defines].
+///
+///Configuration can be reused to generate code of any number of services in the same style:
+///
+///```rust
+///use flatbuffers_tools::{GenConfig, IntType, RpcService};
+///
+///let config = GenConfig::new().int_type(IntType::U16).include_service_name(true);
+///let storage: RpcService = "rpc_service Storage { put(Req):Resp; }".parse().unwrap();
+///let monitor: RpcService = "rpc_service Monitor { ping():Pong; }".parse().unwrap();
+///let defines = format!("{}\n{}", storage.as_rpc_method_defines_with(&config), monitor.as_rpc_method_defines_with(&config));
+///assert!(defines.contains("pub const STORAGE_PUT: u16 = 0;"));
+///assert!(defines.contains("pub const MONITOR_PING: u16 = 0;"));
+///```
+pub struct GenConfig {
+    pub(crate) visibility: Visibility,
+    pub(crate) prefix: String,
+    pub(crate) include_service_name: bool,
+    pub(crate) int_type: Option<IntType>,
+    pub(crate) id_strategy: IdStrategy,
+    pub(crate) id_scope: IdScope,
+    pub(crate) service_router: bool,
+    pub(crate) method_count: bool,
+    pub(crate) fingerprint: bool,
+    pub(crate) name_lookup: bool,
+    pub(crate) id_lookup: bool,
+    pub(crate) registry: bool,
+    pub(crate) skip_docs: bool,
+    pub(crate) context: Option<ContextStyle>,
+    pub(crate) return_style: ReturnStyle<'static>,
+    pub(crate) type_path: Option<std::sync::Arc<TypePathFn>>,
+    pub(crate) type_kind: Option<std::sync::Arc<TypeKindFn>>,
+    pub(crate) is_no_std: bool,
+    pub(crate) is_alloc: bool,
+    pub(crate) c_header_style: CHeaderStyle,
+    pub(crate) ts_style: TsStyle,
+    pub(crate) proto_namespace: ProtoNamespace,
+    pub(crate) router: Option<AsyncRouter<'static>>,
+    pub(crate) item_attributes: Vec<String>,
+    pub(crate) method_attributes: Vec<String>,
+    pub(crate) named_method_attributes: Vec<(String, String)>,
+    pub(crate) category_attributes: Vec<(ItemCategory, String)>,
+    pub(crate) is_sorted: bool,
+    pub(crate) deprecated_policy: DeprecatedPolicy,
+    pub(crate) type_aliases: bool,
+    pub(crate) argument_aliases: ArgumentAliases,
+    pub(crate) alias_lifetime: Option<&'static str>,
+    pub(crate) is_crate_method_trait: bool,
+    pub(crate) default_body: Option<DefaultBody<'static>>,
+    pub(crate) items: Vec<GenItem>,
+    pub(crate) is_recursive: bool,
+    pub(crate) is_framing: bool,
+    pub(crate) is_client_codec: bool,
+    pub(crate) is_runtime: bool,
+    pub(crate) trait_name: Option<&'static str>,
+    pub(crate) receiver: Receiver,
+    pub(crate) send_sync: bool,
+    pub(crate) method_timeout: bool,
+    pub(crate) instrument_style: InstrumentStyle,
+    pub(crate) assertions: bool,
+    pub(crate) asyncness: Asyncness,
+}
+
+impl GenConfig {
+    ///Creates default configuration
+    pub const fn new() -> Self {
+        Self {
+            visibility: Visibility::Pub,
+            prefix: String::new(),
+            include_service_name: false,
+            int_type: None,
+            id_strategy: IdStrategy::Index,
+            id_scope: IdScope::Service,
+            service_router: false,
+            method_count: false,
+            fingerprint: false,
+            name_lookup: false,
+            id_lookup: false,
+            registry: false,
+            skip_docs: false,
+            context: None,
+            return_style: ReturnStyle::Plain,
+            type_path: None,
+            type_kind: None,
+            is_no_std: false,
+            is_alloc: false,
+            c_header_style: CHeaderStyle::Defines,
+            ts_style: TsStyle::ConstEnum,
+            proto_namespace: ProtoNamespace::Package,
+            router: None,
+            item_attributes: Vec::new(),
+            method_attributes: Vec::new(),
+            named_method_attributes: Vec::new(),
+            category_attributes: Vec::new(),
+            is_sorted: false,
+            deprecated_policy: DeprecatedPolicy::Annotate,
+            type_aliases: false,
+            argument_aliases: ArgumentAliases::Tuple,
+            alias_lifetime: None,
+            is_crate_method_trait: false,
+            default_body: None,
+            items: Vec::new(),
+            is_recursive: false,
+            is_framing: false,
+            is_client_codec: false,
+            is_runtime: false,
+            trait_name: None,
+            receiver: Receiver::Mut,
+            send_sync: false,
+            method_timeout: false,
+            instrument_style: InstrumentStyle::Observer,
+            assertions: false,
+            asyncness: Asyncness::Sync,
+        }
+    }
+
+    #[inline]
+    ///Sets visibility of generated items.
+    ///
+    ///Refer to [RpcMethodDefines::visibility]
+    pub fn visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    #[inline]
+    ///Sets prefix of method constants' names.
+    ///
+    ///Refer to [RpcMethodDefines::prefix]
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_owned();
+        self
+    }
+
+    #[inline]
+    ///Sets whether method constant's name includes service name.
+    ///
+    ///Refer to [RpcMethodDefines::include_service_name]
+    pub fn include_service_name(mut self, include_service_name: bool) -> Self {
+        self.include_service_name = include_service_name;
+        self
+    }
+
+    #[inline]
+    ///Sets integer type of method ids.
+    ///
+    ///Refer to [RpcMethodDefines::int_type] and [RpcMethodEnum::int_type]
+    pub fn int_type(mut self, int_type: IntType) -> Self {
+        self.int_type = Some(int_type);
+        self
+    }
+
+    #[inline]
+    ///Sets strategy to assign method ids.
+    ///
+    ///Refer to [RpcMethodDefines::id_strategy]
+    pub fn id_strategy(mut self, id_strategy: IdStrategy) -> Self {
+        self.id_strategy = id_strategy;
+        self
+    }
+
+    #[inline]
+    ///Sets scope, within which method ids are unique, [IdScope::Service] by default.
+    ///
+    ///With [IdScope::Schema] ids of [render_services] and [crate::build] are assigned by [assign_global_ids],
+    ///and method enum uses them as discriminants.
+    pub fn id_scope(mut self, id_scope: IdScope) -> Self {
+        self.id_scope = id_scope;
+        self
+    }
+
+    #[inline]
+    ///Sets whether [render_services] generates [RpcServiceRouter] of every service's dispatcher, along with shared items.
+    ///
+    ///Method ids must be unique across services, e.g. with [IdScope::Schema].
+    ///Router is not generated with async router or [ContextStyle::AssociatedType], as neither is supported by it.
+    pub fn service_router(mut self, service_router: bool) -> Self {
+        self.service_router = service_router;
+        self
+    }
+
+    #[inline]
+    ///Sets whether to define `METHOD_COUNT` constant.
+    ///
+    ///Refer to [RpcMethodDefines::method_count]
+    pub fn method_count(mut self, method_count: bool) -> Self {
+        self.method_count = method_count;
+        self
+    }
+
+    #[inline]
+    ///Sets whether to define `SERVICE_FINGERPRINT` constant.
+    ///
+    ///Refer to [RpcMethodDefines::fingerprint]
+    pub fn fingerprint(mut self, fingerprint: bool) -> Self {
+        self.fingerprint = fingerprint;
+        self
+    }
+
+    #[inline]
+    ///Sets whether to define `rpc_method_name` function.
+    ///
+    ///Refer to [RpcMethodDefines::name_lookup]
+    pub fn name_lookup(mut self, name_lookup: bool) -> Self {
+        self.name_lookup = name_lookup;
+        self
+    }
+
+    #[inline]
+    ///Sets whether to define `rpc_method_id` function.
+    ///
+    ///Refer to [RpcMethodDefines::id_lookup]
+    pub fn id_lookup(mut self, id_lookup: bool) -> Self {
+        self.id_lookup = id_lookup;
+        self
+    }
+
+    #[inline]
+    ///Sets whether to define `METHODS` static.
+    ///
+    ///Refer to [RpcMethodDefines::registry]
+    pub fn registry(mut self, registry: bool) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    #[inline]
+    ///Sets whether documentation is to be omitted.
+    pub fn skip_docs(mut self, skip_docs: bool) -> Self {
+        self.skip_docs = skip_docs;
+        self
+    }
+
+    #[inline]
+    ///Sets style of context to pass to handler methods.
+    ///
+    ///Refer to [RpcServiceDispatcher::context]
+    pub fn context(mut self, context: ContextStyle) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    #[inline]
+    ///Sets return type of handler methods.
+    ///
+    ///Refer to [RpcServiceDispatcher::return_style]
+    pub fn return_style(mut self, return_style: ReturnStyle<'static>) -> Self {
+        self.return_style = return_style;
+        self
+    }
+
+    #[inline]
+    ///Sets template of handler trait name, where `{service}` is replaced with service name.
+    ///
+    ///Refer to [RpcServiceDispatcher::trait_name]
+    ///
+    ///```rust
+    ///use flatbuffers_tools::{GenConfig, GenItem};
+    ///
+    ///let services = flatbuffers_tools::parse_services("rpc_service Storage { put(Req):Resp; }").unwrap();
+    ///let config = GenConfig::new().trait_name("Rpc{service}Handler").item(GenItem::Dispatcher).item(GenItem::Mock).item(GenItem::Instrumentation);
+    ///let code = flatbuffers_tools::render_services(&services, &config).unwrap();
+    ///assert!(code.contains("    pub trait RpcStorageHandler {\n"));
+    ///assert!(code.contains("    pub fn dispatch<H: RpcStorageHandler, C: Codec<crate::interface::Req> + Codec<crate::interface::Resp>>(handler: &mut H, method: u16, payload: &[u8])"));
+    ///assert!(code.contains("    impl RpcStorageHandler for MockRpcStorageHandler {\n"));
+    ///assert!(code.contains("    impl<S: RpcStorageHandler, O: RpcObserver> RpcStorageHandler for InstrumentedRpcStorageHandler<S, O> {\n"));
+    ///assert!(!code.contains("StorageService"));
+    ///```
+    pub fn trait_name(mut self, trait_name: &'static str) -> Self {
+        self.trait_name = Some(trait_name);
+        self
+    }
+
+    #[inline]
+    ///Sets receiver of handler methods.
+    ///
+    ///Refer to [RpcServiceDispatcher::receiver]
+    pub fn receiver(mut self, receiver: Receiver) -> Self {
+        self.receiver = receiver;
+        self
+    }
+
+    #[inline]
+    ///Sets whether handler trait requires `Send + Sync`.
+    ///
+    ///Refer to [RpcServiceDispatcher::send_sync]
+    pub fn send_sync(mut self, send_sync: bool) -> Self {
+        self.send_sync = send_sync;
+        self
+    }
+
+    #[inline]
+    ///Sets asyncness of handler methods.
+    ///
+    ///Refer to [Asyncness]
+    pub fn asyncness(mut self, asyncness: Asyncness) -> Self {
+        self.asyncness = asyncness;
+        self
+    }
+
+    #[inline]
+    ///Sets whether dispatcher generates `method_timeout`.
+    ///
+    ///Refer to [RpcServiceDispatcher::method_timeout]
+    ///
+    ///Values of `timeout_ms` and `priority` are also written to descriptor, which always fails on invalid value.
+    ///
+    ///```rust
+    ///use flatbuffers_tools::{GenConfig, GenError, GenItem};
+    ///
+    ///let schema = "rpc_service Storage {\n  Put(Value):Key (timeout_ms: \"1500\", priority: \"high\");\n  Get(Key):Value (priority: \"low\");\n  Ping():Key;\n}";
+    ///let services = flatbuffers_tools::parse_services(schema).unwrap();
+    ///let config = GenConfig::new().item(GenItem::Dispatcher).item(GenItem::Descriptor).method_timeout(true);
+    ///let code = flatbuffers_tools::render_services(&services, &config).unwrap();
+    ///assert!(code.contains("    pub fn method_timeout(method: u16) -> Option<core::time::Duration> {\n        match method {\n            PUT => Some(core::time::Duration::from_millis(1500)),\n            _ => None,\n        }\n    }\n"));
+    ///assert!(code.contains("    use super::{MethodDescriptor, MethodPriority, ServiceDescriptor};\n"));
+    ///assert!(code.contains("                name: \"Put\",\n                id: 0,\n                arguments: &[\"Value\"],\n                response: \"Key\",\n                attributes: &[(\"timeout_ms\", Some(\"1500\")), (\"priority\", Some(\"high\"))],\n                timeout_ms: Some(1500),\n                priority: Some(MethodPriority::High),\n"));
+    ///assert!(code.contains("                attributes: &[(\"priority\", Some(\"low\"))],\n                timeout_ms: None,\n                priority: Some(MethodPriority::Low),\n"));
+    ///assert!(code.contains("                attributes: &[],\n                timeout_ms: None,\n                priority: None,\n"));
+    ///assert!(!flatbuffers_tools::render_services(&services, &config.clone().method_timeout(false)).unwrap().contains("method_timeout"));
+    ///
+    /////Malformed values are reported with method name
+    ///let services = flatbuffers_tools::parse_services(&schema.replace("\"1500\"", "\"1.5s\"")).unwrap();
+    ///let error = flatbuffers_tools::render_services(&services, &config).unwrap_err();
+    ///assert!(matches!(error, GenError::InvalidAttribute { key: "timeout_ms", .. }));
+    ///assert_eq!(error.to_string(), "Method 'Put' of service 'Storage' has invalid 'timeout_ms' attribute '1.5s', expected unsigned integer");
+    ///
+    ///let services = flatbuffers_tools::parse_services(&schema.replace("\"low\"", "\"urgent\"")).unwrap();
+    ///let error = flatbuffers_tools::render_services(&services, &GenConfig::new().item(GenItem::Descriptor)).unwrap_err();
+    ///assert_eq!(error.to_string(), "Method 'Get' of service 'Storage' has unknown priority 'urgent', expected one of 'low', 'normal' or 'high'");
+    ///```
+    pub fn method_timeout(mut self, method_timeout: bool) -> Self {
+        self.method_timeout = method_timeout;
+        self
+    }
+
+    #[inline]
+    ///Sets callback to map schema types to Rust paths.
+    ///
+    ///Refer to [RpcServiceDispatcher::type_path]
+    pub fn type_path<F: Fn(&TypeName) -> String + Send + Sync + 'static>(mut self, type_path: F) -> Self {
+        self.type_path = Some(std::sync::Arc::new(type_path));
+        self
+    }
+
+    #[inline]
+    ///Sets callback to determine kind of schema types, e.g. which of them are tables.
+    ///
+    ///Refer to [RpcServiceDispatcher::type_kind]
+    pub fn type_kind<F: Fn(&TypeName) -> TypeKind + Send + Sync + 'static>(mut self, type_kind: F) -> Self {
+        self.type_kind = Some(std::sync::Arc::new(type_kind));
+        self
+    }
+
+    #[inline]
+    ///Sets whether generated code is to use `core` only, instead of `std`.
+    ///
+    ///Refer to [StdLib::Core]
+    pub fn no_std(mut self, is_no_std: bool) -> Self {
+        self.is_no_std = is_no_std;
+        self
+    }
+
+    #[inline]
+    ///Sets whether generated code may use `alloc`, when `no_std` is set.
+    ///
+    ///Refer to [StdLib::Alloc]
+    pub fn alloc(mut self, is_alloc: bool) -> Self {
+        self.is_alloc = is_alloc;
+        self
+    }
+
+    #[inline]
+    ///Sets style of method ids within C header.
+    ///
+    ///Refer to [RpcCHeader::style]
+    pub fn c_header_style(mut self, style: CHeaderStyle) -> Self {
+        self.c_header_style = style;
+        self
+    }
+
+    #[inline]
+    ///Sets async router to generate instead of `dispatch`.
+    ///
+    ///Refer to [RpcServiceDispatcher::router]
+    pub fn router(mut self, router: AsyncRouter<'static>) -> Self {
+        self.router = Some(router);
+        self
+    }
+
+    #[inline]
+    ///Sets whether methods are written in alphabetical order.
+    ///
+    ///Refer to [RpcMethodDefines::is_sorted]
+    pub fn sorted(mut self, is_sorted: bool) -> Self {
+        self.is_sorted = is_sorted;
+        self
+    }
+
+    #[inline]
+    ///Sets handling of deprecated methods.
+    ///
+    ///Refer to [DeprecatedPolicy]
+    pub fn deprecated_policy(mut self, deprecated_policy: DeprecatedPolicy) -> Self {
+        self.deprecated_policy = deprecated_policy;
+        self
+    }
+
+    #[inline]
+    ///Sets whether handler trait refers to type aliases instead of types.
+    ///
+    ///Refer to [RpcServiceDispatcher::aliases]
+    pub fn type_aliases(mut self, type_aliases: bool) -> Self {
+        self.type_aliases = type_aliases;
+        self
+    }
+
+    #[inline]
+    ///Sets aliases of methods with multiple arguments.
+    ///
+    ///Refer to [RpcTypeAliases::arguments]
+    pub fn argument_aliases(mut self, argument_aliases: ArgumentAliases) -> Self {
+        self.argument_aliases = argument_aliases;
+        self
+    }
+
+    #[inline]
+    ///Sets lifetime of types, referred by type aliases, e.g. `'a`.
+    ///
+    ///Refer to [RpcTypeAliases::lifetime]
+    pub fn alias_lifetime(mut self, lifetime: &'static str) -> Self {
+        self.alias_lifetime = Some(lifetime);
+        self
+    }
+
+    #[inline]
+    ///Sets whether method markers implement `flatbuffers_tools::Method`, instead of generated trait.
+    ///
+    ///Refer to [RpcMethodMarkers::trait_path]
+    pub fn crate_method_trait(mut self, is_crate_method_trait: bool) -> Self {
+        self.is_crate_method_trait = is_crate_method_trait;
+        self
+    }
+
+    #[inline]
+    ///Sets whether client and dispatcher use framing of `flatbuffers_tools::framing`, which requires `framing` feature.
+    ///
+    ///Client uses `u16` method ids, unless integer type is specified.
+    ///
+    ///Refer to [RpcClientStub::framing] and [RpcServiceDispatcher::framing]
+    ///
+    ///```rust
+    ///use flatbuffers_tools::{GenConfig, GenItem};
+    ///
+    ///let services = flatbuffers_tools::parse_services("rpc_service Storage { put(Req):Resp; }").unwrap();
+    ///let config = GenConfig::new().framing(true).item(GenItem::Client).item(GenItem::Dispatcher);
+    ///let code = flatbuffers_tools::render_services(&services, &config).unwrap();
+    ///assert!(code.contains("fn call(&mut self, frame: &[u8]) -> Result<Vec<u8>, Self::Error>;"));
+    ///assert!(code.contains("flatbuffers_tools::framing::encode_frame(PUT, self.builder.finished_data(), &mut self.frame);"));
+    ///assert!(code.contains("pub fn dispatch_frame<H: StorageService, C: Codec<crate::interface::Req> + Codec<crate::interface::Resp>>(handler: &mut H, frame: &[u8])"));
+    ///assert!(code.contains("pub const PUT: u16 = 0;"));
+    ///
+    ///let error = flatbuffers_tools::render_services(&services, &config.no_std(true)).unwrap_err();
+    ///assert_eq!(error.to_string(), "Framing requires alloc");
+    ///```
+    pub fn framing(mut self, is_framing: bool) -> Self {
+        self.is_framing = is_framing;
+        self
+    }
+
+    #[inline]
+    ///Sets whether client encodes messages by `Codec` of dispatcher, so that it has no dependency on `flatbuffers`.
+    ///
+    ///Refer to [RpcClientStub::codec]
+    pub fn client_codec(mut self, is_client_codec: bool) -> Self {
+        self.is_client_codec = is_client_codec;
+        self
+    }
+
+    #[inline]
+    ///Sets whether modules import shared items from `flatbuffers_tools::runtime`, which requires `runtime` feature, instead of generating them.
+    ///
+    ///Then output has no items, shared by services, and outputs of several schemas can be included within the same module.
+    ///Method ids are to be `u16`, and library `std` or `alloc`, as it is what runtime's items use.
+    ///Method markers implement `flatbuffers_tools::runtime::Method`, which is the same as [crate_method_trait](Self::crate_method_trait).
+    ///
+    ///Refer to [RpcServiceModule::runtime]
+    ///
+    ///```rust
+    ///use flatbuffers_tools::{GenConfig, GenItem};
+    ///
+    ///let services = flatbuffers_tools::parse_services("rpc_service Storage { put(Req):Resp; }").unwrap();
+    ///let config = GenConfig::new().runtime(true).item(GenItem::Dispatcher).item(GenItem::Client);
+    ///let code = flatbuffers_tools::render_services(&services, &config).unwrap();
+    ///assert!(code.starts_with("pub mod storage {\n    use ::flatbuffers_tools::runtime::{Codec, DispatchError};\n"));
+    ///assert!(code.contains("    pub type ClientError<E> = ::flatbuffers_tools::runtime::ClientError<E, flatbuffers::InvalidFlatbuffer>;\n"));
+    ///assert!(!code.contains("pub trait Codec<T>"));
+    ///```
+    pub fn runtime(mut self, is_runtime: bool) -> Self {
+        self.is_runtime = is_runtime;
+        self
+    }
+
+    #[inline]
+    ///Sets reporting of calls by [GenItem::Instrumentation].
+    ///
+    ///Refer to [RpcServiceInstrumentation::style]
+    ///
+    ///```rust
+    ///use flatbuffers_tools::{AsyncRouter, GenConfig, GenItem, InstrumentStyle};
+    ///
+    ///let services = flatbuffers_tools::parse_services("rpc_service Storage { put(Req):Resp; }").unwrap();
+    ///let config = GenConfig::new().instrument_style(InstrumentStyle::Tracing).router(AsyncRouter::new()).item(GenItem::Instrumentation);
+    ///let code = flatbuffers_tools::render_services(&services, &config).unwrap();
+    ///assert!(code.contains("#[tracing::instrument(name = \"put\", skip_all)]"));
+    ///assert!(code.contains("Box::pin(async move { self.inner.put(request).await })"));
+    ///assert!(code.contains("impl<S: StorageService> StorageService for InstrumentedStorageService<S> {"));
+    ///assert!(!code.contains("RpcObserver"));
+    ///```
+    pub fn instrument_style(mut self, style: InstrumentStyle) -> Self {
+        self.instrument_style = style;
+        self
+    }
+
+    #[inline]
+    ///Sets default body of handler methods.
+    ///
+    ///Refer to [RpcServiceDispatcher::default_body]
+    pub fn default_body(mut self, default_body: DefaultBody<'static>) -> Self {
+        self.default_body = Some(default_body);
+        self
+    }
+
+    ///Adds item to generate by [crate::build], which generates only [GenItem::Dispatcher] by default.
+    ///
+    ///Method defines are generated regardless.
+    pub fn item(mut self, item: GenItem) -> Self {
+        if !self.items.contains(&item) {
+            self.items.push(item);
         }
-        fmt.write_fmt(format_args!("{TAB}{TAB}_ => None,\n"))?;
-        fmt.write_fmt(format_args!("{TAB}}}\n"))?;
+        self
+    }
 
-        //end
-        fmt.write_str("}")
+    #[inline]
+    ///Sets whether modules of services end with compile-time assertions, that their items are consistent.
+    ///
+    ///Refer to [RpcServiceModule::assertions]
+    ///
+    ///```rust
+    ///use flatbuffers_tools::{GenConfig, GenItem};
+    ///
+    ///let services = flatbuffers_tools::parse_services("rpc_service Storage { Put(Req):Resp; Get(Key):Resp; }").unwrap();
+    ///let config = GenConfig::new().item(GenItem::MethodEnum).item(GenItem::Descriptor).method_count(true).name_lookup(true).registry(true).assertions(true);
+    ///let code = flatbuffers_tools::render_services(&services, &config).unwrap();
+    ///assert!(code.contains("const _: () = {"));
+    ///assert!(code.contains("assert!(StorageMethod::Get as u64 == GET as u64);"));
+    ///assert!(!flatbuffers_tools::render_services(&services, &config.clone().assertions(false)).unwrap().contains("const _"));
+    ///```
+    pub fn assertions(mut self, assertions: bool) -> Self {
+        self.assertions = assertions;
+        self
+    }
+
+    #[inline]
+    ///Sets whether [crate::build::generate_from_dir] looks for schemas within sub-directories.
+    ///
+    ///Defaults to `false`.
+    pub fn recursive(mut self, is_recursive: bool) -> Self {
+        self.is_recursive = is_recursive;
+        self
+    }
+
+    ///Adds custom attribute to generated trait and module, e.g. `#[allow(clippy::too_many_arguments)]`
+    ///
+    ///Attribute is written verbatim on its own line above the item.
+    ///
+    ///Generation fails with [GenError::EmptyAttribute], if `attribute` is empty.
+    pub fn item_attribute<T: Into<String>>(mut self, attribute: T) -> Self {
+        self.item_attributes.push(attribute.into());
+        self
+    }
+
+    ///Adds custom attribute to every generated trait method, e.g. `#[cfg_attr(feature = "tracing", tracing::instrument)]`
+    ///
+    ///Generation fails with [GenError::EmptyAttribute], if `attribute` is empty.
+    pub fn method_attribute<T: Into<String>>(mut self, attribute: T) -> Self {
+        self.method_attributes.push(attribute.into());
+        self
+    }
+
+    ///Adds custom attribute to generated trait method of `method`, as declared within schema.
+    ///
+    ///Generation fails, if service has no such method, refer to [RpcServiceDispatcher::render].
+    ///
+    ///Generation fails with [GenError::EmptyAttribute], if `attribute` is empty.
+    pub fn named_method_attribute<M: Into<String>, T: Into<String>>(mut self, method: M, attribute: T) -> Self {
+        self.named_method_attributes.push((method.into(), attribute.into()));
+        self
+    }
+
+    ///Adds custom attribute to every generated item of `category`, e.g. `#[doc(hidden)]` for [ItemCategory::Constants]
+    ///
+    ///Attribute is written verbatim on its own line above every item, after its documentation.
+    ///Items of other categories, as well as trait and enum, are not affected.
+    ///
+    ///Generation fails with [GenError::EmptyAttribute], if `attribute` is empty.
+    ///
+    ///```rust
+    ///use flatbuffers_tools::{GenConfig, GenError, GenItem, ItemCategory};
+    ///
+    ///let services = flatbuffers_tools::parse_services("rpc_service Storage {\n  ///Stores value\n  Put(Value):Key;\n  Get(Key):Value (deprecated);\n}").unwrap();
+    ///let config = GenConfig::new().item(GenItem::MethodEnum).item(GenItem::Descriptor).item(GenItem::Markers).method_count(true).name_lookup(true)
+    ///    .category_attribute(ItemCategory::Constants, "#[doc(hidden)]")
+    ///    .category_attribute(ItemCategory::Lookup, "#[doc(hidden)]")
+    ///    .category_attribute(ItemCategory::Descriptor, "#[deprecated(note = \"use descriptor of v2\")]")
+    ///    .category_attribute(ItemCategory::Markers, "#[cfg_attr(docsrs, doc(cfg(feature = \"unstable\")))]");
+    ///let code = flatbuffers_tools::render_services(&services, &config).unwrap();
+    ///assert!(code.contains("    #[doc(hidden)]\n    pub const METHOD_COUNT: usize = 2;\n    ///Stores value\n    #[doc(hidden)]\n    pub const PUT: u16 = 0;\n    #[doc(hidden)]\n    #[deprecated]\n    pub const GET: u16 = 1;\n"));
+    ///assert!(code.contains("    #[doc(hidden)]\n    pub const fn rpc_method_name(id: u16)"));
+    ///assert!(code.contains("    ///Descriptor of `Storage` service\n    #[deprecated(note = \"use descriptor of v2\")]\n    pub static SERVICE"));
+    ///assert!(code.contains("    ///Stores value\n    #[cfg_attr(docsrs, doc(cfg(feature = \"unstable\")))]\n    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]\n    pub struct Put;\n"));
+    /////Enum is not affected
+    ///assert_eq!(code.matches("#[doc(hidden)]").count(), 4);
+    ///assert!(code.contains("    #[repr(u16)]\n    pub enum StorageMethod {\n"));
+    ///
+    /////Trait of dispatcher remains documented
+    ///let dispatcher = services[0].as_rpc_dispatcher_with(&config).to_string();
+    ///assert!(dispatcher.contains("///Handler of `Storage` service\npub trait StorageService {\n"));
+    ///assert!(!dispatcher.contains("#[doc(hidden)]\npub trait"));
+    ///
+    /////Empty attribute is reported, rather than written as empty line
+    ///let error = flatbuffers_tools::render_services(&services, &config.clone().category_attribute(ItemCategory::Markers, " ")).unwrap_err();
+    ///assert_eq!(error, GenError::EmptyAttribute);
+    ///assert_eq!(error.to_string(), "Custom attribute of generated items must not be empty");
+    ///let error = flatbuffers_tools::render_services(&services, &GenConfig::new().named_method_attribute("Put", "")).unwrap_err();
+    ///assert_eq!(error, GenError::EmptyAttribute);
+    ///assert_eq!(services[0].as_rpc_dispatcher_with(&GenConfig::new().item_attribute("")).render(), Err(GenError::EmptyAttribute));
+    ///```
+    pub fn category_attribute<T: Into<String>>(mut self, category: ItemCategory, attribute: T) -> Self {
+        self.category_attributes.push((category, attribute.into()));
+        self
+    }
+
+    ///Checks options, that are not specific to service, e.g. that custom attributes are not empty.
+    pub(crate) fn validate(&self) -> Result<(), GenError> {
+        match self.attributes().has_empty() || self.category_attributes.iter().any(|(_, attribute)| is_empty_attribute(attribute)) {
+            true => Err(GenError::EmptyAttribute),
+            false => Ok(()),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn attributes(&self) -> Attributes<'_> {
+        Attributes {
+            item: &self.item_attributes,
+            method: &self.method_attributes,
+            named: &self.named_method_attributes,
+        }
+    }
+
+    #[inline]
+    ///Sets style of method ids within TypeScript output.
+    ///
+    ///Refer to [RpcTypeScript::style]
+    pub fn ts_style(mut self, style: TsStyle) -> Self {
+        self.ts_style = style;
+        self
+    }
+
+    #[inline]
+    ///Sets representation of service namespace within proto output.
+    ///
+    ///Refer to [RpcProto::namespace]
+    pub fn proto_namespace(mut self, namespace: ProtoNamespace) -> Self {
+        self.proto_namespace = namespace;
+        self
+    }
+
+    #[inline]
+    ///Returns integer type of method ids, `u16` by default, or `u32` with [IdStrategy::Hash].
+    pub(crate) fn id_type(&self) -> IntType {
+        match self.id_strategy {
+            IdStrategy::Index => self.int_type.unwrap_or(IntType::U16),
+            IdStrategy::Hash(_) => IntType::U32,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn library(&self) -> StdLib {
+        match (self.is_no_std, self.is_alloc) {
+            (false, _) => StdLib::Std,
+            (true, true) => StdLib::Alloc,
+            (true, false) => StdLib::Core,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn type_path_fn(&self) -> Option<&dyn Fn(&TypeName) -> String> {
+        match self.type_path.as_ref() {
+            Some(type_path) => Some(type_path.as_ref()),
+            None => None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn type_kind_fn(&self) -> Option<&dyn Fn(&TypeName) -> TypeKind> {
+        match self.type_kind.as_ref() {
+            Some(type_kind) => Some(type_kind.as_ref()),
+            None => None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn framing_path(&self) -> Option<&'static str> {
+        match self.is_framing {
+            true => Some("flatbuffers_tools::framing"),
+            false => None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn runtime_path(&self) -> Option<&'static str> {
+        match self.is_runtime {
+            true => Some("::flatbuffers_tools::runtime"),
+            false => None,
+        }
+    }
+}
+
+impl Default for GenConfig {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
     }
 }
+
+///Approximate length of generated code for `service`, assuming `per_method` bytes in addition to names of each method.
+fn estimate_len(service: &RpcService, per_method: usize) -> usize {
+    let methods: usize = service.methods.iter().map(|method| {
+        let arguments: usize = method.arguments.iter().map(|argument| argument.ty.as_str().len()).sum();
+        per_method + 3 * method.name.len() + method.return_type.as_str().len() + arguments
+    }).sum();
+    256 + service.name.len() + methods
+}
+
+macro_rules! impl_output {
+    ($($formatter:ident => $per_method:expr,)+) => {$(
+        impl<'a> $formatter<'a> {
+            ///Writes generated code into `writer`.
+            ///
+            ///Output is the same as generated by `Display`.
+            pub fn write_to<W: std::io::Write>(&self, mut writer: W) -> std::io::Result<()> {
+                writer.write_fmt(format_args!("{}", self))
+            }
+
+            #[allow(clippy::inherent_to_string_shadow_display)]
+            ///Generates code into string, pre-allocated to approximate size of output.
+            ///
+            ///Output is the same as generated by `Display`.
+            pub fn to_string(&self) -> String {
+                use core::fmt::Write;
+
+                let mut result = String::with_capacity(estimate_len(self.service, $per_method));
+                let _ = result.write_fmt(format_args!("{}", self));
+                result
+            }
+        }
+    )+};
+}
+
+impl_output!(
+    RpcMethodDefines => 64,
+    RpcMethodEnum => 192,
+    RpcServiceImplDefines => 768,
+    RpcClientStub => 320,
+    RpcServiceDispatcher => 384,
+    RpcServiceDescriptor => 160,
+    RpcCHeader => 48,
+    RpcTypeScript => 64,
+    RpcPython => 48,
+    RpcMarkdown => 96,
+    RpcServiceModule => 512,
+    RpcTypeAliases => 160,
+    RpcMethodMarkers => 256,
+);
@@ -0,0 +1,417 @@
+//!Diagrams of services and types of schema
+//!
+//!Services and type definitions are nodes, while edges connect each of them to types it refers to:
+//!
+//!- Service to argument and return types of its methods, labeled by method names;
+//!- Table and struct to types of its fields, labeled by field names;
+//!- Union to types of its members, labeled by member names.
+//!
+//!Nodes are grouped by namespace, and their identifiers are made of kind and qualified name with every
+//!character, that is not alphanumeric, replaced with `_`, e.g. `table_Game_Item`.
+//!
+//!Types of rpc methods, that are not defined within schema (e.g. defined within included file), are nodes of unknown kind.
+//!
+//!```rust
+//!use flatbuffers_tools::schema::Schema;
+//!
+//!let schema: Schema = "namespace Game;
+//!table Item { name: string; parts: [Part]; }
+//!table Part { item: Item; color: Color; }
+//!enum Color : byte { Red }
+//!namespace Game.Rpc;
+//!rpc_service Shop { Buy(Game.Item):Game.Part; Sell(Game.Item):Receipt; }
+//!rpc_service Stats { Count(Query):Total; }".parse().unwrap();
+//!
+//!assert_eq!(schema.as_dot().to_string(), r#"digraph schema {
+//!    subgraph cluster_0 {
+//!        label="Game";
+//!        table_Game_Item [label="Item", shape=box];
+//!        table_Game_Part [label="Part", shape=box];
+//!        enum_Game_Color [label="Color", shape=ellipse];
+//!    }
+//!    subgraph cluster_1 {
+//!        label="Game.Rpc";
+//!        service_Game_Rpc_Shop [label="Shop", shape=box, style=rounded];
+//!        service_Game_Rpc_Stats [label="Stats", shape=box, style=rounded];
+//!        unknown_Game_Rpc_Receipt [label="Receipt", shape=box, style=dotted];
+//!        unknown_Game_Rpc_Query [label="Query", shape=box, style=dotted];
+//!        unknown_Game_Rpc_Total [label="Total", shape=box, style=dotted];
+//!    }
+//!    service_Game_Rpc_Shop -> table_Game_Item [label="Buy, Sell"];
+//!    service_Game_Rpc_Shop -> table_Game_Part [label="Buy"];
+//!    service_Game_Rpc_Shop -> unknown_Game_Rpc_Receipt [label="Sell"];
+//!    service_Game_Rpc_Stats -> unknown_Game_Rpc_Query [label="Count"];
+//!    service_Game_Rpc_Stats -> unknown_Game_Rpc_Total [label="Count"];
+//!    table_Game_Item -> table_Game_Part [label="parts"];
+//!    table_Game_Part -> table_Game_Item [label="item"];
+//!    table_Game_Part -> enum_Game_Color [label="color"];
+//!}
+//!"#);
+//!
+//!//Graph is limited to types, that service refers to directly or through other types
+//!assert_eq!(schema.as_mermaid().service("Stats").to_string(), r#"flowchart LR
+//!    subgraph ns_1 ["Game.Rpc"]
+//!        service_Game_Rpc_Stats(["Stats"])
+//!        unknown_Game_Rpc_Query>"Query"]
+//!        unknown_Game_Rpc_Total>"Total"]
+//!    end
+//!    service_Game_Rpc_Stats -->|"Count"| unknown_Game_Rpc_Query
+//!    service_Game_Rpc_Stats -->|"Count"| unknown_Game_Rpc_Total
+//!"#);
+//!
+//!let shop = schema.as_dot().service("Game.Rpc.Shop").to_string();
+//!assert!(shop.contains("enum_Game_Color"));
+//!assert!(!shop.contains("Stats"));
+//!assert!(!shop.contains("Query"));
+//!```
+
+use crate::schema::{Field, Schema, TypeRef};
+
+use core::fmt;
+use std::collections::HashMap;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///Format of diagram
+pub enum GraphFormat {
+    ///Graphviz `digraph`
+    Dot,
+    ///Mermaid `flowchart`
+    Mermaid,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///Kind of node
+enum NodeKind {
+    Service,
+    Table,
+    Struct,
+    Enum,
+    Union,
+    Unknown,
+}
+
+impl NodeKind {
+    #[inline]
+    const fn prefix(self) -> &'static str {
+        match self {
+            Self::Service => "service",
+            Self::Table => "table",
+            Self::Struct => "struct",
+            Self::Enum => "enum",
+            Self::Union => "union",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    #[inline]
+    const fn dot_attributes(self) -> &'static str {
+        match self {
+            Self::Service => "shape=box, style=rounded",
+            Self::Table => "shape=box",
+            Self::Struct => "shape=box, style=dashed",
+            Self::Enum => "shape=ellipse",
+            Self::Union => "shape=diamond",
+            Self::Unknown => "shape=box, style=dotted",
+        }
+    }
+
+    #[inline]
+    const fn mermaid_brackets(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Service => ("([", "])"),
+            Self::Table => ("[", "]"),
+            Self::Struct => ("[[", "]]"),
+            Self::Enum => ("((", "))"),
+            Self::Union => ("{{", "}}"),
+            Self::Unknown => (">", "]"),
+        }
+    }
+}
+
+struct Node<'a> {
+    kind: NodeKind,
+    name: &'a str,
+    namespace: Option<&'a str>,
+    id: String,
+}
+
+struct Edge {
+    from: usize,
+    to: usize,
+    labels: Vec<String>,
+}
+
+///Nodes and edges of schema
+struct Graph<'a> {
+    nodes: Vec<Node<'a>>,
+    edges: Vec<Edge>,
+    //Nodes by kind and qualified name
+    names: HashMap<(&'static str, String), usize>,
+}
+
+///Returns name, qualified by its namespace.
+fn qualified_name(name: &str, namespace: Option<&str>) -> String {
+    match namespace {
+        Some(namespace) => format!("{namespace}.{name}"),
+        None => name.to_owned(),
+    }
+}
+
+impl<'a> Graph<'a> {
+    #[inline]
+    ///Returns index of node.
+    fn find(&self, kind: NodeKind, name: &str, namespace: Option<&str>) -> Option<usize> {
+        self.names.get(&(kind.prefix(), qualified_name(name, namespace))).copied()
+    }
+
+    ///Adds node, unless it is already present, returning its index.
+    fn node(&mut self, kind: NodeKind, name: &'a str, namespace: Option<&'a str>) -> usize {
+        if let Some(idx) = self.find(kind, name, namespace) {
+            return idx;
+        }
+        let qualified = qualified_name(name, namespace);
+
+        let mut id = format!("{}_", kind.prefix());
+        id.extend(qualified.chars().map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '_' }));
+        //Different names can be sanitized to the same identifier
+        if self.nodes.iter().any(|node| node.id == id) {
+            id = format!("{id}_{}", self.nodes.len());
+        }
+        let idx = self.nodes.len();
+        self.names.insert((kind.prefix(), qualified), idx);
+        self.nodes.push(Node {
+            kind,
+            name,
+            namespace,
+            id,
+        });
+        idx
+    }
+
+    ///Adds edge labeled `label`, merging it with existing edge between the same nodes.
+    fn edge(&mut self, from: usize, to: usize, label: &str) {
+        match self.edges.iter_mut().find(|edge| edge.from == from && edge.to == to) {
+            Some(edge) => if !edge.labels.iter().any(|prev| prev == label) {
+                edge.labels.push(label.to_owned());
+            },
+            None => self.edges.push(Edge {
+                from,
+                to,
+                labels: vec![label.to_owned()],
+            }),
+        }
+    }
+
+    fn type_node(&mut self, ty: TypeRef<'a>) -> usize {
+        let kind = match ty {
+            TypeRef::Table(_) => NodeKind::Table,
+            TypeRef::Struct(_) => NodeKind::Struct,
+            TypeRef::Enum(_) => NodeKind::Enum,
+            TypeRef::Union(_) => NodeKind::Union,
+        };
+        self.node(kind, ty.name(), ty.namespace())
+    }
+
+    fn new(schema: &'a Schema) -> Self {
+        let index = schema.index();
+        let mut graph = Self {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            names: HashMap::new(),
+        };
+
+        for table in schema.tables.iter() {
+            graph.node(NodeKind::Table, &table.name, table.namespace.as_deref());
+        }
+        for structure in schema.structs.iter() {
+            graph.node(NodeKind::Struct, &structure.name, structure.namespace.as_deref());
+        }
+        for definition in schema.enums.iter() {
+            graph.node(NodeKind::Enum, &definition.name, definition.namespace.as_deref());
+        }
+        for definition in schema.unions.iter() {
+            graph.node(NodeKind::Union, &definition.name, definition.namespace.as_deref());
+        }
+        for service in schema.services.iter() {
+            graph.node(NodeKind::Service, &service.name, service.namespace.as_deref());
+        }
+
+        for service in schema.services.iter() {
+            let namespace = service.namespace.as_deref();
+            let from = graph.node(NodeKind::Service, &service.name, namespace);
+            for method in service.methods.iter() {
+                let types = method.arguments.iter().map(|argument| &argument.ty).chain(Some(&method.return_type));
+                for ty in types {
+                    let to = match index.resolve_type(ty, namespace) {
+                        Some(ty) => graph.type_node(ty),
+                        //Type is named within namespace of the service, same as generated code refers to it
+                        None => match ty.namespace() {
+                            Some(ty_namespace) => graph.node(NodeKind::Unknown, ty.name(), Some(ty_namespace)),
+                            None => graph.node(NodeKind::Unknown, ty, namespace),
+                        },
+                    };
+                    graph.edge(from, to, &method.name);
+                }
+            }
+        }
+
+        let fields = |fields: &'a [Field], namespace: Option<&'a str>| fields.iter().map(move |field| (field.name.as_str(), field.ty.element().unwrap_or(&field.ty), namespace));
+        let tables = schema.tables.iter().map(|table| (NodeKind::Table, &table.name, table.namespace.as_deref(), fields(&table.fields, table.namespace.as_deref()).collect::<Vec<_>>()));
+        let structs = schema.structs.iter().map(|structure| (NodeKind::Struct, &structure.name, structure.namespace.as_deref(), fields(&structure.fields, structure.namespace.as_deref()).collect()));
+        let unions = schema.unions.iter().map(|definition| {
+            let members = definition.members.iter().map(|(name, alias)| (name.as_str(), alias.as_deref().unwrap_or(name), definition.namespace.as_deref()));
+            (NodeKind::Union, &definition.name, definition.namespace.as_deref(), members.collect())
+        });
+        for (kind, name, namespace, references) in tables.chain(structs).chain(unions) {
+            let from = graph.node(kind, name, namespace);
+            for (label, ty, namespace) in references {
+                //Scalars and types of included files are not nodes
+                if let Some(ty) = index.resolve_type(ty, namespace) {
+                    let to = graph.type_node(ty);
+                    graph.edge(from, to, label);
+                }
+            }
+        }
+
+        graph
+    }
+
+    ///Returns whether every node is reachable from `start`, following edges.
+    fn reachable(&self, start: usize) -> Vec<bool> {
+        let mut is_reachable = vec![false; self.nodes.len()];
+        is_reachable[start] = true;
+        let mut pending = vec![start];
+        while let Some(node) = pending.pop() {
+            for edge in self.edges.iter().filter(|edge| edge.from == node) {
+                //Visited nodes are not followed again, so that cycles terminate
+                if !is_reachable[edge.to] {
+                    is_reachable[edge.to] = true;
+                    pending.push(edge.to);
+                }
+            }
+        }
+        is_reachable
+    }
+}
+
+///Escapes label within quotes.
+struct Label<'a>(&'a str, GraphFormat);
+
+impl fmt::Display for Label<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for ch in self.0.chars() {
+            match (ch, self.1) {
+                ('"', GraphFormat::Dot) => fmt.write_str("\\\"")?,
+                ('\\', GraphFormat::Dot) => fmt.write_str("\\\\")?,
+                ('"', GraphFormat::Mermaid) => fmt.write_str("#quot;")?,
+                (ch, _) => fmt.write_fmt(format_args!("{ch}"))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+///Diagram of schema, refer to [module](self) for details.
+pub struct SchemaGraph<'a> {
+    ///Schema definition
+    pub schema: &'a Schema,
+    ///Format of output
+    pub format: GraphFormat,
+    ///Name of service, optionally qualified with namespace, to limit diagram to it and types it refers to.
+    ///
+    ///Diagram is empty, if schema has no such service.
+    pub service: Option<&'a str>,
+}
+
+impl<'a> SchemaGraph<'a> {
+    #[inline]
+    ///Sets format of output.
+    pub const fn format(mut self, format: GraphFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    #[inline]
+    ///Limits diagram to service `name` and types it refers to, directly or through other types.
+    pub const fn service(mut self, name: &'a str) -> Self {
+        self.service = Some(name);
+        self
+    }
+}
+
+impl fmt::Display for SchemaGraph<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const INDENT: &str = "    ";
+
+        let format = self.format;
+        let graph = Graph::new(self.schema);
+        let service = self.service.map(|name| self.schema.service(name).and_then(|service| graph.find(NodeKind::Service, &service.name, service.namespace.as_deref())));
+        let is_included = match service {
+            Some(Some(service)) => graph.reachable(service),
+            Some(None) => vec![false; graph.nodes.len()],
+            None => vec![true; graph.nodes.len()],
+        };
+
+        match format {
+            GraphFormat::Dot => fmt.write_str("digraph schema {\n")?,
+            GraphFormat::Mermaid => fmt.write_str("flowchart LR\n")?,
+        }
+
+        //Namespaces in order of first appearance, numbered regardless of filter
+        let mut namespaces: Vec<Option<&str>> = vec![None];
+        for node in graph.nodes.iter() {
+            if !namespaces.contains(&node.namespace) {
+                namespaces.push(node.namespace);
+            }
+        }
+        for (idx, namespace) in namespaces.iter().enumerate() {
+            let nodes: Vec<&Node<'_>> = graph.nodes.iter().zip(is_included.iter()).filter(|(node, is_included)| **is_included && node.namespace == *namespace).map(|(node, _)| node).collect();
+            if nodes.is_empty() {
+                continue;
+            }
+            let indent = match namespace {
+                Some(namespace) => {
+                    match format {
+                        GraphFormat::Dot => fmt.write_fmt(format_args!("{INDENT}subgraph cluster_{} {{\n{INDENT}{INDENT}label=\"{}\";\n", idx - 1, Label(namespace, format)))?,
+                        GraphFormat::Mermaid => fmt.write_fmt(format_args!("{INDENT}subgraph ns_{} [\"{}\"]\n", idx - 1, Label(namespace, format)))?,
+                    }
+                    "        "
+                },
+                None => INDENT,
+            };
+            for node in nodes {
+                let label = Label(node.name, format);
+                match format {
+                    GraphFormat::Dot => fmt.write_fmt(format_args!("{indent}{} [label=\"{label}\", {}];\n", node.id, node.kind.dot_attributes()))?,
+                    GraphFormat::Mermaid => {
+                        let (open, close) = node.kind.mermaid_brackets();
+                        fmt.write_fmt(format_args!("{indent}{}{open}\"{label}\"{close}\n", node.id))?;
+                    },
+                }
+            }
+            if namespace.is_some() {
+                match format {
+                    GraphFormat::Dot => fmt.write_fmt(format_args!("{INDENT}}}\n"))?,
+                    GraphFormat::Mermaid => fmt.write_fmt(format_args!("{INDENT}end\n"))?,
+                }
+            }
+        }
+
+        for edge in graph.edges.iter().filter(|edge| is_included[edge.from] && is_included[edge.to]) {
+            let from = &graph.nodes[edge.from].id;
+            let to = &graph.nodes[edge.to].id;
+            let labels = edge.labels.join(", ");
+            let label = Label(&labels, format);
+            match format {
+                GraphFormat::Dot => fmt.write_fmt(format_args!("{INDENT}{from} -> {to} [label=\"{label}\"];\n"))?,
+                GraphFormat::Mermaid => fmt.write_fmt(format_args!("{INDENT}{from} -->|\"{label}\"| {to}\n"))?,
+            }
+        }
+
+        match format {
+            GraphFormat::Dot => fmt.write_str("}\n"),
+            GraphFormat::Mermaid => Ok(()),
+        }
+    }
+}
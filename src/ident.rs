@@ -0,0 +1,198 @@
+//!Case conversion and identifiers
+//!
+//!Same functions are used by built-in generators, so that custom [Backend](crate::Backend) produces the same names.
+//!
+//!Conversions operate on ASCII only: characters other than ASCII letters, digits and `_` are passed through as they are,
+//!and are never treated as word boundaries. Use [is_valid_identifier] to reject such names.
+//!
+//!- [to_snake] treats run of upper case letters as single word and attaches digits to preceding word. Used by function and module names.
+//!- [to_flatc_snake] is the same as conversion of `flatc`, which does not split runs of upper case letters, but starts new word after digits.
+//!  Used by module path of types generated by `flatc`.
+//!- [to_screaming_snake] is [to_flatc_snake] in upper case. Used by names of constants.
+//!- [to_pascal] makes first letter of every word, separated by `_`, upper case, keeping the rest as it is, same as `flatc`, while separators are removed.
+//!  Used by names of types and variants.
+//!- [to_camel] is [to_pascal] with leading word in lower case, where run of upper case letters is the single word.
+//!
+//!Existing `_` are kept by snake case conversions, including leading and consecutive ones.
+//!
+//!```rust
+//!use flatbuffers_tools::ident::{escape_rust_keyword, is_valid_identifier, to_camel, to_flatc_snake, to_pascal, to_screaming_snake, to_snake, KeywordEscape};
+//!
+//!//name, snake, flatc snake, screaming snake, pascal, camel
+//!let names = [
+//!    ("HTTPServer2", "http_server2", "httpserver_2", "HTTPSERVER_2", "HTTPServer2", "httpServer2"),
+//!    ("already_snake", "already_snake", "already_snake", "ALREADY_SNAKE", "AlreadySnake", "alreadySnake"),
+//!    ("__weird", "__weird", "__weird", "__WEIRD", "Weird", "weird"),
+//!    ("crate", "crate", "crate", "CRATE", "Crate", "crate"),
+//!    ("GetStatus", "get_status", "get_status", "GET_STATUS", "GetStatus", "getStatus"),
+//!    ("getStatus", "get_status", "get_status", "GET_STATUS", "GetStatus", "getStatus"),
+//!    ("GetUserID", "get_user_id", "get_user_id", "GET_USER_ID", "GetUserID", "getUserID"),
+//!    ("IOError", "io_error", "ioerror", "IOERROR", "IOError", "ioError"),
+//!    ("GetV2", "get_v2", "get_v2", "GET_V2", "GetV2", "getV2"),
+//!    ("V2Get", "v2_get", "v2_get", "V2_GET", "V2Get", "v2Get"),
+//!    ("Sha256Sum", "sha256_sum", "sha_256_sum", "SHA_256_SUM", "Sha256Sum", "sha256Sum"),
+//!    ("double__under", "double__under", "double__under", "DOUBLE__UNDER", "DoubleUnder", "doubleUnder"),
+//!    ("trailing_", "trailing_", "trailing_", "TRAILING_", "Trailing", "trailing"),
+//!    ("URL", "url", "url", "URL", "URL", "url"),
+//!    ("A", "a", "a", "A", "A", "a"),
+//!    ("x", "x", "x", "X", "X", "x"),
+//!    ("", "", "", "", "", ""),
+//!    ("überGet", "über_get", "über_get", "üBER_GET", "überGet", "überGet"),
+//!];
+//!for (name, snake, flatc_snake, screaming_snake, pascal, camel) in names {
+//!    assert_eq!(to_snake(name), snake, "{name}");
+//!    assert_eq!(to_flatc_snake(name), flatc_snake, "{name}");
+//!    assert_eq!(to_screaming_snake(name), screaming_snake, "{name}");
+//!    assert_eq!(to_pascal(name), pascal, "{name}");
+//!    assert_eq!(to_camel(name), camel, "{name}");
+//!}
+//!
+//!//name, raw escape, suffix escape, is valid identifier
+//!let idents = [
+//!    ("type", "r#type", "type_", true),
+//!    ("async", "r#async", "async_", true),
+//!    ("crate", "crate_", "crate_", true),
+//!    ("self", "self_", "self_", true),
+//!    ("Self", "Self_", "Self_", true),
+//!    ("super", "super_", "super_", true),
+//!    ("Type", "Type", "Type", true),
+//!    ("types", "types", "types", true),
+//!    ("__weird", "__weird", "__weird", true),
+//!    ("_", "_", "_", true),
+//!    ("HTTPServer2", "HTTPServer2", "HTTPServer2", true),
+//!    ("2fast", "2fast", "2fast", false),
+//!    ("kebab-case", "kebab-case", "kebab-case", false),
+//!    ("Über", "Über", "Über", false),
+//!    ("", "", "", false),
+//!];
+//!for (name, raw, suffix, is_valid) in idents {
+//!    assert_eq!(escape_rust_keyword(name, KeywordEscape::Raw), raw);
+//!    assert_eq!(escape_rust_keyword(name, KeywordEscape::Suffix), suffix);
+//!    assert_eq!(is_valid_identifier(name), is_valid, "{name}");
+//!}
+//!```
+
+use std::borrow::Cow;
+
+pub(crate) const KEYWORDS: [&str; 51] = [
+    "as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn", "for", "if", "impl", "in",
+    "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct",
+    "super", "trait", "true", "type", "unsafe", "use", "where", "while", "async", "await", "dyn", "abstract",
+    "become", "box", "do", "final", "macro", "override", "priv", "typeof", "unsized", "virtual", "yield", "try",
+];
+//Keywords that cannot be used as raw identifiers
+const RESERVED_KEYWORDS: [&str; 4] = ["crate", "self", "Self", "super"];
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+///Escaping of Rust keywords
+pub enum KeywordEscape {
+    #[default]
+    ///Raw identifier, e.g. `r#type`
+    ///
+    ///Keywords `crate`, `self`, `Self` and `super` cannot be raw identifiers, hence they are escaped with suffix.
+    Raw,
+    ///Suffix `_`, e.g. `type_`
+    Suffix,
+}
+
+///Returns `name`, escaped with `escape` if it is Rust keyword, including reserved ones.
+///
+///Other names are returned as they are, without checking whether they are valid identifiers.
+pub fn escape_rust_keyword(name: &str, escape: KeywordEscape) -> Cow<'_, str> {
+    if !KEYWORDS.contains(&name) {
+        Cow::Borrowed(name)
+    } else if escape == KeywordEscape::Suffix || RESERVED_KEYWORDS.contains(&name) {
+        Cow::Owned(format!("{name}_"))
+    } else {
+        Cow::Owned(format!("r#{name}"))
+    }
+}
+
+///Returns whether `name` is valid identifier, same as flatc requires.
+///
+///Identifier must start with ASCII letter or `_`, followed by ASCII alphanumerics or `_`, hence non-ASCII names are rejected.
+///Rust keywords are valid identifiers, that must be escaped with [escape_rust_keyword].
+pub fn is_valid_identifier(name: &str) -> bool {
+    //Only ASCII is allowed, so bytes of multi-byte characters are rejected the same way
+    let mut bytes = name.bytes();
+    match bytes.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == b'_' => bytes.all(|byte| byte.is_ascii_alphanumeric() || byte == b'_'),
+        _ => false,
+    }
+}
+
+///Converts `name` to snake case, suitable for Rust function name.
+///
+///Unlike flatc's conversion, runs of upper case letters are treated as single word, and digits are
+///attached to preceding word, e.g. `HTTPGet` becomes `http_get` and `GetV2` becomes `get_v2`.
+pub fn to_snake(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut result = String::with_capacity(name.len() + 2);
+    for (idx, ch) in chars.iter().copied().enumerate() {
+        if ch.is_ascii_uppercase() && idx > 0 {
+            let prev = chars[idx - 1];
+            let is_acronym_end = prev.is_ascii_uppercase() && chars.get(idx + 1).is_some_and(char::is_ascii_lowercase);
+            if prev.is_ascii_lowercase() || prev.is_ascii_digit() || is_acronym_end {
+                result.push('_');
+            }
+        }
+        result.push(ch.to_ascii_lowercase());
+    }
+    result
+}
+
+///Converts `name` to snake case, same as flatc does, e.g. `HTTPGet` becomes `httpget` and `GetV2` becomes `get_v2`.
+pub fn to_flatc_snake(name: &str) -> String {
+    let mut result = String::with_capacity(name.len() + 2);
+    let mut prev: Option<char> = None;
+    for ch in name.chars() {
+        match prev {
+            None => result.push(ch.to_ascii_lowercase()),
+            Some(_) if ch == '_' || ch.is_ascii_lowercase() => result.push(ch),
+            Some(prev) => {
+                if prev.is_ascii_lowercase() || (prev.is_ascii_digit() && !ch.is_ascii_digit()) {
+                    result.push('_');
+                }
+                result.push(ch.to_ascii_lowercase());
+            },
+        }
+        prev = Some(ch);
+    }
+    result
+}
+
+#[inline]
+///Converts `name` to upper snake case, same as names of method constants within every output, e.g. `GetStatus` becomes `GET_STATUS`.
+pub fn to_screaming_snake(name: &str) -> String {
+    to_flatc_snake(name).to_ascii_uppercase()
+}
+
+///Converts `name` to upper camel case, same as flatc does, e.g. `get_status` becomes `GetStatus`.
+pub fn to_pascal(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut is_word_start = true;
+    for ch in name.chars() {
+        if ch == '_' {
+            is_word_start = true;
+        } else if is_word_start {
+            result.push(ch.to_ascii_uppercase());
+            is_word_start = false;
+        } else {
+            result.push(ch);
+        }
+    }
+    result
+}
+
+///Converts `name` to lower camel case, e.g. `get_status` becomes `getStatus` and `HTTPGet` becomes `httpGet`.
+pub fn to_camel(name: &str) -> String {
+    let mut result = to_pascal(name);
+    let bytes = result.as_bytes();
+    let mut len = bytes.iter().take_while(|byte| byte.is_ascii_uppercase()).count();
+    //Last letter of acronym starts the next word
+    if len > 1 && bytes.get(len).is_some_and(u8::is_ascii_lowercase) {
+        len -= 1;
+    }
+    result[..len].make_ascii_lowercase();
+    result
+}
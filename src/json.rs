@@ -0,0 +1,257 @@
+//!Machine readable JSON of parsed services
+//!
+//!JSON is produced by [RpcService::to_json](crate::RpcService::to_json) and [Schema::to_json](crate::schema::Schema::to_json),
+//!or by `fbs-rpc-gen --dump-json`, so that tools written in other languages can read services without parsing schema.
+//!
+//!Output is indented by 2 spaces and ends with new line, while keys are always written in the same order.
+//!Arrays of strings and objects without nested values are written within single line:
+//!
+//!```json
+//!{
+//!  "version": 1,
+//!  "services": [
+//!    {
+//!      "name": "Storage",
+//!      "namespace": "MyGame",
+//!      "qualified_name": "MyGame.Storage",
+//!      "fingerprint": "0x0123456789abcdef",
+//!      "docs": ["Key value storage"],
+//!      "attributes": [
+//!        {"key": "internal", "value": null}
+//!      ],
+//!      "line": 2,
+//!      "methods": [
+//!        {
+//!          "name": "Get",
+//!          "id": 0,
+//!          "arguments": [
+//!            {"name": null, "type": "Key", "qualified_type": "MyGame.Key"}
+//!          ],
+//!          "return_type": "Value",
+//!          "qualified_return_type": "MyGame.Value",
+//!          "streaming": "none",
+//!          "deprecated": false,
+//!          "docs": [],
+//!          "attributes": [],
+//!          "line": 3
+//!        }
+//!      ]
+//!    }
+//!  ]
+//!}
+//!```
+//!
+//!- `version` is [VERSION], that is incremented whenever meaning of existing field changes or field is removed.
+//!  New fields can be added without incrementing it, hence readers are to ignore unknown fields.
+//!- `namespace` is `null` without namespace, in which case `qualified_name` is the same as `name`.
+//!- `fingerprint` is [RpcServiceIds::fingerprint](crate::RpcServiceIds::fingerprint) as hex string, as 64-bit integers are not portable within JSON.
+//!- `id` is assigned the same as by default [RpcMethodDefines](crate::RpcMethodDefines), that is index of method unless pinned by `id` attribute.
+//!- `type` and `return_type` are written as declared, while qualified types are resolved within namespace of the service.
+//!- `name` of argument is `null` unless it is declared, e.g. `Transfer(from: Account): Receipt`.
+//!- `streaming` is value of `streaming` attribute, or `none` without it.
+//!- `value` of attribute is `null` if attribute has no value, and quotes of value are stripped.
+//!- `line` is line number of definition, starting from 1.
+//!
+//!Output of fixture schema is kept within `tests/golden`, which is to be updated together with any change of format:
+//!
+//!```rust
+//!use flatbuffers_tools::schema::Schema;
+//!
+//!let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden");
+//!let schema: Schema = std::fs::read_to_string(dir.join("dump.fbs")).unwrap().parse().unwrap();
+//!let json = schema.to_json().unwrap();
+//!let golden = dir.join("dump.json");
+//!if std::env::var_os("UPDATE_GOLDEN").is_some() {
+//!    std::fs::write(&golden, &json).unwrap();
+//!}
+//!assert_eq!(json, std::fs::read_to_string(golden).unwrap(), "Run with UPDATE_GOLDEN=1 to update");
+//!assert!(json.starts_with("{\n  \"version\": 1,\n  \"services\": [\n"));
+//!
+//!//Single service has the same shape
+//!let json = schema.services[0].to_json().unwrap();
+//!assert!(json.contains("\"qualified_name\": \"MyGame.Storage\""));
+//!assert!(!json.contains("\"qualified_name\": \"MyGame.Stats.Metrics\""));
+//!```
+
+use crate::{IdStrategy, MethodIdError, RpcService, RpcServiceIds};
+
+use core::fmt::{self, Write};
+
+///Version of JSON format
+pub const VERSION: u32 = 1;
+
+enum Value<'a> {
+    Null,
+    Bool(bool),
+    Number(u64),
+    String(&'a str),
+    OwnedString(String),
+    Array(Vec<Value<'a>>),
+    Object(Vec<(&'static str, Value<'a>)>),
+}
+
+impl<'a> Value<'a> {
+    #[inline]
+    fn optional(value: Option<&'a str>) -> Self {
+        match value {
+            Some(value) => Self::String(value),
+            None => Self::Null,
+        }
+    }
+
+    fn strings<T: AsRef<str>>(values: &'a [T]) -> Self {
+        Self::Array(values.iter().map(|value| Self::String(value.as_ref())).collect())
+    }
+
+    fn attributes(attributes: &'a [crate::Attribute]) -> Self {
+        Self::Array(attributes.iter().map(|(key, value)| Self::Object(vec![
+            ("key", Self::String(key)),
+            ("value", Self::optional(value.as_deref())),
+        ])).collect())
+    }
+
+    ///Returns whether value is written within single line.
+    fn is_inline(&self) -> bool {
+        match self {
+            Self::Array(values) => values.iter().all(|value| !matches!(value, Self::Array(_) | Self::Object(_))),
+            Self::Object(fields) => fields.iter().all(|(_, value)| !matches!(value, Self::Array(_) | Self::Object(_))),
+            _ => true,
+        }
+    }
+
+    fn write(&self, out: &mut String, indent: usize) -> fmt::Result {
+        match self {
+            Self::Null => out.write_str("null"),
+            Self::Bool(value) => out.write_fmt(format_args!("{value}")),
+            Self::Number(value) => out.write_fmt(format_args!("{value}")),
+            Self::String(value) => write_string(out, value),
+            Self::OwnedString(value) => write_string(out, value),
+            Self::Array(values) if values.is_empty() => out.write_str("[]"),
+            Self::Object(fields) if fields.is_empty() => out.write_str("{}"),
+            Self::Array(values) => {
+                let is_inline = self.is_inline();
+                out.write_str("[")?;
+                for (idx, value) in values.iter().enumerate() {
+                    if idx > 0 {
+                        out.write_str(",")?;
+                    }
+                    separator(out, is_inline, idx > 0, indent + 1)?;
+                    value.write(out, indent + 1)?;
+                }
+                separator(out, is_inline, false, indent)?;
+                out.write_str("]")
+            },
+            Self::Object(fields) => {
+                let is_inline = self.is_inline() && indent > 0;
+                out.write_str("{")?;
+                for (idx, (key, value)) in fields.iter().enumerate() {
+                    if idx > 0 {
+                        out.write_str(",")?;
+                    }
+                    separator(out, is_inline, idx > 0, indent + 1)?;
+                    write_string(out, key)?;
+                    out.write_str(": ")?;
+                    value.write(out, indent + 1)?;
+                }
+                separator(out, is_inline, false, indent)?;
+                out.write_str("}")
+            },
+        }
+    }
+}
+
+///Writes separator of elements, which is new line with `indent`, or space between elements of inline value.
+fn separator(out: &mut String, is_inline: bool, is_space: bool, indent: usize) -> fmt::Result {
+    match is_inline {
+        true if is_space => out.write_str(" "),
+        true => Ok(()),
+        false => {
+            out.write_str("\n")?;
+            for _ in 0..indent {
+                out.write_str("  ")?;
+            }
+            Ok(())
+        },
+    }
+}
+
+fn write_string(out: &mut String, value: &str) -> fmt::Result {
+    out.write_str("\"")?;
+    for ch in value.chars() {
+        match ch {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\r' => out.write_str("\\r")?,
+            '\t' => out.write_str("\\t")?,
+            ch if (ch as u32) < 0x20 => out.write_fmt(format_args!("\\u{:04x}", ch as u32))?,
+            ch => out.write_char(ch)?,
+        }
+    }
+    out.write_str("\"")
+}
+
+fn qualified_name(service: &RpcService) -> String {
+    match service.namespace.as_ref() {
+        Some(namespace) => format!("{namespace}.{}", service.name),
+        None => service.name.clone(),
+    }
+}
+
+fn service(service: &RpcService) -> Result<Value<'_>, MethodIdError> {
+    let ids = RpcServiceIds::new(service, IdStrategy::Index)?;
+    let namespace = service.namespace.as_deref();
+    let methods = ids.iter().map(|(id, method)| {
+        let arguments = method.arguments.iter().map(|argument| Value::Object(vec![
+            ("name", Value::optional(argument.name.as_deref())),
+            ("type", Value::String(argument.ty.as_str())),
+            ("qualified_type", Value::OwnedString(argument.ty.resolve(namespace).to_string())),
+        ]));
+        let streaming = match method.streaming() {
+            Ok(streaming) => streaming.as_value(),
+            Err(value) => value,
+        };
+        Value::Object(vec![
+            ("name", Value::String(&method.name)),
+            ("id", Value::Number(id)),
+            ("arguments", Value::Array(arguments.collect())),
+            ("return_type", Value::String(method.return_type.as_str())),
+            ("qualified_return_type", Value::OwnedString(method.return_type.resolve(namespace).to_string())),
+            ("streaming", Value::String(streaming)),
+            ("deprecated", Value::Bool(method.is_deprecated())),
+            ("docs", Value::strings(&method.docs)),
+            ("attributes", Value::attributes(&method.attributes)),
+            ("line", Value::Number(method.span.start.line as u64)),
+        ])
+    });
+
+    Ok(Value::Object(vec![
+        ("name", Value::String(&service.name)),
+        ("namespace", Value::optional(namespace)),
+        ("qualified_name", Value::OwnedString(qualified_name(service))),
+        ("fingerprint", Value::OwnedString(format!("{:#018x}", ids.fingerprint()))),
+        ("docs", Value::strings(&service.docs)),
+        ("attributes", Value::attributes(&service.attributes)),
+        ("line", Value::Number(service.span.start.line as u64)),
+        ("methods", Value::Array(methods.collect())),
+    ]))
+}
+
+///Returns JSON of `services`, in order of iteration.
+///
+///Fails if ids of methods cannot be assigned, refer to [RpcServiceIds::new].
+pub fn to_json<'a>(services: impl IntoIterator<Item = &'a RpcService>) -> Result<String, MethodIdError> {
+    let mut values = Vec::new();
+    for definition in services {
+        values.push(service(definition)?);
+    }
+    let root = Value::Object(vec![
+        ("version", Value::Number(VERSION.into())),
+        ("services", Value::Array(values)),
+    ]);
+
+    let mut out = String::new();
+    let _ = root.write(&mut out, 0);
+    out.push('\n');
+    Ok(out)
+}
@@ -1,13 +1,118 @@
 //! Flatbuffers related tools
+//!
+//!## Usage
+//!
+//!Use [parse_services] to get all services of schema, or [parse_service] when schema is expected
+//!to contain exactly one service:
+//!
+//!```rust
+//!let schema = "rpc_service Store {\n  Put(Request): Response;\n}";
+//!let service = flatbuffers_tools::parse_service(schema).expect("to parse");
+//!assert_eq!(service.name, "Store");
+//!assert_eq!(service.methods[0].name, "Put");
+//!```
+//!
+//![ParserIter] is available to parse services one by one, and [schema] module provides parser
+//!of all declarations.
+//!
+//!## Features
+//!
+//!- `serde` - Implements `Serialize` and `Deserialize` for parsed definitions and parse errors.
+//!  Fields are named as declared, hence their names are part of public API.
+//!- `method-trait` - Provides `Method` trait, implemented by generated method markers.
+//!- `cli` - Provides `cli` module and `fbs-rpc-gen` binary.
+//!- `framing` - Provides `framing` module with wire framing of method calls, used by generated code with [GenConfig::framing].
+//!- `arbitrary` - Implements `Arbitrary` of `arbitrary` crate for definitions, and provides `fuzz` module with generators of valid values and entry point of fuzz target.
+//!- `runtime` - Provides `runtime` module with items shared by generated code, imported by modules with [GenConfig::runtime].
+//!  Enables `method-trait` and `framing`.
 
 #![warn(missing_docs)]
-#![cfg_attr(feature = "cargo-clippy", allow(clippy::style))]
 #![cfg_attr(rustfmt, rustfmt_skip)]
 
 mod gen;
-pub use gen::{RpcMethodDefines, RpcServiceImplDefines};
+mod builder;
+pub mod borrowed;
+pub mod fs;
+pub mod build;
+pub mod config;
+pub mod reader;
+pub mod stream;
+pub mod schema;
+pub mod diff;
+pub mod registry;
+pub mod fbs;
+pub mod graph;
+pub mod json;
+pub mod diagnostics;
+pub mod ident;
+#[cfg(feature = "cli")]
+pub mod cli;
+#[cfg(feature = "framing")]
+pub mod framing;
+#[cfg(feature = "arbitrary")]
+pub mod fuzz;
+#[cfg(feature = "runtime")]
+pub mod runtime;
+pub use gen::{ArgumentAliases, AsyncRouter, Asyncness, Attributes, Backend, CBackend, CHeaderStyle, ContextStyle, DefaultBody, DeprecatedPolicy, GenConfig, GenError, GenItem, HashAlgo, HashCollision, IdScope, IdStrategy, InstrumentStyle, IntType, ItemCategory, MarkdownBackend, MethodIdError, NameCollision, ProtoNamespace, PythonBackend, Receiver, RpcCHeader, RpcClientStub, RpcClientTransport, RpcDescriptorTypes, RpcDispatchCodec, RpcMarkdown, RpcMethodDefines, RpcMethodEnum, RpcMethodMarkers, RpcMethodTrait, RpcObserverTrait, RpcProto, RpcPython, RpcServiceDescriptor, RpcServiceDispatcher, RpcServiceIds, RpcServiceImplDefines, RpcServiceInstrumentation, RpcServiceMock, RpcServiceModule, RpcServiceRouter, RpcTypeAliases, RpcTypeScript, ReturnStyle, RustBackend, StdLib, TsStyle, TypeKind, TypeScriptBackend, Visibility, DEFAULT_ENUM_DERIVES, FINGERPRINT_VERSION, assign_global_ids, assign_ids, render_services, render_services_with, to_camel_case, to_snake_case, to_upper_snake_case, unique_method_names};
+pub use builder::{RpcMethodBuilder, RpcServiceBuilder};
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+static DEFAULT_CONFIG: GenConfig = GenConfig::new();
+
+#[cfg(feature = "method-trait")]
+///RPC method, identified by marker type, generated by [RpcMethodMarkers].
+///
+///Markers implement it, when configured with [GenConfig::crate_method_trait], which requires `u16` method ids.
+pub trait Method {
+    ///Method id
+    const ID: u16;
+    ///Method name, as declared within schema
+    const NAME: &'static str;
+    ///Type of request, `()` if method has no arguments
+    type Request;
+    ///Type of response
+    type Response;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+///Kind of identifier
+pub enum IdentifierKind {
+    ///Service name
+    Service,
+    ///Method name
+    Method,
+    ///Method argument name
+    ArgumentName,
+    ///Method argument type
+    Argument,
+    ///Method return type
+    ReturnType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+///Kind of [Limits]
+pub enum Limit {
+    ///Length of line in bytes
+    LineLength,
+    ///Number of methods within service
+    Methods,
+    ///Number of services within input
+    Services,
+    ///Depth of nested `include` declarations
+    IncludeDepth,
+    ///Size of input in bytes
+    InputSize,
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 ///Possible parser errors
 pub enum ParseError {
     ///Service definition is encountered, but there is no opening bracket
@@ -16,116 +121,2751 @@ pub enum ParseError {
     NoReturnType(String),
     ///Method definition has invalid arguments
     InvalidMethodArgs(String),
+    ///Method arguments list has empty argument, e.g. `(Request,,Extra)`
+    EmptyArgument(String),
+    ///Attributes list is malformed
+    InvalidAttributes(String),
+    ///Table field definition is malformed
+    InvalidField(String),
+    ///Struct field with specified name has default value
+    StructFieldDefault(String),
+    ///Struct field has specified attribute, which is not allowed for structs
+    StructFieldAttribute(String),
+    ///Enum variant definition is malformed
+    InvalidEnumVariant(String),
+    ///Enum definition lacks underlying type
+    NoUnderlyingType(String),
+    ///Union member definition is malformed
+    InvalidUnionMember(String),
+    ///Input ended before body of declaration with specified name is closed
+    ///
+    ///Reported at the line where declaration starts.
+    UnexpectedEof(String),
+    ///Method definition is not terminated with `;`
+    ///
+    ///Reported only by strict parser.
+    MissingSemicolon(String),
+    ///Service body has `;` without method definition, e.g. second `;` of `Get(R):R;;`
+    ///
+    ///Reported only by strict parser.
+    ExtraSemicolon,
+    ///Namespace declaration is malformed
+    InvalidNamespace(String),
+    ///Include declaration is malformed
+    InvalidInclude(String),
+    ///Root type declaration is malformed
+    InvalidRootType(String),
+    ///Root type is declared again with a different table name
+    ConflictingRootType(String),
+    ///File identifier declaration is malformed or identifier is not exactly 4 bytes long
+    InvalidFileIdentifier(String),
+    ///File extension declaration is malformed
+    InvalidFileExtension(String),
+    ///Attribute declaration is malformed
+    InvalidAttributeDeclaration(String),
+    ///Method name is used more than once within the same service
+    DuplicateMethod {
+        ///Service name
+        service: String,
+        ///Repeated method name
+        method: String,
+    },
+    ///Service with the same name is already defined within the same namespace
+    DuplicateService(String),
+    ///Name is not valid identifier
+    InvalidIdentifier {
+        ///What is being named
+        kind: IdentifierKind,
+        ///Name as written
+        name: String,
+    },
+    ///Input contains no service definition
+    NoService,
+    ///Input contains more than one service definition, while only one is expected
+    ///
+    ///Reported at the line where the second service starts.
+    MultipleServices,
+    ///Block comment `/*` is not terminated with `*/` before end of input
+    UnterminatedComment,
+    ///Input exceeds limit of parser
+    ///
+    ///Parser stops on this error, refer to [Limits].
+    LimitExceeded {
+        ///What is limited
+        limit: Limit,
+        ///Maximum value
+        max: usize,
+    },
+    ///Error of method definition within service body
+    InService {
+        ///Service name
+        service: String,
+        ///Method name, if it is parsed before error occurred
+        method: Option<String>,
+        ///Error of method definition
+        error: Box<ParseError>,
+    },
+}
+
+impl ParseError {
+    #[inline(always)]
+    const fn at(self, line: usize) -> ParseErrorAt {
+        ParseErrorAt {
+            line,
+            error: self,
+        }
+    }
+
+    ///Wraps error of method definition `text` within `service`.
+    ///
+    ///Method name is attached, unless it is not valid or is not parsed yet.
+    fn in_service(self, service: &str, text: &str) -> Self {
+        let method = match self {
+            Self::InvalidIdentifier { kind: IdentifierKind::Method, .. } => None,
+            _ => text.find('(').map(|idx| text[..idx].trim()).filter(|name| check_identifier(IdentifierKind::Method, name).is_ok()).map(str::to_owned),
+        };
+        Self::InService {
+            service: service.to_owned(),
+            method,
+            error: Box::new(self),
+        }
+    }
+
+    #[inline]
+    ///Returns error, ignoring context of service, that it occurred within.
+    ///
+    ///```rust
+    ///use flatbuffers_tools::{parse_services, ParseError};
+    ///
+    ///let schema = "rpc_service Storage {\n  Put(Req):Resp;\n}\nrpc_service MonsterStorage {\n  Get(Key:):Resp;\n}\n";
+    ///let error = parse_services(schema).unwrap_err();
+    ///assert_eq!(error.line, 5);
+    ///assert_eq!(error.to_string(), "5: in service 'MonsterStorage', method 'Get': invalid argument type ''");
+    ///match &error.error {
+    ///    ParseError::InService { service, method, .. } => {
+    ///        assert_eq!(service, "MonsterStorage");
+    ///        assert_eq!(method.as_deref(), Some("Get"));
+    ///    },
+    ///    error => panic!("unexpected {:?}", error),
+    ///}
+    ///assert!(matches!(error.error.root(), ParseError::InvalidIdentifier { .. }));
+    ///assert_eq!(flatbuffers_tools::borrowed::parse_ref(schema).unwrap_err(), error);
+    ///
+    ///let error = parse_services("rpc_service Storage {\n  Put(Req):Resp;\n}\nrpc_service MonsterStorage {\n  Get Key:Resp;\n}\n").unwrap_err();
+    ///assert_eq!(error.to_string(), "5: in service 'MonsterStorage': invalid method arguments 'Get Key'");
+    ///```
+    pub fn root(&self) -> &Self {
+        match self {
+            Self::InService { error, .. } => error.root(),
+            error => error,
+        }
+    }
+}
+
+impl core::fmt::Display for IdentifierKind {
+    #[inline]
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Service => fmt.write_str("service name"),
+            Self::Method => fmt.write_str("method name"),
+            Self::ArgumentName => fmt.write_str("argument name"),
+            Self::Argument => fmt.write_str("argument type"),
+            Self::ReturnType => fmt.write_str("return type"),
+        }
+    }
+}
+
+impl core::fmt::Display for Limit {
+    #[inline]
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::LineLength => fmt.write_str("line length"),
+            Self::Methods => fmt.write_str("number of methods"),
+            Self::Services => fmt.write_str("number of services"),
+            Self::IncludeDepth => fmt.write_str("include depth"),
+            Self::InputSize => fmt.write_str("input size"),
+        }
+    }
+}
+
+///Offending text within error message, truncated if it is too long
+struct Snippet<'a>(&'a str);
+
+impl Snippet<'_> {
+    ///Maximum number of characters to display
+    const MAX_LEN: usize = 64;
+}
+
+impl core::fmt::Display for Snippet<'_> {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0.char_indices().nth(Self::MAX_LEN) {
+            Some((idx, _)) => fmt.write_fmt(format_args!("{}...", &self.0[..idx])),
+            None => fmt.write_str(self.0),
+        }
+    }
+}
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NoStartingBracket => fmt.write_str("missing opening bracket '{'"),
+            Self::NoReturnType(text) => fmt.write_fmt(format_args!("cannot determine return type of '{}'", Snippet(text))),
+            Self::InvalidMethodArgs(text) => fmt.write_fmt(format_args!("invalid method arguments '{}'", Snippet(text))),
+            Self::EmptyArgument(text) => fmt.write_fmt(format_args!("empty argument within '{}'", Snippet(text))),
+            Self::InvalidAttributes(text) => fmt.write_fmt(format_args!("invalid attributes '{}'", Snippet(text))),
+            Self::InvalidField(text) => fmt.write_fmt(format_args!("invalid field '{}'", Snippet(text))),
+            Self::StructFieldDefault(name) => fmt.write_fmt(format_args!("struct field '{name}' cannot have default value")),
+            Self::StructFieldAttribute(name) => fmt.write_fmt(format_args!("attribute '{name}' is not allowed on struct field")),
+            Self::InvalidEnumVariant(text) => fmt.write_fmt(format_args!("invalid enum variant '{}'", Snippet(text))),
+            Self::NoUnderlyingType(text) => fmt.write_fmt(format_args!("enum '{}' has no underlying type", Snippet(text))),
+            Self::InvalidUnionMember(text) => fmt.write_fmt(format_args!("invalid union member '{}'", Snippet(text))),
+            Self::UnexpectedEof(name) => fmt.write_fmt(format_args!("'{name}' is not closed before end of input")),
+            Self::MissingSemicolon(text) => fmt.write_fmt(format_args!("missing ';' after '{}'", Snippet(text))),
+            Self::ExtraSemicolon => fmt.write_str("extra ';' without method definition"),
+            Self::InvalidNamespace(text) => fmt.write_fmt(format_args!("invalid namespace '{}'", Snippet(text))),
+            Self::InvalidInclude(text) => fmt.write_fmt(format_args!("invalid include '{}'", Snippet(text))),
+            Self::InvalidRootType(text) => fmt.write_fmt(format_args!("invalid root_type '{}'", Snippet(text))),
+            Self::ConflictingRootType(name) => fmt.write_fmt(format_args!("root_type '{name}' conflicts with previous declaration")),
+            Self::InvalidFileIdentifier(text) => fmt.write_fmt(format_args!("invalid file_identifier '{}', expected 4 bytes in quotes", Snippet(text))),
+            Self::InvalidFileExtension(text) => fmt.write_fmt(format_args!("invalid file_extension '{}'", Snippet(text))),
+            Self::InvalidAttributeDeclaration(text) => fmt.write_fmt(format_args!("invalid attribute declaration '{}'", Snippet(text))),
+            Self::DuplicateMethod { service, method } => fmt.write_fmt(format_args!("method '{method}' is already defined in service '{service}'")),
+            Self::DuplicateService(name) => fmt.write_fmt(format_args!("service '{name}' is already defined")),
+            Self::InvalidIdentifier { kind, name } => fmt.write_fmt(format_args!("invalid {kind} '{name}'")),
+            Self::NoService => fmt.write_str("no service is defined"),
+            Self::MultipleServices => fmt.write_str("more than one service is defined"),
+            Self::UnterminatedComment => fmt.write_str("block comment is not terminated"),
+            Self::LimitExceeded { limit, max } => fmt.write_fmt(format_args!("{limit} exceeds limit of {max}")),
+            Self::InService { service, method: Some(method), error } => fmt.write_fmt(format_args!("in service '{service}', method '{method}': {error}")),
+            Self::InService { service, method: None, error } => fmt.write_fmt(format_args!("in service '{service}': {error}")),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InService { error, .. } => Some(&**error),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-///rpc method
-pub struct RpcMethod {
-    ///Method's name
-    pub name: String,
-    ///List of arguments
-    pub arguments: Vec<String>,
-    ///Return type
-    pub return_type: String,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///Parser error with its location
+pub struct ParseErrorAt {
+    ///Line number, starting from 1.
+    ///
+    ///Points to the line where erroneous definition starts.
+    pub line: usize,
+    ///Error
+    pub error: ParseError,
 }
 
-impl RpcMethod {
-    fn parse(line: &str) -> Result<Self, ParseError> {
-        let mut parts = line.split(':');
-        let method_args = parts.next().unwrap();
-        let return_type = match parts.next() {
-            Some(return_type) => return_type.trim().trim_end_matches(';'),
-            None => return Err(ParseError::NoReturnType(line.to_owned())),
-        };
-        let mut parts = method_args.split('(');
-        let name = parts.next().unwrap().trim();
-        let mut args = match parts.next() {
-            Some(args) => args.trim(),
-            None => return Err(ParseError::InvalidMethodArgs(method_args.to_owned())),
-        };
-        args = if let Some(args) = args.strip_suffix(')') {
-            args
-        } else {
-            return Err(ParseError::InvalidMethodArgs(method_args.to_owned()))
-        };
-        let arguments = args.split(',').map(str::to_owned).collect();
+impl core::fmt::Display for ParseErrorAt {
+    #[inline]
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt.write_fmt(format_args!("{}: {}", self.line, self.error))
+    }
+}
 
-        Ok(Self {
-            name: name.to_owned(),
-            arguments,
-            return_type: return_type.to_owned()
-        })
+impl std::error::Error for ParseErrorAt {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///Position within source text
+pub struct Position {
+    ///Line number, starting from 1.
+    pub line: usize,
+    ///Byte offset within line, starting from 1.
+    pub column: usize,
+}
+
+impl Position {
+    #[inline(always)]
+    const fn new(line: usize, offset: usize) -> Self {
+        Self {
+            line,
+            column: offset + 1,
+        }
     }
 }
 
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///Location of definition within source text.
+///
+///Default value is used for definitions, which are not produced by parser.
+///
+///Spans are ignored when comparing, so that definitions are equal regardless of their location.
+pub struct Span {
+    ///Position of first character
+    pub start: Position,
+    ///Position of last character
+    pub end: Position,
+}
+
+impl PartialEq for Span {
+    #[inline(always)]
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Span {}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
-///rpc_service definition
-pub struct RpcService {
-    ///Service name
-    pub name: String,
-    ///List of service methods
-    pub methods: Vec<RpcMethod>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///Line of input, that is not part of any service, recorded by [ParserIter::with_skipped_lines]
+pub struct SkippedLine {
+    ///Line number, starting from 1.
+    pub line_no: usize,
+    ///Text as read, including comments, without line ending.
+    pub text: String,
 }
 
-impl RpcService {
-    ///Gets formatter to generate RPC method defines which are upper case constants corresponding
-    ///to RPC method name.
-    pub fn as_rpc_method_defines(&self) -> RpcMethodDefines<'_> {
-        RpcMethodDefines {
-            service: self
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///Limits of parser, protecting against resource exhaustion by malicious input
+///
+///Parser fails with [LimitExceeded](ParseError::LimitExceeded) once any limit is exceeded, and stops, not producing anything afterwards.
+///Default limits are generous enough for any schema written by hand, while [Limits::unlimited] disables them.
+///
+///```rust
+///use flatbuffers_tools::{Limit, Limits, ParseError, ParserIter};
+///use std::io;
+///
+///let limits = Limits::new().max_line_length(16).max_methods(2).max_services(1);
+///let exceeded = |text: &str| {
+///    let mut parser = ParserIter::from_str(text).limits(limits);
+///    let error = parser.by_ref().find_map(Result::err).expect("to fail");
+///    assert!(parser.next().is_none());
+///    match error.error {
+///        ParseError::LimitExceeded { limit, max } => (limit, max, error.line),
+///        error => panic!("unexpected error {error:?}"),
+///    }
+///};
+///assert_eq!(exceeded("rpc_service A {\n  Get(R):R;\n  Put(R):R;\n  Del(R):R;\n  Ping(R):R;\n}"), (Limit::Methods, 2, 4));
+///assert_eq!(exceeded("rpc_service A {\n  Get(R):R;\n}\nrpc_service B {\n  Get(R):R;\n}"), (Limit::Services, 1, 4));
+///assert_eq!(exceeded("rpc_service A {\n  Get(Request):Response;\n}"), (Limit::LineLength, 16, 2));
+///assert_eq!(exceeded("table Value {\n  very_long_name: string;\n}"), (Limit::LineLength, 16, 2));
+///
+/////Reader stops without reading whole line, even if it never ends
+///let mut parser = ParserIter::from_reader(io::BufReader::new(io::repeat(b'a'))).limits(limits);
+///match parser.next() {
+///    Some(Err(flatbuffers_tools::reader::Error::Parse(error))) => assert_eq!(error.error, ParseError::LimitExceeded { limit: Limit::LineLength, max: 16 }),
+///    result => panic!("unexpected result {result:?}"),
+///}
+///assert!(parser.next().is_none());
+///
+/////As well as input, that never ends
+///let mut parser = ParserIter::from_reader(io::BufReader::new(io::repeat(b'\n'))).limits(limits.max_input_size(1024));
+///match parser.next() {
+///    Some(Err(flatbuffers_tools::reader::Error::Parse(error))) => assert_eq!(error.error, ParseError::LimitExceeded { limit: Limit::InputSize, max: 1024 }),
+///    result => panic!("unexpected result {result:?}"),
+///}
+///
+/////Limits are not enforced when disabled
+///let methods: String = (0..100).map(|idx| format!("  Method{idx}(Request):Response;\n")).collect();
+///let text = format!("rpc_service A {{\n{methods}}}\nrpc_service B {{\n{methods}}}");
+///let services = ParserIter::from_str(&text).limits(Limits::unlimited().max_line_length(40)).collect::<Result<Vec<_>, _>>().unwrap();
+///assert_eq!(services.len(), 2);
+///```
+pub struct Limits {
+    ///Maximum length of line in bytes, 1 MiB by default.
+    pub max_line_length: usize,
+    ///Maximum number of methods within service, 65536 by default.
+    pub max_methods: usize,
+    ///Maximum number of services within input, 65536 by default.
+    pub max_services: usize,
+    ///Maximum depth of nested `include` declarations, 64 by default.
+    ///
+    ///Enforced only when includes are followed, refer to [fs].
+    pub max_include_depth: usize,
+    ///Maximum size of input in bytes, 1 GiB by default.
+    ///
+    ///Enforced only by [parser over reader](ParserIter::from_reader) and when files are read, refer to [fs],
+    ///as input of other parsers is already in memory.
+    pub max_input_size: usize,
+}
+
+impl Limits {
+    #[inline]
+    ///Creates default limits.
+    pub const fn new() -> Self {
+        Self {
+            max_line_length: 1 << 20,
+            max_methods: 1 << 16,
+            max_services: 1 << 16,
+            max_include_depth: 64,
+            max_input_size: 1 << 30,
+        }
+    }
+
+    #[inline]
+    ///Creates limits, that are never exceeded.
+    pub const fn unlimited() -> Self {
+        Self {
+            max_line_length: usize::MAX,
+            max_methods: usize::MAX,
+            max_services: usize::MAX,
+            max_include_depth: usize::MAX,
+            max_input_size: usize::MAX,
+        }
+    }
+
+    #[inline]
+    ///Sets maximum length of line in bytes.
+    pub const fn max_line_length(mut self, max_line_length: usize) -> Self {
+        self.max_line_length = max_line_length;
+        self
+    }
+
+    #[inline]
+    ///Sets maximum number of methods within service.
+    pub const fn max_methods(mut self, max_methods: usize) -> Self {
+        self.max_methods = max_methods;
+        self
+    }
+
+    #[inline]
+    ///Sets maximum number of services within input.
+    pub const fn max_services(mut self, max_services: usize) -> Self {
+        self.max_services = max_services;
+        self
+    }
+
+    #[inline]
+    ///Sets maximum depth of nested `include` declarations.
+    pub const fn max_include_depth(mut self, max_include_depth: usize) -> Self {
+        self.max_include_depth = max_include_depth;
+        self
+    }
+
+    #[inline]
+    ///Sets maximum size of input in bytes.
+    pub const fn max_input_size(mut self, max_input_size: usize) -> Self {
+        self.max_input_size = max_input_size;
+        self
+    }
+
+    #[inline(always)]
+    const fn exceeded(limit: Limit, max: usize) -> ParseError {
+        ParseError::LimitExceeded {
+            limit,
+            max,
         }
     }
 }
 
-///rpc_service parser
-pub struct ParserIter<T> {
-    lines: T,
+impl Default for Limits {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-impl<I: AsRef<str>, T: Iterator<Item=I>> ParserIter<T> {
-    ///Creates new parser from iterator over lines.
-    pub fn new(lines: T) -> Self {
+///Attribute as pair of key and optional value, with quotes stripped.
+pub type Attribute = (String, Option<String>);
+
+///Type name as written, shared between methods using the same type.
+///
+///Parser interns type names, so that all occurrences of the same type within single input share storage of [TypeRef],
+///while comparison and hashing are still by value:
+///
+///```rust
+///use flatbuffers_tools::RpcService;
+///use std::sync::Arc;
+///
+///let service: RpcService = "rpc_service Storage { Put(Key): Value; Get(Key): Value; }".parse().unwrap();
+///let (put, get) = (&service.methods[0], &service.methods[1]);
+///assert!(Arc::ptr_eq(put.arguments[0].ty.as_type_str(), get.arguments[0].ty.as_type_str()));
+///assert!(Arc::ptr_eq(put.return_type.as_type_str(), get.return_type.as_type_str()));
+///assert_eq!(put.return_type, "Value");
+///```
+pub type TypeStr = std::sync::Arc<str>;
+
+#[derive(Default)]
+///Type names, shared within single parser
+pub(crate) struct Interner {
+    names: HashSet<TypeStr>,
+}
+
+impl Interner {
+    ///Returns shared copy of type `name`.
+    pub(crate) fn intern(&mut self, name: &str) -> TypeRef {
+        match self.names.get(name) {
+            Some(name) => name.clone().into(),
+            None => {
+                let name = TypeStr::from(name);
+                self.names.insert(name.clone());
+                name.into()
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///Type name, optionally qualified with namespace, e.g. `MyGame.Sample.Request`
+pub struct TypeName {
+    ///Dot separated segments, where last one is name of the type itself.
+    pub segments: Vec<String>,
+}
+
+impl TypeName {
+    ///Splits dot separated type name into segments.
+    pub fn new(name: &str) -> Self {
         Self {
-            lines
+            segments: name.split('.').map(|segment| segment.trim().to_owned()).collect(),
+        }
+    }
+
+    #[inline]
+    ///Returns unqualified name of the type.
+    pub fn name(&self) -> &str {
+        match self.segments.last() {
+            Some(name) => name.as_str(),
+            None => "",
+        }
+    }
+
+    #[inline]
+    ///Returns namespace segments, empty if type is not qualified.
+    pub fn namespace(&self) -> &[String] {
+        match self.segments.split_last() {
+            Some((_, namespace)) => namespace,
+            None => &[],
         }
     }
 }
 
-impl<I: AsRef<str>, T: Iterator<Item=I>> Iterator for ParserIter<T> {
-    type Item = Result<RpcService, ParseError>;
+impl core::fmt::Display for TypeName {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (idx, segment) in self.segments.iter().enumerate() {
+            if idx > 0 {
+                fmt.write_str(".")?;
+            }
+            fmt.write_str(segment)?;
+        }
+        Ok(())
+    }
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        while let Some(line) = self.lines.next() {
-            let line = line.as_ref().trim();
-            if let Some(name) = line.strip_prefix("rpc_service") {
-                if let Some(name_end_idx) = name.find('{') {
-                    let name = name[..name_end_idx].trim();
-                    let mut methods = Vec::new();
-
-                    while let Some(method) = self.lines.next() {
-                        let method = method.as_ref().trim();
-                        if method == "}" {
-                            break;
-                        }
-
-                        match RpcMethod::parse(method) {
-                            Ok(method) => methods.push(method),
-                            Err(error) => return Some(Err(error)),
-                        }
-                    }
+//Built-in scalar types, including aliases of sized types
+const SCALARS: [&str; 21] = [
+    "bool", "byte", "ubyte", "short", "ushort", "int", "uint", "long", "ulong", "float", "double",
+    "int8", "uint8", "int16", "uint16", "int32", "uint32", "int64", "uint64", "float32", "float64",
+];
 
-                    return Some(Ok(RpcService {
-                        name: name.to_owned(),
-                        methods,
-                    }));
-                } else {
-                    return Some(Err(ParseError::NoStartingBracket));
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+///Kind of [TypeRef], as determined by spelling of the type.
+pub enum TypeRefKind {
+    ///Built-in scalar, e.g. `int`, `uint64` or `bool`
+    Scalar,
+    ///Built-in `string`
+    String,
+    ///Vector, e.g. `[ubyte]`
+    Vector,
+    ///Fixed size array within struct, e.g. `[ubyte:4]`
+    Array,
+    ///User defined type, that is table, struct, enum or union.
+    ///
+    ///Which one is known only with declarations of schema, refer to [SchemaIndex::resolve_type](schema::SchemaIndex::resolve_type).
+    Named,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+///Reference to type, as written within schema, e.g. `MyGame.Request`, `string` or `[ubyte]`.
+///
+///It is type of method arguments, return types and fields of tables and structs, that is shared by parsers and generators,
+///so that namespace and name of the type are split the same way everywhere.
+///Unlike [schema::TypeRef], which is declaration found by schema, it is only spelling of the type.
+///
+///`Display` writes type as written, while [resolve](Self::resolve) qualifies it with namespace of the definition, same as flatc.
+///
+///```rust
+///use flatbuffers_tools::{RpcService, TypeRef, TypeRefKind};
+///
+///let service: RpcService = "namespace MyGame;\nrpc_service Storage { Get(Shared.Key): Value; }".parse().unwrap();
+///let method = &service.methods[0];
+///let argument = &method.arguments[0].ty;
+///assert_eq!(argument.to_string(), "Shared.Key");
+///assert_eq!(argument.kind(), TypeRefKind::Named);
+///assert_eq!(argument.namespace(), Some("Shared"));
+///assert_eq!(argument.name(), "Key");
+///assert_eq!(argument.resolve(service.namespace.as_deref()).to_string(), "Shared.Key");
+///assert_eq!(method.return_type.namespace(), None);
+///assert_eq!(method.return_type.resolve(service.namespace.as_deref()).to_string(), "MyGame.Value");
+///
+///let vector = TypeRef::new("[MyGame.Item]");
+///assert_eq!(vector.kind(), TypeRefKind::Vector);
+///assert_eq!(vector.element(), Some("MyGame.Item"));
+///assert_eq!(vector.namespace(), None);
+///assert_eq!(TypeRef::new("[ubyte:4]").kind(), TypeRefKind::Array);
+///assert_eq!(TypeRef::new("[ubyte:4]").element(), Some("ubyte"));
+///assert_eq!(TypeRef::new("float32").kind(), TypeRefKind::Scalar);
+///assert_eq!(TypeRef::new("string").kind(), TypeRefKind::String);
+///assert_eq!(TypeRef::new("string").element(), None);
+///```
+///
+///## Migration
+///
+///[Argument::ty] and [RpcMethod::return_type] used to be [TypeStr], while [schema::Field::ty] used to be `String`.
+///
+///- `TypeRef` dereferences to `str` and compares with `str`, hence code reading type names compiles as it is.
+///  Dereferencing is kept for compatibility only, prefer [as_str](Self::as_str).
+///- Fields are constructed with `into()` from `&str`, `String` or [TypeStr], e.g. `Argument { name: None, ty: "Key".into() }`.
+///- Shared storage of parsed type names is available with [as_type_str](Self::as_type_str), and `TypeRef` converts into [TypeStr] and `String`.
+///- Namespace and name are to be taken from [namespace](Self::namespace) and [name](Self::name), instead of splitting type name by `.`,
+///  and [resolve](Self::resolve) replaces qualifying it with namespace of the service.
+///
+///Generated code is the same, as types are written as they were:
+///
+///```rust
+///use flatbuffers_tools::{Argument, RpcService, TypeStr};
+///
+///let parsed: RpcService = "namespace MyGame;\nrpc_service Storage { Put(Shared.Key): Value; }".parse().unwrap();
+///let mut built = parsed.clone();
+///let legacy: TypeStr = "Shared.Key".into();
+///built.methods[0].arguments = vec![Argument { name: None, ty: legacy.into() }];
+///built.methods[0].return_type = String::from("Value").into();
+///assert_eq!(built, parsed);
+///assert_eq!(&*built.methods[0].return_type, "Value");
+///
+///let aliases = built.as_type_aliases().to_string();
+///assert_eq!(aliases, parsed.as_type_aliases().to_string());
+///assert!(aliases.contains("pub type PutRequest = crate::interface::shared::Key;\n"));
+///assert!(aliases.contains("pub type PutResponse = crate::interface::my_game::Value;\n"));
+///```
+pub struct TypeRef {
+    text: TypeStr,
+}
+
+impl TypeRef {
+    #[inline]
+    ///Creates reference to type, as written.
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.into(),
+        }
+    }
+
+    #[inline]
+    ///Returns type as written.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    #[inline]
+    ///Returns type as written, which is shared between all uses of the same type within single input of parser.
+    pub fn as_type_str(&self) -> &TypeStr {
+        &self.text
+    }
+
+    ///Returns kind of the type, as determined by its spelling.
+    pub fn kind(&self) -> TypeRefKind {
+        let text = self.text.trim();
+        match text.strip_prefix('[').and_then(|element| element.strip_suffix(']')) {
+            Some(element) if element.contains(':') => TypeRefKind::Array,
+            Some(_) => TypeRefKind::Vector,
+            None if text == "string" => TypeRefKind::String,
+            None if SCALARS.contains(&text) => TypeRefKind::Scalar,
+            None => TypeRefKind::Named,
+        }
+    }
+
+    ///Returns type of elements, if type is vector or array, e.g. `Item` of `[Item]` or `[Item:4]`.
+    pub fn element(&self) -> Option<&str> {
+        let element = self.text.trim().strip_prefix('[')?.strip_suffix(']')?;
+        match element.split_once(':') {
+            Some((element, _)) => Some(element.trim()),
+            None => Some(element.trim()),
+        }
+    }
+
+    ///Returns namespace of the type, if it is qualified.
+    ///
+    ///Vectors and arrays have no namespace of their own, refer to [element](Self::element).
+    pub fn namespace(&self) -> Option<&str> {
+        match self.kind() {
+            TypeRefKind::Named => self.text.rfind('.').map(|idx| self.text[..idx].trim()),
+            _ => None,
+        }
+    }
+
+    ///Returns unqualified name of the type.
+    ///
+    ///Vectors and arrays are returned as they are written.
+    pub fn name(&self) -> &str {
+        match self.kind() {
+            TypeRefKind::Named => match self.text.rfind('.') {
+                Some(idx) => self.text[idx + 1..].trim(),
+                None => self.text.trim(),
+            },
+            _ => self.text.trim(),
+        }
+    }
+
+    #[inline]
+    ///Returns type split into namespace and name, as written.
+    pub fn type_name(&self) -> TypeName {
+        TypeName::new(&self.text)
+    }
+
+    #[inline]
+    ///Resolves type, as written within `namespace`, to fully qualified name.
+    ///
+    ///Name without namespace is within `namespace`, same as flatc resolves it.
+    pub fn resolve(&self, namespace: Option<&str>) -> TypeName {
+        resolve_type_name(&self.text, namespace)
+    }
+}
+
+//Qualifies type `name` with `namespace` of definition, unless it is already qualified
+fn resolve_type_name(name: &str, namespace: Option<&str>) -> TypeName {
+    match namespace {
+        Some(namespace) if !name.contains('.') => TypeName::new(&format!("{namespace}.{name}")),
+        _ => TypeName::new(name),
+    }
+}
+
+impl core::fmt::Display for TypeRef {
+    #[inline]
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt.write_str(&self.text)
+    }
+}
+
+impl core::ops::Deref for TypeRef {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.text
+    }
+}
+
+impl AsRef<str> for TypeRef {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl core::borrow::Borrow<str> for TypeRef {
+    #[inline]
+    fn borrow(&self) -> &str {
+        &self.text
+    }
+}
+
+impl PartialEq<str> for TypeRef {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        *self.text == *other
+    }
+}
+
+impl PartialEq<&str> for TypeRef {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        *self.text == **other
+    }
+}
+
+impl From<&str> for TypeRef {
+    #[inline]
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for TypeRef {
+    #[inline]
+    fn from(text: String) -> Self {
+        Self {
+            text: text.into(),
+        }
+    }
+}
+
+impl From<TypeStr> for TypeRef {
+    #[inline]
+    fn from(text: TypeStr) -> Self {
+        Self {
+            text,
+        }
+    }
+}
+
+impl From<TypeRef> for TypeStr {
+    #[inline]
+    fn from(ty: TypeRef) -> Self {
+        ty.text
+    }
+}
+
+impl From<TypeRef> for String {
+    #[inline]
+    fn from(ty: TypeRef) -> Self {
+        ty.text.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///rpc method argument
+pub struct Argument {
+    ///Argument's name, if declared, e.g. `from` in `Transfer(from: Account): Receipt`
+    pub name: Option<String>,
+    ///Type as written, including namespace, if any.
+    pub ty: TypeRef,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+///Kind of streaming, specified by `streaming` attribute of method
+pub enum Streaming {
+    ///Single request and single response, same as without attribute.
+    None,
+    ///Single request and stream of responses
+    Server,
+    ///Stream of requests and single response
+    Client,
+    ///Stream of requests and stream of responses
+    Bidi,
+}
+
+impl Streaming {
+    ///Parses value of `streaming` attribute, as accepted by flatc.
+    pub fn from_value(value: &str) -> Option<Self> {
+        match value {
+            "none" => Some(Self::None),
+            "server" => Some(Self::Server),
+            "client" => Some(Self::Client),
+            "bidi" => Some(Self::Bidi),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    ///Returns value of `streaming` attribute, that is parsed by [from_value](Self::from_value).
+    pub const fn as_value(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Server => "server",
+            Self::Client => "client",
+            Self::Bidi => "bidi",
+        }
+    }
+
+    #[inline]
+    ///Returns whether there is stream of requests.
+    pub const fn is_client(self) -> bool {
+        matches!(self, Self::Client | Self::Bidi)
+    }
+
+    #[inline]
+    ///Returns whether there is stream of responses.
+    pub const fn is_server(self) -> bool {
+        matches!(self, Self::Server | Self::Bidi)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+///Priority of method, specified by `priority` attribute
+pub enum Priority {
+    ///Value `low`
+    Low,
+    ///Value `normal`
+    Normal,
+    ///Value `high`
+    High,
+}
+
+impl Priority {
+    ///Parses value of `priority` attribute.
+    pub fn from_value(value: &str) -> Option<Self> {
+        match value {
+            "low" => Some(Self::Low),
+            "normal" => Some(Self::Normal),
+            "high" => Some(Self::High),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    ///Returns value of `priority` attribute, that is parsed by [from_value](Self::from_value).
+    pub const fn as_value(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Normal => "normal",
+            Self::High => "high",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///rpc method
+pub struct RpcMethod {
+    ///Method's name
+    pub name: String,
+    ///List of arguments
+    ///
+    ///Empty if method has no arguments.
+    pub arguments: Vec<Argument>,
+    ///Return type as written, including namespace, if any.
+    pub return_type: TypeRef,
+    ///List of attributes in order of definition.
+    ///
+    ///Each attribute is pair of key and optional value, with quotes stripped.
+    ///E.g. `(streaming: "server", idempotent)` results in `[("streaming", Some("server")), ("idempotent", None)]`
+    pub attributes: Vec<Attribute>,
+    ///Documentation comments `///` preceding definition, one entry per line.
+    pub docs: Vec<String>,
+    ///Location of definition
+    pub span: Span,
+}
+
+#[inline]
+///Removes `//` comment from the line, if any, ignoring `//` within quoted strings.
+fn strip_comment(line: &str) -> &str {
+    let bytes = line.as_bytes();
+    match unquoted_bytes(line).find(|(idx, byte)| *byte == b'/' && bytes.get(idx + 1) == Some(&b'/')) {
+        Some((idx, _)) => &line[..idx],
+        None => line,
+    }
+}
+
+///Removes all comments from the line.
+///
+///Block comments are replaced with whitespace to retain position of text after them.
+///
+///`in_comment` tracks whether block comment `/* */` is still open, and is updated accordingly.
+fn strip_comments<'a>(mut line: &'a str, in_comment: &mut bool) -> Cow<'a, str> {
+    if !*in_comment {
+        //Fast path for line without block comments, which is found within single pass
+        match find_comment(line) {
+            None => return Cow::Borrowed(line),
+            Some((idx, b'/')) => return Cow::Borrowed(&line[..idx]),
+            Some(_) => (),
+        }
+    }
+
+    let mut result = String::with_capacity(line.len());
+    loop {
+        if *in_comment {
+            match line.find("*/") {
+                Some(idx) => {
+                    *in_comment = false;
+                    result.extend(core::iter::repeat_n(' ', idx + 2));
+                    line = &line[idx + 2..];
+                },
+                None => break,
+            }
+        } else {
+            match find_comment(line) {
+                Some((idx, b'*')) => {
+                    result.push_str(&line[..idx]);
+                    result.push_str("  ");
+                    *in_comment = true;
+                    line = &line[idx + 2..];
+                },
+                Some((idx, _)) => {
+                    result.push_str(&line[..idx]);
+                    break;
+                },
+                None => {
+                    result.push_str(line);
+                    break;
                 }
-            } else {
-                continue
             }
         }
-
-        None
     }
+
+    Cow::Owned(result)
+}
+
+///Finds start of the first comment outside of quoted strings, returning its index and second character, which is `/` or `*`
+///
+///Bytes are searched, as ASCII bytes never occur within multi-byte UTF-8 characters.
+fn find_comment(text: &str) -> Option<(usize, u8)> {
+    let bytes = text.as_bytes();
+    unquoted_bytes(text).find_map(|(idx, byte)| match (byte, bytes.get(idx + 1)) {
+        (b'/', Some(next)) if *next == b'/' || *next == b'*' => Some((idx, *next)),
+        _ => None,
+    })
+}
+
+///Splits `text` by `separator`, ignoring separators within quoted strings.
+fn split_unquoted(text: &str, separator: char) -> impl Iterator<Item = &str> {
+    let mut rest = Some(text);
+    core::iter::from_fn(move || {
+        let text = rest?;
+        match find_unquoted(text, separator) {
+            Some(idx) => {
+                rest = Some(&text[idx+1..]);
+                Some(&text[..idx])
+            },
+            None => {
+                rest = None;
+                Some(text)
+            },
+        }
+    })
+}
+
+#[inline]
+///Finds first occurrence of ASCII `ch` outside of quoted strings.
+fn find_unquoted(text: &str, ch: char) -> Option<usize> {
+    find_unquoted_any(text, ch, ch).map(|(idx, _)| idx)
+}
+
+///Finds first occurrence of either ASCII `first` or `second` outside of quoted strings, returning its index and byte.
+fn find_unquoted_any(text: &str, first: char, second: char) -> Option<(usize, u8)> {
+    let (first, second) = (first as u8, second as u8);
+    unquoted_bytes(text).find(|(_, byte)| *byte == first || *byte == second)
+}
+
+///Iterates over bytes of `text` outside of quoted strings, with their indexes.
+///
+///Quotes themselves are skipped, and `\` escapes the next byte within quoted string, e.g. `"a\"}"` is single string.
+///Bytes are iterated, as ASCII bytes never occur within multi-byte UTF-8 characters.
+fn unquoted_bytes(text: &str) -> impl Iterator<Item = (usize, u8)> + '_ {
+    let mut is_quoted = false;
+    let mut is_escaped = false;
+    text.bytes().enumerate().filter(move |(_, byte)| {
+        if is_escaped {
+            is_escaped = false;
+            return false;
+        }
+        match byte {
+            b'\\' if is_quoted => {
+                is_escaped = true;
+                false
+            },
+            b'"' => {
+                is_quoted = !is_quoted;
+                false
+            },
+            _ => !is_quoted,
+        }
+    })
+}
+
+///Returns nesting of brackets after `text`, starting with `depth`, ignoring brackets within quoted strings.
+fn bracket_depth(text: &str, depth: usize) -> usize {
+    unquoted_bytes(text).fold(depth, |depth, (_, byte)| match byte {
+        b'{' => depth + 1,
+        b'}' => depth.saturating_sub(1),
+        _ => depth,
+    })
+}
+
+///Returns index after the end of skipped declaration within `text`, with brackets of `depth` open before it.
+///
+///Declaration ends with bracket, that closes all brackets, or with `;` outside of brackets, followed by optional `;`.
+fn declaration_end(text: &str, depth: usize) -> Option<usize> {
+    let mut depth = depth;
+    let end = unquoted_bytes(text).find_map(|(idx, byte)| match byte {
+        b'{' => {
+            depth += 1;
+            None
+        },
+        b'}' if depth > 0 => {
+            depth -= 1;
+            Some(idx + 1).filter(|_| depth == 0)
+        },
+        b';' if depth == 0 => Some(idx + 1),
+        _ => None,
+    })?;
+    let rest = &text[end..];
+    match rest.trim_start().strip_prefix(';') {
+        Some(after) if text.as_bytes()[end - 1] == b'}' => Some(text.len() - after.len()),
+        _ => Some(end),
+    }
+}
+
+#[inline]
+fn unquote(value: &str) -> &str {
+    match value.strip_prefix('"').and_then(|value| value.strip_suffix('"')) {
+        Some(value) => value,
+        None => value,
+    }
+}
+
+///Parses comma separated list of attributes, without enclosing parenthesis.
+fn parse_attributes(text: &str) -> Result<Vec<Attribute>, ParseError> {
+    parse_attributes_ref(text).map(owned_attributes)
+}
+
+#[inline]
+fn owned_attributes(attributes: Vec<borrowed::AttributeRef<'_>>) -> Vec<Attribute> {
+    attributes.into_iter().map(|(key, value)| (key.to_owned(), value.map(str::to_owned))).collect()
+}
+
+///Parses declaration header `<name> (<attributes>)`, borrowing from `header`.
+fn parse_header_ref(header: &str) -> Result<(&str, Vec<borrowed::AttributeRef<'_>>), ParseError> {
+    match find_unquoted(header, '(') {
+        Some(idx) => match header[idx+1..].trim_end().strip_suffix(')') {
+            Some(attributes) => Ok((header[..idx].trim(), parse_attributes_ref(attributes)?)),
+            None => Err(ParseError::InvalidAttributes(header[idx..].trim().to_owned())),
+        },
+        None => Ok((header.trim(), Vec::new())),
+    }
+}
+
+///Parses comma separated list of attributes, without enclosing parenthesis, borrowing from `text`.
+fn parse_attributes_ref(text: &str) -> Result<Vec<borrowed::AttributeRef<'_>>, ParseError> {
+    let mut attributes = Vec::new();
+
+    for attribute in split_unquoted(text, ',') {
+        let attribute = attribute.trim();
+        if attribute.is_empty() {
+            continue;
+        }
+
+        let (key, value) = match attribute.split_once(':') {
+            Some((key, value)) => (key.trim(), Some(unquote(value.trim()))),
+            None => (attribute, None),
+        };
+
+        if key.is_empty() {
+            return Err(ParseError::InvalidAttributes(text.to_owned()));
+        }
+        attributes.push((key, value));
+    }
+
+    Ok(attributes)
+}
+
+impl RpcMethod {
+    #[inline]
+    ///Returns whether method has `deprecated` attribute.
+    pub fn is_deprecated(&self) -> bool {
+        self.attributes.iter().any(|(key, _)| key == "deprecated")
+    }
+
+    ///Returns kind of streaming, specified by `streaming` attribute.
+    ///
+    ///Without attribute it is [Streaming::None], while unknown value or attribute without value results in `Err` with the value.
+    ///
+    ///```rust
+    ///use flatbuffers_tools::{RpcService, Streaming};
+    ///
+    ///let service: RpcService = "rpc_service Feed { Get(Key):Item; Watch(Key):Item (streaming: \"server\"); Tail(Key):Item (streaming: \"tail\"); }".parse().unwrap();
+    ///assert_eq!(service.methods[0].streaming(), Ok(Streaming::None));
+    ///assert_eq!(service.methods[1].streaming(), Ok(Streaming::Server));
+    ///assert_eq!(service.methods[2].streaming(), Err("tail"));
+    ///```
+    pub fn streaming(&self) -> Result<Streaming, &str> {
+        match self.attributes.iter().find(|(key, _)| key == "streaming") {
+            Some((_, Some(value))) => Streaming::from_value(value).ok_or(value.as_str()),
+            Some((_, None)) => Err(""),
+            None => Ok(Streaming::None),
+        }
+    }
+
+    ///Returns value of attribute `key`, or `None` without attribute.
+    ///
+    ///Attribute without value results in `Err` with empty value, as value is required.
+    ///
+    ///```rust
+    ///use flatbuffers_tools::{Priority, RpcService};
+    ///
+    ///let service: RpcService = "rpc_service Feed { Get(Key):Item (timeout_ms: \"250\", priority: \"high\"); Put(Item):Item (timeout_ms: \"soon\", priority); Tail(Key):Item; }".parse().unwrap();
+    ///let (get, put, tail) = (&service.methods[0], &service.methods[1], &service.methods[2]);
+    ///assert_eq!(get.attribute_str("priority"), Ok(Some("high")));
+    ///assert_eq!(get.attribute_u64("timeout_ms"), Ok(Some(250)));
+    ///assert_eq!((get.timeout_ms(), get.priority()), (Ok(Some(250)), Ok(Some(Priority::High))));
+    ///
+    ///assert_eq!(put.attribute_str("priority"), Err(""));
+    ///assert_eq!(put.attribute_u64("timeout_ms"), Err("soon"));
+    ///assert_eq!((put.timeout_ms(), put.priority()), (Err("soon"), Err("")));
+    ///
+    ///assert_eq!(tail.attribute_str("priority"), Ok(None));
+    ///assert_eq!(tail.attribute_u64("timeout_ms"), Ok(None));
+    ///assert_eq!((tail.timeout_ms(), tail.priority()), (Ok(None), Ok(None)));
+    ///```
+    pub fn attribute_str(&self, key: &str) -> Result<Option<&str>, &str> {
+        match self.attributes.iter().find(|(name, _)| name == key) {
+            Some((_, Some(value))) => Ok(Some(value.as_str())),
+            Some((_, None)) => Err(""),
+            None => Ok(None),
+        }
+    }
+
+    ///Returns value of attribute `key` as unsigned integer, or `None` without attribute.
+    ///
+    ///Value, that is not decimal integer, results in `Err` with the value, refer to [attribute_str](Self::attribute_str).
+    pub fn attribute_u64(&self, key: &str) -> Result<Option<u64>, &str> {
+        match self.attribute_str(key)? {
+            Some(value) => match value.parse() {
+                Ok(value) => Ok(Some(value)),
+                Err(_) => Err(value),
+            },
+            None => Ok(None),
+        }
+    }
+
+    #[inline]
+    ///Returns timeout in milliseconds, specified by `timeout_ms` attribute, refer to [attribute_u64](Self::attribute_u64).
+    pub fn timeout_ms(&self) -> Result<Option<u64>, &str> {
+        self.attribute_u64("timeout_ms")
+    }
+
+    ///Returns priority, specified by `priority` attribute.
+    ///
+    ///Unknown value results in `Err` with the value, refer to [attribute_str](Self::attribute_str).
+    pub fn priority(&self) -> Result<Option<Priority>, &str> {
+        match self.attribute_str("priority")? {
+            Some(value) => Priority::from_value(value).map(Some).ok_or(value),
+            None => Ok(None),
+        }
+    }
+
+    #[inline]
+    ///Returns argument types split into namespace and name.
+    pub fn argument_types(&self) -> Vec<TypeName> {
+        self.arguments.iter().map(|argument| argument.ty.type_name()).collect()
+    }
+
+    #[inline]
+    ///Returns return type split into namespace and name.
+    pub fn return_type_name(&self) -> TypeName {
+        self.return_type.type_name()
+    }
+
+    #[inline]
+    fn parse(line: &str, interner: &mut Interner) -> Result<Self, ParseError> {
+        borrowed::RpcMethodRef::parse(line).map(|method| method.to_owned_with(|name| interner.intern(name)))
+    }
+
+    ///Brings method to canonical form, refer to [RpcService::canonicalize]
+    fn canonicalize(&mut self) {
+        for argument in self.arguments.iter_mut() {
+            argument.ty = strip_whitespace(&argument.ty).into();
+        }
+        self.return_type = strip_whitespace(&self.return_type).into();
+        self.attributes.sort();
+    }
+
+    ///Returns key to compare methods, ignoring location.
+    fn key(&self) -> MethodKey<'_> {
+        let arguments = self.arguments.iter().map(|argument| (argument.name.as_deref(), argument.ty.as_str())).collect();
+        (&self.name, arguments, self.return_type.as_str(), &self.attributes, &self.docs)
+    }
+}
+
+//Name, arguments, return type, attributes and docs
+type MethodKey<'a> = (&'a str, Vec<(Option<&'a str>, &'a str)>, &'a str, &'a [Attribute], &'a [String]);
+//Name, namespace, methods, attributes and docs
+type ServiceKey<'a> = (&'a str, Option<&'a str>, Vec<MethodKey<'a>>, &'a [Attribute], &'a [String]);
+
+#[inline]
+///Removes whitespace within type name, e.g. `MyGame. Request` becomes `MyGame.Request`
+fn strip_whitespace(text: &str) -> String {
+    text.chars().filter(|ch| !ch.is_whitespace()).collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///rpc_service definition
+pub struct RpcService {
+    ///Service name
+    pub name: String,
+    ///List of service methods
+    pub methods: Vec<RpcMethod>,
+    ///Documentation comments `///` preceding definition, one entry per line.
+    pub docs: Vec<String>,
+    ///Namespace in effect at the definition, e.g. `MyGame.Rpc`
+    pub namespace: Option<String>,
+    ///List of attributes in order of definition, e.g. `rpc_service Monitor (internal) {`
+    pub attributes: Vec<Attribute>,
+    ///Location of definition
+    pub span: Span,
+}
+
+impl core::hash::Hash for RpcService {
+    ///Hashes canonical form, so that equivalent services have the same hash.
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.canonical().key().hash(state)
+    }
+}
+
+impl PartialOrd for RpcService {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RpcService {
+    ///Compares canonical forms, ordering equivalent services by their exact definitions.
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        let ordering = self.canonical().key().cmp(&other.canonical().key());
+        ordering.then_with(|| self.key().cmp(&other.key()))
+    }
+}
+
+impl core::str::FromStr for RpcService {
+    type Err = ParseErrorAt;
+
+    #[inline]
+    ///Parses single service, same as [parse_service]
+    ///
+    ///```rust
+    ///use flatbuffers_tools::RpcService;
+    ///
+    ///let service: RpcService = "rpc_service Store { Get(Key): Value; }".parse().expect("to parse");
+    ///assert_eq!(service.name, "Store");
+    ///```
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        parse_service(text)
+    }
+}
+
+impl RpcService {
+    ///Resolves type `name`, as written within service, to fully qualified name.
+    ///
+    ///Name without namespace is within namespace of the service, same as flatc resolves it.
+    pub fn resolve_type(&self, name: &str) -> TypeName {
+        resolve_type_name(name, self.namespace.as_deref())
+    }
+
+    #[inline]
+    ///Returns whether service has attribute with specified `name`.
+    pub fn has_attribute(&self, name: &str) -> bool {
+        self.attributes.iter().any(|(key, _)| key == name)
+    }
+
+    #[inline]
+    ///Returns method with specified `name`, as declared within schema.
+    pub fn method(&self, name: &str) -> Option<&RpcMethod> {
+        self.methods.iter().find(|method| method.name == name)
+    }
+
+    #[inline]
+    ///Returns id of method with specified `name`, as assigned by default [RpcMethodDefines] with [IntType::U16].
+    ///
+    ///Use [RpcServiceIds] for other [IdStrategy] or repeated lookups.
+    pub fn method_id(&self, name: &str) -> Option<u16> {
+        let ids = RpcServiceIds::new(self, IdStrategy::Index).ok()?;
+        ids.id(name).and_then(|id| core::convert::TryFrom::try_from(id).ok())
+    }
+
+    #[inline]
+    ///Returns method with specified `id`, as assigned by default [RpcMethodDefines] with [IntType::U16].
+    ///
+    ///Use [RpcServiceIds] for other [IdStrategy] or repeated lookups.
+    pub fn method_by_id(&self, id: u16) -> Option<&RpcMethod> {
+        let ids = RpcServiceIds::new(self, IdStrategy::Index).ok()?;
+        ids.method_by_id(id.into())
+    }
+
+    #[inline]
+    ///Returns method ids, assigned using `id_strategy`.
+    ///
+    ///Refer to [RpcServiceIds]
+    pub fn ids(&self, id_strategy: IdStrategy) -> Result<RpcServiceIds<'_>, MethodIdError> {
+        RpcServiceIds::new(self, id_strategy)
+    }
+
+    #[inline]
+    ///Returns method ids, recorded by [registry::IdRegistry].
+    pub fn assigned_ids(&self, assignments: &registry::Assignments) -> RpcServiceIds<'_> {
+        RpcServiceIds::with_assignments(self, assignments)
+    }
+
+    ///Brings service to canonical form, which does not depend on order of declarations.
+    ///
+    ///Methods are sorted by name, whitespace is removed within argument and return types,
+    ///and attributes of service and its methods are sorted.
+    ///
+    ///Note that method ids, assigned by index, depend on order of methods.
+    pub fn canonicalize(&mut self) {
+        for method in self.methods.iter_mut() {
+            method.canonicalize();
+        }
+        self.methods.sort_by(|left, right| left.name.cmp(&right.name));
+        self.attributes.sort();
+    }
+
+    ///Returns whether services are the same in canonical form, without modifying them.
+    ///
+    ///Unlike `==`, it ignores order of methods and attributes.
+    ///
+    ///```rust
+    ///use flatbuffers_tools::RpcService;
+    ///
+    ///let service: RpcService = "rpc_service Store { Get(Key): Value; Put(Value): Key; }".parse().unwrap();
+    ///let reordered: RpcService = "rpc_service Store { Put(Value): Key; Get(Key): Value; }".parse().unwrap();
+    ///assert_ne!(service, reordered);
+    ///assert!(service.equivalent(&reordered));
+    ///```
+    pub fn equivalent(&self, other: &Self) -> bool {
+        self.canonical().key() == other.canonical().key()
+    }
+
+    #[inline]
+    ///Returns stable fingerprint of the service, with method ids of [IdStrategy::Index].
+    ///
+    ///It is the same as `SERVICE_FINGERPRINT`, generated with [GenConfig::fingerprint], so that code,
+    ///generated from different schemas, can be detected at runtime.
+    ///Use [RpcServiceIds::fingerprint] for other [IdStrategy].
+    ///If ids cannot be assigned, methods have ids of their indexes.
+    ///
+    ///Refer to [FINGERPRINT_VERSION] for algorithm.
+    ///
+    ///```rust
+    ///use flatbuffers_tools::{GenConfig, RpcService};
+    ///
+    ///let fingerprint = |schema: &str| schema.parse::<RpcService>().expect("to parse").fingerprint();
+    ///let service = "namespace MyGame;\nrpc_service Store { Get(Key):Value; Put(key: Key, Value):Key (id: 5); }";
+    ///let expected = fingerprint(service);
+    ///
+    /////Formatting, documentation, attributes and names of arguments are ignored
+    ///assert_eq!(fingerprint("namespace MyGame ;\n///Storage\nrpc_service  Store (internal)\n{\n  Get ( MyGame.Key ) : Value;\n  Put(Key, value: Value): MyGame.Key (id:5);\n}"), expected);
+    /////Same as methods are ordered by ids
+    ///assert_eq!(fingerprint("namespace MyGame;\nrpc_service Store { Put(Key, Value):Key (id: 5); Get(Key):Value; }"), expected);
+    ///
+    ///assert_ne!(fingerprint("namespace MyGame;\nrpc_service Store { Fetch(Key):Value; Put(Key, Value):Key (id: 5); }"), expected);
+    ///assert_ne!(fingerprint("namespace MyGame;\nrpc_service Store { Get(Key):Key; Put(Key, Value):Key (id: 5); }"), expected);
+    ///assert_ne!(fingerprint("namespace MyGame;\nrpc_service Store { Get(Key):Value; Put(Key, Value):Key (id: 6); }"), expected);
+    ///assert_ne!(fingerprint("namespace MyGame;\nrpc_service Store { Get(Key):Value; Put(Value, Key):Key (id: 5); }"), expected);
+    ///assert_ne!(fingerprint("namespace MyGame.V2;\nrpc_service Store { Get(Key):Value; Put(Key, Value):Key (id: 5); }"), expected);
+    ///
+    ///let service: RpcService = service.parse().unwrap();
+    ///let defines = service.as_rpc_method_defines_with(&GenConfig::new().fingerprint(true)).to_string();
+    ///assert!(defines.contains(&format!("pub const SERVICE_FINGERPRINT: u64 = {:#018x};", service.fingerprint())));
+    ///```
+    pub fn fingerprint(&self) -> u64 {
+        gen::fingerprint(core::iter::once(self))
+    }
+
+    #[inline]
+    ///Returns machine readable JSON of the service, refer to [json] for its format.
+    ///
+    ///Fails if ids of methods cannot be assigned.
+    ///
+    ///```rust
+    ///use flatbuffers_tools::RpcService;
+    ///
+    ///let service: RpcService = "rpc_service Storage {\n  ///Returns \"value\"\n  Get(key: Key): Value (id: 4);\n}".parse().unwrap();
+    ///let json = service.to_json().unwrap();
+    ///assert!(json.contains("      \"methods\": [\n        {\n          \"name\": \"Get\",\n          \"id\": 4,\n"));
+    ///assert!(json.contains("\"arguments\": [\n            {\"name\": \"key\", \"type\": \"Key\", \"qualified_type\": \"Key\"}\n          ],\n"));
+    ///assert!(json.contains("\"docs\": [\"Returns \\\"value\\\"\"],\n"));
+    ///assert!(json.contains("\"attributes\": [\n            {\"key\": \"id\", \"value\": \"4\"}\n          ],\n"));
+    ///```
+    pub fn to_json(&self) -> Result<String, MethodIdError> {
+        json::to_json(core::iter::once(self))
+    }
+
+    #[inline]
+    fn canonical(&self) -> Self {
+        let mut canonical = self.clone();
+        canonical.canonicalize();
+        canonical
+    }
+
+    ///Returns key to compare services, ignoring location.
+    fn key(&self) -> ServiceKey<'_> {
+        let methods = self.methods.iter().map(RpcMethod::key).collect();
+        (&self.name, self.namespace.as_deref(), methods, &self.attributes, &self.docs)
+    }
+
+    #[inline]
+    ///Gets formatter to print service as `rpc_service` declaration of flatbuffers schema.
+    pub fn as_fbs(&self) -> fbs::FbsService<'_> {
+        fbs::FbsService {
+            service: self,
+            style: fbs::FbsStyle::new(),
+            include_namespace: false,
+        }
+    }
+
+    #[inline]
+    ///Prints service as `rpc_service` declaration with default style, without namespace.
+    ///
+    ///Refer to [fbs] for details.
+    pub fn to_fbs(&self) -> String {
+        self.as_fbs().to_string()
+    }
+
+    #[inline]
+    ///Gets formatter to generate RPC method defines which are upper case constants corresponding
+    ///to RPC method name.
+    pub fn as_rpc_method_defines(&self) -> RpcMethodDefines<'_> {
+        self.as_rpc_method_defines_with(&DEFAULT_CONFIG)
+    }
+
+    ///Gets formatter to generate RPC method defines, using `config`.
+    pub fn as_rpc_method_defines_with<'a>(&'a self, config: &'a GenConfig) -> RpcMethodDefines<'a> {
+        RpcMethodDefines {
+            service: self,
+            file_identifier: None,
+            visibility: config.visibility,
+            prefix: &config.prefix,
+            include_service_name: config.include_service_name,
+            int_type: config.int_type,
+            id_strategy: config.id_strategy,
+            assignments: None,
+            method_count: config.method_count,
+            fingerprint: config.fingerprint,
+            name_lookup: config.name_lookup,
+            id_lookup: config.id_lookup,
+            registry: config.registry,
+            skip_docs: config.skip_docs,
+            is_sorted: config.is_sorted,
+            deprecated_policy: config.deprecated_policy,
+            category_attributes: &config.category_attributes,
+        }
+    }
+
+    #[inline]
+    ///Gets formatter to generate typed client of the service.
+    ///
+    ///Method ids are names, as defined by default [RpcMethodDefines].
+    pub fn as_client_stub(&self) -> RpcClientStub<'_> {
+        self.as_client_stub_with(&DEFAULT_CONFIG)
+    }
+
+    ///Gets formatter to generate typed client of the service, using `config`.
+    pub fn as_client_stub_with<'a>(&'a self, config: &'a GenConfig) -> RpcClientStub<'a> {
+        let mut defines = self.as_rpc_method_defines_with(config);
+        if config.is_framing {
+            defines.int_type = Some(config.int_type.unwrap_or(IntType::U16));
+        }
+        RpcClientStub {
+            service: self,
+            defines,
+            type_path: config.type_path_fn(),
+            skip_docs: config.skip_docs,
+            library: config.library(),
+            framing: config.framing_path(),
+            codec: config.is_client_codec,
+        }
+    }
+
+    #[inline]
+    ///Gets formatter to generate handler trait of the service and dispatcher of its methods.
+    ///
+    ///Method ids are `u16` indexes, as defined by [RpcMethodDefines] with [IntType::U16].
+    pub fn as_rpc_dispatcher(&self) -> RpcServiceDispatcher<'_> {
+        self.as_rpc_dispatcher_with(&DEFAULT_CONFIG)
+    }
+
+    ///Gets formatter to generate handler trait of the service and dispatcher of its methods, using `config`.
+    ///
+    ///Method ids are `u16` indexes, unless integer type is specified by `config`.
+    pub fn as_rpc_dispatcher_with<'a>(&'a self, config: &'a GenConfig) -> RpcServiceDispatcher<'a> {
+        let mut defines = self.as_rpc_method_defines_with(config);
+        defines.int_type = Some(config.int_type.unwrap_or(IntType::U16));
+        RpcServiceDispatcher {
+            service: self,
+            defines,
+            type_path: config.type_path_fn(),
+            context: config.context,
+            skip_docs: config.skip_docs,
+            return_style: config.return_style,
+            library: config.library(),
+            router: config.router,
+            attributes: config.attributes(),
+            aliases: config.type_aliases,
+            default_body: config.default_body,
+            type_kind: config.type_kind_fn(),
+            framing: config.framing_path(),
+            trait_name: config.trait_name,
+            receiver: config.receiver,
+            send_sync: config.send_sync,
+            method_timeout: config.method_timeout,
+            asyncness: config.asyncness,
+        }
+    }
+
+    #[inline]
+    ///Gets formatter to generate module of the service, containing its method defines.
+    ///
+    ///Other formatters can be added to the module, e.g. [RpcServiceModule::with_dispatcher].
+    pub fn as_module(&self) -> RpcServiceModule<'_> {
+        self.as_module_with(&DEFAULT_CONFIG)
+    }
+
+    ///Gets formatter to generate module of the service, containing its method defines, using `config`.
+    pub fn as_module_with<'a>(&'a self, config: &'a GenConfig) -> RpcServiceModule<'a> {
+        RpcServiceModule {
+            service: self,
+            visibility: Visibility::Pub,
+            defines: Some(self.as_rpc_method_defines_with(config)),
+            method_enum: None,
+            dispatcher: None,
+            client: None,
+            descriptor: None,
+            type_aliases: None,
+            markers: None,
+            mock: None,
+            instrumentation: None,
+            skip_docs: config.skip_docs,
+            assertions: config.assertions,
+            runtime: config.runtime_path(),
+            attributes: Attributes {
+                item: &config.item_attributes,
+                ..Attributes::default()
+            },
+        }
+    }
+
+    #[inline]
+    ///Gets formatter to generate static descriptor of the service and its methods.
+    ///
+    ///Method ids are `u16` indexes, as defined by [RpcMethodDefines] with [IntType::U16].
+    pub fn as_descriptor(&self) -> RpcServiceDescriptor<'_> {
+        self.as_descriptor_with(&DEFAULT_CONFIG)
+    }
+
+    ///Gets formatter to generate static descriptor of the service and its methods, using `config`.
+    ///
+    ///Method ids are `u16` indexes, unless integer type is specified by `config`.
+    pub fn as_descriptor_with<'a>(&'a self, config: &'a GenConfig) -> RpcServiceDescriptor<'a> {
+        let mut defines = self.as_rpc_method_defines_with(config);
+        defines.int_type = Some(config.int_type.unwrap_or(IntType::U16));
+        RpcServiceDescriptor {
+            service: self,
+            defines,
+            name: None,
+            visibility: config.visibility,
+        }
+    }
+
+    #[inline]
+    ///Gets formatter to generate C header with method ids.
+    ///
+    ///Method ids are `u16` indexes, as defined by [RpcMethodDefines] with [IntType::U16].
+    pub fn as_c_header(&self) -> RpcCHeader<'_> {
+        self.as_c_header_with(&DEFAULT_CONFIG)
+    }
+
+    ///Gets formatter to generate C header with method ids, using `config`.
+    ///
+    ///Method ids are the same as of [RpcService::as_rpc_method_defines_with], with integer type `u16`, unless specified by `config`.
+    pub fn as_c_header_with<'a>(&'a self, config: &'a GenConfig) -> RpcCHeader<'a> {
+        let mut defines = self.as_rpc_method_defines_with(config);
+        defines.int_type = Some(config.int_type.unwrap_or(IntType::U16));
+        RpcCHeader {
+            service: self,
+            defines,
+            style: config.c_header_style,
+            skip_docs: config.skip_docs,
+        }
+    }
+
+    #[inline]
+    ///Gets formatter to generate TypeScript module with method ids.
+    ///
+    ///Method ids are indexes, as defined by [RpcMethodDefines].
+    pub fn as_typescript(&self) -> RpcTypeScript<'_> {
+        self.as_typescript_with(&DEFAULT_CONFIG)
+    }
+
+    ///Gets formatter to generate TypeScript module with method ids, using `config`.
+    ///
+    ///Method ids are the same as of [RpcService::as_rpc_method_defines_with].
+    pub fn as_typescript_with<'a>(&'a self, config: &'a GenConfig) -> RpcTypeScript<'a> {
+        RpcTypeScript {
+            service: self,
+            defines: self.as_rpc_method_defines_with(config),
+            style: config.ts_style,
+            skip_docs: config.skip_docs,
+        }
+    }
+
+    #[inline]
+    ///Gets formatter to generate Python module with method ids.
+    ///
+    ///Method ids are indexes, as defined by [RpcMethodDefines].
+    pub fn as_python(&self) -> RpcPython<'_> {
+        self.as_python_with(&DEFAULT_CONFIG)
+    }
+
+    ///Gets formatter to generate Python module with method ids, using `config`.
+    ///
+    ///Method ids are the same as of [RpcService::as_rpc_method_defines_with].
+    pub fn as_python_with<'a>(&'a self, config: &'a GenConfig) -> RpcPython<'a> {
+        RpcPython {
+            service: self,
+            defines: self.as_rpc_method_defines_with(config),
+            skip_docs: config.skip_docs,
+        }
+    }
+
+    #[inline]
+    ///Gets formatter to generate proto3 definition of the service.
+    pub fn as_proto(&self) -> RpcProto<'_> {
+        self.as_proto_with(&DEFAULT_CONFIG)
+    }
+
+    ///Gets formatter to generate proto3 definition of the service, using `config`.
+    pub fn as_proto_with<'a>(&'a self, config: &'a GenConfig) -> RpcProto<'a> {
+        RpcProto {
+            service: self,
+            namespace: config.proto_namespace,
+            message_name: None,
+            deprecated_policy: config.deprecated_policy,
+            skip_docs: config.skip_docs,
+        }
+    }
+
+    #[inline]
+    ///Gets formatter to generate Markdown reference of the service.
+    ///
+    ///Method ids are indexes, as defined by [RpcMethodDefines].
+    pub fn as_markdown(&self) -> RpcMarkdown<'_> {
+        self.as_markdown_with(&DEFAULT_CONFIG)
+    }
+
+    ///Gets formatter to generate Markdown reference of the service, using `config`.
+    ///
+    ///Method ids are the same as of [RpcService::as_rpc_method_defines_with].
+    pub fn as_markdown_with<'a>(&'a self, config: &'a GenConfig) -> RpcMarkdown<'a> {
+        RpcMarkdown {
+            service: self,
+            defines: self.as_rpc_method_defines_with(config),
+            skip_docs: config.skip_docs,
+        }
+    }
+
+    #[inline]
+    ///Gets formatter to generate type aliases of requests and responses of every method.
+    pub fn as_type_aliases(&self) -> RpcTypeAliases<'_> {
+        self.as_type_aliases_with(&DEFAULT_CONFIG)
+    }
+
+    ///Gets formatter to generate type aliases of requests and responses of every method, using `config`.
+    pub fn as_type_aliases_with<'a>(&'a self, config: &'a GenConfig) -> RpcTypeAliases<'a> {
+        RpcTypeAliases {
+            service: self,
+            type_path: config.type_path_fn(),
+            visibility: config.visibility,
+            arguments: config.argument_aliases,
+            lifetime: config.alias_lifetime,
+        }
+    }
+
+    #[inline]
+    ///Gets formatter to generate marker type per method.
+    ///
+    ///Method ids are `u16` indexes, as defined by [RpcMethodDefines] with [IntType::U16].
+    pub fn as_method_markers(&self) -> RpcMethodMarkers<'_> {
+        self.as_method_markers_with(&DEFAULT_CONFIG)
+    }
+
+    ///Gets formatter to generate marker type per method, using `config`.
+    ///
+    ///Method ids are `u16` indexes, unless integer type is specified by `config`.
+    pub fn as_method_markers_with<'a>(&'a self, config: &'a GenConfig) -> RpcMethodMarkers<'a> {
+        let mut defines = self.as_rpc_method_defines_with(config);
+        defines.int_type = Some(config.int_type.unwrap_or(IntType::U16));
+        RpcMethodMarkers {
+            service: self,
+            defines,
+            type_path: config.type_path_fn(),
+            visibility: config.visibility,
+            trait_path: match (config.runtime_path(), config.is_crate_method_trait) {
+                (Some(_), _) => Some("::flatbuffers_tools::runtime::Method"),
+                (None, true) => Some("flatbuffers_tools::Method"),
+                (None, false) => None,
+            },
+            lifetime: None,
+            skip_docs: config.skip_docs,
+        }
+    }
+
+    #[inline]
+    ///Gets formatter to generate enum of RPC methods.
+    pub fn as_rpc_method_enum(&self) -> RpcMethodEnum<'_> {
+        self.as_rpc_method_enum_with(&DEFAULT_CONFIG)
+    }
+
+    ///Gets formatter to generate enum of RPC methods, using `config`.
+    ///
+    ///Discriminant is `u16`, unless integer type is specified by `config`.
+    pub fn as_rpc_method_enum_with<'a>(&'a self, config: &'a GenConfig) -> RpcMethodEnum<'a> {
+        RpcMethodEnum {
+            service: self,
+            name: None,
+            derives: &DEFAULT_ENUM_DERIVES,
+            int_type: config.int_type.unwrap_or(IntType::U16),
+            visibility: config.visibility,
+            library: config.library(),
+            deprecated_policy: config.deprecated_policy,
+            assignments: None,
+        }
+    }
+}
+
+#[inline]
+///Returns whether `name` is valid identifier, same as flatc requires.
+///
+///Refer to [ident::is_valid_identifier] for details.
+pub fn is_identifier(name: &str) -> bool {
+    ident::is_valid_identifier(name)
+}
+
+fn check_identifier(kind: IdentifierKind, name: &str) -> Result<(), ParseError> {
+    if is_identifier(name) {
+        Ok(())
+    } else {
+        Err(ParseError::InvalidIdentifier {
+            kind,
+            name: name.to_owned(),
+        })
+    }
+}
+
+///Checks type name, which can be qualified with namespace.
+fn check_type_name(kind: IdentifierKind, name: &str) -> Result<(), ParseError> {
+    if name.split('.').all(is_identifier) {
+        Ok(())
+    } else {
+        Err(ParseError::InvalidIdentifier {
+            kind,
+            name: name.to_owned(),
+        })
+    }
+}
+
+///Returns text following `keyword`, if `line` starts with it.
+fn strip_keyword<'a>(line: &'a str, keyword: &str) -> Option<&'a str> {
+    let rest = line.strip_prefix(keyword)?;
+    match rest.chars().next() {
+        Some(ch) if ch.is_ascii_alphanumeric() || ch == '_' => None,
+        _ => Some(rest),
+    }
+}
+
+///Parses namespace declaration, without keyword
+fn parse_namespace(text: &str) -> Result<String, ParseError> {
+    let name = match text.trim().strip_suffix(';') {
+        Some(name) => name.trim(),
+        None => return Err(ParseError::InvalidNamespace(text.trim().to_owned())),
+    };
+
+    if name.split('.').any(|part| part.trim().is_empty()) {
+        return Err(ParseError::InvalidNamespace(name.to_owned()));
+    }
+
+    Ok(name.to_owned())
+}
+
+///Parses quoted string declaration, without keyword, returning content without quotes.
+fn parse_quoted(text: &str) -> Option<&str> {
+    let mut chars = text.trim().chars();
+    match chars.next() {
+        Some(quote) if quote == '"' || quote == '\'' => {
+            let text = chars.as_str();
+            let end = text.find(quote)?;
+            let rest = text[end + 1..].trim();
+            if rest.is_empty() || rest == ";" {
+                Some(&text[..end])
+            } else {
+                None
+            }
+        },
+        _ => None,
+    }
+}
+
+///Parses include declaration, without keyword, returning path without quotes.
+fn parse_include(text: &str) -> Result<String, ParseError> {
+    match parse_quoted(text) {
+        Some(path) if !path.is_empty() => Ok(path.to_owned()),
+        _ => Err(ParseError::InvalidInclude(text.trim().to_owned())),
+    }
+}
+
+///Returns offset of `inner` slice within `outer`
+#[inline(always)]
+fn offset_of(outer: &str, inner: &str) -> usize {
+    inner.as_ptr() as usize - outer.as_ptr() as usize
+}
+
+///Single statement within body of block declaration
+struct Statement<'a> {
+    ///Text without separator
+    text: &'a str,
+    ///Whether statement is terminated with separator
+    is_terminated: bool,
+    span: Span,
+    docs: Vec<String>,
+}
+
+///State of block body, while it is being parsed.
+struct Body {
+    //Character that terminates each statement
+    separator: char,
+    //Statement that spans multiple lines
+    statement: String,
+    //Location of `statement`
+    statement_span: Span,
+}
+
+impl Body {
+    #[inline]
+    fn new(separator: char) -> Self {
+        Self {
+            separator,
+            statement: String::new(),
+            statement_span: Span::default(),
+        }
+    }
+
+    ///Passes accumulated statement to the `on_statement`.
+    fn finish_statement<F: FnMut(Statement<'_>) -> Result<(), ParseErrorAt>>(&mut self, is_terminated: bool, docs: &mut Vec<String>, on_statement: &mut F) -> Result<(), ParseErrorAt> {
+        let result = on_statement(Statement {
+            text: &self.statement,
+            is_terminated,
+            span: self.statement_span,
+            docs: core::mem::take(docs),
+        });
+        self.statement.clear();
+        result
+    }
+
+    ///Processes next line of the body, where `offset` is position of `line` within original line.
+    ///
+    ///`docs` are documentation comments collected so far, which are attached to the next statement.
+    ///
+    ///Once body is finished, returns position of closing bracket and remaining text after it.
+    fn feed<'a, F: FnMut(Statement<'_>) -> Result<(), ParseErrorAt>>(&mut self, line: &'a str, line_no: usize, offset: usize, docs: &mut Vec<String>, on_statement: &mut F) -> Result<Option<(usize, &'a str)>, ParseErrorAt> {
+        //Quoted strings, e.g. attribute values, may contain both separator and brackets
+        let mut text = line;
+        let mut rest = None;
+        //Separators and closing bracket are found within single pass over the line
+        while let Some((idx, byte)) = find_unquoted_any(text, self.separator, '}') {
+            if byte == b'}' {
+                let bracket_idx = offset_of(line, text) + idx;
+                rest = Some((offset + bracket_idx, &line[bracket_idx+1..]));
+                text = &text[..idx];
+                break;
+            }
+            let statement = text[..idx].trim();
+            let end = Position::new(line_no, offset + offset_of(line, text) + idx);
+            text = &text[idx+1..];
+
+            if self.statement.is_empty() {
+                let start = if statement.is_empty() {
+                    end
+                } else {
+                    Position::new(line_no, offset + offset_of(line, statement))
+                };
+                //Fast path for statement on a single line
+                on_statement(Statement {
+                    text: statement,
+                    is_terminated: true,
+                    span: Span {
+                        start,
+                        end,
+                    },
+                    docs: core::mem::take(docs),
+                })?;
+            } else {
+                if !statement.is_empty() {
+                    self.statement.push(' ');
+                    self.statement.push_str(statement);
+                }
+                self.statement_span.end = end;
+                self.finish_statement(true, docs, on_statement)?;
+            }
+        }
+
+        let text = text.trim();
+        if !text.is_empty() {
+            let start = offset + offset_of(line, text);
+            if self.statement.is_empty() {
+                self.statement_span.start = Position::new(line_no, start);
+            } else {
+                self.statement.push(' ');
+            }
+            self.statement.push_str(text);
+            self.statement_span.end = Position::new(line_no, start + text.len() - 1);
+        }
+
+        if rest.is_some() && !self.statement.is_empty() {
+            self.finish_statement(false, docs, on_statement)?;
+        }
+
+        Ok(rest)
+    }
+}
+
+#[inline]
+///Returns end of block, failing if block of declaration `name` is not closed.
+fn block_end(name: &str, end: Option<Position>, start: Position) -> Result<Position, ParseErrorAt> {
+    match end {
+        Some(end) => Ok(end),
+        None => Err(ParseError::UnexpectedEof(name.to_owned()).at(start.line)),
+    }
+}
+
+///Parses rpc method out of body statement of `service`
+fn parse_method(statement: Statement<'_>, service: &str, is_strict: bool, interner: &mut Interner) -> Result<Option<RpcMethod>, ParseErrorAt> {
+    let error = if statement.text.is_empty() {
+        //Empty statement
+        if !is_strict {
+            return Ok(None);
+        }
+        ParseError::ExtraSemicolon
+    } else if is_strict && !statement.is_terminated {
+        ParseError::MissingSemicolon(statement.text.to_owned())
+    } else {
+        match RpcMethod::parse(statement.text, interner) {
+            Ok(mut method) => {
+                method.span = statement.span;
+                method.docs = statement.docs;
+                return Ok(Some(method));
+            },
+            Err(error) => error,
+        }
+    };
+    Err(error.in_service(service, statement.text).at(statement.span.start.line))
+}
+
+///rpc_service parser
+///
+///Opening and closing brackets can be placed either on the header's line or on lines of their own.
+///Service body may be empty, in which case service has no methods.
+///
+///Declarations are not required to span separate lines, so whole service, or several services,
+///can be written on a single line, e.g. `rpc_service Store { Put(Req): Resp; Get(Key): Resp; }`
+///
+///Keyword `rpc_service` is recognized only as a whole word at the start of declaration, and text within quoted strings,
+///including escaped quotes, is never scanned for brackets or semicolons:
+///
+///```rust
+///use flatbuffers_tools::{borrowed, parse_services, ParserIter, RpcService};
+///use flatbuffers_tools::schema::Schema;
+///use flatbuffers_tools::stream::StreamingParser;
+///
+///let schema = r#"attribute "rpc_service_marker";
+///table Request {
+///  rpc_service: string = "}";
+///  note: string = "a\"}";
+///}
+///rpc_servicex;
+///rpc_service Store (note: "{x}") {
+///  Put(Request):Request (doc: "a\";b}");
+///  Get(Request):Request;
+///}
+///"#;
+///let services = parse_services(schema).expect("to parse");
+///assert_eq!(services.len(), 1);
+///assert_eq!(services[0].name, "Store");
+///assert_eq!(services[0].attributes, [("note".to_owned(), Some("{x}".to_owned()))]);
+///assert_eq!(services[0].methods.len(), 2);
+///assert_eq!(services[0].methods[0].attributes, [("doc".to_owned(), Some(r#"a\";b}"#.to_owned()))]);
+///
+///let strict: Vec<RpcService> = ParserIter::from_str(schema).strict().collect::<Result<_, _>>().expect("to parse");
+///assert_eq!(strict, services);
+///let borrowed: Vec<RpcService> = borrowed::parse_ref(schema).expect("to parse").iter().map(|service| service.to_owned()).collect();
+///assert_eq!(borrowed, services);
+///let schema: Schema = schema.parse().expect("to parse");
+///assert_eq!(schema.services, services);
+///
+///let mut parser = StreamingParser::new();
+///let mut streamed: Vec<RpcService> = Vec::new();
+///for chunk in r#"rpc_service Store (note: "{x}") { Put(Request):Request (doc: "a\";b}"); Get(Request):Request; }"#.as_bytes().chunks(7) {
+///    streamed.extend(parser.feed(core::str::from_utf8(chunk).unwrap()).into_iter().map(Result::unwrap));
+///}
+///streamed.extend(parser.finish().into_iter().map(Result::unwrap));
+///assert_eq!(streamed, services);
+///```
+pub struct ParserIter<T> {
+    lines: T,
+    //Number of the last read line
+    line_no: usize,
+    in_comment: bool,
+    //Line where last block comment started
+    comment_line: usize,
+    //Remaining text of the last line, after block declaration, with its offset
+    pending: Option<(usize, String)>,
+    //Documentation comments, to be attached to the next definition
+    docs: Vec<String>,
+    //Nesting of brackets within skipped declaration
+    skipped_depth: usize,
+    //Skipped declaration, that is not terminated yet, if skipped declarations are to be produced
+    raw: Option<schema::RawDeclaration>,
+    //Last declared namespace
+    namespace: Option<String>,
+    includes: Vec<String>,
+    root_type: Option<String>,
+    file_identifier: Option<String>,
+    file_extension: Option<String>,
+    //Names of `attribute` declarations
+    declared_attributes: Vec<String>,
+    //Namespace and name of every parsed service, if uniqueness is to be checked
+    service_names: Option<HashSet<(Option<String>, String)>>,
+    //Errors of skipped statements, if parser is to recover from them
+    errors: Option<Vec<ParseErrorAt>>,
+    //Warnings, if they are requested
+    diagnostics: Option<diagnostics::Collector>,
+    is_strict: bool,
+    //Whether to parse all declarations, instead of services only
+    is_schema: bool,
+    //Whether to produce skipped declarations
+    is_lossless: bool,
+    limits: Limits,
+    //Number of services started so far
+    services_count: usize,
+    //Error of exceeded limit, that is detected outside of parsing declaration
+    exceeded: Option<ParseErrorAt>,
+    //Whether limit is exceeded, and parser produces nothing
+    is_stopped: bool,
+    //Lines, that are not within services, if they are to be recorded
+    skipped_lines: Option<Vec<SkippedLine>>,
+    //Type names of parsed methods
+    interner: Interner,
+}
+
+impl<'a> ParserIter<core::str::Lines<'a>> {
+    #[inline]
+    ///Creates new parser over lines of `text`.
+    ///
+    ///Both `\n` and `\r\n` line endings are accepted, as well as UTF-8 BOM at the start of `text`.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(text: &'a str) -> Self {
+        Self::new(text.lines())
+    }
+}
+
+impl<R: std::io::BufRead> ParserIter<reader::Lines<R>> {
+    #[inline]
+    ///Creates new parser reading lines from `reader`.
+    ///
+    ///Unlike parser over lines, it fails with [Error](reader::Error) in case of IO error.
+    pub fn from_reader(reader: R) -> reader::ReaderIter<R> {
+        reader::ReaderIter::new(reader)
+    }
+}
+
+impl<I: AsRef<str>, T: Iterator<Item=I>> ParserIter<T> {
+    ///Creates new parser from iterator over lines.
+    ///
+    ///Trailing `\r` of every line and UTF-8 BOM at the start of the first line are ignored.
+    pub fn new(lines: T) -> Self {
+        Self {
+            lines,
+            line_no: 0,
+            in_comment: false,
+            comment_line: 0,
+            skipped_depth: 0,
+            raw: None,
+            pending: None,
+            docs: Vec::new(),
+            namespace: None,
+            includes: Vec::new(),
+            root_type: None,
+            file_identifier: None,
+            file_extension: None,
+            declared_attributes: Vec::new(),
+            service_names: None,
+            errors: None,
+            diagnostics: None,
+            is_strict: false,
+            is_schema: false,
+            is_lossless: false,
+            limits: Limits::new(),
+            services_count: 0,
+            exceeded: None,
+            is_stopped: false,
+            skipped_lines: None,
+            interner: Interner::default(),
+        }
+    }
+
+    ///Enables strict parsing.
+    ///
+    ///By default parser is lenient, accepting some definitions that are rejected by `flatc`:
+    ///
+    ///- Method definition without terminating `;`, reported as [ParseError::MissingSemicolon]
+    ///- Empty statement `;` within service body, reported as [ParseError::ExtraSemicolon]
+    pub fn strict(mut self) -> Self {
+        self.is_strict = true;
+        self
+    }
+
+    ///Enables check that service names are unique.
+    ///
+    ///When enabled, every repeated definition of a service within the same namespace results in
+    ///[DuplicateService](ParseError::DuplicateService) error.
+    pub fn unique_services(mut self) -> Self {
+        self.service_names = Some(HashSet::new());
+        self
+    }
+
+    ///Sets limits of parser.
+    ///
+    ///Refer to [Limits] for details.
+    pub fn limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    ///Enables collection of warnings, produced by `checks`.
+    ///
+    ///Warnings do not affect parsing, and are available via [diagnostics](Self::diagnostics).
+    pub fn with_diagnostics(mut self, checks: diagnostics::Checks) -> Self {
+        self.diagnostics = Some(diagnostics::Collector::new(checks));
+        self
+    }
+
+    #[inline]
+    ///Returns warnings collected so far, in order of occurrence.
+    ///
+    ///Empty, unless enabled by [with_diagnostics](Self::with_diagnostics).
+    pub fn diagnostics(&self) -> &[diagnostics::Diagnostic] {
+        match self.diagnostics.as_ref() {
+            Some(collector) => &collector.diagnostics,
+            None => &[],
+        }
+    }
+
+    #[inline]
+    ///Takes warnings collected so far.
+    pub fn take_diagnostics(&mut self) -> Vec<diagnostics::Diagnostic> {
+        match self.diagnostics.as_mut() {
+            Some(collector) => core::mem::take(&mut collector.diagnostics),
+            None => Vec::new(),
+        }
+    }
+
+    #[inline]
+    ///Returns paths of `include` declarations encountered so far, in order of declaration.
+    ///
+    ///Paths are returned as written, without quotes.
+    pub fn includes(&self) -> &[String] {
+        &self.includes
+    }
+
+    ///Enables recording of lines, that are not part of any service, so that rest of input can be processed without reading it again.
+    ///
+    ///Line is part of service if it is within [span](RpcService::span) of service, that is parsed successfully,
+    ///even if it has other text before or after service, while documentation comments of service are not.
+    ///Every other line is recorded exactly once, in order of input, including empty lines and lines within comments.
+    ///
+    ///Lines are available via [skipped_lines](Self::skipped_lines) as soon as parser reads them.
+    ///
+    ///```rust
+    ///use flatbuffers_tools::{ParserIter, RpcService};
+    ///
+    ///let schema = "namespace MyGame;\n\n/* Storage\nrpc_service Commented {} */\nrpc_service Store {\n  Get(Key):Value;\n}\ntable Key {\n  value: string;\n}\nrpc_service Log { Append(Key):Key; }\n// end";
+    ///let mut parser = ParserIter::from_str(schema).with_skipped_lines();
+    ///let services: Vec<RpcService> = parser.by_ref().collect::<Result<_, _>>().expect("to parse");
+    ///assert_eq!(services.len(), 2);
+    ///
+    ///let (mut lines, skipped) = parser.into_parts();
+    ///assert!(lines.next().is_none());
+    ///let skipped: Vec<(usize, &str)> = skipped.iter().map(|line| (line.line_no, line.text.as_str())).collect();
+    ///assert_eq!(skipped, [
+    ///    (1, "namespace MyGame;"),
+    ///    (2, ""),
+    ///    (3, "/* Storage"),
+    ///    (4, "rpc_service Commented {} */"),
+    ///    (8, "table Key {"),
+    ///    (9, "  value: string;"),
+    ///    (10, "}"),
+    ///    (12, "// end"),
+    ///]);
+    ///
+    /////Every line is either skipped or within service
+    ///let mut line_nos: Vec<usize> = skipped.iter().map(|(line_no, _)| *line_no).collect();
+    ///for service in services.iter() {
+    ///    line_nos.extend(service.span.start.line..=service.span.end.line);
+    ///}
+    ///line_nos.sort();
+    ///assert_eq!(line_nos, (1..=schema.lines().count()).collect::<Vec<_>>());
+    ///
+    /////Parser stops at the first service, leaving the rest of lines unread
+    ///let mut parser = ParserIter::from_str(schema).with_skipped_lines();
+    ///assert_eq!(parser.next().unwrap().unwrap().name, "Store");
+    ///assert_eq!(parser.take_skipped_lines().len(), 4);
+    ///let (mut lines, skipped) = parser.into_parts();
+    ///assert!(skipped.is_empty());
+    ///assert_eq!(lines.next(), Some("table Key {"));
+    ///```
+    pub fn with_skipped_lines(mut self) -> Self {
+        self.skipped_lines = Some(Vec::new());
+        self
+    }
+
+    #[inline]
+    ///Returns lines, that are not part of any service, recorded so far, in order of input.
+    ///
+    ///Empty, unless enabled by [with_skipped_lines](Self::with_skipped_lines).
+    pub fn skipped_lines(&self) -> &[SkippedLine] {
+        match self.skipped_lines.as_ref() {
+            Some(skipped_lines) => skipped_lines,
+            None => &[],
+        }
+    }
+
+    #[inline]
+    ///Takes lines, that are not part of any service, recorded so far.
+    pub fn take_skipped_lines(&mut self) -> Vec<SkippedLine> {
+        match self.skipped_lines.as_mut() {
+            Some(skipped_lines) => core::mem::take(skipped_lines),
+            None => Vec::new(),
+        }
+    }
+
+    #[inline]
+    ///Returns underlying iterator over lines, that are not read yet, and lines, that are not part of any service, recorded so far.
+    pub fn into_parts(self) -> (T, Vec<SkippedLine>) {
+        (self.lines, self.skipped_lines.unwrap_or_default())
+    }
+
+    #[inline]
+    fn next_line(&mut self) -> Option<I> {
+        if self.is_stopped {
+            return None;
+        }
+        let line = self.lines.next()?;
+        self.line_no += 1;
+        //Input ends here, so that declaration, which is being parsed, is not completed
+        if line.as_ref().len() > self.limits.max_line_length {
+            self.exceeded = Some(Limits::exceeded(Limit::LineLength, self.limits.max_line_length).at(self.line_no));
+            self.is_stopped = true;
+            return None;
+        }
+        if let Some(skipped_lines) = self.skipped_lines.as_mut() {
+            let text = line.as_ref();
+            skipped_lines.push(SkippedLine {
+                line_no: self.line_no,
+                text: text.strip_suffix('\r').unwrap_or(text).to_owned(),
+            });
+        }
+        Some(line)
+    }
+
+    #[inline]
+    fn strip_comments<'a>(&mut self, line: &'a str) -> Cow<'a, str> {
+        //Lines are not guaranteed to be split by `lines()`, so handle windows line ending
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        let line = match self.line_no {
+            1 => line.strip_prefix('\u{feff}').unwrap_or(line),
+            _ => line,
+        };
+        let was_in_comment = self.in_comment;
+        if !was_in_comment {
+            if let Some(doc) = line.trim_start().strip_prefix("///") {
+                let doc = doc.trim_end();
+                let doc = doc.strip_prefix(' ').unwrap_or(doc);
+                self.docs.push(doc.to_owned());
+                return Cow::Borrowed("");
+            }
+        }
+
+        let line = strip_comments(line, &mut self.in_comment);
+        if !was_in_comment && self.in_comment {
+            self.comment_line = self.line_no;
+        }
+        line
+    }
+
+    #[inline]
+    fn unterminated_comment(&mut self) -> Option<ParseErrorAt> {
+        if self.in_comment {
+            self.in_comment = false;
+            Some(ParseError::UnterminatedComment.at(self.comment_line))
+        } else {
+            None
+        }
+    }
+
+    ///Parses block declaration `<keyword> <header> { <body> }`, passing every body statement to `on_statement`.
+    ///
+    ///`header` is text following keyword, at `header_offset` within original line.
+    ///
+    ///Returns header text and position of closing bracket, if body is terminated before end of input.
+    fn parse_block<F: FnMut(Statement<'_>) -> Result<(), ParseErrorAt>>(&mut self, header: &str, header_offset: usize, separator: char, mut on_statement: F) -> Result<(String, Option<Position>), ParseErrorAt> {
+        let header_line = self.line_no;
+        let mut body = Body::new(separator);
+        let (header, rest) = match find_unquoted(header, '{') {
+            Some(header_end_idx) => {
+                let body_offset = header_offset + header_end_idx + 1;
+                let rest = body.feed(&header[header_end_idx+1..], self.line_no, body_offset, &mut self.docs, &mut on_statement)?;
+                (header[..header_end_idx].trim().to_owned(), rest.map(|(idx, rest)| (idx, rest.to_owned())))
+            },
+            None => {
+                let header = header.trim().to_owned();
+                //Opening bracket is allowed to be on any of following lines
+                loop {
+                    let line = match self.next_line() {
+                        Some(line) => line,
+                        None => return Err(ParseError::NoStartingBracket.at(header_line)),
+                    };
+                    let line = self.strip_comments(line.as_ref());
+                    let trimmed = line.trim();
+                    if trimmed.is_empty() {
+                        continue;
+                    } else if let Some(body_line) = trimmed.strip_prefix('{') {
+                        let rest = body.feed(body_line, self.line_no, offset_of(&line, body_line), &mut self.docs, &mut on_statement)?;
+                        break (header, rest.map(|(idx, rest)| (idx, rest.to_owned())));
+                    } else {
+                        return Err(ParseError::NoStartingBracket.at(header_line));
+                    }
+                }
+            }
+        };
+
+        let mut rest = rest;
+        while rest.is_none() {
+            let line = match self.next_line() {
+                Some(line) => line,
+                None => break,
+            };
+            let line = self.strip_comments(line.as_ref());
+            rest = body.feed(&line, self.line_no, 0, &mut self.docs, &mut on_statement)?.map(|(idx, rest)| (idx, rest.to_owned()));
+        }
+
+        if let Some(error) = self.unterminated_comment() {
+            return Err(error);
+        }
+        //Documentation at the end of body has nothing to describe
+        self.docs.clear();
+
+        let mut end = None;
+        if let Some((idx, rest)) = rest {
+            end = Some(Position::new(self.line_no, idx));
+            //Text after closing bracket may contain next declaration
+            if !rest.trim().is_empty() {
+                self.pending = Some((idx + 1, rest));
+            }
+        }
+
+        Ok((header, end))
+    }
+
+    ///Parses service, where `header` is text following keyword.
+    fn parse_service(&mut self, header: &str, header_offset: usize, start: Position) -> Result<RpcService, ParseErrorAt> {
+        let docs = core::mem::take(&mut self.docs);
+        let is_strict = self.is_strict;
+        let is_recovering = self.errors.is_some();
+        let mut methods = Vec::new();
+        let mut errors = Vec::new();
+        //Parser is borrowed by block, so interner is taken out of it
+        let mut interner = core::mem::take(&mut self.interner);
+        let max_methods = self.limits.max_methods;
+        //Header is validated after body, but errors within body refer to service name
+        let service = &header[..find_unquoted(header, '{').unwrap_or(header.len())];
+        let service = parse_header_ref(service).map_or(service.trim(), |(name, _)| name).to_owned();
+        let result = self.parse_block(header, header_offset, ';', |statement| {
+            let line = statement.span.start.line;
+            match parse_method(statement, &service, is_strict, &mut interner) {
+                Ok(Some(_)) if methods.len() == max_methods => return Err(Limits::exceeded(Limit::Methods, max_methods).at(line)),
+                Ok(Some(method)) => methods.push(method),
+                Ok(None) => (),
+                //Statement is already complete, so parsing can continue with the next one
+                Err(error) if is_recovering => errors.push(error),
+                Err(error) => return Err(error),
+            }
+            Ok(())
+        });
+        self.interner = interner;
+        let is_partial = !errors.is_empty();
+        if let Some(recovered) = self.errors.as_mut() {
+            recovered.append(&mut errors);
+        }
+        let (header, end) = result?;
+        let (name, attributes) = match parse_header_ref(&header) {
+            Ok((name, attributes)) => (name.to_owned(), owned_attributes(attributes)),
+            Err(error) => return Err(error.at(start.line)),
+        };
+        check_identifier(IdentifierKind::Service, &name).map_err(|error| error.at(start.line))?;
+        let end = block_end(&name, end, start)?;
+
+        let mut idx = 0;
+        while idx < methods.len() {
+            let method = &methods[idx];
+            //Same as flatc, names are case sensitive
+            if methods[..idx].iter().any(|prev| prev.name == method.name) {
+                let error = ParseError::DuplicateMethod {
+                    service: name.clone(),
+                    method: method.name.clone(),
+                }.at(method.span.start.line);
+                match self.errors.as_mut() {
+                    Some(recovered) => {
+                        recovered.push(error);
+                        methods.remove(idx);
+                        continue;
+                    },
+                    None => return Err(error),
+                }
+            }
+            idx += 1;
+        }
+
+        if let Some(service_names) = self.service_names.as_mut() {
+            if !service_names.insert((self.namespace.clone(), name.clone())) {
+                return Err(ParseError::DuplicateService(name).at(start.line));
+            }
+        }
+
+        if let Some(collector) = self.diagnostics.as_mut() {
+            //Methods that failed to parse are reported already
+            if methods.is_empty() && !is_partial {
+                collector.warn(diagnostics::Checks::EMPTY_SERVICE, start.line, || format!("service '{name}' has no methods"));
+            }
+            for method in methods.iter() {
+                if method.arguments.iter().any(|argument| argument.ty == method.return_type) {
+                    collector.warn(diagnostics::Checks::SAME_TYPES, method.span.start.line, || format!("method '{}' returns type of its argument '{}'", method.name, method.return_type));
+                }
+            }
+        }
+
+        Ok(RpcService {
+            name,
+            methods,
+            docs,
+            namespace: self.namespace.clone(),
+            attributes,
+            span: Span {
+                start,
+                end,
+            }
+        })
+    }
+
+    ///Parses declaration, that starts within `line`.
+    ///
+    ///`offset` is position of `line` within original line.
+    fn parse_declaration(&mut self, line: &str, offset: usize) -> Option<Result<schema::Item, ParseErrorAt>> {
+        const SERVICE: &str = "rpc_service";
+
+        let trimmed = line.trim_start();
+        //Body of skipped declaration may contain anything, e.g. field `rpc_service: string;` of table
+        if self.skipped_depth > 0 {
+            return self.skip_declaration(line, offset);
+        }
+        match trimmed.as_bytes().first() {
+            None => return None,
+            //Fast path for lines, that cannot start any of parsed declarations, e.g. table fields
+            Some(b'r' | b'n' | b'i') => (),
+            Some(_) if !self.is_schema => return self.skip_declaration(line, offset),
+            Some(_) => (),
+        }
+        let start_offset = offset + offset_of(line, trimmed);
+        let start = Position::new(self.line_no, start_offset);
+
+        if let Some(header) = strip_keyword(trimmed, SERVICE) {
+            if self.services_count == self.limits.max_services {
+                return Some(Err(Limits::exceeded(Limit::Services, self.limits.max_services).at(self.line_no)));
+            }
+            self.services_count += 1;
+            let result = self.parse_service(header, start_offset + SERVICE.len(), start);
+            if let (Ok(_), Some(skipped_lines)) = (&result, self.skipped_lines.as_mut()) {
+                //Every line since the first one of service is within it
+                let len = skipped_lines.iter().rposition(|line| line.line_no < start.line).map_or(0, |idx| idx + 1);
+                skipped_lines.truncate(len);
+            }
+            return Some(result.map(schema::Item::Service));
+        } else if let Some(namespace) = strip_keyword(trimmed, "namespace") {
+            match parse_namespace(namespace) {
+                Ok(namespace) => self.namespace = Some(namespace),
+                Err(error) => return Some(Err(error.at(self.line_no))),
+            }
+        } else if let Some(include) = strip_keyword(trimmed, "include") {
+            match parse_include(include) {
+                Ok(include) => self.includes.push(include),
+                Err(error) => return Some(Err(error.at(self.line_no))),
+            }
+        } else if self.is_schema {
+            if let Some(result) = schema::parse_declaration(self, trimmed, start_offset, start) {
+                return Some(result);
+            }
+        }
+
+        self.skip_declaration(line, offset)
+    }
+
+    ///Skips declaration, that is not parsed, continuing within `line` at `offset`.
+    ///
+    ///Returns skipped declaration, once its brackets are balanced at the end of line, if parser is lossless.
+    fn skip_declaration(&mut self, line: &str, offset: usize) -> Option<Result<schema::Item, ParseErrorAt>> {
+        //Text after the end of declaration may contain next declaration, e.g. `table T {} rpc_service S {}`
+        let line = match declaration_end(line, self.skipped_depth) {
+            Some(end) if !line[end..].trim().is_empty() => {
+                self.pending = Some((offset + end, line[end..].to_owned()));
+                &line[..end]
+            },
+            _ => line,
+        };
+        let trimmed = line.trim_start();
+        //Same as flatc, documentation belongs only to the next definition
+        self.docs.clear();
+        self.skipped_depth = bracket_depth(trimmed, self.skipped_depth);
+        if let Some(collector) = self.diagnostics.as_mut() {
+            collector.skipped(self.line_no, trimmed);
+        }
+        if !self.is_lossless {
+            return None;
+        }
+
+        let text = line.trim_end();
+        let raw = match self.raw.as_mut() {
+            Some(raw) => {
+                raw.text.push('\n');
+                raw.text.push_str(text);
+                raw
+            },
+            None if trimmed.is_empty() => return None,
+            None => self.raw.get_or_insert(schema::RawDeclaration {
+                text: text.trim_start().to_owned(),
+                span: Span {
+                    start: Position::new(self.line_no, offset + offset_of(line, trimmed)),
+                    end: Position::default(),
+                },
+            }),
+        };
+        if let Some(last) = text.char_indices().next_back() {
+            raw.span.end = Position::new(self.line_no, offset + last.0);
+        }
+
+        match self.skipped_depth {
+            0 => self.take_raw(),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    ///Returns skipped declaration, that is not produced yet.
+    fn take_raw(&mut self) -> Option<Result<schema::Item, ParseErrorAt>> {
+        self.raw.take().map(|raw| Ok(schema::Item::Raw(raw)))
+    }
+
+    ///Parses next declaration, stopping once limit is exceeded.
+    fn next_declaration(&mut self) -> Option<Result<schema::Item, ParseErrorAt>> {
+        if self.is_stopped {
+            return None;
+        }
+        let result = self.parse_next();
+        //Error of declaration, that is cut off by exceeded limit, is meaningless
+        if let Some(error) = self.exceeded.take() {
+            return Some(Err(error));
+        }
+        if let Some(Err(ParseErrorAt { error: ParseError::LimitExceeded { .. }, .. })) = result {
+            self.is_stopped = true;
+        }
+        result
+    }
+
+    ///Parses next declaration
+    fn parse_next(&mut self) -> Option<Result<schema::Item, ParseErrorAt>> {
+        loop {
+            //Remaining text of line may contain several declarations
+            if let Some((offset, pending)) = self.pending.take() {
+                if let Some(result) = self.parse_declaration(&pending, offset) {
+                    return Some(result);
+                }
+                continue;
+            }
+
+            let line = match self.next_line() {
+                Some(line) => line,
+                None => break,
+            };
+            let line = self.strip_comments(line.as_ref());
+            if let Some(result) = self.parse_declaration(&line, 0) {
+                return Some(result);
+            }
+        }
+
+        //Declaration with unbalanced brackets lasts till the end of input
+        if let Some(raw) = self.take_raw() {
+            return Some(raw);
+        }
+        self.unterminated_comment().map(Err)
+    }
+}
+
+impl<I: AsRef<str>, T: Iterator<Item=I>> Iterator for ParserIter<T> {
+    type Item = Result<RpcService, ParseErrorAt>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.next_declaration()? {
+                Ok(schema::Item::Service(service)) => return Some(Ok(service)),
+                Ok(_) => continue,
+                Err(error) => return Some(Err(error)),
+            }
+        }
+    }
+}
+
+///Parses all services within `input`, failing on the first error.
+pub fn parse_services(input: &str) -> Result<Vec<RpcService>, ParseErrorAt> {
+    ParserIter::from_str(input).collect()
+}
+
+///Parses single service within `input`, failing if there is no service or more than one.
+pub fn parse_service(input: &str) -> Result<RpcService, ParseErrorAt> {
+    let mut parser = ParserIter::from_str(input);
+    let service = match parser.next() {
+        Some(service) => service?,
+        None => return Err(ParseError::NoService.at(parser.line_no)),
+    };
+
+    match parser.next() {
+        Some(Ok(next)) => Err(ParseError::MultipleServices.at(next.span.start.line)),
+        Some(Err(error)) => Err(error),
+        None => Ok(service),
+    }
+}
+
+///Parses all services, recovering from errors instead of stopping at the first one.
+///
+///Malformed method is skipped, while the rest of its service is parsed as usual, so services
+///can be partial.
+///Returns parsed services, and all errors in order of occurrence.
+pub fn parse_all<I: AsRef<str>, T: Iterator<Item=I>>(lines: T) -> (Vec<RpcService>, Vec<ParseErrorAt>) {
+    let mut parser = ParserIter::new(lines);
+    parser.errors = Some(Vec::new());
+
+    let mut services = Vec::new();
+    let mut errors = Vec::new();
+    while let Some(result) = parser.next() {
+        if let Some(recovered) = parser.errors.as_mut() {
+            errors.append(recovered);
+        }
+        match result {
+            Ok(service) => services.push(service),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    (services, errors)
+}
+
+///Parses all services within `input`, failing on the first error, same as [parse_services].
+///
+///Returns parsed services, and warnings produced by `checks` ordered by line.
+///See [diagnostics] for details.
+pub fn parse_with_diagnostics(input: &str, checks: diagnostics::Checks) -> Result<(Vec<RpcService>, Vec<diagnostics::Diagnostic>), ParseErrorAt> {
+    let mut collector = diagnostics::Collector::new(checks);
+    collector.line_endings(input);
+    let mut parser = ParserIter::from_str(input);
+    parser.diagnostics = Some(collector);
+
+    let services = parser.by_ref().collect::<Result<Vec<_>, _>>()?;
+    let mut diagnostics = parser.take_diagnostics();
+    diagnostics.sort_by_key(|diagnostic| diagnostic.line);
+    Ok((services, diagnostics))
 }
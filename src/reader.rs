@@ -0,0 +1,200 @@
+//!Parsing of services from reader
+
+use crate::{Limit, Limits, ParseErrorAt, ParserIter, RpcService};
+
+use core::{fmt, mem};
+use core::cell::Cell;
+use std::io::{self, BufRead, Read};
+use std::rc::Rc;
+
+#[derive(Debug)]
+///Error parsing schema from reader
+pub enum Error {
+    ///Unable to read
+    Io(io::Error),
+    ///Read input contains invalid definition
+    Parse(ParseErrorAt),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => fmt::Display::fmt(error, fmt),
+            Self::Parse(error) => fmt::Display::fmt(error, fmt),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Parse(error) => Some(&error.error),
+        }
+    }
+}
+
+///Line read by [Lines]
+///
+///Its buffer is given back to reader once line is dropped.
+pub struct Line {
+    text: String,
+    buffer: Rc<Cell<String>>,
+}
+
+impl AsRef<str> for Line {
+    #[inline(always)]
+    fn as_ref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Drop for Line {
+    #[inline]
+    fn drop(&mut self) {
+        self.buffer.set(mem::take(&mut self.text));
+    }
+}
+
+///Iterator over lines of reader
+///
+///Stops on IO error or once limit is exceeded.
+pub struct Lines<R> {
+    reader: R,
+    //Buffer of previous line, to be reused
+    buffer: Rc<Cell<String>>,
+    error: Rc<Cell<Option<Error>>>,
+    //Number of the last read line
+    line_no: usize,
+    //Number of bytes read so far
+    size: usize,
+    max_line_length: usize,
+    max_input_size: usize,
+}
+
+impl<R: io::BufRead> Lines<R> {
+    #[inline]
+    fn fail(&mut self, error: Error) -> Option<Line> {
+        self.error.set(Some(error));
+        None
+    }
+}
+
+impl<R: io::BufRead> Iterator for Lines<R> {
+    type Item = Line;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut bytes = self.buffer.take().into_bytes();
+        bytes.clear();
+
+        //Line is read only till it exceeds limit, so that oversized line is never buffered
+        let limit = self.max_line_length.min(self.max_input_size - self.size).saturating_add(1);
+        loop {
+            match (&mut self.reader).take(limit as u64).read_until(b'\n', &mut bytes) {
+                Ok(0) => return None,
+                Ok(_) => break,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return self.fail(Error::Io(error)),
+            }
+        }
+        self.line_no += 1;
+        self.size += bytes.len();
+
+        if self.size > self.max_input_size {
+            return self.fail(Error::Parse(Limits::exceeded(Limit::InputSize, self.max_input_size).at(self.line_no)));
+        }
+        if bytes.last() == Some(&b'\n') {
+            bytes.pop();
+        } else if bytes.len() == limit {
+            return self.fail(Error::Parse(Limits::exceeded(Limit::LineLength, self.max_line_length).at(self.line_no)));
+        }
+        let text = match String::from_utf8(bytes) {
+            Ok(text) => text,
+            Err(_) => return self.fail(Error::Io(io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8"))),
+        };
+        Some(Line {
+            text,
+            buffer: self.buffer.clone(),
+        })
+    }
+}
+
+///rpc_service parser over reader
+///
+///Refer to [ParserIter] for details.
+pub struct ReaderIter<R> {
+    parser: ParserIter<Lines<R>>,
+    error: Rc<Cell<Option<Error>>>,
+    is_failed: bool,
+}
+
+impl<R: io::BufRead> ReaderIter<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        let error = Rc::new(Cell::new(None));
+        let lines = Lines {
+            reader,
+            buffer: Rc::new(Cell::new(String::new())),
+            error: error.clone(),
+            line_no: 0,
+            size: 0,
+            max_line_length: usize::MAX,
+            max_input_size: usize::MAX,
+        };
+
+        Self {
+            parser: ParserIter::new(lines),
+            error,
+            is_failed: false,
+        }.limits(Limits::new())
+    }
+
+    #[inline]
+    ///Enables strict parsing.
+    ///
+    ///Refer to [ParserIter::strict] for details.
+    pub fn strict(mut self) -> Self {
+        self.parser = self.parser.strict();
+        self
+    }
+
+    #[inline]
+    ///Sets limits of parser.
+    ///
+    ///Unlike other parsers, size of input is limited, while line is read only till its length exceeds limit.
+    ///Refer to [Limits] for details.
+    pub fn limits(mut self, limits: Limits) -> Self {
+        self.parser.lines.max_line_length = limits.max_line_length;
+        self.parser.lines.max_input_size = limits.max_input_size;
+        self.parser = self.parser.limits(limits);
+        self
+    }
+
+    #[inline]
+    ///Returns paths of `include` declarations encountered so far, in order of declaration.
+    pub fn includes(&self) -> &[String] {
+        self.parser.includes()
+    }
+}
+
+impl<R: io::BufRead> Iterator for ReaderIter<R> {
+    type Item = Result<RpcService, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_failed {
+            return None;
+        }
+
+        let result = self.parser.next();
+        //Error of reader ends input prematurely, so parse result is meaningless
+        if let Some(error) = self.error.take() {
+            self.is_failed = true;
+            return Some(Err(error));
+        }
+
+        match result? {
+            Ok(service) => Some(Ok(service)),
+            Err(error) => Some(Err(Error::Parse(error))),
+        }
+    }
+}
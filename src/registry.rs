@@ -0,0 +1,315 @@
+//!Persisted method ids
+//!
+//![IdRegistry] keeps ids of methods within lock file, so that ids do not change when methods are inserted or removed:
+//!
+//!```rust,no_run
+//!use flatbuffers_tools::RpcService;
+//!use flatbuffers_tools::registry::IdRegistry;
+//!
+//!let service: RpcService = "rpc_service Storage { Put(Req):Resp; }".parse().unwrap();
+//!let mut registry = IdRegistry::load("schema/ids.lock").expect("to load");
+//!let assignments = registry.assign(&service).expect("to assign");
+//!registry.save("schema/ids.lock").expect("to save");
+//!let defines = service.as_rpc_method_defines().assignments(&assignments).to_string();
+//!```
+//!
+//!Lock file is TOML with every method id under `ids` table, keyed by `Service.Method`.
+//!Ids of removed methods are kept under `removed` table, to never assign them to other method:
+//!
+//!```toml
+//![ids]
+//!"Storage.Get" = 1
+//!"Storage.Put" = 0
+//!
+//![removed]
+//!"Storage.Delete" = 2
+//!```
+
+use crate::RpcService;
+
+use core::fmt;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+///Error of method ids registry
+pub enum IdError {
+    ///Unable to read or write lock file
+    Io(PathBuf, io::Error),
+    ///Lock file contains invalid line
+    Parse {
+        ///Line number, starting from 1.
+        line: usize,
+        ///Line as written
+        text: String,
+    },
+    ///Methods of the same service have the same id
+    DuplicateId {
+        ///Service name
+        service: String,
+        ///Name of the first method
+        first: String,
+        ///Name of the second method
+        second: String,
+        ///Id of both methods
+        id: u64,
+    },
+    ///Method with recorded id is no longer defined, while removals are not allowed
+    Removed {
+        ///Service name
+        service: String,
+        ///Method name
+        method: String,
+    },
+}
+
+impl fmt::Display for IdError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, error) => fmt.write_fmt(format_args!("{}: {}", path.display(), error)),
+            Self::Parse { line, text } => fmt.write_fmt(format_args!("{line}: invalid entry '{text}'")),
+            Self::DuplicateId { service, first, second, id } => fmt.write_fmt(format_args!("Methods '{first}' and '{second}' of service '{service}' have the same id {id}")),
+            Self::Removed { service, method } => fmt.write_fmt(format_args!("Method '{method}' of service '{service}' is removed, but removals are not allowed")),
+        }
+    }
+}
+
+impl std::error::Error for IdError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(_, error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+///Method ids of the service, assigned by [IdRegistry]
+pub struct Assignments {
+    ///Service name
+    pub service: String,
+    ///Name and id of every method, in order of declaration.
+    pub ids: Vec<(String, u64)>,
+}
+
+impl Assignments {
+    #[inline]
+    ///Returns id of method with specified `name`.
+    pub fn id(&self, name: &str) -> Option<u64> {
+        self.ids.iter().find(|(method, _)| method == name).map(|(_, id)| *id)
+    }
+
+    #[inline]
+    ///Returns name of method with specified `id`.
+    pub fn name(&self, id: u64) -> Option<&str> {
+        self.ids.iter().find(|(_, method_id)| *method_id == id).map(|(name, _)| name.as_str())
+    }
+
+    ///Returns ids of methods of `service` in order of declaration.
+    ///
+    ///Methods without assigned id get ids following the greatest one, same as [IdRegistry::assign] would assign them.
+    pub(crate) fn method_ids(&self, service: &RpcService) -> Vec<u64> {
+        let mut next = self.ids.iter().map(|(_, id)| id + 1).max().unwrap_or(0);
+        service.methods.iter().map(|method| match self.id(&method.name) {
+            Some(id) => id,
+            None => {
+                next += 1;
+                next - 1
+            },
+        }).collect()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+///Registry of method ids, persisted within lock file.
+pub struct IdRegistry {
+    //Ids of methods, keyed by `Service.Method`
+    ids: BTreeMap<String, u64>,
+    //Ids of removed methods, which are never assigned again
+    removed: BTreeMap<String, u64>,
+    allow_removals: bool,
+}
+
+impl IdRegistry {
+    #[inline]
+    ///Creates empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    ///Sets whether methods can be removed, `false` by default.
+    ///
+    ///Ids of removed methods are reserved, and assigned again only to method with the same name.
+    pub fn allow_removals(mut self, allow_removals: bool) -> Self {
+        self.allow_removals = allow_removals;
+        self
+    }
+
+    ///Loads registry from lock file, creating empty registry if file does not exist.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, IdError> {
+        let path = path.as_ref();
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::new()),
+            Err(error) => Err(IdError::Io(path.to_owned(), error)),
+        }
+    }
+
+    ///Parses content of lock file.
+    pub fn parse(text: &str) -> Result<Self, IdError> {
+        let mut registry = Self::new();
+        let mut is_removed = false;
+        for (idx, line) in text.lines().enumerate() {
+            let error = || IdError::Parse {
+                line: idx + 1,
+                text: line.to_owned(),
+            };
+            let line = line.trim();
+            match line {
+                "" => continue,
+                _ if line.starts_with('#') => continue,
+                "[ids]" => is_removed = false,
+                "[removed]" => is_removed = true,
+                _ => {
+                    let (key, id) = line.split_once('=').ok_or_else(error)?;
+                    let key = key.trim();
+                    let key = key.strip_prefix('"').and_then(|key| key.strip_suffix('"')).unwrap_or(key);
+                    let id = id.trim().parse().map_err(|_| error())?;
+                    if key.split_once('.').is_none_or(|(service, method)| service.is_empty() || method.is_empty()) {
+                        return Err(error());
+                    }
+                    match is_removed {
+                        true => registry.removed.insert(key.to_owned(), id),
+                        false => registry.ids.insert(key.to_owned(), id),
+                    };
+                },
+            }
+        }
+
+        registry.check_ids()?;
+        Ok(registry)
+    }
+
+    ///Checks that ids are unique within every service.
+    fn check_ids(&self) -> Result<(), IdError> {
+        let mut used: BTreeMap<(&str, u64), &str> = BTreeMap::new();
+        for key in self.ids.iter().chain(self.removed.iter()) {
+            let (name, id) = key;
+            let (service, method) = name.split_once('.').unwrap_or((name, ""));
+            if let Some(prev) = used.insert((service, *id), method) {
+                return Err(IdError::DuplicateId {
+                    service: service.to_owned(),
+                    first: prev.to_owned(),
+                    second: method.to_owned(),
+                    id: *id,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    ///Assigns ids to methods of `service`, recording new ones.
+    ///
+    ///Recorded methods keep their ids, while new methods get ids following the greatest id of the service, including removed methods.
+    ///
+    ///Fails if recorded method is no longer defined, unless removals are allowed.
+    ///
+    ///```rust
+    ///use flatbuffers_tools::RpcService;
+    ///use flatbuffers_tools::registry::{IdError, IdRegistry};
+    ///
+    ///let first: RpcService = "rpc_service Storage { Put(Req):Resp; Get(Key):Resp; Delete(Key):Resp; }".parse().unwrap();
+    ///let mut registry = IdRegistry::new();
+    ///registry.assign(&first).unwrap();
+    ///
+    /////Method is inserted, `Delete` is renamed to `Remove` and `Get` is removed
+    ///let second: RpcService = "rpc_service Storage { List(Key):Resp; Put(Req):Resp; Remove(Key):Resp; }".parse().unwrap();
+    ///let mut registry = IdRegistry::parse(&registry.to_string()).unwrap();
+    ///assert!(matches!(registry.assign(&second), Err(IdError::Removed { .. })));
+    ///
+    ///let mut registry = registry.allow_removals(true);
+    ///let assignments = registry.assign(&second).unwrap();
+    ///assert_eq!(assignments.id("Put"), Some(0));
+    ///assert_eq!(assignments.id("List"), Some(3));
+    ///assert_eq!(assignments.id("Remove"), Some(4));
+    ///
+    ///let defines = second.as_rpc_method_defines().assignments(&assignments).to_string();
+    ///assert!(defines.contains("pub const PUT: u16 = 0;"));
+    ///assert!(defines.contains("pub const REMOVE: u16 = 4;"));
+    ///assert_eq!(second.assigned_ids(&assignments).method_by_id(3).unwrap().name, "List");
+    ///
+    /////Removed method gets its id back
+    ///let assignments = registry.assign(&first).unwrap();
+    ///assert_eq!(assignments.ids, [("Put".to_owned(), 0), ("Get".to_owned(), 1), ("Delete".to_owned(), 2)]);
+    ///```
+    pub fn assign(&mut self, service: &RpcService) -> Result<Assignments, IdError> {
+        let prefix = format!("{}.", service.name);
+        let is_defined = |key: &str| service.methods.iter().any(|method| key[prefix.len()..] == method.name);
+
+        let recorded: Vec<String> = self.ids.keys().filter(|key| key.starts_with(&prefix)).cloned().collect();
+        for key in recorded {
+            if !is_defined(&key) {
+                if !self.allow_removals {
+                    return Err(IdError::Removed {
+                        service: service.name.clone(),
+                        method: key[prefix.len()..].to_owned(),
+                    });
+                }
+                if let Some(id) = self.ids.remove(&key) {
+                    self.removed.insert(key, id);
+                }
+            }
+        }
+
+        let mut next = self.ids.iter().chain(self.removed.iter()).filter(|(key, _)| key.starts_with(&prefix)).map(|(_, id)| id + 1).max().unwrap_or(0);
+        let mut ids = Vec::with_capacity(service.methods.len());
+        for method in service.methods.iter() {
+            let key = format!("{prefix}{}", method.name);
+            let id = match self.ids.get(&key) {
+                Some(id) => *id,
+                //Method, that is added back, gets its previous id
+                None => match self.removed.remove(&key) {
+                    Some(id) => id,
+                    None => {
+                        next += 1;
+                        next - 1
+                    },
+                },
+            };
+            self.ids.insert(key, id);
+            ids.push((method.name.clone(), id));
+        }
+
+        Ok(Assignments {
+            service: service.name.clone(),
+            ids,
+        })
+    }
+
+    ///Writes registry into lock file, with entries sorted by name.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), IdError> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_string()).map_err(|error| IdError::Io(path.to_owned(), error))
+    }
+}
+
+impl fmt::Display for IdRegistry {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("# Method ids of rpc services, maintained by flatbuffers-tools.\n")?;
+        fmt.write_str("# Ids of removed methods are reserved, so that they are never assigned again.\n\n")?;
+        fmt.write_str("[ids]\n")?;
+        for (key, id) in self.ids.iter() {
+            fmt.write_fmt(format_args!("\"{key}\" = {id}\n"))?;
+        }
+        if !self.removed.is_empty() {
+            fmt.write_str("\n[removed]\n")?;
+            for (key, id) in self.removed.iter() {
+                fmt.write_fmt(format_args!("\"{key}\" = {id}\n"))?;
+            }
+        }
+        Ok(())
+    }
+}
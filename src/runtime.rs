@@ -0,0 +1,170 @@
+//!Shared items of generated code
+//!
+//!By default output of [render_services](crate::render_services) defines items, that are shared by modules of services, such as `Codec`.
+//!Hence outputs of several schemas cannot be included within the same module.
+//!
+//!With [GenConfig::runtime](crate::GenConfig::runtime) modules import items of this module instead, and output has no shared items.
+//!Items are the same as generated ones with `u16` method ids and `std` library, so that handlers and codecs are implemented
+//!the same way regardless of where items are defined.
+//!
+//!This crate does not depend on `flatbuffers`, hence `ClientError` is generic over error of response verification,
+//!and generated client's module defines alias `ClientError<E>`, specifying `flatbuffers::InvalidFlatbuffer`.
+//!
+//!```rust
+//!use flatbuffers_tools::{parse_services, render_services, GenConfig, GenItem, TypeName};
+//!
+//!let config = GenConfig::new().skip_docs(true).runtime(true).type_path(|name: &TypeName| format!("crate::{}", name.name())).item(GenItem::Dispatcher).item(GenItem::Descriptor).item(GenItem::Markers);
+//!let storage = render_services(&parse_services("rpc_service Storage { Put(Request):Response; }").unwrap(), &config).unwrap();
+//!let monitor = render_services(&parse_services("rpc_service Monitor { Ping():Response; }").unwrap(), &config).unwrap();
+//!assert!(storage.starts_with("pub mod storage {\n    use ::flatbuffers_tools::runtime::{Codec, DispatchError};\n"));
+//!assert!(monitor.starts_with("pub mod monitor {\n"));
+//!```
+
+pub use crate::Method;
+pub use crate::framing::FrameError;
+
+///Codec of RPC messages of type `T`
+pub trait Codec<T> {
+    ///Decodes request from `payload`
+    fn decode(payload: &[u8]) -> Result<T, DispatchError>;
+    ///Encodes response
+    fn encode(message: &T) -> Vec<u8>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+///RPC dispatch error
+pub enum DispatchError {
+    ///Method is not defined by service
+    UnknownMethod(u16),
+    ///Request cannot be decoded
+    InvalidRequest,
+    ///Handler returned error, encoded by `Codec`
+    Failed(Vec<u8>),
+}
+
+///Transport of RPC calls
+pub trait Transport {
+    ///Transport error
+    type Error;
+
+    ///Calls `method` with serialized request `payload`, returning serialized response.
+    fn call(&mut self, method: u16, payload: &[u8]) -> Result<Vec<u8>, Self::Error>;
+}
+
+#[derive(Debug)]
+///RPC call error
+///
+///Generated module specifies `R` as `flatbuffers::InvalidFlatbuffer` by alias `ClientError<E>`.
+pub enum ClientError<E, R> {
+    ///Transport failed to perform call
+    Transport(E),
+    ///Response is not valid flatbuffer
+    InvalidResponse(R),
+}
+
+///Observer of calls of instrumented handlers
+pub trait RpcObserver {
+    ///Reports start of `method` call, with method name as declared within schema
+    fn on_call_start(&self, method: &'static str);
+    ///Reports end of `method` call after `elapsed` time, with `is_ok` being `false` if handler returned error
+    fn on_call_end(&self, method: &'static str, elapsed: core::time::Duration, is_ok: bool);
+}
+
+#[derive(Debug, Clone, Copy)]
+///Descriptor of RPC service
+pub struct ServiceDescriptor {
+    ///Service name
+    pub name: &'static str,
+    ///Namespace in effect at the definition
+    pub namespace: Option<&'static str>,
+    ///Attributes, as pairs of key and optional value
+    pub attributes: &'static [(&'static str, Option<&'static str>)],
+    ///Service methods
+    pub methods: &'static [MethodDescriptor],
+}
+
+impl ServiceDescriptor {
+    ///Returns method with specified `id`, if any.
+    pub fn method(&self, id: u16) -> Option<&'static MethodDescriptor> {
+        self.methods.iter().find(|method| method.id == id)
+    }
+
+    ///Returns method with specified `name`, if any.
+    pub fn method_by_name(&self, name: &str) -> Option<&'static MethodDescriptor> {
+        self.methods.iter().find(|method| method.name == name)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+///Descriptor of RPC method
+pub struct MethodDescriptor {
+    ///Method name, as declared within schema
+    pub name: &'static str,
+    ///Method id
+    pub id: u16,
+    ///Fully qualified argument types
+    pub arguments: &'static [&'static str],
+    ///Fully qualified return type
+    pub response: &'static str,
+    ///Attributes, as pairs of key and optional value
+    pub attributes: &'static [(&'static str, Option<&'static str>)],
+    ///Timeout in milliseconds, specified by `timeout_ms` attribute
+    pub timeout_ms: Option<u64>,
+    ///Priority, specified by `priority` attribute
+    pub priority: Option<MethodPriority>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+///Priority of RPC method
+pub enum MethodPriority {
+    ///Value `low`
+    Low,
+    ///Value `normal`
+    Normal,
+    ///Value `high`
+    High,
+}
+
+impl MethodDescriptor {
+    ///Returns value of attribute `key`, which is `Some(None)` if attribute has no value.
+    pub fn attribute(&self, key: &str) -> Option<Option<&'static str>> {
+        self.attributes.iter().find(|attribute| attribute.0 == key).map(|attribute| attribute.1)
+    }
+
+    #[inline]
+    ///Returns value of `streaming` attribute, if any.
+    pub fn streaming(&self) -> Option<&'static str> {
+        self.attribute("streaming").flatten()
+    }
+
+    #[inline]
+    ///Returns whether method has `deprecated` attribute.
+    pub fn is_deprecated(&self) -> bool {
+        self.attribute("deprecated").is_some()
+    }
+}
+
+///Items of framed calls, used by client with [GenConfig::framing](crate::GenConfig::framing)
+pub mod framed {
+    ///Transport of framed RPC calls
+    pub trait Transport {
+        ///Transport error
+        type Error;
+
+        ///Sends request `frame`, returning response frame.
+        fn call(&mut self, frame: &[u8]) -> Result<Vec<u8>, Self::Error>;
+    }
+
+    #[derive(Debug)]
+    ///RPC call error
+    ///
+    ///Generated module specifies `R` as `flatbuffers::InvalidFlatbuffer` by alias `ClientError<E>`.
+    pub enum ClientError<E, R> {
+        ///Transport failed to perform call
+        Transport(E),
+        ///Response is not valid flatbuffer
+        InvalidResponse(R),
+        ///Response frame cannot be decoded
+        InvalidFrame,
+    }
+}
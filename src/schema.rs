@@ -0,0 +1,1216 @@
+//!Schema declarations
+
+use crate::{block_end, find_unquoted, owned_attributes, parse_attributes, parse_header_ref, parse_quoted, strip_keyword, Attribute, ParseError, ParseErrorAt, ParserIter, Position, RpcService, Span, Statement};
+
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///table field
+pub struct Field {
+    ///Field's name
+    pub name: String,
+    ///Type as written, e.g. `int`, `string` or `[ubyte]`
+    pub ty: crate::TypeRef,
+    ///Default value as written, if any
+    pub default: Option<String>,
+    ///List of attributes in order of definition.
+    pub attributes: Vec<Attribute>,
+    ///Documentation comments `///` preceding definition, one entry per line.
+    pub docs: Vec<String>,
+    ///Location of definition
+    pub span: Span,
+}
+
+impl Field {
+    fn parse(text: &str) -> Result<Self, ParseError> {
+        let (name, rest) = match text.split_once(':') {
+            Some((name, rest)) => (name.trim(), rest),
+            None => return Err(ParseError::InvalidField(text.to_owned())),
+        };
+
+        let (rest, attributes) = match find_unquoted(rest, '(') {
+            Some(idx) => match rest[idx+1..].trim_end().strip_suffix(')') {
+                Some(attributes) => (&rest[..idx], parse_attributes(attributes)?),
+                None => return Err(ParseError::InvalidAttributes(rest[idx..].trim().to_owned())),
+            },
+            None => (rest, Vec::new()),
+        };
+
+        let (ty, default) = match find_unquoted(rest, '=') {
+            Some(idx) => (rest[..idx].trim(), Some(rest[idx+1..].trim())),
+            None => (rest.trim(), None),
+        };
+
+        if name.is_empty() || ty.is_empty() || default.is_some_and(str::is_empty) {
+            return Err(ParseError::InvalidField(text.to_owned()));
+        }
+
+        Ok(Self {
+            name: name.to_owned(),
+            ty: ty.into(),
+            default: default.map(str::to_owned),
+            attributes,
+            docs: Vec::new(),
+            span: Span::default(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///table definition
+pub struct Table {
+    ///Table name
+    pub name: String,
+    ///List of fields in order of definition
+    pub fields: Vec<Field>,
+    ///List of attributes in order of definition.
+    pub attributes: Vec<Attribute>,
+    ///Documentation comments `///` preceding definition, one entry per line.
+    pub docs: Vec<String>,
+    ///Namespace in effect at the definition
+    pub namespace: Option<String>,
+    ///Location of definition
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///struct definition
+///
+///Unlike table, its fields cannot have default values and most of attributes.
+pub struct Struct {
+    ///Struct name
+    pub name: String,
+    ///List of fields in order of definition
+    pub fields: Vec<Field>,
+    ///List of attributes in order of definition.
+    pub attributes: Vec<Attribute>,
+    ///Documentation comments `///` preceding definition, one entry per line.
+    pub docs: Vec<String>,
+    ///Namespace in effect at the definition
+    pub namespace: Option<String>,
+    ///Location of definition
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///enum definition
+pub struct Enum {
+    ///Enum name
+    pub name: String,
+    ///Underlying integer type, e.g. `byte`
+    pub underlying_type: String,
+    ///List of variants in order of definition.
+    ///
+    ///Value is `None` when it is not specified explicitly, in which case flatbuffers implicitly
+    ///assigns previous value plus one, or zero for the first variant.
+    pub variants: Vec<(String, Option<i64>)>,
+    ///List of attributes in order of definition.
+    pub attributes: Vec<Attribute>,
+    ///Documentation comments `///` preceding definition, one entry per line.
+    pub docs: Vec<String>,
+    ///Namespace in effect at the definition
+    pub namespace: Option<String>,
+    ///Location of definition
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///union definition
+pub struct Union {
+    ///Union name
+    pub name: String,
+    ///List of members in order of definition.
+    ///
+    ///Each member is pair of name and optional aliased type, e.g. `Foo: MyGame.Foo` results in `("Foo", Some("MyGame.Foo"))`
+    pub members: Vec<(String, Option<String>)>,
+    ///List of attributes in order of definition.
+    pub attributes: Vec<Attribute>,
+    ///Documentation comments `///` preceding definition, one entry per line.
+    pub docs: Vec<String>,
+    ///Namespace in effect at the definition
+    pub namespace: Option<String>,
+    ///Location of definition
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///Declaration, that is not parsed, produced only by [lossless](SchemaParser::lossless) parser
+pub struct RawDeclaration {
+    ///Lines of declaration as written, without comments and trailing whitespace.
+    pub text: String,
+    ///Location of declaration
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+///Schema declaration
+pub enum Item {
+    ///rpc_service definition
+    Service(RpcService),
+    ///table definition
+    Table(Table),
+    ///struct definition
+    Struct(Struct),
+    ///enum definition
+    Enum(Enum),
+    ///union definition
+    Union(Union),
+    ///Declaration, that is not parsed, e.g. `namespace`, `root_type` or unknown one
+    Raw(RawDeclaration),
+}
+
+impl Item {
+    #[inline]
+    ///Returns location of definition.
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Service(service) => service.span,
+            Self::Table(table) => table.span,
+            Self::Struct(structure) => structure.span,
+            Self::Enum(definition) => definition.span,
+            Self::Union(definition) => definition.span,
+            Self::Raw(declaration) => declaration.span,
+        }
+    }
+
+    #[inline]
+    ///Returns documentation comments of definition.
+    pub fn docs(&self) -> &[String] {
+        match self {
+            Self::Service(service) => &service.docs,
+            Self::Table(table) => &table.docs,
+            Self::Struct(structure) => &structure.docs,
+            Self::Enum(definition) => &definition.docs,
+            Self::Union(definition) => &definition.docs,
+            Self::Raw(_) => &[],
+        }
+    }
+}
+
+///Parses declaration header `<name> (<attributes>)`
+fn parse_header(header: &str) -> Result<(String, Vec<Attribute>), ParseError> {
+    let (name, attributes) = parse_header_ref(header)?;
+    Ok((name.to_owned(), owned_attributes(attributes)))
+}
+
+///Parses field out of body statement
+fn parse_field(statement: Statement<'_>, is_strict: bool) -> Result<Option<Field>, ParseErrorAt> {
+    if statement.text.is_empty() {
+        Ok(None)
+    } else if is_strict && !statement.is_terminated {
+        Err(ParseError::MissingSemicolon(statement.text.to_owned()).at(statement.span.start.line))
+    } else {
+        match Field::parse(statement.text) {
+            Ok(mut field) => {
+                field.span = statement.span;
+                field.docs = statement.docs;
+                Ok(Some(field))
+            },
+            Err(error) => Err(error.at(statement.span.start.line)),
+        }
+    }
+}
+
+fn parse_table<I: AsRef<str>, T: Iterator<Item=I>>(parser: &mut ParserIter<T>, header: &str, header_offset: usize, start: Position) -> Result<Table, ParseErrorAt> {
+    let docs = core::mem::take(&mut parser.docs);
+    let is_strict = parser.is_strict;
+    let mut fields = Vec::new();
+    let (header, end) = parser.parse_block(header, header_offset, ';', |statement| {
+        if let Some(field) = parse_field(statement, is_strict)? {
+            fields.push(field);
+        }
+        Ok(())
+    })?;
+    let (name, attributes) = parse_header(&header).map_err(|error| error.at(start.line))?;
+    let end = block_end(&name, end, start)?;
+
+    Ok(Table {
+        name,
+        fields,
+        attributes,
+        docs,
+        namespace: parser.namespace.clone(),
+        span: Span {
+            start,
+            end,
+        }
+    })
+}
+
+///Field attributes, that are meaningless for struct
+const ILLEGAL_STRUCT_ATTRIBUTES: [&str; 3] = ["deprecated", "required", "id"];
+
+fn parse_struct<I: AsRef<str>, T: Iterator<Item=I>>(parser: &mut ParserIter<T>, header: &str, header_offset: usize, start: Position) -> Result<Struct, ParseErrorAt> {
+    let docs = core::mem::take(&mut parser.docs);
+    let is_strict = parser.is_strict;
+    let mut fields = Vec::new();
+    let (header, end) = parser.parse_block(header, header_offset, ';', |statement| {
+        let line = statement.span.start.line;
+        if let Some(field) = parse_field(statement, is_strict)? {
+            if field.default.is_some() {
+                return Err(ParseError::StructFieldDefault(field.name).at(line));
+            }
+            if let Some((attribute, _)) = field.attributes.iter().find(|(key, _)| ILLEGAL_STRUCT_ATTRIBUTES.contains(&key.as_str())) {
+                return Err(ParseError::StructFieldAttribute(attribute.clone()).at(line));
+            }
+            fields.push(field);
+        }
+        Ok(())
+    })?;
+    let (name, attributes) = parse_header(&header).map_err(|error| error.at(start.line))?;
+    let end = block_end(&name, end, start)?;
+
+    Ok(Struct {
+        name,
+        fields,
+        attributes,
+        docs,
+        namespace: parser.namespace.clone(),
+        span: Span {
+            start,
+            end,
+        }
+    })
+}
+
+///Parses integer literal, either decimal or hexadecimal.
+fn parse_integer(text: &str) -> Option<i64> {
+    let (is_negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+
+    let value = match digits.strip_prefix("0x").or_else(|| digits.strip_prefix("0X")) {
+        Some(digits) => i64::from_str_radix(digits, 16).ok()?,
+        None => digits.parse::<i64>().ok()?,
+    };
+
+    if is_negative {
+        Some(-value)
+    } else {
+        Some(value)
+    }
+}
+
+///Parses enum variant `<name> (= <value>)`
+fn parse_variant(text: &str) -> Result<(String, Option<i64>), ParseError> {
+    let (name, value) = match text.split_once('=') {
+        Some((name, value)) => match parse_integer(value.trim()) {
+            Some(value) => (name.trim(), Some(value)),
+            None => return Err(ParseError::InvalidEnumVariant(text.to_owned())),
+        },
+        None => (text, None),
+    };
+
+    if name.is_empty() {
+        return Err(ParseError::InvalidEnumVariant(text.to_owned()));
+    }
+
+    Ok((name.to_owned(), value))
+}
+
+fn parse_enum<I: AsRef<str>, T: Iterator<Item=I>>(parser: &mut ParserIter<T>, header: &str, header_offset: usize, start: Position) -> Result<Enum, ParseErrorAt> {
+    let docs = core::mem::take(&mut parser.docs);
+    let mut variants = Vec::new();
+    let (header, end) = parser.parse_block(header, header_offset, ',', |statement| {
+        if !statement.text.is_empty() {
+            let variant = parse_variant(statement.text).map_err(|error| error.at(statement.span.start.line))?;
+            variants.push(variant);
+        }
+        Ok(())
+    })?;
+
+    let (name, underlying_type) = match header.split_once(':') {
+        Some((name, underlying_type)) => (name.trim(), underlying_type),
+        None => return Err(ParseError::NoUnderlyingType(header).at(start.line)),
+    };
+    let (underlying_type, attributes) = parse_header(underlying_type).map_err(|error| error.at(start.line))?;
+    if underlying_type.is_empty() {
+        return Err(ParseError::NoUnderlyingType(header).at(start.line));
+    }
+    let end = block_end(name, end, start)?;
+
+    Ok(Enum {
+        name: name.to_owned(),
+        underlying_type,
+        variants,
+        attributes,
+        docs,
+        namespace: parser.namespace.clone(),
+        span: Span {
+            start,
+            end,
+        }
+    })
+}
+
+///Parses union member `<name> (: <type>)`
+fn parse_member(text: &str) -> Result<(String, Option<String>), ParseError> {
+    let (name, alias) = match text.split_once(':') {
+        Some((name, alias)) => (name.trim(), Some(alias.trim())),
+        None => (text, None),
+    };
+
+    if name.is_empty() || alias.is_some_and(str::is_empty) {
+        return Err(ParseError::InvalidUnionMember(text.to_owned()));
+    }
+
+    Ok((name.to_owned(), alias.map(str::to_owned)))
+}
+
+fn parse_union<I: AsRef<str>, T: Iterator<Item=I>>(parser: &mut ParserIter<T>, header: &str, header_offset: usize, start: Position) -> Result<Union, ParseErrorAt> {
+    let docs = core::mem::take(&mut parser.docs);
+    let mut members = Vec::new();
+    let (header, end) = parser.parse_block(header, header_offset, ',', |statement| {
+        if !statement.text.is_empty() {
+            let member = parse_member(statement.text).map_err(|error| error.at(statement.span.start.line))?;
+            members.push(member);
+        }
+        Ok(())
+    })?;
+    let (name, attributes) = parse_header(&header).map_err(|error| error.at(start.line))?;
+    let end = block_end(&name, end, start)?;
+
+    Ok(Union {
+        name,
+        members,
+        attributes,
+        docs,
+        namespace: parser.namespace.clone(),
+        span: Span {
+            start,
+            end,
+        }
+    })
+}
+
+///Parses declarations, other than service.
+///Parses root type declaration, without keyword
+fn parse_root_type(text: &str) -> Result<String, ParseError> {
+    let name = match text.trim().strip_suffix(';') {
+        Some(name) => name.trim(),
+        None => return Err(ParseError::InvalidRootType(text.trim().to_owned())),
+    };
+
+    if name.split('.').any(|part| part.is_empty() || part.contains(char::is_whitespace)) {
+        return Err(ParseError::InvalidRootType(name.to_owned()));
+    }
+
+    Ok(name.to_owned())
+}
+
+///Parses file identifier declaration, without keyword
+fn parse_file_identifier(text: &str) -> Result<String, ParseError> {
+    match parse_quoted(text) {
+        Some(identifier) if identifier.len() == 4 => Ok(identifier.to_owned()),
+        _ => Err(ParseError::InvalidFileIdentifier(text.trim().to_owned())),
+    }
+}
+
+///Parses file extension declaration, without keyword
+fn parse_file_extension(text: &str) -> Result<String, ParseError> {
+    match parse_quoted(text) {
+        Some(extension) if !extension.is_empty() => Ok(extension.to_owned()),
+        _ => Err(ParseError::InvalidFileExtension(text.trim().to_owned())),
+    }
+}
+
+///Parses attribute declaration, without keyword.
+///
+///Same as flatc, accepts both quoted and bare names.
+fn parse_attribute_declaration(text: &str) -> Result<String, ParseError> {
+    let name = match parse_quoted(text) {
+        Some(name) => name,
+        None => match text.trim().strip_suffix(';') {
+            Some(name) => name.trim(),
+            None => return Err(ParseError::InvalidAttributeDeclaration(text.trim().to_owned())),
+        },
+    };
+
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        Err(ParseError::InvalidAttributeDeclaration(text.trim().to_owned()))
+    } else {
+        Ok(name.to_owned())
+    }
+}
+
+pub(crate) fn parse_declaration<I: AsRef<str>, T: Iterator<Item=I>>(parser: &mut ParserIter<T>, line: &str, offset: usize, start: Position) -> Option<Result<Item, ParseErrorAt>> {
+    const TABLE: &str = "table";
+    const STRUCT: &str = "struct";
+    const ENUM: &str = "enum";
+    const UNION: &str = "union";
+    const ROOT_TYPE: &str = "root_type";
+    const FILE_IDENTIFIER: &str = "file_identifier";
+    const FILE_EXTENSION: &str = "file_extension";
+    const ATTRIBUTE: &str = "attribute";
+
+    if let Some(header) = strip_keyword(line, TABLE) {
+        Some(parse_table(parser, header, offset + TABLE.len(), start).map(Item::Table))
+    } else if let Some(header) = strip_keyword(line, STRUCT) {
+        Some(parse_struct(parser, header, offset + STRUCT.len(), start).map(Item::Struct))
+    } else if let Some(header) = strip_keyword(line, ENUM) {
+        Some(parse_enum(parser, header, offset + ENUM.len(), start).map(Item::Enum))
+    } else if let Some(header) = strip_keyword(line, UNION) {
+        Some(parse_union(parser, header, offset + UNION.len(), start).map(Item::Union))
+    } else if let Some(root_type) = strip_keyword(line, ROOT_TYPE) {
+        let root_type = match parse_root_type(root_type) {
+            Ok(root_type) => root_type,
+            Err(error) => return Some(Err(error.at(parser.line_no))),
+        };
+        match parser.root_type.as_ref() {
+            //Repeating the same declaration is harmless
+            Some(declared) if *declared != root_type => Some(Err(ParseError::ConflictingRootType(root_type).at(parser.line_no))),
+            _ => {
+                parser.root_type = Some(root_type);
+                None
+            }
+        }
+    } else if let Some(identifier) = strip_keyword(line, FILE_IDENTIFIER) {
+        match parse_file_identifier(identifier) {
+            Ok(identifier) => {
+                parser.file_identifier = Some(identifier);
+                None
+            },
+            Err(error) => Some(Err(error.at(parser.line_no))),
+        }
+    } else if let Some(extension) = strip_keyword(line, FILE_EXTENSION) {
+        match parse_file_extension(extension) {
+            Ok(extension) => {
+                parser.file_extension = Some(extension);
+                None
+            },
+            Err(error) => Some(Err(error.at(parser.line_no))),
+        }
+    } else if let Some(attribute) = strip_keyword(line, ATTRIBUTE) {
+        match parse_attribute_declaration(attribute) {
+            Ok(attribute) => {
+                parser.declared_attributes.push(attribute);
+                None
+            },
+            Err(error) => Some(Err(error.at(parser.line_no))),
+        }
+    } else {
+        None
+    }
+}
+
+///Attributes known to flatc, that require no declaration.
+pub const BUILTIN_ATTRIBUTES: [&str; 27] = [
+    "id", "deprecated", "required", "key", "hash", "force_align", "bit_flags", "nested_flatbuffer",
+    "flexbuffer", "original_order", "private", "shared", "idempotent", "streaming", "vector64", "offset64",
+    "native_inline", "native_default", "native_custom_alloc", "native_type", "native_type_pack_name",
+    "cpp_type", "cpp_ptr_type", "cpp_ptr_type_get", "cpp_str_type", "cpp_str_flex_ctor", "csharp_partial",
+];
+
+fn fields_attributes(fields: &[Field]) -> impl Iterator<Item=&Attribute> {
+    fields.iter().flat_map(|field| field.attributes.iter())
+}
+
+fn service_attributes(service: &RpcService) -> impl Iterator<Item=&Attribute> {
+    service.attributes.iter().chain(service.methods.iter().flat_map(|method| method.attributes.iter()))
+}
+
+fn undeclared_attributes<'a>(declared: &[String], attributes: impl Iterator<Item=&'a Attribute>) -> Vec<&'a str> {
+    let mut undeclared = Vec::new();
+    for (name, _) in attributes {
+        let name = name.as_str();
+        if !BUILTIN_ATTRIBUTES.contains(&name) && !declared.iter().any(|declared| declared == name) && !undeclared.contains(&name) {
+            undeclared.push(name);
+        }
+    }
+
+    undeclared
+}
+
+///Returns attributes used within `items`, that are neither declared nor built-in.
+///
+///Each attribute is returned once, in order of first use.
+pub fn check_attributes<'a>(declared: &[String], items: &'a [Item]) -> Vec<&'a str> {
+    let attributes = items.iter().flat_map(|item| -> Box<dyn Iterator<Item=&Attribute>> {
+        match item {
+            Item::Service(service) => Box::new(service_attributes(service)),
+            Item::Table(table) => Box::new(table.attributes.iter().chain(fields_attributes(&table.fields))),
+            Item::Struct(structure) => Box::new(structure.attributes.iter().chain(fields_attributes(&structure.fields))),
+            Item::Enum(definition) => Box::new(definition.attributes.iter()),
+            Item::Union(definition) => Box::new(definition.attributes.iter()),
+            Item::Raw(_) => Box::new(core::iter::empty()),
+        }
+    });
+
+    undeclared_attributes(declared, attributes)
+}
+
+///Parser of all schema declarations
+///
+///Unlike [ParserIter](crate::ParserIter), which extracts services only, produces every declaration.
+pub struct SchemaParser<T> {
+    parser: ParserIter<T>,
+}
+
+impl<'a> SchemaParser<core::str::Lines<'a>> {
+    #[inline]
+    ///Creates new parser over lines of `text`.
+    ///
+    ///Refer to [ParserIter::from_str](crate::ParserIter::from_str) for details.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(text: &'a str) -> Self {
+        Self::new(text.lines())
+    }
+}
+
+impl<I: AsRef<str>, T: Iterator<Item=I>> SchemaParser<T> {
+    ///Creates new parser from iterator over lines.
+    pub fn new(lines: T) -> Self {
+        let mut parser = ParserIter::new(lines);
+        parser.is_schema = true;
+        Self {
+            parser
+        }
+    }
+
+    #[inline]
+    ///Enables strict parsing.
+    ///
+    ///Refer to [ParserIter::strict](crate::ParserIter::strict) for details.
+    pub fn strict(mut self) -> Self {
+        self.parser.is_strict = true;
+        self
+    }
+
+    #[inline]
+    ///Enables check that service names are unique.
+    ///
+    ///Refer to [ParserIter::unique_services](crate::ParserIter::unique_services) for details.
+    pub fn unique_services(mut self) -> Self {
+        self.parser = self.parser.unique_services();
+        self
+    }
+
+    #[inline]
+    ///Sets limits of parser.
+    ///
+    ///Refer to [Limits](crate::Limits) for details.
+    pub fn limits(mut self, limits: crate::Limits) -> Self {
+        self.parser = self.parser.limits(limits);
+        self
+    }
+
+    #[inline]
+    ///Enables lossless parsing.
+    ///
+    ///Every declaration, that parser skips, is produced as [Item::Raw] in order with other declarations,
+    ///including `namespace`, `include` and others, that are not definitions.
+    ///Declaration spans lines till its brackets are balanced, hence unknown declaration, e.g. from newer `flatc`, is kept as whole,
+    ///and text after its closing bracket is the next declaration,
+    ///while its documentation and comments between declarations are not part of it.
+    ///
+    ///```rust
+    ///use flatbuffers_tools::schema::{Item, SchemaParser};
+    ///
+    ///let text = "namespace MyGame;\n\n/// Key\ntable Key { name: string; }\nvendor_pragma Foo {\n  bar: baz; // comment\n\n} root_type Key;\n";
+    ///let items: Vec<Item> = SchemaParser::from_str(text).lossless().collect::<Result<_, _>>().unwrap();
+    ///let raw: Vec<_> = items.iter().map(|item| match item {
+    ///    Item::Raw(declaration) => (declaration.text.as_str(), declaration.span.start.line, declaration.span.end.column),
+    ///    Item::Table(table) => (table.name.as_str(), table.span.start.line, table.span.end.column),
+    ///    _ => unreachable!(),
+    ///}).collect();
+    ///assert_eq!(raw, [
+    ///    ("namespace MyGame;", 1, 17),
+    ///    ("Key", 4, 27),
+    ///    ("vendor_pragma Foo {\n  bar: baz;\n\n}", 5, 1),
+    ///    //Declaration after closing bracket is separate one
+    ///    ("root_type Key;", 8, 16),
+    ///]);
+    ///
+    /////Raw declarations are skipped by default
+    ///assert_eq!(SchemaParser::from_str(text).count(), 1);
+    ///```
+    pub fn lossless(mut self) -> Self {
+        self.parser.is_lossless = true;
+        self
+    }
+
+    #[inline]
+    ///Sets number of lines, that precede the first line, so that locations are counted after them.
+    pub(crate) fn after_lines(mut self, lines: usize) -> Self {
+        self.parser.line_no = lines;
+        self
+    }
+
+    #[inline]
+    ///Returns paths of `include` declarations encountered so far, in order of declaration.
+    pub fn includes(&self) -> &[String] {
+        self.parser.includes()
+    }
+
+    #[inline]
+    ///Returns table name of `root_type` declaration, if encountered so far.
+    ///
+    ///Note that table itself may be defined after declaration.
+    ///
+    ///```rust
+    ///use flatbuffers_tools::schema::{Item, Schema, SchemaParser};
+    ///
+    ///let text = "root_type  Monster ; // root table\n\ntable Monster {\n  hp: short;\n}\n";
+    ///let mut parser = SchemaParser::from_str(text);
+    ///
+    /////Known before table is declared
+    ///assert_eq!(parser.root_type(), None);
+    ///let item = parser.next().unwrap().unwrap();
+    ///assert!(matches!(item, Item::Table(ref table) if table.name == "Monster"));
+    ///assert_eq!(parser.root_type(), Some("Monster"));
+    ///assert!(parser.next().is_none());
+    ///
+    ///let schema = Schema::parse(text.lines()).unwrap();
+    ///assert_eq!(schema.root_type.as_deref(), Some("Monster"));
+    ///assert_eq!(schema.tables[0].name, "Monster");
+    ///
+    /////Repeated declaration must name the same table
+    ///let text = "root_type Monster;\ntable Monster {}\nroot_type Weapon;\n";
+    ///let error = Schema::parse(text.lines()).unwrap_err();
+    ///assert_eq!(error.to_string(), "3: root_type 'Weapon' conflicts with previous declaration");
+    ///assert!(Schema::parse("root_type Monster;\nroot_type Monster;\ntable Monster {}".lines()).is_ok());
+    ///```
+    pub fn root_type(&self) -> Option<&str> {
+        self.parser.root_type.as_deref()
+    }
+
+    #[inline]
+    ///Returns names of `attribute` declarations encountered so far, in order of declaration.
+    pub fn attributes(&self) -> &[String] {
+        &self.parser.declared_attributes
+    }
+
+    #[inline]
+    ///Returns 4 character identifier of `file_identifier` declaration, if encountered so far.
+    pub fn file_identifier(&self) -> Option<&str> {
+        self.parser.file_identifier.as_deref()
+    }
+
+    #[inline]
+    ///Returns extension of `file_extension` declaration, if encountered so far.
+    pub fn file_extension(&self) -> Option<&str> {
+        self.parser.file_extension.as_deref()
+    }
+}
+
+impl<I: AsRef<str>, T: Iterator<Item=I>> Iterator for SchemaParser<T> {
+    type Item = Result<Item, ParseErrorAt>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.parser.next_declaration()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///Schema file with all of its declarations
+pub struct Schema {
+    ///Last declared namespace
+    pub namespace: Option<String>,
+    ///Paths of `include` declarations, in order of declaration.
+    pub includes: Vec<String>,
+    ///Table definitions
+    pub tables: Vec<Table>,
+    ///Struct definitions
+    pub structs: Vec<Struct>,
+    ///Enum definitions
+    pub enums: Vec<Enum>,
+    ///Union definitions
+    pub unions: Vec<Union>,
+    ///Service definitions
+    pub services: Vec<RpcService>,
+    ///Table name of `root_type` declaration
+    pub root_type: Option<String>,
+    ///Identifier of `file_identifier` declaration
+    pub file_identifier: Option<String>,
+    ///Extension of `file_extension` declaration
+    pub file_extension: Option<String>,
+    ///Names of `attribute` declarations, in order of declaration.
+    pub attributes: Vec<String>,
+}
+
+///Finds definition with specified name, which can be qualified with its namespace.
+fn find_by_name<'a, T>(definitions: &'a [T], name: &str, definition_name: impl Fn(&T) -> (&str, Option<&str>)) -> Option<&'a T> {
+    definitions.iter().find(|definition| {
+        let (definition_name, namespace) = definition_name(definition);
+        if definition_name == name {
+            return true;
+        }
+
+        match (namespace, name.strip_suffix(definition_name)) {
+            (Some(namespace), Some(prefix)) => prefix.strip_suffix('.') == Some(namespace),
+            _ => false,
+        }
+    })
+}
+
+///Returns name, qualified by its namespace.
+fn qualified_name(name: &str, namespace: Option<&str>) -> String {
+    match namespace {
+        Some(namespace) => format!("{namespace}.{name}"),
+        None => name.to_owned(),
+    }
+}
+
+///Returns names, that type `name` may refer to within namespace `current_ns`, from innermost to outermost namespace.
+fn type_candidates(name: &str, current_ns: Option<&str>) -> Vec<String> {
+    let mut candidates = Vec::new();
+    let mut namespace = current_ns.filter(|namespace| !namespace.is_empty());
+    while let Some(current) = namespace {
+        candidates.push(format!("{current}.{name}"));
+        namespace = current.rfind('.').map(|idx| &current[..idx]);
+    }
+    candidates.push(name.to_owned());
+    candidates
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///Type definition, referred by name within schema
+///
+///Type as written is [crate::TypeRef], which is resolved to definition by [SchemaIndex::resolve_type].
+pub enum TypeRef<'a> {
+    ///table definition
+    Table(&'a Table),
+    ///struct definition
+    Struct(&'a Struct),
+    ///enum definition
+    Enum(&'a Enum),
+    ///union definition
+    Union(&'a Union),
+}
+
+impl<'a> TypeRef<'a> {
+    #[inline]
+    ///Returns name of the type, as declared.
+    pub fn name(&self) -> &'a str {
+        match self {
+            Self::Table(table) => &table.name,
+            Self::Struct(structure) => &structure.name,
+            Self::Enum(definition) => &definition.name,
+            Self::Union(definition) => &definition.name,
+        }
+    }
+
+    #[inline]
+    ///Returns namespace of the type.
+    pub fn namespace(&self) -> Option<&'a str> {
+        match self {
+            Self::Table(table) => table.namespace.as_deref(),
+            Self::Struct(structure) => structure.namespace.as_deref(),
+            Self::Enum(definition) => definition.namespace.as_deref(),
+            Self::Union(definition) => definition.namespace.as_deref(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+///Index of definitions of the same kind
+struct Names<'a> {
+    //Names qualified by namespace
+    qualified: HashMap<String, usize>,
+    //Unqualified names, pointing to the first definition
+    unqualified: HashMap<&'a str, usize>,
+}
+
+impl<'a> Names<'a> {
+    fn new<T>(definitions: &'a [T], definition_name: impl Fn(&'a T) -> (&'a str, Option<&'a str>)) -> Self {
+        let mut names = Self::default();
+        for (idx, definition) in definitions.iter().enumerate() {
+            let (name, namespace) = definition_name(definition);
+            names.qualified.entry(qualified_name(name, namespace)).or_insert(idx);
+            names.unqualified.entry(name).or_insert(idx);
+        }
+        names
+    }
+
+    #[inline]
+    fn get(&self, name: &str) -> Option<usize> {
+        match name.contains('.') {
+            true => self.qualified.get(name).copied(),
+            false => self.unqualified.get(name).copied(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+///Index of schema definitions by name, built once to avoid scanning definitions on every lookup.
+///
+///Lookups are the same as [Schema] methods of the same name.
+///
+///```rust
+///use flatbuffers_tools::schema::{Schema, TypeRef};
+///
+///let schema: Schema = "namespace Game;\ntable Item {}\nnamespace Game.Inventory;\ntable Item {}\nstruct Slot { id: int; }".parse().unwrap();
+///let index = schema.index();
+///match index.resolve_type("Item", Some("Game.Inventory")) {
+///    Some(TypeRef::Table(table)) => assert_eq!(table.namespace.as_deref(), Some("Game.Inventory")),
+///    _ => panic!("Item is not resolved"),
+///}
+///assert_eq!(index.resolve_type("Slot", Some("Game")), None);
+///assert!(index.resolve_type("Inventory.Slot", Some("Game")).is_some());
+///```
+pub struct SchemaIndex<'a> {
+    schema: &'a Schema,
+    tables: Names<'a>,
+    structs: Names<'a>,
+    enums: Names<'a>,
+    unions: Names<'a>,
+    services: Names<'a>,
+}
+
+impl<'a> SchemaIndex<'a> {
+    ///Creates index of all definitions within `schema`.
+    pub fn new(schema: &'a Schema) -> Self {
+        Self {
+            schema,
+            tables: Names::new(&schema.tables, |table| (&table.name, table.namespace.as_deref())),
+            structs: Names::new(&schema.structs, |structure| (&structure.name, structure.namespace.as_deref())),
+            enums: Names::new(&schema.enums, |definition| (&definition.name, definition.namespace.as_deref())),
+            unions: Names::new(&schema.unions, |definition| (&definition.name, definition.namespace.as_deref())),
+            services: Names::new(&schema.services, |service| (&service.name, service.namespace.as_deref())),
+        }
+    }
+
+    #[inline]
+    ///Finds service by name, optionally qualified with namespace.
+    pub fn service(&self, name: &str) -> Option<&'a RpcService> {
+        self.services.get(name).map(|idx| &self.schema.services[idx])
+    }
+
+    #[inline]
+    ///Finds table by name, optionally qualified with namespace.
+    pub fn table(&self, name: &str) -> Option<&'a Table> {
+        self.tables.get(name).map(|idx| &self.schema.tables[idx])
+    }
+
+    #[inline]
+    ///Finds struct by name, optionally qualified with namespace.
+    pub fn structure(&self, name: &str) -> Option<&'a Struct> {
+        self.structs.get(name).map(|idx| &self.schema.structs[idx])
+    }
+
+    #[inline]
+    ///Finds enum by name, optionally qualified with namespace.
+    pub fn enumeration(&self, name: &str) -> Option<&'a Enum> {
+        self.enums.get(name).map(|idx| &self.schema.enums[idx])
+    }
+
+    #[inline]
+    ///Finds union by name, optionally qualified with namespace.
+    pub fn union(&self, name: &str) -> Option<&'a Union> {
+        self.unions.get(name).map(|idx| &self.schema.unions[idx])
+    }
+
+    ///Resolves type `name`, as written within namespace `current_ns`, to its definition.
+    ///
+    ///Same as flatc, `name` is looked up within `current_ns` first, and then within each of its parent namespaces,
+    ///up to the root namespace, e.g. `Item` within `Game.Inventory` is looked up as `Game.Inventory.Item`, `Game.Item` and `Item`.
+    ///
+    ///Returns `None` if there is no such type, including built-in scalar types.
+    pub fn resolve_type(&self, name: &str, current_ns: Option<&str>) -> Option<TypeRef<'a>> {
+        let schema = self.schema;
+        type_candidates(name, current_ns).iter().find_map(|candidate| {
+            if let Some(idx) = self.tables.qualified.get(candidate) {
+                Some(TypeRef::Table(&schema.tables[*idx]))
+            } else if let Some(idx) = self.structs.qualified.get(candidate) {
+                Some(TypeRef::Struct(&schema.structs[*idx]))
+            } else if let Some(idx) = self.enums.qualified.get(candidate) {
+                Some(TypeRef::Enum(&schema.enums[*idx]))
+            } else {
+                self.unions.qualified.get(candidate).map(|idx| TypeRef::Union(&schema.unions[*idx]))
+            }
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+///Usage of type within rpc method
+pub enum TypeUsage {
+    ///Argument with specified index, starting from 0
+    Argument(usize),
+    ///Return type
+    Return,
+}
+
+impl core::fmt::Display for TypeUsage {
+    #[inline]
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Argument(idx) => fmt.write_fmt(format_args!("argument {}", idx + 1)),
+            Self::Return => fmt.write_str("return type"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+///Kind of rpc type problem
+pub enum ValidationErrorKind {
+    ///Type is not defined
+    UnknownType,
+    ///Type is not visible from namespace of the service, but it is defined within other namespace with specified qualified name
+    OtherNamespace(String),
+    ///Type is struct, while rpc requires table
+    Struct,
+    ///Type is enum, while rpc requires table
+    Enum,
+    ///Type is union, while rpc requires table
+    Union,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+///Invalid type of rpc method
+pub struct ValidationError {
+    ///Service name
+    pub service: String,
+    ///Method name
+    pub method: String,
+    ///Type as written
+    pub ty: String,
+    ///Where type is used within method
+    pub usage: TypeUsage,
+    ///Location of method definition
+    pub span: Span,
+    ///Kind of problem
+    pub kind: ValidationErrorKind,
+}
+
+impl core::fmt::Display for ValidationError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let Self { service, method, ty, usage, span, kind } = self;
+        fmt.write_fmt(format_args!("{}:{}: {usage} '{ty}' of method '{method}' of service '{service}' ", span.start.line, span.start.column))?;
+        match kind {
+            ValidationErrorKind::UnknownType => fmt.write_str("is not defined"),
+            ValidationErrorKind::OtherNamespace(name) => fmt.write_fmt(format_args!("is not defined, but there is '{name}' within other namespace")),
+            ValidationErrorKind::Struct => fmt.write_str("is struct, but rpc requires table"),
+            ValidationErrorKind::Enum => fmt.write_str("is enum, but rpc requires table"),
+            ValidationErrorKind::Union => fmt.write_str("is union, but rpc requires table"),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl Schema {
+    #[inline]
+    ///Creates index of definitions for repeated lookups.
+    pub fn index(&self) -> SchemaIndex<'_> {
+        SchemaIndex::new(self)
+    }
+
+    #[inline]
+    ///Resolves type `name`, as written within namespace `current_ns`, to its definition.
+    ///
+    ///Refer to [SchemaIndex::resolve_type], which should be used for repeated lookups.
+    pub fn resolve_type(&self, name: &str, current_ns: Option<&str>) -> Option<TypeRef<'_>> {
+        self.index().resolve_type(name, current_ns)
+    }
+
+    #[inline]
+    ///Assigns ids to methods of every service, that are unique across services of the schema.
+    ///
+    ///Refer to [assign_global_ids](crate::assign_global_ids)
+    pub fn assign_global_ids(&self, config: &crate::GenConfig) -> Result<Vec<crate::registry::Assignments>, crate::MethodIdError> {
+        crate::assign_global_ids(&self.services, config)
+    }
+
+    #[inline]
+    ///Returns stable fingerprint of all services, which does not depend on their order.
+    ///
+    ///Other declarations are not part of fingerprint. Refer to [RpcService::fingerprint]
+    ///
+    ///```rust
+    ///use flatbuffers_tools::schema::Schema;
+    ///
+    ///let schema: Schema = "table Key {}\nrpc_service Store { Get(Key):Key; }\nrpc_service Log { Append(Key):Key; }".parse().unwrap();
+    ///let reordered: Schema = "rpc_service Log { Append(Key):Key; }\n\nrpc_service Store {\n  Get(Key): Key;\n}".parse().unwrap();
+    ///assert_eq!(schema.fingerprint(), reordered.fingerprint());
+    ///assert_ne!(schema.fingerprint(), schema.services[0].fingerprint());
+    ///```
+    pub fn fingerprint(&self) -> u64 {
+        crate::gen::fingerprint(self.services.iter())
+    }
+
+    #[inline]
+    ///Returns machine readable JSON of every service, refer to [json](crate::json) for its format.
+    ///
+    ///Fails if ids of methods cannot be assigned.
+    pub fn to_json(&self) -> Result<String, crate::MethodIdError> {
+        crate::json::to_json(self.services.iter())
+    }
+
+    #[inline]
+    ///Gets formatter to print schema declarations.
+    pub fn as_fbs(&self) -> crate::fbs::FbsSchema<'_> {
+        crate::fbs::FbsSchema {
+            schema: self,
+            style: crate::fbs::FbsStyle::new(),
+        }
+    }
+
+    #[inline]
+    ///Gets formatter to print Graphviz diagram of services and types.
+    ///
+    ///Refer to [crate::graph] for details.
+    pub fn as_dot(&self) -> crate::graph::SchemaGraph<'_> {
+        crate::graph::SchemaGraph {
+            schema: self,
+            format: crate::graph::GraphFormat::Dot,
+            service: None,
+        }
+    }
+
+    #[inline]
+    ///Gets formatter to print Mermaid diagram of services and types.
+    ///
+    ///Refer to [crate::graph] for details.
+    pub fn as_mermaid(&self) -> crate::graph::SchemaGraph<'_> {
+        self.as_dot().format(crate::graph::GraphFormat::Mermaid)
+    }
+
+    #[inline]
+    ///Prints schema declarations with default style.
+    ///
+    ///Refer to [crate::fbs] for details.
+    pub fn to_fbs(&self) -> String {
+        self.as_fbs().to_string()
+    }
+
+    #[inline]
+    ///Parses schema from iterator over lines, failing on the first error.
+    pub fn parse<I: AsRef<str>, T: Iterator<Item=I>>(lines: T) -> Result<Self, ParseErrorAt> {
+        Self::parse_with(SchemaParser::new(lines))
+    }
+
+    ///Parses schema with all declarations of `parser`, failing on the first error.
+    pub(crate) fn parse_with<I: AsRef<str>, T: Iterator<Item=I>>(mut parser: SchemaParser<T>) -> Result<Self, ParseErrorAt> {
+        let mut schema = Self::default();
+
+        for item in parser.by_ref() {
+            match item? {
+                Item::Service(service) => schema.services.push(service),
+                Item::Table(table) => schema.tables.push(table),
+                Item::Struct(structure) => schema.structs.push(structure),
+                Item::Enum(definition) => schema.enums.push(definition),
+                Item::Union(definition) => schema.unions.push(definition),
+                //Produced only by lossless parser
+                Item::Raw(_) => (),
+            }
+        }
+
+        let parser = parser.parser;
+        schema.namespace = parser.namespace;
+        schema.includes = parser.includes;
+        schema.root_type = parser.root_type;
+        schema.file_identifier = parser.file_identifier;
+        schema.file_extension = parser.file_extension;
+        schema.attributes = parser.declared_attributes;
+        Ok(schema)
+    }
+
+    #[inline]
+    ///Finds service by name, optionally qualified with namespace.
+    ///
+    ///Definitions are scanned on every call, use [Schema::index] for repeated lookups.
+    pub fn service(&self, name: &str) -> Option<&RpcService> {
+        find_by_name(&self.services, name, |service| (&service.name, service.namespace.as_deref()))
+    }
+
+    #[inline]
+    ///Finds table by name, optionally qualified with namespace.
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        find_by_name(&self.tables, name, |table| (&table.name, table.namespace.as_deref()))
+    }
+
+    #[inline]
+    ///Finds struct by name, optionally qualified with namespace.
+    pub fn structure(&self, name: &str) -> Option<&Struct> {
+        find_by_name(&self.structs, name, |structure| (&structure.name, structure.namespace.as_deref()))
+    }
+
+    #[inline]
+    ///Finds enum by name, optionally qualified with namespace.
+    pub fn enumeration(&self, name: &str) -> Option<&Enum> {
+        find_by_name(&self.enums, name, |definition| (&definition.name, definition.namespace.as_deref()))
+    }
+
+    #[inline]
+    ///Finds union by name, optionally qualified with namespace.
+    pub fn union(&self, name: &str) -> Option<&Union> {
+        find_by_name(&self.unions, name, |definition| (&definition.name, definition.namespace.as_deref()))
+    }
+
+    ///Checks that arguments and return types of every rpc method are tables, as flatc requires.
+    ///
+    ///Types are resolved within namespace of the service, refer to [SchemaIndex::resolve_type].
+    ///Types of included files are not known, hence schema must contain them, e.g. by merging included schemas.
+    ///
+    ///Returns every problem found, in order of declarations.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let index = self.index();
+        let mut errors = Vec::new();
+        for service in self.services.iter() {
+            for method in service.methods.iter() {
+                let arguments = method.arguments.iter().enumerate().map(|(idx, argument)| (TypeUsage::Argument(idx), &argument.ty));
+                for (usage, ty) in arguments.chain(core::iter::once((TypeUsage::Return, &method.return_type))) {
+                    let kind = match index.resolve_type(ty, service.namespace.as_deref()) {
+                        Some(TypeRef::Table(_)) => continue,
+                        Some(TypeRef::Struct(_)) => ValidationErrorKind::Struct,
+                        Some(TypeRef::Enum(_)) => ValidationErrorKind::Enum,
+                        Some(TypeRef::Union(_)) => ValidationErrorKind::Union,
+                        None => {
+                            match index.table(ty.name()) {
+                                Some(table) => ValidationErrorKind::OtherNamespace(qualified_name(&table.name, table.namespace.as_deref())),
+                                None => ValidationErrorKind::UnknownType,
+                            }
+                        },
+                    };
+                    errors.push(ValidationError {
+                        service: service.name.clone(),
+                        method: method.name.clone(),
+                        ty: ty.to_string(),
+                        usage,
+                        span: method.span,
+                        kind,
+                    });
+                }
+            }
+        }
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors),
+        }
+    }
+
+    ///Returns attributes used within schema, that are neither declared nor built-in.
+    ///
+    ///Each attribute is returned once.
+    pub fn check_attributes(&self) -> Vec<&str> {
+        let attributes = self.services.iter().flat_map(service_attributes)
+                                            .chain(self.tables.iter().flat_map(|table| table.attributes.iter().chain(fields_attributes(&table.fields))))
+                                            .chain(self.structs.iter().flat_map(|structure| structure.attributes.iter().chain(fields_attributes(&structure.fields))))
+                                            .chain(self.enums.iter().flat_map(|definition| definition.attributes.iter()))
+                                            .chain(self.unions.iter().flat_map(|definition| definition.attributes.iter()));
+        undeclared_attributes(&self.attributes, attributes)
+    }
+}
+
+impl core::str::FromStr for Schema {
+    type Err = ParseErrorAt;
+
+    #[inline]
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::parse(text.lines())
+    }
+}
@@ -0,0 +1,137 @@
+//!Push based parsing of services
+
+use crate::{strip_comments, ParseErrorAt, ParserIter, RpcService};
+
+use core::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+///Lines, that are ready to be parsed
+struct Queue(Rc<RefCell<VecDeque<String>>>);
+
+impl Iterator for Queue {
+    type Item = String;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.borrow_mut().pop_front()
+    }
+}
+
+#[derive(Default)]
+///State of declarations within complete lines
+struct Scan {
+    in_comment: bool,
+    //Number of open brackets
+    depth: usize,
+    //Whether top level declaration is started, but not finished
+    is_pending: bool,
+}
+
+impl Scan {
+    fn feed(&mut self, line: &str) {
+        let line = strip_comments(line, &mut self.in_comment);
+        let mut is_quoted = false;
+        let mut is_escaped = false;
+        for ch in line.chars() {
+            match ch {
+                _ if is_escaped => is_escaped = false,
+                '\\' if is_quoted => is_escaped = true,
+                '"' => is_quoted = !is_quoted,
+                _ if is_quoted => (),
+                '{' => self.depth += 1,
+                '}' => {
+                    self.depth = self.depth.saturating_sub(1);
+                    if self.depth == 0 {
+                        self.is_pending = false;
+                    }
+                },
+                ';' if self.depth == 0 => self.is_pending = false,
+                ch if self.depth == 0 && !ch.is_whitespace() => self.is_pending = true,
+                _ => (),
+            }
+        }
+    }
+
+    #[inline]
+    fn is_complete(&self) -> bool {
+        !self.in_comment && self.depth == 0 && !self.is_pending
+    }
+}
+
+///Push based rpc_service parser
+///
+///Accepts input in chunks of arbitrary size, which are not required to end on line boundary.
+///Lines are handed over to [ParserIter] once all declarations within them are complete,
+///which makes results identical to parsing whole input at once.
+pub struct StreamingParser {
+    parser: ParserIter<Queue>,
+    queue: Rc<RefCell<VecDeque<String>>>,
+    //Complete lines, that end within unfinished declaration
+    held: Vec<String>,
+    //Last line without line ending yet
+    partial: String,
+    scan: Scan,
+}
+
+impl StreamingParser {
+    ///Creates new parser
+    pub fn new() -> Self {
+        let queue = Rc::new(RefCell::new(VecDeque::new()));
+        Self {
+            parser: ParserIter::new(Queue(queue.clone())),
+            queue,
+            held: Vec::new(),
+            partial: String::new(),
+            scan: Scan::default(),
+        }
+    }
+
+    #[inline]
+    ///Enables strict parsing.
+    ///
+    ///Refer to [ParserIter::strict] for details.
+    pub fn strict(mut self) -> Self {
+        self.parser = self.parser.strict();
+        self
+    }
+
+    ///Feeds next chunk of input, returning services, that are complete within it.
+    pub fn feed(&mut self, mut chunk: &str) -> Vec<Result<RpcService, ParseErrorAt>> {
+        while let Some(idx) = chunk.find('\n') {
+            let mut line = core::mem::take(&mut self.partial);
+            line.push_str(&chunk[..idx]);
+            chunk = &chunk[idx + 1..];
+
+            self.scan.feed(&line);
+            self.held.push(line);
+            if self.scan.is_complete() {
+                self.queue.borrow_mut().extend(self.held.drain(..));
+            }
+        }
+        self.partial.push_str(chunk);
+
+        self.parser.by_ref().collect()
+    }
+
+    ///Finishes input, returning services within its remaining part.
+    ///
+    ///Unterminated declaration at the end of input is reported as error.
+    pub fn finish(mut self) -> Vec<Result<RpcService, ParseErrorAt>> {
+        let mut queue = self.queue.borrow_mut();
+        queue.extend(self.held.drain(..));
+        if !self.partial.is_empty() {
+            queue.push_back(core::mem::take(&mut self.partial));
+        }
+        drop(queue);
+
+        self.parser.by_ref().collect()
+    }
+}
+
+impl Default for StreamingParser {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+///Runs `fbs-rpc-gen` with `args`, writing `stdin` to its input.
+fn fbs_rpc_gen(args: &[&str], stdin: &str) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_fbs-rpc-gen"));
+    command.args(args).stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+    let mut child = command.spawn().expect("to spawn fbs-rpc-gen");
+    child.stdin.take().unwrap().write_all(stdin.as_bytes()).expect("to write stdin");
+    child.wait_with_output().expect("to wait fbs-rpc-gen")
+}
+
+#[test]
+fn should_generate_code_of_schema() {
+    let dir = common::OutDir::new("cli-generate");
+    let schema = dir.write("rpc.fbs", "rpc_service Store {\n  Get(Key): Value;\n}\n");
+    let schema = schema.to_str().unwrap();
+
+    let output = fbs_rpc_gen(&[schema, "--prefix", "RPC_"], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "#[rustfmt::skip]\n\npub const RPC_GET: &str = \"Get\";");
+    assert!(output.stderr.is_empty());
+
+    //Stdin
+    let output = fbs_rpc_gen(&["-", "--prefix", "RPC_"], "rpc_service Store {\n  Get(Key): Value;\n}\n");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "#[rustfmt::skip]\n\npub const RPC_GET: &str = \"Get\";");
+
+    //Output file is checked
+    let out = dir.path().join("rpc.rs");
+    let out = out.to_str().unwrap();
+    let output = fbs_rpc_gen(&[schema, "--trait", "-o", out], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty());
+    assert!(std::fs::read_to_string(out).unwrap().contains("pub trait Store"));
+    assert_eq!(fbs_rpc_gen(&[schema, "--trait", "-o", out, "--check"], "").status.code(), Some(0));
+    assert_eq!(fbs_rpc_gen(&[schema, "-o", out, "--check"], "").status.code(), Some(1));
+}
+
+#[test]
+fn should_exit_with_error_code() {
+    let dir = common::OutDir::new("cli-error");
+    let schema = dir.write("broken.fbs", "rpc_service Store {\n  Get(Key);\n}\n");
+    let schema = schema.to_str().unwrap();
+
+    let output = fbs_rpc_gen(&[schema], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert_eq!(String::from_utf8(output.stderr).unwrap(), format!("{}:2: in service 'Store', method 'Get': cannot determine return type of 'Get(Key)'\n", schema));
+
+    let missing = dir.path().join("missing.fbs");
+    assert_eq!(fbs_rpc_gen(&[missing.to_str().unwrap()], "").status.code(), Some(1));
+
+    //Invalid usage
+    let output = fbs_rpc_gen(&[schema, "--bogus"], "");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("Unknown option '--bogus'\n\nUsage: fbs-rpc-gen"), "{}", stderr);
+}
@@ -0,0 +1,111 @@
+//!Compilation of generated code by `rustc`, which is expected to be found by `RUSTC` variable or on `PATH`.
+
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+///Temporary directory of compiled code, removed on drop.
+pub struct OutDir {
+    path: PathBuf,
+}
+
+impl OutDir {
+    ///Creates directory, unique for the test `name`.
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("flatbuffers-tools-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&path).expect("to create output directory");
+        Self {
+            path,
+        }
+    }
+
+    #[inline]
+    ///Returns path of the directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    ///Writes `code` to the file `name`, returning its path.
+    pub fn write(&self, name: &str, code: &str) -> PathBuf {
+        let path = self.path.join(name);
+        std::fs::write(&path, code).expect("to write code");
+        path
+    }
+}
+
+impl Drop for OutDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+///Creates `rustc` command of `edition`, writing output to `out`.
+pub fn rustc(out: &OutDir, edition: &str) -> Command {
+    let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let mut command = Command::new(rustc);
+    command.arg(format!("--edition={}", edition)).arg("--out-dir").arg(out.path());
+    command
+}
+
+///Runs `command`, returning its stderr if it fails.
+pub fn run(command: &mut Command) -> Result<(), String> {
+    let output = command.output().unwrap_or_else(|error| panic!("to run {:?}: {}", command.get_program(), error));
+    match output.status.success() {
+        true => Ok(()),
+        false => Err(format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr))),
+    }
+}
+
+///Compiles `code`, with extra `args`, as library, returning errors of compiler on failure.
+pub fn check_generated(name: &str, code: &str, args: &[&str]) -> Result<(), String> {
+    let out = OutDir::new(name);
+    let path = out.write("lib.rs", code);
+    run(rustc(&out, "2021").arg("--crate-type=lib").arg("--emit=metadata").args(args).arg(path))
+}
+
+///Compiles `code` as library, panicking with errors of compiler on failure.
+pub fn compile_generated(name: &str, code: &str) {
+    if let Err(error) = check_generated(name, code, &[]) {
+        panic!("Generated code of '{}' does not compile:\n{}\n{}", name, error, code);
+    }
+}
+
+///Compiles program of generated `code`, followed by `harness`, and runs it, panicking on failure of either.
+///
+///Harness refers to items of generated code, and has `fn main()`, that asserts their behavior.
+pub fn run_generated(name: &str, code: &str, harness: &str, args: &[&str]) {
+    let out = OutDir::new(name);
+    let path = out.write("main.rs", &format!("{}\n{}", code, harness));
+    if let Err(error) = run(rustc(&out, "2021").arg("--crate-type=bin").arg("--crate-name=main").args(args).arg(path)) {
+        panic!("Generated code of '{}' does not compile:\n{}\n{}", name, error, code);
+    }
+    if let Err(error) = run(&mut Command::new(out.path().join("main"))) {
+        panic!("Program of '{}' failed:\n{}", name, error);
+    }
+}
+
+///Compiles library of this crate with `features` into `out`, returning path of `rlib` to be passed as `--extern`.
+///
+///Enabled features must not require optional dependencies.
+pub fn compile_library(out: &OutDir, features: &[&str]) -> PathBuf {
+    let mut command = rustc(out, "2018");
+    command.arg("--crate-type=rlib").arg("--crate-name=flatbuffers_tools").arg("--cap-lints=allow");
+    for feature in features {
+        command.arg("--cfg").arg(format!("feature=\"{}\"", feature));
+    }
+    command.arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("src").join("lib.rs"));
+    if let Err(error) = run(&mut command) {
+        panic!("Library does not compile:\n{}", error);
+    }
+    out.path().join("libflatbuffers_tools.rlib")
+}
+
+///Compiles procedural macro crate `name` of `code` into `out`, returning path of library to be passed as `--extern`.
+pub fn compile_proc_macro(out: &OutDir, name: &str, code: &str) -> PathBuf {
+    let path = out.write(&format!("{}.rs", name), code);
+    if let Err(error) = run(rustc(out, "2021").arg("--crate-type=proc-macro").arg(format!("--crate-name={}", name)).arg(path)) {
+        panic!("Procedural macro '{}' does not compile:\n{}", name, error);
+    }
+    out.path().join(format!("{}{}{}", std::env::consts::DLL_PREFIX, name, std::env::consts::DLL_SUFFIX))
+}
@@ -0,0 +1,156 @@
+//!Output of `render_services`, compiled along with programs of `tests/generated`, that use it.
+
+mod common;
+
+use flatbuffers_tools::{parse_services, render_services, AsyncRouter, Asyncness, GenConfig, GenItem, IdScope, ItemCategory, Receiver, ReturnStyle, TypeName};
+
+#[inline]
+fn crate_path(name: &TypeName) -> String {
+    format!("crate::{}", name.name())
+}
+
+#[inline]
+fn super_path(name: &TypeName) -> String {
+    format!("super::{}", name.name())
+}
+
+///Types of `interface` module, referred by default type paths.
+const INTERFACE: &str = "mod interface {\n    pub struct Key;\n    pub struct Value;\n}\n";
+
+///Stand-in of `async_trait` crate, leaving items unchanged.
+const ASYNC_TRAIT: &str = "extern crate proc_macro;\nuse proc_macro::TokenStream;\n#[proc_macro_attribute]\npub fn async_trait(_: TokenStream, item: TokenStream) -> TokenStream {\n    item\n}\n";
+
+const FEED: &str = "rpc_service Feed {\n  Watch(Key):Item (streaming: \"server\");\n  Upload(Item):Key (streaming: \"client\");\n  Chat(Item):Item (streaming: \"bidi\");\n}";
+
+#[test]
+fn should_dispatch_to_shared_handler() {
+    let config = GenConfig::new().skip_docs(true).type_path(crate_path).receiver(Receiver::Ref).send_sync(true).item(GenItem::Dispatcher).item(GenItem::Mock);
+    let code = render_services(&parse_services("rpc_service Storage { Put(Request):Response; }").unwrap(), &config).unwrap();
+    common::run_generated("receiver-ref", &code, include_str!("generated/receiver_ref.rs"), &[]);
+}
+
+#[test]
+fn should_dispatch_to_arc_handler() {
+    let config = GenConfig::new().skip_docs(true).type_path(crate_path).receiver(Receiver::Arc).item(GenItem::Dispatcher).item(GenItem::Instrumentation);
+    let code = render_services(&parse_services("rpc_service Cache { Get(Request):Response; }").unwrap(), &config).unwrap();
+    common::run_generated("receiver-arc", &code, include_str!("generated/receiver_arc.rs"), &[]);
+}
+
+#[test]
+fn should_route_async_streams() {
+    let config = GenConfig::new().skip_docs(true).type_path(super_path).router(AsyncRouter::new().stream("super::Stream"));
+    let code = render_services(&parse_services(FEED).unwrap(), &config).unwrap();
+    common::run_generated("async-stream", &code, include_str!("generated/async_stream.rs"), &[]);
+}
+
+#[test]
+fn should_dispatch_streams() {
+    let config = GenConfig::new().skip_docs(true).type_path(super_path);
+    let code = render_services(&parse_services(FEED).unwrap(), &config).unwrap();
+    common::run_generated("stream", &code, include_str!("generated/stream.rs"), &[]);
+}
+
+#[test]
+fn should_route_services_by_global_ids() {
+    let services = parse_services("rpc_service Storage { Put(Request):Response; Get(Request):Response; }\nrpc_service Monitor { Put(Request):Pong; }").unwrap();
+    let config = GenConfig::new().skip_docs(true).type_path(crate_path).id_scope(IdScope::Schema).service_router(true);
+    let code = render_services(&services, &config).unwrap();
+    common::run_generated("router", &code, include_str!("generated/router.rs"), &[]);
+}
+
+#[test]
+fn should_answer_by_mock() {
+    let config = GenConfig::new().skip_docs(true).type_path(super_path).item(GenItem::Mock);
+    let code = render_services(&parse_services("rpc_service Storage { put(Request):Response; ping():Pong; }").unwrap(), &config).unwrap();
+    common::run_generated("mock", &code, include_str!("generated/mock.rs"), &[]);
+}
+
+#[test]
+fn should_report_to_observer() {
+    let config = GenConfig::new().skip_docs(true).return_style(ReturnStyle::Result).type_path(super_path).item(GenItem::Instrumentation);
+    let code = render_services(&parse_services("rpc_service Storage { put(Request):Response; ping():Pong; }").unwrap(), &config).unwrap();
+    common::run_generated("instrument", &code, include_str!("generated/instrument.rs"), &[]);
+}
+
+#[test]
+fn should_call_dispatcher_by_client() {
+    let config = GenConfig::new().skip_docs(true).type_path(super_path).client_codec(true).item(GenItem::Dispatcher).item(GenItem::Client);
+    let code = render_services(&parse_services("rpc_service Storage { put(Request):Response; ping():Pong; }").unwrap(), &config).unwrap();
+    assert!(!code.contains("flatbuffers::"));
+    common::run_generated("client", &code, include_str!("generated/client.rs"), &[]);
+}
+
+#[test]
+fn should_await_native_async_handler() {
+    let config = GenConfig::new().skip_docs(true).type_path(super_path).asyncness(Asyncness::Native).item(GenItem::Dispatcher).item(GenItem::Mock).item(GenItem::Instrumentation);
+    let code = render_services(&parse_services("rpc_service Storage { put(Request):Response; ping():Pong; }").unwrap(), &config).unwrap();
+    common::run_generated("native-async", &code, include_str!("generated/native_async.rs"), &[]);
+}
+
+#[test]
+fn should_await_async_trait_handler() {
+    let config = GenConfig::new().skip_docs(true).type_path(super_path).asyncness(Asyncness::AsyncTrait).receiver(Receiver::Ref).item(GenItem::Dispatcher).item(GenItem::Mock).item(GenItem::Instrumentation);
+    let code = render_services(&parse_services("rpc_service Storage { put(Request):Response; ping():Pong; }").unwrap(), &config).unwrap();
+    assert_eq!(code.matches("#[async_trait::async_trait]\n").count(), 3);
+
+    let macros = common::OutDir::new("async-trait-crate");
+    let library = common::compile_proc_macro(&macros, "async_trait", ASYNC_TRAIT);
+    let library = format!("async_trait={}", library.display());
+    common::run_generated("async-trait", &code, include_str!("generated/async_trait.rs"), &["--extern", &library]);
+}
+
+#[test]
+fn should_fail_assertions_of_inconsistent_items() {
+    let services = parse_services("rpc_service Storage { Put(Req):Resp; Get(Key):Resp; }").unwrap();
+    let config = GenConfig::new().item(GenItem::MethodEnum).item(GenItem::Descriptor).method_count(true).name_lookup(true).registry(true).assertions(true);
+    let code = render_services(&services, &config).unwrap();
+    common::compile_generated("assertions", &code);
+
+    let edited = code.replacen("pub const GET: u16 = 1;", "pub const GET: u16 = 2;", 1);
+    assert_ne!(edited, code);
+    assert!(common::check_generated("assertions-edited", &edited, &[]).is_err());
+}
+
+#[test]
+fn should_compile_category_attributes() {
+    let services = parse_services("rpc_service Storage {\n  ///Stores value\n  Put(Value):Key;\n  Get(Key):Value (deprecated);\n}").unwrap();
+    let config = GenConfig::new().item(GenItem::MethodEnum).item(GenItem::Descriptor).item(GenItem::Markers).method_count(true).name_lookup(true)
+        .category_attribute(ItemCategory::Constants, "#[doc(hidden)]")
+        .category_attribute(ItemCategory::Lookup, "#[doc(hidden)]")
+        .category_attribute(ItemCategory::Descriptor, "#[deprecated(note = \"use descriptor of v2\")]")
+        .category_attribute(ItemCategory::Markers, "#[cfg_attr(docsrs, doc(cfg(feature = \"unstable\")))]");
+    let code = render_services(&services, &config).unwrap();
+    common::compile_generated("category", &format!("#![allow(deprecated)]\n{}{}", INTERFACE, code));
+}
+
+#[test]
+fn should_compile_method_timeout() {
+    let services = parse_services("rpc_service Storage {\n  Put(Value):Key (timeout_ms: \"1500\", priority: \"high\");\n  Get(Key):Value (priority: \"low\");\n  Ping():Key;\n}").unwrap();
+    let config = GenConfig::new().item(GenItem::Dispatcher).item(GenItem::Descriptor).method_timeout(true);
+    let code = render_services(&services, &config).unwrap();
+    common::compile_generated("timeout", &format!("{}{}", INTERFACE, code));
+}
+
+#[test]
+fn should_call_dispatcher_by_framed_client() {
+    let config = GenConfig::new().skip_docs(true).type_path(super_path).framing(true).client_codec(true).item(GenItem::Dispatcher).item(GenItem::Client);
+    let code = render_services(&parse_services("rpc_service Storage { put(Request):Response; ping():Pong; }").unwrap(), &config).unwrap();
+
+    let framing = common::OutDir::new("framing-crate");
+    let library = common::compile_library(&framing, &["framing"]);
+    let library = format!("flatbuffers_tools={}", library.display());
+    common::run_generated("framing", &code, include_str!("generated/framing.rs"), &["--extern", &library]);
+}
+
+#[cfg(feature = "runtime")]
+#[test]
+fn should_share_runtime_items() {
+    let config = GenConfig::new().skip_docs(true).runtime(true).type_path(crate_path).item(GenItem::Dispatcher).item(GenItem::Descriptor).item(GenItem::Markers);
+    let storage = render_services(&parse_services("rpc_service Storage { Put(Request):Response; }").unwrap(), &config).unwrap();
+    let monitor = render_services(&parse_services("rpc_service Monitor { Ping():Response; }").unwrap(), &config).unwrap();
+
+    let runtime = common::OutDir::new("runtime-crate");
+    let library = common::compile_library(&runtime, &["runtime", "method-trait", "framing"]);
+    let library = format!("flatbuffers_tools={}", library.display());
+    common::run_generated("runtime", &format!("{}\n{}", storage, monitor), include_str!("generated/runtime.rs"), &["--extern", &library]);
+}
@@ -0,0 +1,85 @@
+//Appended to output of `Feed` service with streaming methods and `AsyncRouter`
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::sync::Arc;
+
+//Same as futures_core::Stream
+pub trait Stream {
+    type Item;
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>>;
+}
+
+pub struct Iter<I>(I);
+impl<I: Iterator + Unpin> Stream for Iter<I> {
+    type Item = I::Item;
+    fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<I::Item>> {
+        Poll::Ready(self.0.next())
+    }
+}
+type Boxed<T> = Pin<Box<dyn Stream<Item = T> + Send>>;
+
+#[derive(Debug, PartialEq)]
+pub struct Key(u8);
+#[derive(Debug, PartialEq)]
+pub struct Item(u8);
+
+pub struct Bytes;
+impl Codec<Key> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Key, DispatchError> {
+        payload.first().map(|byte| Key(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Key) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+impl Codec<Item> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Item, DispatchError> {
+        payload.first().map(|byte| Item(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Item) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+
+pub struct Feed;
+impl feed::FeedService for Feed {
+    fn watch(&self, request: Key) -> Boxed<Item> {
+        Box::pin(Iter((0..request.0).map(Item)))
+    }
+    fn upload(&self, requests: Boxed<Item>) -> Pin<Box<dyn Future<Output = Key> + Send + '_>> {
+        Box::pin(async move { Key(collect(requests).iter().map(|item| item.0).sum()) })
+    }
+    fn chat(&self, requests: Boxed<Item>) -> Boxed<Item> {
+        Box::pin(Iter(collect(requests).into_iter().map(|item| Item(item.0 * 2))))
+    }
+}
+
+//Streams and futures are always ready
+fn collect<T>(mut stream: Boxed<T>) -> Vec<T> {
+    let mut cx = Context::from_waker(std::task::Waker::noop());
+    let mut items = Vec::new();
+    while let Poll::Ready(Some(item)) = stream.as_mut().poll_next(&mut cx) {
+        items.push(item);
+    }
+    items
+}
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut cx = Context::from_waker(std::task::Waker::noop());
+    match core::pin::pin!(future).poll(&mut cx) {
+        Poll::Ready(output) => output,
+        Poll::Pending => unreachable!(),
+    }
+}
+
+fn main() {
+    use feed::{route_bidi, route_client, route_server, CHAT, UPLOAD, WATCH};
+
+    let handler: Arc<dyn feed::FeedService> = Arc::new(Feed);
+    assert_eq!(collect(route_server::<Bytes>(handler.clone(), WATCH, vec![3]).unwrap()), [[0], [1], [2]]);
+    //Requests end at the first payload, that cannot be decoded
+    let payloads = || -> Boxed<Vec<u8>> { Box::pin(Iter(vec![vec![1], vec![2], vec![], vec![3]].into_iter())) };
+    assert_eq!(block_on(route_client::<Bytes>(handler.clone(), UPLOAD, payloads())), Ok(vec![3]));
+    assert_eq!(collect(route_bidi::<Bytes>(handler.clone(), CHAT, payloads()).unwrap()), [[2], [4]]);
+    assert!(route_bidi::<Bytes>(handler, UPLOAD, payloads()).is_err());
+}
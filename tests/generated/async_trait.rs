@@ -0,0 +1,104 @@
+//Appended to output of `Storage` service with `Asyncness::AsyncTrait`, `Receiver::Ref`, mock and instrumentation
+//
+//`async_trait` is stand-in of the crate, leaving items as they are, as `async fn` of trait is stable.
+use std::future::Future;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request(u8);
+#[derive(Debug, PartialEq)]
+pub struct Response(u8);
+#[derive(Debug, PartialEq)]
+pub struct Pong;
+
+pub struct Bytes;
+impl Codec<Request> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Request, DispatchError> {
+        payload.first().map(|byte| Request(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Request) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+impl Codec<Response> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Response, DispatchError> {
+        payload.first().map(|byte| Response(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Response) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+impl Codec<Pong> for Bytes {
+    fn decode(_: &[u8]) -> Result<Pong, DispatchError> {
+        Ok(Pong)
+    }
+    fn encode(_: &Pong) -> Vec<u8> {
+        Vec::new()
+    }
+}
+
+//Handler awaits nothing, hence future is ready on first poll
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+    match future.as_mut().poll(&mut context) {
+        std::task::Poll::Ready(output) => output,
+        std::task::Poll::Pending => panic!("Future is not ready"),
+    }
+}
+
+//Handler is shared, as trait requires `Send + Sync`
+#[derive(Default)]
+struct Store {
+    last: AtomicU8,
+}
+
+#[async_trait::async_trait]
+impl storage::StorageService for Store {
+    async fn put(&self, request: Request) -> Response {
+        let request = core::future::ready(request).await;
+        Response(self.last.swap(request.0, Ordering::SeqCst))
+    }
+    async fn ping(&self) -> Pong {
+        Pong
+    }
+}
+
+#[derive(Default)]
+struct Counter {
+    ended: Mutex<Vec<&'static str>>,
+}
+impl RpcObserver for Counter {
+    fn on_call_start(&self, _: &'static str) {
+    }
+    fn on_call_end(&self, method: &'static str, _: std::time::Duration, _: bool) {
+        self.ended.lock().unwrap().push(method);
+    }
+}
+
+fn assert_send_sync<T: Send + Sync>(_: &T) {
+}
+
+fn main() {
+    use storage::{dispatch, InstrumentedStorageService, MockStorageService, StorageCall, PING, PUT};
+
+    let store = Store::default();
+    assert_eq!(block_on(dispatch::<_, Bytes>(&store, PUT, &[1])), Ok(vec![0]));
+    assert_eq!(block_on(dispatch::<_, Bytes>(&store, PUT, &[2])), Ok(vec![1]));
+    assert_eq!(block_on(dispatch::<_, Bytes>(&store, PING, &[])), Ok(vec![]));
+
+    //Mock records calls behind mutex
+    let mock = MockStorageService::new();
+    assert_send_sync(&mock);
+    mock.return_put(Response(7));
+    assert_eq!(block_on(dispatch::<_, Bytes>(&mock, PUT, &[3])), Ok(vec![7]));
+    assert_eq!(mock.calls(), [StorageCall::Put(Request(3))]);
+
+    //Call ends once future of inner handler is ready
+    let handler = InstrumentedStorageService::new(store, Counter::default());
+    assert_send_sync(&handler);
+    assert_eq!(block_on(dispatch::<_, Bytes>(&handler, PUT, &[3])), Ok(vec![2]));
+    assert_eq!(block_on(dispatch::<_, Bytes>(&handler, PING, &[])), Ok(vec![]));
+    assert_eq!(*handler.observer.ended.lock().unwrap(), ["put", "ping"]);
+}
@@ -0,0 +1,75 @@
+//Appended to output of `Storage` service with dispatcher and client, encoding messages by the same codec
+#[derive(Debug, PartialEq)]
+pub struct Request(u8);
+#[derive(Debug, PartialEq)]
+pub struct Response(u8);
+#[derive(Debug, PartialEq)]
+pub struct Pong;
+
+pub struct Bytes;
+impl Codec<Request> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Request, DispatchError> {
+        payload.first().map(|byte| Request(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Request) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+impl Codec<Response> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Response, DispatchError> {
+        payload.first().map(|byte| Response(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Response) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+impl Codec<Pong> for Bytes {
+    fn decode(_: &[u8]) -> Result<Pong, DispatchError> {
+        Ok(Pong)
+    }
+    fn encode(_: &Pong) -> Vec<u8> {
+        Vec::new()
+    }
+}
+
+struct Node;
+
+impl storage::StorageService for Node {
+    fn put(&mut self, request: Request) -> Response {
+        Response(request.0 * 2)
+    }
+    fn ping(&mut self) -> Pong {
+        Pong
+    }
+}
+
+//Calls dispatcher within the same process
+struct Loopback(Node);
+
+impl Transport for Loopback {
+    type Error = DispatchError;
+
+    fn call(&mut self, method: u16, payload: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        storage::dispatch::<_, Bytes>(&mut self.0, method, payload)
+    }
+}
+
+//Returns response, which cannot be decoded
+struct Empty;
+
+impl Transport for Empty {
+    type Error = ();
+
+    fn call(&mut self, _: u16, _: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        Ok(Vec::new())
+    }
+}
+
+fn main() {
+    let mut client = storage::StorageClient::new(Loopback(Node));
+    assert_eq!(client.put::<Bytes>(&Request(21)).unwrap(), Response(42));
+    assert_eq!(client.ping::<Bytes>().unwrap(), Pong);
+
+    let mut client = storage::StorageClient::new(Empty);
+    assert!(matches!(client.put::<Bytes>(&Request(1)), Err(ClientError::InvalidResponse(DispatchError::InvalidRequest))));
+}
@@ -0,0 +1,80 @@
+//Appended to output of `Storage` service with framed dispatcher and client, encoding messages by the same codec
+use flatbuffers_tools::framing::decode_frame;
+
+#[derive(Debug, PartialEq)]
+pub struct Request(u8);
+#[derive(Debug, PartialEq)]
+pub struct Response(u8);
+#[derive(Debug, PartialEq)]
+pub struct Pong;
+
+pub struct Bytes;
+impl Codec<Request> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Request, DispatchError> {
+        payload.first().map(|byte| Request(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Request) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+impl Codec<Response> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Response, DispatchError> {
+        payload.first().map(|byte| Response(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Response) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+impl Codec<Pong> for Bytes {
+    fn decode(_: &[u8]) -> Result<Pong, DispatchError> {
+        Ok(Pong)
+    }
+    fn encode(_: &Pong) -> Vec<u8> {
+        Vec::new()
+    }
+}
+
+struct Node;
+
+impl storage::StorageService for Node {
+    fn put(&mut self, request: Request) -> Response {
+        Response(request.0 + 1)
+    }
+    fn ping(&mut self) -> Pong {
+        Pong
+    }
+}
+
+//Passes frames to dispatcher within the same process, recording method ids
+struct Loopback(Node, Vec<u16>);
+
+impl Transport for Loopback {
+    type Error = DispatchError;
+
+    fn call(&mut self, frame: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        self.1.push(decode_frame(frame).unwrap().method_id);
+        storage::dispatch_frame::<_, Bytes>(&mut self.0, frame)
+    }
+}
+
+//Returns response, which is not a frame
+struct Garbage;
+
+impl Transport for Garbage {
+    type Error = ();
+
+    fn call(&mut self, _: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        Ok(vec![0xFF])
+    }
+}
+
+fn main() {
+    let mut client = storage::StorageClient::new(Loopback(Node, Vec::new()));
+    assert_eq!(client.put::<Bytes>(&Request(41)).unwrap(), Response(42));
+    assert_eq!(client.ping::<Bytes>().unwrap(), Pong);
+    assert_eq!(client.put::<Bytes>(&Request(0)).unwrap(), Response(1));
+    assert_eq!(client.into_inner().1, [storage::PUT, storage::PING, storage::PUT]);
+
+    let mut client = storage::StorageClient::new(Garbage);
+    assert!(matches!(client.put::<Bytes>(&Request(1)), Err(ClientError::InvalidFrame)));
+}
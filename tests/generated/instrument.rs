@@ -0,0 +1,45 @@
+//Appended to output of `Storage` service with `ReturnStyle::Result` and instrumentation
+use std::cell::RefCell;
+
+pub struct Request(u8);
+pub struct Response(u8);
+pub struct Pong;
+
+struct Store;
+impl storage::StorageService for Store {
+    type Error = ();
+    fn put(&mut self, request: Request) -> Result<Response, ()> {
+        match request.0 {
+            0 => Err(()),
+            value => Ok(Response(value)),
+        }
+    }
+    fn ping(&mut self) -> Result<Pong, ()> {
+        Ok(Pong)
+    }
+}
+
+#[derive(Default)]
+struct Counter {
+    started: RefCell<Vec<&'static str>>,
+    ended: RefCell<Vec<(&'static str, bool)>>,
+}
+impl RpcObserver for Counter {
+    fn on_call_start(&self, method: &'static str) {
+        self.started.borrow_mut().push(method);
+    }
+    fn on_call_end(&self, method: &'static str, _: std::time::Duration, is_ok: bool) {
+        self.ended.borrow_mut().push((method, is_ok));
+    }
+}
+
+fn main() {
+    use storage::{InstrumentedStorageService, StorageService};
+
+    let mut handler = InstrumentedStorageService::new(Store, Counter::default());
+    assert_eq!(handler.put(Request(1)).unwrap().0, 1);
+    assert!(handler.ping().is_ok());
+    assert!(handler.put(Request(0)).is_err());
+    assert_eq!(*handler.observer.started.borrow(), ["put", "ping", "put"]);
+    assert_eq!(*handler.observer.ended.borrow(), [("put", true), ("ping", true), ("put", false)]);
+}
@@ -0,0 +1,50 @@
+//Appended to output of `Storage` service with mock
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request(u8);
+#[derive(Debug, PartialEq)]
+pub struct Response(u8);
+#[derive(Debug, PartialEq)]
+pub struct Pong;
+
+pub struct Bytes;
+impl Codec<Request> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Request, DispatchError> {
+        payload.first().map(|byte| Request(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Request) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+impl Codec<Response> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Response, DispatchError> {
+        payload.first().map(|byte| Response(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Response) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+impl Codec<Pong> for Bytes {
+    fn decode(_: &[u8]) -> Result<Pong, DispatchError> {
+        Ok(Pong)
+    }
+    fn encode(_: &Pong) -> Vec<u8> {
+        Vec::new()
+    }
+}
+
+fn main() {
+    use storage::{dispatch, MockStorageService, StorageCall, PING, PUT};
+
+    let mut mock = MockStorageService::new();
+    mock.expect_put(|request| Response(request.0 * 2)).return_put(Response(0)).return_ping(Pong);
+    assert_eq!(dispatch::<_, Bytes>(&mut mock, PUT, &[21]), Ok(vec![42]));
+    assert_eq!(dispatch::<_, Bytes>(&mut mock, PING, &[]), Ok(vec![]));
+    assert_eq!(dispatch::<_, Bytes>(&mut mock, PUT, &[1]), Ok(vec![0]));
+    //Request is not decoded, hence handler is not called
+    assert_eq!(dispatch::<_, Bytes>(&mut mock, PUT, &[]), Err(DispatchError::InvalidRequest));
+    assert_eq!(mock.calls(), [StorageCall::Put(Request(21)), StorageCall::Ping, StorageCall::Put(Request(1))]);
+
+    let unexpected = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || dispatch::<_, Bytes>(&mut mock, PING, &[])));
+    let message = unexpected.unwrap_err();
+    assert_eq!(message.downcast_ref::<&str>(), Some(&"MockStorageService: unexpected call of `ping`, no response is queued"));
+}
@@ -0,0 +1,94 @@
+//Appended to output of `Storage` service with `Asyncness::Native`, mock and instrumentation
+use std::cell::RefCell;
+use std::future::Future;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request(u8);
+#[derive(Debug, PartialEq)]
+pub struct Response(u8);
+#[derive(Debug, PartialEq)]
+pub struct Pong;
+
+pub struct Bytes;
+impl Codec<Request> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Request, DispatchError> {
+        payload.first().map(|byte| Request(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Request) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+impl Codec<Response> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Response, DispatchError> {
+        payload.first().map(|byte| Response(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Response) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+impl Codec<Pong> for Bytes {
+    fn decode(_: &[u8]) -> Result<Pong, DispatchError> {
+        Ok(Pong)
+    }
+    fn encode(_: &Pong) -> Vec<u8> {
+        Vec::new()
+    }
+}
+
+//Handler awaits nothing, hence future is ready on first poll
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+    match future.as_mut().poll(&mut context) {
+        std::task::Poll::Ready(output) => output,
+        std::task::Poll::Pending => panic!("Future is not ready"),
+    }
+}
+
+#[derive(Default)]
+struct Store {
+    last: u8,
+}
+
+impl storage::StorageService for Store {
+    async fn put(&mut self, request: Request) -> Response {
+        let last = core::future::ready(self.last).await;
+        self.last = request.0;
+        Response(last)
+    }
+    async fn ping(&mut self) -> Pong {
+        Pong
+    }
+}
+
+#[derive(Default)]
+struct Counter {
+    ended: RefCell<Vec<&'static str>>,
+}
+impl RpcObserver for Counter {
+    fn on_call_start(&self, _: &'static str) {
+    }
+    fn on_call_end(&self, method: &'static str, _: std::time::Duration, _: bool) {
+        self.ended.borrow_mut().push(method);
+    }
+}
+
+fn main() {
+    use storage::{dispatch, InstrumentedStorageService, MockStorageService, StorageCall, PING, PUT};
+
+    let mut store = Store::default();
+    assert_eq!(block_on(dispatch::<_, Bytes>(&mut store, PUT, &[1])), Ok(vec![0]));
+    assert_eq!(block_on(dispatch::<_, Bytes>(&mut store, PUT, &[2])), Ok(vec![1]));
+    assert_eq!(block_on(dispatch::<_, Bytes>(&mut store, PING, &[])), Ok(vec![]));
+
+    let mut mock = MockStorageService::new();
+    mock.return_put(Response(7));
+    assert_eq!(block_on(dispatch::<_, Bytes>(&mut mock, PUT, &[3])), Ok(vec![7]));
+    assert_eq!(mock.calls(), [StorageCall::Put(Request(3))]);
+
+    //Call ends once future of inner handler is ready
+    let mut handler = InstrumentedStorageService::new(store, Counter::default());
+    assert_eq!(block_on(dispatch::<_, Bytes>(&mut handler, PUT, &[3])), Ok(vec![2]));
+    assert_eq!(block_on(dispatch::<_, Bytes>(&mut handler, PING, &[])), Ok(vec![]));
+    assert_eq!(*handler.observer.ended.borrow(), ["put", "ping"]);
+}
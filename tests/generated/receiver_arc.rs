@@ -0,0 +1,51 @@
+//Appended to output of `Cache` service with `Receiver::Arc` and instrumentation
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+pub struct Request(u8);
+#[derive(Debug, PartialEq)]
+pub struct Response(u8);
+
+pub struct Bytes;
+impl Codec<Request> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Request, DispatchError> {
+        payload.first().map(|byte| Request(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Request) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+impl Codec<Response> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Response, DispatchError> {
+        payload.first().map(|byte| Response(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Response) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+
+struct Cache;
+
+impl cache::CacheService for Cache {
+    fn get(self: Arc<Self>, request: Request) -> Response {
+        Response(request.0 * 2)
+    }
+}
+
+#[derive(Default)]
+struct Counter(AtomicUsize);
+
+impl RpcObserver for Counter {
+    fn on_call_start(&self, _: &'static str) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+    fn on_call_end(&self, _: &'static str, _: std::time::Duration, _: bool) {
+    }
+}
+
+fn main() {
+    let handler = Arc::new(cache::InstrumentedCacheService::new(Arc::new(Cache), Counter::default()));
+    assert_eq!(cache::dispatch::<_, Bytes>(&handler, cache::GET, &[4]), Ok(vec![8]));
+    assert_eq!(cache::dispatch::<_, Bytes>(&handler, cache::GET, &[5]), Ok(vec![10]));
+    assert_eq!(handler.observer.0.load(Ordering::SeqCst), 2);
+}
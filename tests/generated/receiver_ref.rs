@@ -0,0 +1,51 @@
+//Appended to output of `Storage` service with `Receiver::Ref`, `send_sync` and mock
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request(u8);
+#[derive(Debug, PartialEq)]
+pub struct Response(u8);
+
+pub struct Bytes;
+impl Codec<Request> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Request, DispatchError> {
+        payload.first().map(|byte| Request(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Request) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+impl Codec<Response> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Response, DispatchError> {
+        payload.first().map(|byte| Response(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Response) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+
+//Handler is shared between threads
+#[derive(Default)]
+struct Storage {
+    last: AtomicU8,
+}
+
+impl storage::StorageService for Storage {
+    fn put(&self, request: Request) -> Response {
+        Response(self.last.swap(request.0, Ordering::SeqCst))
+    }
+}
+
+fn main() {
+    let handler = Storage::default();
+    std::thread::scope(|scope| {
+        scope.spawn(|| storage::dispatch::<_, Bytes>(&handler, storage::PUT, &[1]).unwrap());
+    });
+    assert_eq!(storage::dispatch::<_, Bytes>(&handler, storage::PUT, &[2]), Ok(vec![1]));
+
+    //Mock records calls behind mutex
+    let mock = storage::MockStorageService::new();
+    mock.return_put(Response(7));
+    assert_eq!(storage::dispatch::<_, Bytes>(&mock, storage::PUT, &[3]), Ok(vec![7]));
+    assert_eq!(mock.calls(), [storage::StorageCall::Put(Request(3))]);
+}
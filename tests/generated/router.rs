@@ -0,0 +1,67 @@
+//Appended to output of `Storage` and `Monitor` services with `service_router` and ids of schema scope
+#[derive(Debug, PartialEq)]
+pub struct Request(u8);
+#[derive(Debug, PartialEq)]
+pub struct Response(u8);
+#[derive(Debug, PartialEq)]
+pub struct Pong;
+
+pub struct Bytes;
+impl Codec<Request> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Request, DispatchError> {
+        payload.first().map(|byte| Request(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Request) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+impl Codec<Response> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Response, DispatchError> {
+        payload.first().map(|byte| Response(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Response) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+impl Codec<Pong> for Bytes {
+    fn decode(_: &[u8]) -> Result<Pong, DispatchError> {
+        Ok(Pong)
+    }
+    fn encode(_: &Pong) -> Vec<u8> {
+        vec![0xff]
+    }
+}
+
+#[derive(Default)]
+struct Node {
+    stored: Vec<u8>,
+}
+
+impl storage::StorageService for Node {
+    fn put(&mut self, request: Request) -> Response {
+        self.stored.push(request.0);
+        Response(self.stored.len() as u8)
+    }
+    fn get(&mut self, request: Request) -> Response {
+        Response(self.stored[request.0 as usize])
+    }
+}
+
+impl monitor::MonitorService for Node {
+    fn put(&mut self, _: Request) -> Pong {
+        Pong
+    }
+}
+
+fn main() {
+    //Methods of the same name have distinct ids
+    assert_eq!((monitor::PUT, storage::PUT, storage::GET), (0, 1, 2));
+    assert_eq!(service_of(storage::GET), Some(Service::Storage));
+    assert_eq!(service_of(3), None);
+
+    let mut node = Node::default();
+    assert_eq!(dispatch::<_, Bytes>(&mut node, storage::PUT, &[7]), Ok(vec![1]));
+    assert_eq!(dispatch::<_, Bytes>(&mut node, monitor::PUT, &[7]), Ok(vec![0xff]));
+    assert_eq!(dispatch::<_, Bytes>(&mut node, storage::GET, &[0]), Ok(vec![7]));
+    assert_eq!(dispatch::<_, Bytes>(&mut node, 3, &[]), Err(DispatchError::UnknownMethod(3)));
+}
@@ -0,0 +1,55 @@
+//Appended to outputs of `Storage` and `Monitor` services, importing shared items from runtime
+use flatbuffers_tools::runtime::{Codec, DispatchError, Method};
+
+#[derive(Debug, PartialEq)]
+pub struct Request(u8);
+#[derive(Debug, PartialEq)]
+pub struct Response(u8);
+
+struct Bytes;
+
+impl Codec<Request> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Request, DispatchError> {
+        payload.first().map(|byte| Request(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Request) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+
+impl Codec<Response> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Response, DispatchError> {
+        payload.first().map(|byte| Response(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Response) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+
+struct Node;
+
+impl storage::StorageService for Node {
+    fn put(&mut self, request: Request) -> Response {
+        Response(request.0 + 1)
+    }
+}
+
+impl monitor::MonitorService for Node {
+    fn ping(&mut self) -> Response {
+        Response(0)
+    }
+}
+
+fn name_of<M: Method>() -> &'static str {
+    M::NAME
+}
+
+fn main() {
+    //Both services share the same types
+    assert_eq!(storage::dispatch::<_, Bytes>(&mut Node, storage::PUT, &[1]), Ok(vec![2]));
+    assert_eq!(monitor::dispatch::<_, Bytes>(&mut Node, monitor::PING, &[]), Ok(vec![0]));
+    assert_eq!(storage::dispatch::<_, Bytes>(&mut Node, storage::PUT, &[]), Err(DispatchError::InvalidRequest));
+    let services: [&flatbuffers_tools::runtime::ServiceDescriptor; 2] = [&storage::SERVICE, &monitor::SERVICE];
+    assert_eq!(services[1].method(monitor::PING).unwrap().name, "Ping");
+    assert_eq!((name_of::<storage::Put>(), name_of::<monitor::Ping>()), ("Put", "Ping"));
+}
@@ -0,0 +1,53 @@
+//Appended to output of `Feed` service with streaming methods
+#[derive(Debug, PartialEq)]
+pub struct Key(u8);
+#[derive(Debug, PartialEq)]
+pub struct Item(u8);
+
+pub struct Bytes;
+impl Codec<Key> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Key, DispatchError> {
+        payload.first().map(|byte| Key(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Key) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+impl Codec<Item> for Bytes {
+    fn decode(payload: &[u8]) -> Result<Item, DispatchError> {
+        payload.first().map(|byte| Item(*byte)).ok_or(DispatchError::InvalidRequest)
+    }
+    fn encode(message: &Item) -> Vec<u8> {
+        vec![message.0]
+    }
+}
+
+pub struct Feed;
+impl feed::FeedService for Feed {
+    fn watch(&mut self, request: Key) -> impl Iterator<Item = Item> {
+        (0..request.0).map(Item)
+    }
+    fn upload(&mut self, requests: impl Iterator<Item = Item>) -> Key {
+        Key(requests.map(|item| item.0).sum())
+    }
+    fn chat(&mut self, requests: impl Iterator<Item = Item>) -> impl Iterator<Item = Item> {
+        requests.map(|item| Item(item.0 * 2))
+    }
+}
+
+fn main() {
+    use feed::{dispatch, dispatch_bidi, dispatch_client, dispatch_server, CHAT, UPLOAD, WATCH};
+
+    let mut handler = Feed;
+    let responses: Vec<_> = dispatch_server::<_, Bytes>(&mut handler, WATCH, &[3]).unwrap().collect();
+    assert_eq!(responses, [[0], [1], [2]]);
+    //Requests end at the first payload, that cannot be decoded
+    let payloads: [&[u8]; 4] = [&[1], &[2], &[], &[3]];
+    assert_eq!(dispatch_client::<_, Bytes, _, _>(&mut handler, UPLOAD, payloads), Ok(vec![3]));
+    let responses: Vec<_> = dispatch_bidi::<_, Bytes, _, _>(&mut handler, CHAT, payloads).unwrap().collect();
+    assert_eq!(responses, [[2], [4]]);
+
+    //Streaming methods are routed only by function of their kind
+    assert_eq!(dispatch::<_, Bytes>(&mut handler, WATCH, &[3]), Err(DispatchError::UnknownMethod(WATCH)));
+    assert!(matches!(dispatch_server::<_, Bytes>(&mut handler, CHAT, &[3]), Err(DispatchError::UnknownMethod(CHAT))));
+}
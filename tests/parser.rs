@@ -0,0 +1,751 @@
+mod common;
+
+use std::io::{self, Cursor, Read};
+
+use flatbuffers_tools::borrowed::parse_ref;
+use flatbuffers_tools::fs::{parse_file, parse_file_with_include_dirs, parse_file_with_includes, Error};
+use flatbuffers_tools::reader;
+use flatbuffers_tools::stream::StreamingParser;
+use flatbuffers_tools::schema::{check_attributes, Schema, SchemaParser};
+use flatbuffers_tools::{parse_all, parse_services, render_services, GenConfig, IdentifierKind, ParseError, ParseErrorAt, ParserIter, RpcService, Span};
+
+type Shape<'a> = Vec<(&'a str, Vec<(&'a str, Vec<&'a str>, &'a str)>)>;
+
+///Returns services as `(name, [(method, arguments, return type)])`, ignoring locations.
+fn shape(services: &[RpcService]) -> Shape<'_> {
+    services.iter().map(|service| {
+        let methods = service.methods.iter().map(|method| {
+            let arguments = method.arguments.iter().map(|argument| argument.ty.as_str()).collect();
+            (method.name.as_str(), arguments, method.return_type.as_str())
+        });
+        (service.name.as_str(), methods.collect())
+    }).collect()
+}
+
+#[test]
+fn should_parse_service_after_skipped_declaration_on_same_line() {
+    let services = parse_services("table T { a: int; } rpc_service A { M(T):T; }").unwrap();
+    assert_eq!(shape(&services), [("A", vec![("M", vec!["T"], "T")])]);
+    assert_eq!((services[0].span.start.line, services[0].span.start.column), (1, 21));
+
+    //Bracket of skipped declaration on its own line
+    let services = parse_services("table T {\n  a: int;\n} rpc_service A { M(T):T; }\n").unwrap();
+    assert_eq!(shape(&services), [("A", vec![("M", vec!["T"], "T")])]);
+
+    //Several declarations, including ones terminated by `;`
+    let services = parse_services("enum E : byte { X } root_type T; rpc_service A { M(T):T; } table T {}; rpc_service B { N(T):T; }").unwrap();
+    assert_eq!(shape(&services), [
+        ("A", vec![("M", vec!["T"], "T")]),
+        ("B", vec![("N", vec!["T"], "T")]),
+    ]);
+}
+
+#[test]
+fn should_parse_empty_service() {
+    let services = parse_services("rpc_service Placeholder {}\n").unwrap();
+    assert_eq!(shape(&services), [("Placeholder", vec![])]);
+    assert_eq!((services[0].span.start.line, services[0].span.end.line), (1, 1));
+
+    let services = parse_services("rpc_service Placeholder {\n}\n").unwrap();
+    assert_eq!(shape(&services), [("Placeholder", vec![])]);
+    assert_eq!((services[0].span.start.line, services[0].span.end.line), (1, 2));
+
+    let services = parse_services("rpc_service Placeholder\n{\n\n}\n").unwrap();
+    assert_eq!(shape(&services), [("Placeholder", vec![])]);
+}
+
+#[test]
+fn should_parse_service_after_empty_service() {
+    let services = parse_services("rpc_service Placeholder {}\nrpc_service Store {\n  Get(Key): Value;\n}\n").unwrap();
+    assert_eq!(shape(&services), [
+        ("Placeholder", vec![]),
+        ("Store", vec![("Get", vec!["Key"], "Value")]),
+    ]);
+    assert_eq!(services[1].span.start.line, 2);
+
+    let services = parse_services("rpc_service Placeholder {\n}\nrpc_service Store {\n  Get(Key): Value;\n}\n").unwrap();
+    assert_eq!(shape(&services), [
+        ("Placeholder", vec![]),
+        ("Store", vec![("Get", vec!["Key"], "Value")]),
+    ]);
+    assert_eq!(services[1].span.start.line, 3);
+}
+
+///Prints services and parses them back.
+fn round_trip(services: &[RpcService]) -> Vec<RpcService> {
+    let text = services.iter().map(|service| service.as_fbs().to_string()).collect::<Vec<_>>().join("\n");
+    parse_services(&text).unwrap()
+}
+
+#[test]
+fn should_round_trip_single_line_service() {
+    let services = parse_services("rpc_service Store { Put(Req): Resp; Get(Key): Resp; Delete(Key): Empty; }\n").unwrap();
+    let expected = [
+        ("Store", vec![("Put", vec!["Req"], "Resp"), ("Get", vec!["Key"], "Resp"), ("Delete", vec!["Key"], "Empty")]),
+    ];
+    assert_eq!(shape(&services), expected);
+    assert_eq!((services[0].span.start.line, services[0].span.end.line), (1, 1));
+    assert_eq!(shape(&round_trip(&services)), expected);
+
+    //Same as service written over several lines
+    let multi_line = parse_services("rpc_service Store {\n  Put(Req): Resp;\n  Get(Key): Resp;\n  Delete(Key): Empty;\n}\n").unwrap();
+    assert_eq!(services[0].as_fbs().to_string(), multi_line[0].as_fbs().to_string());
+}
+
+#[test]
+fn should_round_trip_two_services_on_one_line() {
+    let services = parse_services("rpc_service Store { Put(Req): Resp; Get(Key): Resp; } rpc_service Monitor { Ping(Empty): Empty; }\nrpc_service Last { Get(Key): Value; }\n").unwrap();
+    let expected = [
+        ("Store", vec![("Put", vec!["Req"], "Resp"), ("Get", vec!["Key"], "Resp")]),
+        ("Monitor", vec![("Ping", vec!["Empty"], "Empty")]),
+        ("Last", vec![("Get", vec!["Key"], "Value")]),
+    ];
+    assert_eq!(shape(&services), expected);
+    assert_eq!(services.iter().map(|service| service.span.start.line).collect::<Vec<_>>(), [1, 1, 2]);
+    assert_eq!(shape(&round_trip(&services)), expected);
+}
+
+#[test]
+fn should_parse_several_methods_on_one_line() {
+    let services = parse_services("rpc_service Store {\n  Put(Req): Resp; Get(Key): Resp;\n}\n").unwrap();
+    assert_eq!(shape(&services), [
+        ("Store", vec![("Put", vec!["Req"], "Resp"), ("Get", vec!["Key"], "Resp")]),
+    ]);
+    assert_eq!(services[0].methods.iter().map(|method| method.span.start.line).collect::<Vec<_>>(), [2, 2]);
+
+    let services = parse_services("rpc_service Store {\n Put ( Req ) : Resp ;Get(Key):Resp;\t\tDelete(Key)  :  Empty ;  \n}\n").unwrap();
+    assert_eq!(shape(&services), [
+        ("Store", vec![("Put", vec!["Req"], "Resp"), ("Get", vec!["Key"], "Resp"), ("Delete", vec!["Key"], "Empty")]),
+    ]);
+}
+
+#[test]
+fn should_parse_method_with_closing_bracket_on_one_line() {
+    let services = parse_services("rpc_service Store {\n  Put(Req): Resp;\n  Get(Key): Resp; }\nrpc_service Monitor {\n  Ping(Empty): Empty;\n}\n").unwrap();
+    assert_eq!(shape(&services), [
+        ("Store", vec![("Put", vec!["Req"], "Resp"), ("Get", vec!["Key"], "Resp")]),
+        ("Monitor", vec![("Ping", vec!["Empty"], "Empty")]),
+    ]);
+    assert_eq!(services[0].span.end.line, 3);
+}
+
+#[test]
+fn should_not_split_methods_on_quoted_semicolon() {
+    let services = parse_services("rpc_service Store {\n  Put(Req): Resp (doc: \"a; b\"); Get(Key): Resp (id: \"x;\");\n}\n").unwrap();
+    assert_eq!(shape(&services), [
+        ("Store", vec![("Put", vec!["Req"], "Resp"), ("Get", vec!["Key"], "Resp")]),
+    ]);
+    let methods = &services[0].methods;
+    assert_eq!(methods[0].attributes, [("doc".to_owned(), Some("a; b".to_owned()))]);
+    assert_eq!(methods[1].attributes, [("id".to_owned(), Some("x;".to_owned()))]);
+}
+
+#[test]
+fn should_not_strip_comment_within_quoted_string() {
+    let services = parse_services("rpc_service A { Get(R): R (doc: \"http://x\"); }").unwrap();
+    assert_eq!(shape(&services), [("A", vec![("Get", vec!["R"], "R")])]);
+    assert_eq!(services[0].methods[0].attributes, [("doc".to_owned(), Some("http://x".to_owned()))]);
+
+    let services = parse_services("rpc_service A {\n  Get(R): R (doc: \"a /* b\"); /* comment */\n  Put(R): R;\n}\n").unwrap();
+    assert_eq!(shape(&services), [("A", vec![("Get", vec!["R"], "R"), ("Put", vec!["R"], "R")])]);
+    assert_eq!(services[0].methods[0].attributes, [("doc".to_owned(), Some("a /* b".to_owned()))]);
+
+    //Escaped quote does not end string, while comment after it is stripped
+    let services = parse_services("rpc_service A {\n  Get(R): R (doc: \"a\\\"//b\"); // trailing \"\n}\n").unwrap();
+    assert_eq!(services[0].methods[0].attributes, [("doc".to_owned(), Some("a\\\"//b".to_owned()))]);
+
+    let services = parse_ref("rpc_service A {\n  Get(R): R (doc: \"http://x\"); /* \"comment */\n}\n").unwrap();
+    assert_eq!(services[0].methods[0].attributes, [("doc", Some("http://x"))]);
+}
+
+#[test]
+fn should_strip_line_comment_after_attribute_with_slashes() {
+    let services = parse_services("rpc_service A {\n  //Get(R): R;\n  Get(R): R (url: \"//host/path\", streaming: \"server\"); //comment (url: \"x\")\n  Put(R): R; //Delete(R): R;\n}\n").unwrap();
+    assert_eq!(shape(&services), [("A", vec![("Get", vec!["R"], "R"), ("Put", vec!["R"], "R")])]);
+    assert_eq!(services[0].methods[0].attributes, [
+        ("url".to_owned(), Some("//host/path".to_owned())),
+        ("streaming".to_owned(), Some("server".to_owned())),
+    ]);
+    assert!(services[0].methods[1].attributes.is_empty());
+}
+
+#[test]
+fn should_skip_block_comments() {
+    //Whole service is commented out
+    let services = parse_services("/*\nrpc_service Old {\n  Get(Key): Value;\n}\n*/\nrpc_service New {\n  Get(Key): Value;\n}\n").unwrap();
+    assert_eq!(shape(&services), [("New", vec![("Get", vec!["Key"], "Value")])]);
+    assert_eq!(services[0].span.start.line, 6);
+
+    //Comment within body, spanning several lines and sharing lines with methods
+    let services = parse_services("rpc_service Store {\n  Put(Req): Resp; /* Delete(Key): Empty;\n  Clear(Empty): Empty; */ Get(Key): Resp;\n  /* Ping(Empty): Empty; */\n}\n").unwrap();
+    assert_eq!(shape(&services), [("Store", vec![("Put", vec!["Req"], "Resp"), ("Get", vec!["Key"], "Resp")])]);
+    assert_eq!(services[0].methods[1].span.start.line, 3);
+}
+
+#[test]
+fn should_fail_on_unterminated_block_comment() {
+    let error = parse_services("rpc_service Store {\n  Put(Req): Resp;\n}\n/* Get(Key): Resp;\n").unwrap_err();
+    assert_eq!(error.error, ParseError::UnterminatedComment);
+    assert_eq!(error.line, 4);
+
+    let error = parse_services("rpc_service Store {\n  Put(Req): Resp; /*\n}\n").unwrap_err();
+    assert_eq!(error.error, ParseError::UnterminatedComment);
+}
+
+///Parses services of `text` by strict parser.
+fn parse_strict(text: &str) -> Result<Vec<RpcService>, ParseErrorAt> {
+    ParserIter::from_str(text).strict().collect()
+}
+
+#[test]
+fn should_fail_strict_parsing_on_missing_semicolon() {
+    let text = "rpc_service A {\n  Get(R):R;\n  Put(R):R\n}\n";
+    let error = parse_strict(text).unwrap_err();
+    assert_eq!(error.line, 3);
+    assert!(matches!(error.error, ParseError::InService { error: ref inner, .. } if **inner == ParseError::MissingSemicolon("Put(R):R".to_owned())), "{}", error);
+    //Lenient parser accepts it
+    assert_eq!(shape(&parse_services(text).unwrap()), [("A", vec![("Get", vec!["R"], "R"), ("Put", vec!["R"], "R")])]);
+
+    //Comment is not terminator
+    let error = parse_strict("rpc_service A {\n  Get(R):R // ;\n}\n").unwrap_err();
+    assert!(matches!(error.error, ParseError::InService { error: ref inner, .. } if **inner == ParseError::MissingSemicolon("Get(R):R".to_owned())), "{}", error);
+}
+
+#[test]
+fn should_fail_strict_parsing_on_double_semicolon() {
+    let text = "rpc_service A {\n  Get(R):R;;\n}\n";
+    let error = parse_strict(text).unwrap_err();
+    assert_eq!(error.line, 2);
+    assert!(matches!(error.error, ParseError::InService { error: ref inner, .. } if **inner == ParseError::ExtraSemicolon), "{}", error);
+    assert_eq!(error.to_string(), "2: in service 'A': extra ';' without method definition");
+    assert_eq!(shape(&parse_services(text).unwrap()), [("A", vec![("Get", vec!["R"], "R")])]);
+}
+
+#[test]
+fn should_accept_semicolon_followed_by_comment_in_strict_parsing() {
+    let text = "rpc_service A {\n  Get(R):R; //Put(R):R\n  Put(R):R; /* ; */\n}\n";
+    assert_eq!(shape(&parse_strict(text).unwrap()), [("A", vec![("Get", vec!["R"], "R"), ("Put", vec!["R"], "R")])]);
+}
+
+#[test]
+fn should_fail_on_unexpected_eof() {
+    //Right after opening bracket
+    let error = parse_services("rpc_service A {").unwrap_err();
+    assert_eq!((error.line, error.error), (1, ParseError::UnexpectedEof("A".to_owned())));
+
+    //After methods, reported at line of service
+    let error = parse_services("table T {}\nrpc_service A {\n  Get(R):R;\n  Put(R):R;\n").unwrap_err();
+    assert_eq!((error.line, error.error), (2, ParseError::UnexpectedEof("A".to_owned())));
+
+    //Within multi-line method
+    let error = parse_services("rpc_service A {\n  Get(R):R;\n  Put(R)\n    :R (streaming:\n").unwrap_err();
+    assert_eq!(error.line, 1);
+    assert_eq!(error.to_string(), "1: 'A' is not closed before end of input");
+}
+
+#[test]
+fn should_parse_method_split_across_lines() {
+    let services = parse_services("rpc_service Store {\n  Put(\n    Req\n  ):\n    Resp\n    (streaming: \"server\");\n  Get(Key)\n    : Resp;\n}\n").unwrap();
+    assert_eq!(shape(&services), [("Store", vec![("Put", vec!["Req"], "Resp"), ("Get", vec!["Key"], "Resp")])]);
+    let methods = &services[0].methods;
+    assert_eq!(methods[0].attributes, [("streaming".to_owned(), Some("server".to_owned()))]);
+    assert_eq!((methods[0].span.start.line, methods[0].span.end.line), (2, 6));
+    assert_eq!((methods[1].span.start.line, methods[1].span.end.line), (7, 8));
+}
+
+#[test]
+fn should_parse_opening_bracket_on_next_line() {
+    let services = parse_services("rpc_service Store\n{\n  Get(Key): Value;\n}\nrpc_service Monitor (id: \"1\")\n\n  {  Ping(Empty): Empty; }\n").unwrap();
+    assert_eq!(shape(&services), [
+        ("Store", vec![("Get", vec!["Key"], "Value")]),
+        ("Monitor", vec![("Ping", vec!["Empty"], "Empty")]),
+    ]);
+    assert_eq!(services[1].attributes, [("id".to_owned(), Some("1".to_owned()))]);
+    assert_eq!(services[1].span.start.line, 5);
+
+    let error = parse_services("rpc_service Store\nGet(Key): Value;\n").unwrap_err();
+    assert_eq!((error.line, error.error), (1, ParseError::NoStartingBracket));
+}
+
+#[test]
+fn should_report_line_of_erroneous_definition() {
+    let error = parse_services("namespace a;\n\nrpc_service Store {\n  Put(Req): Resp;\n  Get(Key);\n}\n").unwrap_err();
+    assert_eq!(error.line, 5);
+    assert_eq!(error.to_string(), "5: in service 'Store', method 'Get': cannot determine return type of 'Get(Key)'");
+
+    //Method spanning several lines is reported at its first line
+    let error = parse_services("rpc_service Store {\n  Put(Req): Resp;\n\n  Get(\n    Key\n  );\n}\n").unwrap_err();
+    assert_eq!(error.line, 4);
+
+    //Error within second service on the same line
+    let error = parse_services("rpc_service A { Get(R):R; }\n\nrpc_service B { Put R: R; }\n").unwrap_err();
+    assert_eq!(error.line, 3);
+}
+
+///Returns span as `((start line, start column), (end line, end column))`.
+fn span_of(span: Span) -> ((usize, usize), (usize, usize)) {
+    ((span.start.line, span.start.column), (span.end.line, span.end.column))
+}
+
+#[test]
+fn should_locate_services_and_methods() {
+    let services = parse_services("namespace x;\n\nrpc_service Store {\n  Put(Req): Resp;\n  Get(Key)\n    : Resp (id: \"1\");\n}\n  rpc_service B { M(T):T; }\n").unwrap();
+    let spans: Vec<_> = services.iter().map(|service| {
+        (span_of(service.span), service.methods.iter().map(|method| span_of(method.span)).collect::<Vec<_>>())
+    }).collect();
+    assert_eq!(spans, [
+        (((3, 1), (7, 1)), vec![((4, 3), (4, 17)), ((5, 3), (6, 21))]),
+        (((8, 3), (8, 27)), vec![((8, 19), (8, 25))]),
+    ]);
+}
+
+#[test]
+fn should_capture_method_docs() {
+    let services = parse_services("rpc_service Store {\n  /// Stores value\n  ///  under key  \n  Put(Req): Resp; /// trailing comment\n  /* ///hidden */\n  Get(Key): Resp;\n  ///Describes nothing\n}\nrpc_service Next { M(T):T; }\n").unwrap();
+    let methods = &services[0].methods;
+    //Single space after `///` and trailing whitespace are stripped
+    assert_eq!(methods[0].docs, ["Stores value", " under key"]);
+    //Neither comment after definition, nor within block comment is documentation
+    assert!(methods[1].docs.is_empty());
+    //Documentation at the end of body is discarded instead of describing next service
+    assert!(services[1].docs.is_empty());
+}
+
+#[test]
+fn should_capture_service_docs() {
+    let services = parse_services("///Table\ntable T {}\n/// Storage\n///\n///of values\nrpc_service Store { Put(Req): Resp; }\n///Monitor\n\nrpc_service Monitor (id: \"1\") { Ping(T):T; }\n").unwrap();
+    //Documentation of skipped declaration is discarded, while empty lines are kept within documentation
+    assert_eq!(services[0].docs, ["Storage", "", "of values"]);
+    //Empty line between documentation and definition is allowed
+    assert_eq!(services[1].docs, ["Monitor"]);
+    assert!(services[0].methods[0].docs.is_empty());
+}
+
+#[test]
+fn should_track_namespaces_of_services() {
+    let services = parse_services("rpc_service Global { Get(R):R; }\nnamespace MyGame.Rpc;\nrpc_service Game { Get(Req):Other.Resp; }\nnamespace Admin;\n\nrpc_service Panel { Get(R):R; }\n").unwrap();
+    let namespaces: Vec<_> = services.iter().map(|service| (service.name.as_str(), service.namespace.as_deref())).collect();
+    assert_eq!(namespaces, [("Global", None), ("Game", Some("MyGame.Rpc")), ("Panel", Some("Admin"))]);
+    assert_eq!(services[1].resolve_type("Req").to_string(), "MyGame.Rpc.Req");
+    assert_eq!(services[1].methods[0].return_type.as_str(), "Other.Resp");
+    assert_eq!(services[2].resolve_type("R").to_string(), "Admin.R");
+    assert_eq!(services[0].resolve_type("R").to_string(), "R");
+
+    let error = parse_services("namespace MyGame..Rpc;\nrpc_service A { Get(R):R; }\n").unwrap_err();
+    assert!(matches!(error.error, ParseError::InvalidNamespace(_)), "{}", error);
+}
+
+#[test]
+fn should_parse_include_declarations() {
+    let mut parser = ParserIter::from_str("include \"common.fbs\";\ninclude 'nested/types.fbs';\nrpc_service A { Get(R):R; }\ninclude \"late.fbs\"\n");
+    let services: Vec<RpcService> = parser.by_ref().collect::<Result<_, _>>().unwrap();
+    assert_eq!(shape(&services), [("A", vec![("Get", vec!["R"], "R")])]);
+    assert_eq!(parser.includes(), ["common.fbs", "nested/types.fbs", "late.fbs"]);
+
+    for (text, line) in [("include \"common.fbs;\n", 1), ("rpc_service A { Get(R):R; }\ninclude 'common.fbs\";\n", 2), ("include common.fbs;\n", 1), ("include \"\";\n", 1), ("include \"a.fbs\" \"b.fbs\";\n", 1)] {
+        let error = parse_services(text).unwrap_err();
+        assert!(matches!(error.error, ParseError::InvalidInclude(_)), "{}: {}", text, error);
+        assert_eq!(error.line, line);
+    }
+}
+
+#[test]
+fn should_follow_include_chain() {
+    let dir = common::OutDir::new("include-chain");
+    std::fs::create_dir_all(dir.path().join("nested")).unwrap();
+    std::fs::create_dir_all(dir.path().join("shared")).unwrap();
+    //Includes are relative to including file, then within include directories
+    dir.write("main.fbs", "include \"nested/a.fbs\";\nrpc_service Main { Get(R):R; }\n");
+    dir.write("nested/a.fbs", "include 'b.fbs';\nrpc_service A (id: \"a\") { Get(R):R; }\n");
+    dir.write("nested/b.fbs", "include \"common.fbs\";\nnamespace B;\nrpc_service B { Get(R):R; }\n");
+    dir.write("shared/common.fbs", "rpc_service Common { Ping(R):R; }\n");
+
+    let shared = dir.path().join("shared");
+    let services = parse_file_with_include_dirs(&dir.path().join("main.fbs"), &[&shared]).unwrap();
+    let names: Vec<_> = services.iter().map(|service| (service.name.as_str(), service.namespace.as_deref())).collect();
+    assert_eq!(names, [("Common", None), ("B", Some("B")), ("A", None), ("Main", None)]);
+
+    //Without include directory
+    match parse_file_with_includes(&dir.path().join("main.fbs")) {
+        Err(Error::IncludeNotFound { path, include }) => {
+            assert_eq!(path.file_name().unwrap(), "b.fbs");
+            assert_eq!(include, "common.fbs");
+        },
+        result => panic!("unexpected result {:?}", result),
+    }
+
+    //Error within included file refers to it
+    dir.write("shared/common.fbs", "rpc_service Common {\n  Ping(R);\n}\n");
+    let error = parse_file_with_include_dirs(&dir.path().join("main.fbs"), &[&shared]).unwrap_err();
+    assert!(matches!(&error, Error::Parse(_, error) if error.line == 2), "{}", error);
+    assert_eq!(error.path().file_name().unwrap(), "common.fbs");
+}
+
+///Parses whole schema of `text`.
+fn parse_schema(text: &str) -> Result<Schema, ParseErrorAt> {
+    Schema::parse(text.lines())
+}
+
+#[test]
+fn should_parse_table_body() {
+    let schema = parse_schema("namespace G;\n///Monster\ntable Monster (private) {\n  ///Health\n  hp: short = 100;\n  name: string (required);\n  inventory: [ubyte];\n  pos: Vec3; friendly: bool = false (deprecated);\n}\nrpc_service A { Get(Monster):Monster; }\n").unwrap();
+    let table = &schema.tables[0];
+    assert_eq!((table.name.as_str(), table.namespace.as_deref()), ("Monster", Some("G")));
+    assert_eq!(table.attributes, [("private".to_owned(), None)]);
+    assert_eq!(table.docs, ["Monster"]);
+    let fields: Vec<_> = table.fields.iter().map(|field| (field.name.as_str(), field.ty.as_str(), field.default.as_deref(), field.attributes.len())).collect();
+    assert_eq!(fields, [
+        ("hp", "short", Some("100"), 0),
+        ("name", "string", None, 1),
+        ("inventory", "[ubyte]", None, 0),
+        ("pos", "Vec3", None, 0),
+        ("friendly", "bool", Some("false"), 1),
+    ]);
+    assert_eq!(table.fields[0].docs, ["Health"]);
+    assert_eq!((table.fields[4].span.start.line, table.fields[4].span.start.column), (8, 14));
+    assert_eq!((table.span.start.line, table.span.end.line), (3, 9));
+    assert_eq!(schema.services.len(), 1);
+
+    let error = parse_schema("table T {\n  a: int;\n  b int;\n}\n").unwrap_err();
+    assert_eq!((error.line, error.error), (3, ParseError::InvalidField("b int".to_owned())));
+    let error = parse_schema("table T {\n  a: int;\n").unwrap_err();
+    assert_eq!((error.line, error.error), (1, ParseError::UnexpectedEof("T".to_owned())));
+}
+
+#[test]
+fn should_parse_enum_body() {
+    let schema = parse_schema("enum Color : byte (bit_flags) {\n  Red = 1,\n  Green, Blue = 8\n}\nenum Empty : uint {}\n").unwrap();
+    let color = &schema.enums[0];
+    assert_eq!((color.name.as_str(), color.underlying_type.as_str()), ("Color", "byte"));
+    assert_eq!(color.variants, [("Red".to_owned(), Some(1)), ("Green".to_owned(), None), ("Blue".to_owned(), Some(8))]);
+    assert_eq!(color.attributes, [("bit_flags".to_owned(), None)]);
+    assert!(schema.enums[1].variants.is_empty());
+
+    let error = parse_schema("enum E { A }\n").unwrap_err();
+    assert_eq!(error.error, ParseError::NoUnderlyingType("E".to_owned()));
+    let error = parse_schema("enum E : byte {\n  A,\n  B = x\n}\n").unwrap_err();
+    assert_eq!((error.line, error.error), (3, ParseError::InvalidEnumVariant("B = x".to_owned())));
+}
+
+#[test]
+fn should_parse_union_body() {
+    let schema = parse_schema("namespace G;\nunion Any {\n  Monster,\n  Weapon: G.Weapon, Pickup\n}\n").unwrap();
+    let union = &schema.unions[0];
+    assert_eq!((union.name.as_str(), union.namespace.as_deref()), ("Any", Some("G")));
+    assert_eq!(union.members, [("Monster".to_owned(), None), ("Weapon".to_owned(), Some("G.Weapon".to_owned())), ("Pickup".to_owned(), None)]);
+    assert_eq!((union.span.start.line, union.span.end.line), (2, 5));
+
+    let error = parse_schema("union Any {\n  Monster,\n  Weapon:\n}\n").unwrap_err();
+    assert_eq!((error.line, error.error), (3, ParseError::InvalidUnionMember("Weapon:".to_owned())));
+}
+
+#[test]
+fn should_parse_struct_body() {
+    let schema = parse_schema("struct Vec3 (force_align: 16) {\n  x: float;\n  y: float; z: float;\n  id: ulong (key);\n}\n").unwrap();
+    let structure = &schema.structs[0];
+    let fields: Vec<_> = structure.fields.iter().map(|field| (field.name.as_str(), field.ty.as_str())).collect();
+    assert_eq!(fields, [("x", "float"), ("y", "float"), ("z", "float"), ("id", "ulong")]);
+    assert_eq!(structure.attributes, [("force_align".to_owned(), Some("16".to_owned()))]);
+    assert_eq!(structure.fields[3].attributes, [("key".to_owned(), None)]);
+
+    //Fields of struct are always present, hence they have neither default value, nor can be deprecated
+    let error = parse_schema("struct S {\n  a: int = 1;\n}\n").unwrap_err();
+    assert_eq!((error.line, error.error), (2, ParseError::StructFieldDefault("a".to_owned())));
+    let error = parse_schema("struct S { a: int (deprecated); }\n").unwrap_err();
+    assert_eq!(error.error, ParseError::StructFieldAttribute("deprecated".to_owned()));
+}
+
+#[test]
+fn should_parse_file_identifier_and_extension() {
+    let schema = parse_schema("file_identifier \"MONS\";\nfile_extension \"mon\";\nrpc_service A { Get(R):R; }\n").unwrap();
+    assert_eq!((schema.file_identifier.as_deref(), schema.file_extension.as_deref()), (Some("MONS"), Some("mon")));
+    assert_eq!(parse_schema("file_identifier 'MONS';").unwrap().file_identifier.as_deref(), Some("MONS"));
+    //Identifier is limited by bytes, rather than characters
+    assert_eq!(parse_schema("file_identifier \"ÄBC\";").unwrap().file_identifier.as_deref(), Some("ÄBC"));
+
+    for text in ["file_identifier \"MON\";", "file_identifier \"MONST\";", "file_identifier \"ÄBCD\";", "file_identifier \"\";", "file_identifier MONS;"] {
+        let error = parse_schema(text).unwrap_err();
+        assert!(matches!(error.error, ParseError::InvalidFileIdentifier(_)), "{}: {}", text, error);
+    }
+    let error = parse_schema("file_identifier \"MON\";").unwrap_err();
+    assert_eq!(error.to_string(), "1: invalid file_identifier '\"MON\";', expected 4 bytes in quotes");
+    for text in ["file_extension \"\";", "file_extension mon;"] {
+        let error = parse_schema(text).unwrap_err();
+        assert!(matches!(error.error, ParseError::InvalidFileExtension(_)), "{}: {}", text, error);
+    }
+}
+
+#[test]
+fn should_check_undeclared_attributes() {
+    let text = "attribute \"priority\";\nattribute \"internal\";\ntable T (priority: 1) { a: int (internal, unknown, id: 0); }\nrpc_service A (other) { Get(T):T (priority: \"1\", hidden, streaming: \"server\", unknown); }\n";
+    let schema = parse_schema(text).unwrap();
+    assert_eq!(schema.attributes, ["priority", "internal"]);
+    //Built-in attributes require no declaration, while every undeclared one is reported once, in order of first use
+    assert_eq!(schema.check_attributes(), ["other", "hidden", "unknown"]);
+
+    let items: Vec<_> = SchemaParser::from_str(text).collect::<Result<_, _>>().unwrap();
+    //Items are checked in order of definition
+    assert_eq!(check_attributes(&schema.attributes, &items), ["unknown", "other", "hidden"]);
+
+    assert!(parse_schema("attribute \"other\";\nrpc_service A (other) { Get(T):T (deprecated); }").unwrap().check_attributes().is_empty());
+}
+
+#[test]
+fn should_aggregate_schema_with_two_namespaces() {
+    let schema = parse_schema("include \"a.fbs\";\nnamespace Game;\ntable Monster {}\nstruct Vec3 { x: float; }\nenum Color : byte { Red }\nunion Any { Monster }\nnamespace Admin;\ntable Monster {}\nrpc_service Panel { Get(Game.Monster):Monster; }\nroot_type Monster;\n").unwrap();
+    //Namespace is the last one in effect
+    assert_eq!(schema.namespace.as_deref(), Some("Admin"));
+    assert_eq!(schema.includes, ["a.fbs"]);
+    assert_eq!(schema.root_type.as_deref(), Some("Monster"));
+    assert_eq!((schema.tables.len(), schema.structs.len(), schema.enums.len(), schema.unions.len(), schema.services.len()), (2, 1, 1, 1, 1));
+
+    //Qualified names are looked up within their namespace, while unqualified one refers to the first definition
+    let namespace_of = |name: &str| schema.table(name).map(|table| table.namespace.as_deref());
+    assert_eq!(namespace_of("Game.Monster"), Some(Some("Game")));
+    assert_eq!(namespace_of("Admin.Monster"), Some(Some("Admin")));
+    assert_eq!(namespace_of("Monster"), Some(Some("Game")));
+    assert_eq!(namespace_of("Other.Monster"), None);
+    assert!(schema.service("Admin.Panel").is_some() && schema.service("Game.Panel").is_none());
+    assert!(schema.structure("Game.Vec3").is_some() && schema.enumeration("Game.Color").is_some() && schema.union("Game.Any").is_some());
+}
+
+#[test]
+fn should_reject_duplicate_method() {
+    let error = parse_services("rpc_service A {\n  Get(R):R;\n  Put(R):R;\n  Get(K):K;\n}\n").unwrap_err();
+    assert_eq!((error.line, &error.error), (4, &ParseError::DuplicateMethod { service: "A".to_owned(), method: "Get".to_owned() }));
+    assert_eq!(error.to_string(), "4: method 'Get' is already defined in service 'A'");
+
+    //Names are case sensitive, same as flatc
+    assert_eq!(parse_services("rpc_service A {\n  Get(R):R;\n  get(R):R;\n}\n").unwrap()[0].methods.len(), 2);
+    //Methods of different services are independent
+    assert_eq!(parse_services("rpc_service A { Get(R):R; }\nrpc_service B { Get(R):R; }\n").unwrap().len(), 2);
+}
+
+#[test]
+fn should_reject_duplicate_service_when_unique() {
+    let text = "rpc_service A { Get(R):R; }\nrpc_service B { Get(R):R; }\nnamespace X;\nrpc_service A { Put(R):R; }\nrpc_service A { Get(R):R; }\n";
+    //Check is opt-in
+    assert_eq!(parse_services(text).unwrap().len(), 4);
+
+    let error = ParserIter::from_str(text).unique_services().collect::<Result<Vec<_>, _>>().unwrap_err();
+    //Service of other namespace is distinct
+    assert_eq!((error.line, error.error), (5, ParseError::DuplicateService("A".to_owned())));
+}
+
+#[test]
+fn should_recover_from_broken_methods_of_two_services() {
+    let text = "rpc_service A {\n  Get(R);\n  Put(R):R;\n}\nrpc_service B {\n  Ok(R):R;\n  Worse(R:R;\n}\nrpc_service C { M(T):T; }\n";
+    let (services, errors) = parse_all(text.lines());
+    //Both services are returned without broken methods, and parser re-synchronizes to parse the next service
+    assert_eq!(shape(&services), [
+        ("A", vec![("Put", vec!["R"], "R")]),
+        ("B", vec![("Ok", vec!["R"], "R")]),
+        ("C", vec![("M", vec!["T"], "T")]),
+    ]);
+    let errors: Vec<_> = errors.iter().map(|error| (error.line, error.error.root().clone())).collect();
+    assert_eq!(errors, [(2, ParseError::NoReturnType("Get(R)".to_owned())), (7, ParseError::InvalidMethodArgs("Worse(R".to_owned()))]);
+
+    //First error stops default parser
+    assert_eq!(parse_services(text).unwrap_err().line, 2);
+}
+
+#[test]
+fn should_reject_invalid_identifiers() {
+    let invalid = |text: &str| match parse_services(text).unwrap_err().error.root() {
+        ParseError::InvalidIdentifier { kind, name } => (*kind, name.clone()),
+        error => panic!("unexpected error {}", error),
+    };
+    assert_eq!(invalid("rpc_service My-Service { Get(R):R; }"), (IdentifierKind::Service, "My-Service".to_owned()));
+    assert_eq!(invalid("rpc_service A { 1Get(R):R; }"), (IdentifierKind::Method, "1Get".to_owned()));
+    assert_eq!(invalid("rpc_service A { Get(1R):R; }"), (IdentifierKind::Argument, "1R".to_owned()));
+    assert_eq!(invalid("rpc_service A { Get(R):Game..R; }"), (IdentifierKind::ReturnType, "Game..R".to_owned()));
+    assert_eq!(invalid("rpc_service Ä { Get(R):R; }"), (IdentifierKind::Service, "Ä".to_owned()));
+
+    //Rust keywords are valid identifiers, which generators escape
+    let services = parse_services("rpc_service A { type(R):R; }").unwrap();
+    assert_eq!(services[0].methods[0].name, "type");
+    let code = render_services(&services, &GenConfig::new().skip_docs(true)).unwrap();
+    assert!(code.contains("fn r#type(&mut self, request: crate::interface::R) -> crate::interface::R;"));
+}
+
+#[test]
+fn should_parse_same_services_regardless_of_line_endings() {
+    let lf = "///Storage\nrpc_service Store (id: \"1\")\n{\n  Put(Req): Resp;\n  Get(\n    Key\n  ): Resp;\n}\n";
+    let crlf = lf.replace('\n', "\r\n");
+    let bom = format!("\u{feff}{}", crlf);
+    let expected = parse_services(lf).unwrap();
+    assert_eq!(shape(&expected), [("Store", vec![("Put", vec!["Req"], "Resp"), ("Get", vec!["Key"], "Resp")])]);
+    assert_eq!(expected[0].docs, ["Storage"]);
+
+    for text in [crlf.as_str(), bom.as_str()] {
+        assert_eq!(parse_services(text).unwrap(), expected);
+        //Lines keep `\r`, when split by caller
+        let services: Vec<RpcService> = ParserIter::new(text.split('\n')).collect::<Result<_, _>>().unwrap();
+        assert_eq!(services, expected);
+        assert_eq!(parse_ref(text).unwrap().iter().map(|service| service.to_owned()).collect::<Vec<_>>(), expected);
+    }
+}
+
+#[test]
+fn should_tolerate_trailing_comma_and_whitespace_in_arguments() {
+    let arguments = |text: &str| {
+        let services = parse_services(text).unwrap();
+        shape(&services)[0].1[0].1.iter().map(|argument| argument.to_string()).collect::<Vec<_>>()
+    };
+    //Trailing comma
+    assert_eq!(arguments("rpc_service A { Store(Request,): R; }"), ["Request"]);
+    //Heavy whitespace, with and without trailing comma
+    assert_eq!(arguments("rpc_service A { Store( Request , Extra ,): R; }"), ["Request", "Extra"]);
+    assert_eq!(arguments("rpc_service A { Store(\tRequest ,\tExtra\t): R; }"), ["Request", "Extra"]);
+    //No arguments
+    assert!(arguments("rpc_service A { Store(): R; }").is_empty());
+    assert!(arguments("rpc_service A { Store( ): R; }").is_empty());
+
+    //Internal double comma or lone comma is genuinely empty slot
+    for text in ["rpc_service A { Store(Request,,Extra): R; }", "rpc_service A { Store(, Request): R; }", "rpc_service A { Store(,): R; }", "rpc_service A { Store(Request, ,): R; }"] {
+        let error = parse_services(text).unwrap_err();
+        assert!(matches!(error.error.root(), ParseError::EmptyArgument(_)), "{}: {}", text, error);
+    }
+    let error = parse_services("rpc_service A { Store(Request,,Extra): R; }").unwrap_err();
+    assert_eq!(error.to_string(), "1: in service 'A', method 'Store': empty argument within '(Request,,Extra)'");
+}
+
+#[test]
+fn should_parse_services_from_reader() {
+    let text = "include \"common.fbs\";\nrpc_service A {\n  Get(R):R;\n}\nrpc_service B { Put(K, V):R; }\n";
+    let parser = ParserIter::from_reader(Cursor::new(text));
+    let services = parser.collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(services, parse_services(text).unwrap());
+
+    let mut parser = ParserIter::from_reader(Cursor::new(text));
+    assert_eq!(parser.next().unwrap().unwrap().name, "A");
+    assert_eq!(parser.includes(), ["common.fbs"]);
+
+    //Parse error is reported with line
+    match ParserIter::from_reader(Cursor::new("rpc_service A {\n  Get(R);\n}\n")).next() {
+        Some(Err(reader::Error::Parse(error))) => assert_eq!((error.line, error.error.root()), (2, &ParseError::NoReturnType("Get(R)".to_owned()))),
+        result => panic!("unexpected result {:?}", result),
+    }
+}
+
+///Reader of `text`, which is interrupted before every read and fails with `error` once `text` is exhausted.
+struct FaultyReader {
+    text: Cursor<&'static str>,
+    is_interrupted: bool,
+    error: Option<io::ErrorKind>,
+}
+
+impl FaultyReader {
+    fn new(text: &'static str, error: Option<io::ErrorKind>) -> io::BufReader<Self> {
+        //Small buffer so that lines are read in several chunks
+        io::BufReader::with_capacity(4, Self {
+            text: Cursor::new(text),
+            is_interrupted: false,
+            error,
+        })
+    }
+}
+
+impl Read for FaultyReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.is_interrupted = !self.is_interrupted;
+        if self.is_interrupted {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+        match (self.text.read(buf)?, self.error) {
+            (0, Some(kind)) => Err(io::Error::new(kind, "injected failure")),
+            (size, _) => Ok(size),
+        }
+    }
+}
+
+#[test]
+fn should_retry_interrupted_reads() {
+    let text = "rpc_service A {\n  Get(R):R;\n}\nrpc_service B { Put(K, V):R; }\n";
+    let services = ParserIter::from_reader(FaultyReader::new(text, None)).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(services, parse_services(text).unwrap());
+}
+
+#[test]
+fn should_fail_on_io_error_of_reader() {
+    //Reader fails in the middle of service B
+    let mut parser = ParserIter::from_reader(FaultyReader::new("rpc_service A {\n  Get(R):R;\n}\nrpc_service B {\n  Put(K):R;\n", Some(io::ErrorKind::ConnectionReset)));
+    assert_eq!(parser.next().unwrap().unwrap().name, "A");
+    match parser.next() {
+        Some(Err(reader::Error::Io(error))) => {
+            assert_eq!(error.kind(), io::ErrorKind::ConnectionReset);
+            assert_eq!(error.to_string(), "injected failure");
+        },
+        result => panic!("unexpected result {:?}", result),
+    }
+    //Incomplete service B is never returned
+    assert!(parser.next().is_none());
+
+    //Invalid UTF-8 is reported as IO error
+    match ParserIter::from_reader(Cursor::new(b"rpc_service A {\n  \xff(R):R;\n}\n")).next() {
+        Some(Err(reader::Error::Io(error))) => assert_eq!(error.kind(), io::ErrorKind::InvalidData),
+        result => panic!("unexpected result {:?}", result),
+    }
+}
+
+///Feeds chunks to streaming parser, collecting all results.
+fn parse_chunks<'a>(chunks: impl IntoIterator<Item = &'a str>) -> Vec<Result<RpcService, ParseErrorAt>> {
+    let mut parser = StreamingParser::new();
+    let mut results = Vec::new();
+    for chunk in chunks {
+        results.extend(parser.feed(chunk));
+    }
+    results.extend(parser.finish());
+    results
+}
+
+#[test]
+fn should_stream_same_services_regardless_of_chunk_boundary() {
+    let texts = [
+        //Keywords, methods, brackets, comments and quoted text, including multi-byte characters
+        "include \"a.fbs\";\nnamespace Game;\ntable T { a: int; }\n///Store of values\nrpc_service Store (id: \"}{;\") {\n  Put(Key, Value): Resp; // «Put» }\n  /* Get(Key)\n  : Resp; */ Get(\n    Key\n  ): Resp;\n}\nrpc_service Empty {}\r\nrpc_service Last\n{ M(T):T; }",
+        //Unterminated service at the end of input
+        "rpc_service A { M(T):T; }\nrpc_service B {\n  N(T):T;\n",
+    ];
+    for text in texts {
+        let expected = ParserIter::from_str(text).collect::<Vec<_>>();
+
+        for idx in (0..=text.len()).filter(|idx| text.is_char_boundary(*idx)) {
+            let (head, tail) = text.split_at(idx);
+            assert_eq!(parse_chunks([head, tail]), expected, "split at {}", idx);
+        }
+
+        let chars = text.char_indices().map(|(idx, ch)| &text[idx..idx + ch.len_utf8()]);
+        assert_eq!(parse_chunks(chars), expected);
+    }
+
+    let services = parse_chunks([texts[0]]);
+    assert_eq!(services.iter().map(|service| service.as_ref().unwrap().name.as_str()).collect::<Vec<_>>(), ["Store", "Empty", "Last"]);
+    let services = parse_chunks([texts[1]]);
+    assert!(matches!(services.as_slice(), [Ok(_), Err(error)] if error.error == ParseError::UnexpectedEof("B".to_owned())), "{:?}", services);
+}
+
+#[test]
+fn should_parse_file() {
+    let dir = common::OutDir::new("parse-file");
+    let text = "namespace Game;\nrpc_service Store {\n  Get(Key): Value;\n}\n";
+    let path = dir.write("service.fbs", text);
+    assert_eq!(parse_file(&path).unwrap(), parse_services(text).unwrap());
+
+    let missing = dir.path().join("missing.fbs");
+    match parse_file(&missing) {
+        Err(Error::Io(path, error)) => {
+            assert_eq!(path, missing);
+            assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        },
+        result => panic!("unexpected result {:?}", result),
+    }
+
+    let path = dir.write("broken.fbs", "rpc_service Store {\n  Get(Key): Value;\n  Put(Key);\n}\n");
+    let error = parse_file(&path).unwrap_err();
+    assert_eq!(error.path(), path);
+    assert_eq!(error.to_string(), format!("{}:3: in service 'Store', method 'Put': cannot determine return type of 'Put(Key)'", path.display()));
+    //Source is parse error without location
+    let source = std::error::Error::source(&error).unwrap();
+    assert_eq!(source.to_string(), "in service 'Store', method 'Put': cannot determine return type of 'Put(Key)'");
+}